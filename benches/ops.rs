@@ -0,0 +1,236 @@
+// SPDX-License-Identifier: LGPL-2.1-or-later
+// See Notices.txt for copyright information
+
+//! benchmarks for the operations that are used the most, run with
+//! `cargo bench`.
+//!
+//! the inputs are random values with exponents near 1, since the general
+//! (algebraic) paths get much slower for huge or tiny values, and that would
+//! drown out everything else.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64Mcg;
+use simple_soft_float::{Float, FloatBitsType, FloatTraits, RoundingMode, Sign, F16, F64};
+
+/// how many inputs are processed in each iteration
+const BATCH_SIZE: usize = 16;
+
+/// generate random finite values in `(-2^8, -2^-8] ∪ [2^-8, 2^8)`.
+fn random_values<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits> + Default>(
+    rng: &mut Pcg64Mcg,
+) -> Vec<Float<FT>> {
+    let properties = FT::default().properties();
+    let exponent_bias: u64 = properties.exponent_bias::<Bits>().to_u64().unwrap();
+    let mantissa_field_max: u64 = properties.mantissa_field_max::<Bits>().to_u64().unwrap();
+    (0..BATCH_SIZE)
+        .map(|_| {
+            let mut value = Float::<FT>::positive_zero();
+            value.set_sign(if rng.gen() {
+                Sign::Positive
+            } else {
+                Sign::Negative
+            });
+            let exponent = exponent_bias - 8 + rng.gen_range(0, 16);
+            value.set_exponent_field(Bits::from_u64(exponent).unwrap());
+            value
+                .set_mantissa_field(Bits::from_u64(rng.gen::<u64>() & mantissa_field_max).unwrap());
+            value
+        })
+        .collect()
+}
+
+/// generate random values with the same exponent, whose sums and
+/// differences `add` computes and rounds using integer arithmetic on the
+/// significands instead of algebraic arithmetic.
+fn aligned_values<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits> + Default>(
+    rng: &mut Pcg64Mcg,
+) -> Vec<Float<FT>> {
+    random_values(rng)
+        .into_iter()
+        .map(|mut value: Float<FT>| {
+            value.set_exponent_field(FT::default().properties().exponent_bias());
+            value
+        })
+        .collect()
+}
+
+/// generate `±2^k` for random `k` near zero, which `mul` and `div` handle
+/// by adjusting the other operand's exponent field.
+fn power_of_two_values<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits> + Default>(
+    rng: &mut Pcg64Mcg,
+) -> Vec<Float<FT>> {
+    random_values(rng)
+        .into_iter()
+        .map(|mut value: Float<FT>| {
+            value.set_mantissa_field(FT::default().properties().mantissa_field_normal_min());
+            value
+        })
+        .collect()
+}
+
+fn bench_binary_op<FT: FloatTraits>(
+    c: &mut Criterion,
+    name: &str,
+    lhs: &[Float<FT>],
+    rhs: &[Float<FT>],
+    op: fn(&Float<FT>, &Float<FT>) -> Float<FT>,
+) {
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            for (lhs, rhs) in lhs.iter().zip(rhs) {
+                black_box(op(black_box(lhs), black_box(rhs)));
+            }
+        })
+    });
+}
+
+macro_rules! bench_format {
+    ($c:ident, $rng:ident, $name:literal, $traits:ident) => {{
+        let lhs = random_values::<_, simple_soft_float::$traits>(&mut $rng);
+        let rhs = random_values(&mut $rng);
+        let aligned_lhs = aligned_values::<_, simple_soft_float::$traits>(&mut $rng);
+        let aligned_rhs = aligned_values(&mut $rng);
+        let powers_of_two = power_of_two_values::<_, simple_soft_float::$traits>(&mut $rng);
+        let rounding_mode = Some(RoundingMode::TiesToEven);
+        bench_binary_op($c, concat!($name, " add"), &lhs, &rhs, |lhs, rhs| {
+            lhs.add(rhs, Some(RoundingMode::TiesToEven), None)
+        });
+        bench_binary_op(
+            $c,
+            concat!($name, " add aligned"),
+            &aligned_lhs,
+            &aligned_rhs,
+            |lhs, rhs| lhs.add(rhs, Some(RoundingMode::TiesToEven), None),
+        );
+        bench_binary_op($c, concat!($name, " mul"), &lhs, &rhs, |lhs, rhs| {
+            lhs.mul(rhs, Some(RoundingMode::TiesToEven), None)
+        });
+        bench_binary_op(
+            $c,
+            concat!($name, " mul power of two"),
+            &lhs,
+            &powers_of_two,
+            |lhs, rhs| lhs.mul(rhs, Some(RoundingMode::TiesToEven), None),
+        );
+        bench_binary_op($c, concat!($name, " div"), &lhs, &rhs, |lhs, rhs| {
+            lhs.div(rhs, Some(RoundingMode::TiesToEven), None)
+        });
+        bench_binary_op(
+            $c,
+            concat!($name, " div power of two"),
+            &lhs,
+            &powers_of_two,
+            |lhs, rhs| lhs.div(rhs, Some(RoundingMode::TiesToEven), None),
+        );
+        let term = random_values(&mut $rng);
+        $c.bench_function(concat!($name, " fused_mul_add"), |b| {
+            b.iter(|| {
+                for ((lhs, rhs), term) in lhs.iter().zip(&rhs).zip(&term) {
+                    black_box(black_box(lhs).fused_mul_add(
+                        black_box(rhs),
+                        black_box(term),
+                        rounding_mode,
+                        None,
+                    ));
+                }
+            })
+        });
+        #[cfg(not(feature = "rational-only"))]
+        {
+            let positive: Vec<_> = lhs.iter().map(|value| value.abs()).collect();
+            $c.bench_function(concat!($name, " sqrt"), |b| {
+                b.iter(|| {
+                    for value in &positive {
+                        black_box(black_box(value).sqrt(rounding_mode, None));
+                    }
+                })
+            });
+        }
+        $c.bench_function(concat!($name, " to_i64"), |b| {
+            b.iter(|| {
+                for value in &lhs {
+                    black_box(black_box(value).to_i64(false, rounding_mode, None));
+                }
+            })
+        });
+        let integers: Vec<i64> = (0..BATCH_SIZE).map(|_| $rng.gen_range(-256, 256)).collect();
+        $c.bench_function(concat!($name, " from_i64"), |b| {
+            b.iter(|| {
+                for &value in &integers {
+                    black_box(Float::<simple_soft_float::$traits>::from_i64(
+                        black_box(value),
+                        rounding_mode,
+                        None,
+                    ));
+                }
+            })
+        });
+        $c.bench_function(concat!($name, " to F16"), |b| {
+            b.iter(|| {
+                for value in &lhs {
+                    black_box(F16::convert_from_float(
+                        black_box(value),
+                        rounding_mode,
+                        None,
+                    ));
+                }
+            })
+        });
+        $c.bench_function(concat!($name, " to F64"), |b| {
+            b.iter(|| {
+                for value in &lhs {
+                    black_box(F64::convert_from_float(
+                        black_box(value),
+                        rounding_mode,
+                        None,
+                    ));
+                }
+            })
+        });
+    }};
+}
+
+fn bench_ops(c: &mut Criterion) {
+    let mut rng = Pcg64Mcg::seed_from_u64(0x5EED);
+    bench_format!(c, rng, "F16", F16Traits);
+    bench_format!(c, rng, "F32", F32Traits);
+    bench_format!(c, rng, "F64", F64Traits);
+}
+
+/// the worst case for the algebraic backend: square roots that are irrational,
+/// so they can't be computed exactly, of values with every mantissa bit
+/// significant.
+#[cfg(not(feature = "rational-only"))]
+fn bench_irrational_sqrt(c: &mut Criterion) {
+    let rounding_mode = Some(RoundingMode::TiesToEven);
+    // 2, and the largest value less than 4
+    let f32_values = [
+        simple_soft_float::F32::from_bits(0x4000_0000),
+        simple_soft_float::F32::from_bits(0x407F_FFFF),
+    ];
+    let f64_values = [
+        F64::from_bits(0x4000_0000_0000_0000),
+        F64::from_bits(0x400F_FFFF_FFFF_FFFF),
+    ];
+    c.bench_function("F32 sqrt irrational", |b| {
+        b.iter(|| {
+            for value in &f32_values {
+                black_box(black_box(value).sqrt(rounding_mode, None));
+            }
+        })
+    });
+    c.bench_function("F64 sqrt irrational", |b| {
+        b.iter(|| {
+            for value in &f64_values {
+                black_box(black_box(value).sqrt(rounding_mode, None));
+            }
+        })
+    });
+}
+
+#[cfg(not(feature = "rational-only"))]
+criterion_group!(benches, bench_ops, bench_irrational_sqrt);
+#[cfg(feature = "rational-only")]
+criterion_group!(benches, bench_ops);
+criterion_main!(benches);
@@ -0,0 +1,137 @@
+#!/bin/bash
+# SPDX-License-Identifier: LGPL-2.1-or-later
+# See Notices.txt for copyright information
+set -e
+
+# generates the Berkeley TestFloat-format vectors in test_data/testfloat
+# using the host's hardware floating-point arithmetic (f16 via GCC's
+# _Float16), so it must be run on x86-64 with a recent GCC.
+
+function fail() {
+    echo "$*">&2
+    exit 1
+}
+
+[[ "`uname -m`" == x86_64 ]] || fail "must be run on x86-64"
+CC="${CC:-gcc}"
+which "$CC" > /dev/null || fail "can't find C compiler: $CC"
+
+output_dir="$(dirname "$0")/test_data/testfloat"
+temp_dir="`mktemp -d`"
+trap 'rm -rf "$temp_dir"' EXIT
+
+cat > "$temp_dir/gen.c" <<'EOF'
+#include <fenv.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <string.h>
+#include <stdlib.h>
+#pragma STDC FENV_ACCESS ON
+
+static uint64_t state = 0x5EED1234ABCDull;
+static uint32_t rnd(void) {
+    state = state * 6364136223846793005ull + 1442695040888963407ull;
+    return (uint32_t)(state >> 32);
+}
+
+static int flags(void) {
+    int f = 0;
+    if (fetestexcept(FE_INEXACT)) f |= 1;
+    if (fetestexcept(FE_UNDERFLOW)) f |= 2;
+    if (fetestexcept(FE_OVERFLOW)) f |= 4;
+    if (fetestexcept(FE_DIVBYZERO)) f |= 8;
+    if (fetestexcept(FE_INVALID)) f |= 16;
+    return f;
+}
+
+static const uint16_t f16_special[] = {
+    0x0000, 0x0001, 0x0002, 0x01FF, 0x0200, 0x03FF, 0x0400, 0x0401, 0x07FF,
+    0x1000, 0x2E66, 0x3555, 0x3BFF, 0x3C00, 0x3C01, 0x3E00, 0x4000, 0x4200,
+    0x5640, 0x7800, 0x7BFE, 0x7BFF, 0x7C00, 0x7E00, 0x7D00,
+};
+static const uint32_t f32_special[] = {
+    0x00000000, 0x00000001, 0x00000002, 0x003FFFFF, 0x00400000, 0x007FFFFF,
+    0x00800000, 0x00800001, 0x00FFFFFF, 0x0C000000, 0x33800000, 0x3EAAAAAB,
+    0x3F7FFFFF, 0x3F800000, 0x3F800001, 0x3FC00000, 0x40000000, 0x40400000,
+    0x4B7FFFFF, 0x4B800000, 0x7F000000, 0x7F7FFFFE, 0x7F7FFFFF, 0x7F800000,
+    0x7FC00000, 0x7FA00000,
+};
+
+static void f16_line(FILE *out, int op, uint16_t a, uint16_t b) {
+    _Float16 x, y, r;
+    memcpy(&x, &a, 2);
+    memcpy(&y, &b, 2);
+    feclearexcept(FE_ALL_EXCEPT);
+    volatile _Float16 vx = x, vy = y;
+    r = op ? vx * vy : vx + vy;
+    volatile _Float16 vr = r;
+    int f = flags();
+    uint16_t rb;
+    _Float16 rr = vr;
+    memcpy(&rb, &rr, 2);
+    fprintf(out, "%04X %04X %04X %02X\n", a, b, rb, f);
+}
+
+static void f32_line(FILE *out, int op, uint32_t a, uint32_t b) {
+    float x, y;
+    memcpy(&x, &a, 4);
+    memcpy(&y, &b, 4);
+    feclearexcept(FE_ALL_EXCEPT);
+    volatile float vx = x, vy = y;
+    volatile float vr = op ? vx * vy : vx + vy;
+    int f = flags();
+    float rr = vr;
+    uint32_t rb;
+    memcpy(&rb, &rr, 4);
+    fprintf(out, "%08X %08X %08X %02X\n", a, b, rb, f);
+}
+
+static const char *header =
+    "# Berkeley TestFloat format: operands, result, and flags in hex\n"
+    "# (1 = inexact, 2 = underflow, 4 = overflow, 8 = infinite, 16 = invalid).\n"
+    "# rounding to nearest, ties to even; tininess detected after rounding.\n"
+    "# generated by make_testfloat_test_cases.sh from the host's x86-64 SSE arithmetic.\n";
+
+int main(void) {
+    const char *names[] = {"add", "mul"};
+    for (int op = 0; op < 2; op++) {
+        char path[64];
+        snprintf(path, sizeof path, "f16_%s_rne.txt", names[op]);
+        FILE *out = fopen(path, "w");
+        fputs(header, out);
+        size_t n = sizeof f16_special / sizeof f16_special[0];
+        for (size_t i = 0; i < 2 * n; i++)
+            for (size_t j = 0; j < 2 * n; j++)
+                f16_line(out, op, f16_special[i % n] | (i >= n ? 0x8000 : 0),
+                         f16_special[j % n] | (j >= n ? 0x8000 : 0));
+        for (int i = 0; i < 1000; i++) {
+            uint16_t b = rnd() & 0xFFFF;
+            uint16_t a = rnd() & 0xFFFF;
+            f16_line(out, op, a, b);
+        }
+        fclose(out);
+        snprintf(path, sizeof path, "f32_%s_rne.txt", names[op]);
+        out = fopen(path, "w");
+        fputs(header, out);
+        n = sizeof f32_special / sizeof f32_special[0];
+        for (size_t i = 0; i < 2 * n; i++)
+            for (size_t j = 0; j < 2 * n; j++)
+                f32_line(out, op, f32_special[i % n] | (i >= n ? 0x80000000u : 0),
+                         f32_special[j % n] | (j >= n ? 0x80000000u : 0));
+        for (int i = 0; i < 1000; i++) {
+            uint32_t a = rnd(), b = rnd();
+            if (op == 0 && (i & 1)) {
+                /* keep exponents close so the addition is interesting */
+                b = (b & 0x807FFFFF) | (((a >> 23) & 0xFF) + (rnd() % 48) - 24) % 255 << 23;
+            }
+            f32_line(out, op, a, b);
+        }
+        fclose(out);
+    }
+    return 0;
+}
+EOF
+
+"$CC" -O0 -frounding-math -o "$temp_dir/gen" "$temp_dir/gen.c" -lm
+mkdir -p "$output_dir"
+(cd "$output_dir" && "$temp_dir/gen")
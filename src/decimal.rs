@@ -0,0 +1,1195 @@
+// SPDX-License-Identifier: LGPL-2.1-or-later
+// See Notices.txt for copyright information
+
+//! IEEE 754 decimal floating-point formats using the binary integer decimal (BID) encoding.
+//!
+//! The decimal formats have their own `DecimalFloatProperties` and
+//! `DecimalFloat` types, since the encoding and rounding don't fit
+//! `FloatProperties`, but they use the same `RoundingMode`, `StatusFlags`,
+//! and `FPState` as the binary formats.
+//!
+//! Each finite value is `(-1)^sign * significand * 10^exponent` where
+//! `significand` is an integer with at most `precision` decimal digits and
+//! `exponent` is the quantum exponent. Since a value can have several
+//! representations (its cohort), operations pick the result's exponent using
+//! IEEE 754's preferred exponent rules.
+
+use crate::{
+    ExceptionHandlingMode, FPState, FPStateInvariantsGuard, FloatClass, RoundingMode, Sign,
+    TininessDetectionMode,
+};
+use core::fmt;
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_rational::Ratio;
+use num_traits::{One as _, Signed as _, ToPrimitive, Zero as _};
+
+fn pow10(exponent: usize) -> BigInt {
+    num_traits::pow(BigInt::from(10), exponent)
+}
+
+fn ratio_pow10(exponent: i64) -> Ratio<BigInt> {
+    let magnitude = pow10(exponent.unsigned_abs() as usize);
+    if exponent < 0 {
+        Ratio::new(BigInt::one(), magnitude)
+    } else {
+        magnitude.into()
+    }
+}
+
+/// get `floor(log10(value))` for a positive `value`
+fn floor_log10(value: &Ratio<BigInt>) -> i64 {
+    assert!(value.is_positive());
+    let digit_count = |v: &BigInt| v.to_str_radix(10).len() as i64;
+    let mut retval = digit_count(value.numer()) - digit_count(value.denom());
+    while ratio_pow10(retval) > *value {
+        retval -= 1;
+    }
+    while ratio_pow10(retval + 1) <= *value {
+        retval += 1;
+    }
+    retval
+}
+
+/// properties of a decimal floating-point format
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DecimalFloatProperties {
+    precision: usize,
+    exponent_continuation_width: usize,
+}
+
+impl DecimalFloatProperties {
+    /// `DecimalFloatProperties` for standard [__decimal32__ format](https://en.wikipedia.org/wiki/Decimal32_floating-point_format)
+    pub const DECIMAL32: Self = Self {
+        precision: 7,
+        exponent_continuation_width: 6,
+    };
+    /// `DecimalFloatProperties` for standard [__decimal64__ format](https://en.wikipedia.org/wiki/Decimal64_floating-point_format)
+    pub const DECIMAL64: Self = Self {
+        precision: 16,
+        exponent_continuation_width: 8,
+    };
+    /// `DecimalFloatProperties` for standard [__decimal128__ format](https://en.wikipedia.org/wiki/Decimal128_floating-point_format)
+    pub const DECIMAL128: Self = Self {
+        precision: 34,
+        exponent_continuation_width: 12,
+    };
+    /// get the standard decimal format with the specified width,
+    /// only 32, 64, and 128 are supported.
+    pub const fn standard_with_width(width: usize) -> Option<Self> {
+        match width {
+            32 => Some(Self::DECIMAL32),
+            64 => Some(Self::DECIMAL64),
+            128 => Some(Self::DECIMAL128),
+            _ => None,
+        }
+    }
+    /// get the number of decimal digits in the significand
+    #[inline]
+    pub const fn precision(self) -> usize {
+        self.precision
+    }
+    /// get the number of bits in the exponent continuation field
+    #[inline]
+    pub const fn exponent_continuation_width(self) -> usize {
+        self.exponent_continuation_width
+    }
+    /// get the number of bits in the trailing significand field
+    #[inline]
+    pub const fn trailing_significand_width(self) -> usize {
+        (self.precision - 1) / 3 * 10
+    }
+    /// get the number of bits in the combination field
+    #[inline]
+    pub const fn combination_width(self) -> usize {
+        self.exponent_continuation_width + 5
+    }
+    /// get the total number of bits
+    #[inline]
+    pub const fn width(self) -> usize {
+        1 + self.combination_width() + self.trailing_significand_width()
+    }
+    /// get the maximum exponent, `emax` in IEEE 754
+    #[inline]
+    pub const fn emax(self) -> i64 {
+        3 << (self.exponent_continuation_width - 1)
+    }
+    /// get the minimum exponent of normal values, `emin` in IEEE 754
+    #[inline]
+    pub const fn emin(self) -> i64 {
+        1 - self.emax()
+    }
+    /// get the bias of the encoded exponent
+    #[inline]
+    pub const fn exponent_bias(self) -> i64 {
+        self.emax() + self.precision as i64 - 2
+    }
+    /// get the minimum quantum exponent
+    #[inline]
+    pub const fn quantum_exponent_min(self) -> i64 {
+        self.emin() - self.precision as i64 + 1
+    }
+    /// get the maximum quantum exponent
+    #[inline]
+    pub const fn quantum_exponent_max(self) -> i64 {
+        self.emax() - self.precision as i64 + 1
+    }
+    /// get the largest significand, `10^precision - 1`
+    pub fn significand_max(self) -> BigUint {
+        (pow10(self.precision) - 1u32)
+            .to_biguint()
+            .expect("known to be positive")
+    }
+    fn mask(self) -> u128 {
+        if self.width() == 128 {
+            !0
+        } else {
+            (1 << self.width()) - 1
+        }
+    }
+    fn sign_shift(self) -> usize {
+        self.width() - 1
+    }
+    fn combination_shift(self) -> usize {
+        self.trailing_significand_width()
+    }
+}
+
+enum Decoded {
+    Finite {
+        sign: Sign,
+        significand: BigInt,
+        exponent: i64,
+    },
+    Infinity(Sign),
+    NaN {
+        sign: Sign,
+        signaling: bool,
+        payload: u128,
+    },
+}
+
+/// a decimal floating-point value using the binary integer decimal (BID) encoding
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DecimalFloat {
+    bits: u128,
+    properties: DecimalFloatProperties,
+}
+
+impl DecimalFloat {
+    /// create from the encoded bits
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` doesn't fit in `properties.width()` bits.
+    pub fn from_bits_and_properties(bits: u128, properties: DecimalFloatProperties) -> Self {
+        assert_eq!(bits & !properties.mask(), 0, "bits doesn't fit");
+        Self { bits, properties }
+    }
+    /// get the encoded bits
+    #[inline]
+    pub fn bits(&self) -> u128 {
+        self.bits
+    }
+    /// get the `DecimalFloatProperties`
+    #[inline]
+    pub fn properties(&self) -> DecimalFloatProperties {
+        self.properties
+    }
+    /// get the sign
+    pub fn sign(&self) -> Sign {
+        if (self.bits >> self.properties.sign_shift()) & 1 == 0 {
+            Sign::Positive
+        } else {
+            Sign::Negative
+        }
+    }
+    fn combination_field(&self) -> u128 {
+        (self.bits >> self.properties.combination_shift())
+            & ((1 << self.properties.combination_width()) - 1)
+    }
+    fn trailing_significand_field(&self) -> u128 {
+        self.bits & ((1 << self.properties.trailing_significand_width()) - 1)
+    }
+    fn decode(&self) -> Decoded {
+        let properties = self.properties;
+        let sign = self.sign();
+        let combination_width = properties.combination_width();
+        let combination = self.combination_field();
+        let top_5 = combination >> (combination_width - 5);
+        if top_5 == 0b11111 {
+            let signaling = (combination >> (combination_width - 6)) & 1 != 0;
+            let mut payload = self.trailing_significand_field();
+            // non-canonical payloads are treated as zero
+            if BigInt::from(payload) >= pow10(properties.precision - 1) {
+                payload = 0;
+            }
+            return Decoded::NaN {
+                sign,
+                signaling,
+                payload,
+            };
+        }
+        if top_5 == 0b11110 {
+            return Decoded::Infinity(sign);
+        }
+        let trailing_width = properties.trailing_significand_width();
+        let exponent_width = properties.exponent_continuation_width + 2;
+        let (encoded_exponent, significand) = if top_5 >> 3 == 0b11 {
+            let encoded_exponent = (combination >> 1) & ((1 << exponent_width) - 1);
+            let significand = ((0b1000 | (combination & 1)) << trailing_width)
+                | self.trailing_significand_field();
+            (encoded_exponent, significand)
+        } else {
+            let encoded_exponent = combination >> 3;
+            let significand =
+                ((combination & 0b111) << trailing_width) | self.trailing_significand_field();
+            (encoded_exponent, significand)
+        };
+        let mut significand = BigInt::from(significand);
+        // non-canonical significands are treated as zero
+        if significand > BigInt::from(properties.significand_max()) {
+            significand = BigInt::zero();
+        }
+        Decoded::Finite {
+            sign,
+            significand,
+            exponent: encoded_exponent as i64 - properties.exponent_bias(),
+        }
+    }
+    /// create a finite value from the sign, significand, and quantum exponent.
+    ///
+    /// returns `None` if the significand has too many digits or the exponent
+    /// is out of range.
+    pub fn from_finite_parts(
+        sign: Sign,
+        significand: &BigUint,
+        exponent: i64,
+        properties: DecimalFloatProperties,
+    ) -> Option<Self> {
+        if *significand > properties.significand_max()
+            || exponent < properties.quantum_exponent_min()
+            || exponent > properties.quantum_exponent_max()
+        {
+            return None;
+        }
+        let significand = significand.to_u128().expect("known to fit");
+        let encoded_exponent = (exponent + properties.exponent_bias()) as u128;
+        let trailing_width = properties.trailing_significand_width();
+        let trailing_mask = (1 << trailing_width) - 1;
+        let combination = if significand >> trailing_width < 0b1000 {
+            (encoded_exponent << 3) | (significand >> trailing_width)
+        } else {
+            let exponent_width = properties.exponent_continuation_width + 2;
+            (0b11 << (exponent_width + 1))
+                | (encoded_exponent << 1)
+                | ((significand >> trailing_width) & 1)
+        };
+        let mut bits =
+            (combination << properties.combination_shift()) | (significand & trailing_mask);
+        if sign == Sign::Negative {
+            bits |= 1 << properties.sign_shift();
+        }
+        Some(Self::from_bits_and_properties(bits, properties))
+    }
+    /// get the significand of finite values
+    pub fn significand(&self) -> Option<BigUint> {
+        match self.decode() {
+            Decoded::Finite { significand, .. } => {
+                Some(significand.to_biguint().expect("known to be positive"))
+            }
+            Decoded::Infinity(_) | Decoded::NaN { .. } => None,
+        }
+    }
+    /// get the quantum exponent of finite values, like IEEE 754's `quantumExponent`.
+    ///
+    /// infinities and NaNs signal invalid and return `None`.
+    pub fn quantum_exponent(&self, fp_state: Option<&mut FPState>) -> Option<i64> {
+        match self.decode() {
+            Decoded::Finite { exponent, .. } => Some(exponent),
+            Decoded::Infinity(_) | Decoded::NaN { .. } => {
+                let mut default_fp_state = FPState::default();
+                let mut fp_state_guard =
+                    FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+                let fp_state: &mut FPState = &mut fp_state_guard;
+                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+                None
+            }
+        }
+    }
+    /// get the unit in the last place of `self`, like IEEE 754's `quantum`.
+    ///
+    /// for finite values, the result is positive with a significand of 1 and
+    /// the same quantum exponent as `self`. Infinities give positive
+    /// infinity, and NaNs are propagated (signaling NaNs signal invalid).
+    pub fn quantum(&self, fp_state: Option<&mut FPState>) -> Self {
+        match self.decode() {
+            Decoded::Finite { exponent, .. } => {
+                Self::from_finite_parts(Sign::Positive, &BigUint::one(), exponent, self.properties)
+                    .expect("known to be in range")
+            }
+            Decoded::Infinity(_) => Self::signed_infinity(Sign::Positive, self.properties),
+            Decoded::NaN { signaling, .. } => {
+                if signaling {
+                    let mut default_fp_state = FPState::default();
+                    let mut fp_state_guard =
+                        FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+                    let fp_state: &mut FPState = &mut fp_state_guard;
+                    fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+                }
+                self.to_quiet_nan()
+            }
+        }
+    }
+    /// check if `self` and `rhs` have the same quantum exponent, like IEEE
+    /// 754's `sameQuantum`.
+    ///
+    /// two infinities or two NaNs have the same quantum, an infinity or NaN
+    /// never has the same quantum as a finite value. This never signals.
+    pub fn same_quantum(&self, rhs: &Self) -> bool {
+        assert_eq!(self.properties, rhs.properties);
+        match (self.decode(), rhs.decode()) {
+            (
+                Decoded::Finite {
+                    exponent: self_exponent,
+                    ..
+                },
+                Decoded::Finite {
+                    exponent: rhs_exponent,
+                    ..
+                },
+            ) => self_exponent == rhs_exponent,
+            (Decoded::Infinity(_), Decoded::Infinity(_))
+            | (Decoded::NaN { .. }, Decoded::NaN { .. }) => true,
+            _ => false,
+        }
+    }
+    fn special(sign: Sign, top_6: u128, properties: DecimalFloatProperties) -> Self {
+        let mut bits = top_6 << (properties.width() - 7);
+        if sign == Sign::Negative {
+            bits |= 1 << properties.sign_shift();
+        }
+        Self::from_bits_and_properties(bits, properties)
+    }
+    /// get zero with sign `sign` and quantum exponent `exponent`, which is clamped to be in range
+    pub fn signed_zero_with_exponent(
+        sign: Sign,
+        exponent: i64,
+        properties: DecimalFloatProperties,
+    ) -> Self {
+        let exponent = exponent
+            .max(properties.quantum_exponent_min())
+            .min(properties.quantum_exponent_max());
+        Self::from_finite_parts(sign, &BigUint::zero(), exponent, properties)
+            .expect("known to be in range")
+    }
+    /// get zero with sign `sign`, the quantum exponent is zero
+    pub fn signed_zero(sign: Sign, properties: DecimalFloatProperties) -> Self {
+        Self::signed_zero_with_exponent(sign, 0, properties)
+    }
+    /// get infinity with sign `sign`
+    pub fn signed_infinity(sign: Sign, properties: DecimalFloatProperties) -> Self {
+        Self::special(sign, 0b111100, properties)
+    }
+    /// get the largest finite value with sign `sign`
+    pub fn signed_max_finite(sign: Sign, properties: DecimalFloatProperties) -> Self {
+        Self::from_finite_parts(
+            sign,
+            &properties.significand_max(),
+            properties.quantum_exponent_max(),
+            properties,
+        )
+        .expect("known to be in range")
+    }
+    /// get the canonical quiet NaN, which is positive with a zero payload.
+    /// It's also the NaN generated by invalid operations.
+    pub fn quiet_nan(properties: DecimalFloatProperties) -> Self {
+        Self::special(Sign::Positive, 0b111110, properties)
+    }
+    /// get the canonical signaling NaN, which is positive with a zero payload
+    pub fn signaling_nan(properties: DecimalFloatProperties) -> Self {
+        Self::special(Sign::Positive, 0b111111, properties)
+    }
+    /// convert `self` into a quiet NaN, keeping the sign and the payload
+    /// (non-canonical payloads become zero)
+    pub fn to_quiet_nan(&self) -> Self {
+        let sign = match self.decode() {
+            Decoded::NaN { sign, payload, .. } => {
+                let mut retval = Self::special(sign, 0b111110, self.properties);
+                retval.bits |= payload;
+                return retval;
+            }
+            Decoded::Finite { sign, .. } | Decoded::Infinity(sign) => sign,
+        };
+        Self::special(sign, 0b111110, self.properties)
+    }
+    /// get the `FloatClass` of `self`
+    pub fn class(&self) -> FloatClass {
+        match self.decode() {
+            Decoded::NaN {
+                signaling: true, ..
+            } => FloatClass::SignalingNaN,
+            Decoded::NaN {
+                signaling: false, ..
+            } => FloatClass::QuietNaN,
+            Decoded::Infinity(Sign::Positive) => FloatClass::PositiveInfinity,
+            Decoded::Infinity(Sign::Negative) => FloatClass::NegativeInfinity,
+            Decoded::Finite {
+                sign,
+                significand,
+                exponent,
+            } => {
+                let class = if significand.is_zero() {
+                    FloatClass::PositiveZero
+                } else if Ratio::from(significand) * ratio_pow10(exponent)
+                    < ratio_pow10(self.properties.emin())
+                {
+                    FloatClass::PositiveSubnormal
+                } else {
+                    FloatClass::PositiveNormal
+                };
+                match sign {
+                    Sign::Positive => class,
+                    Sign::Negative => -class,
+                }
+            }
+        }
+    }
+    /// return `true` if `self` is a NaN
+    pub fn is_nan(&self) -> bool {
+        self.class().is_nan()
+    }
+    /// return `true` if `self` is a signaling NaN
+    pub fn is_signaling_nan(&self) -> bool {
+        self.class().is_signaling_nan()
+    }
+    /// return `true` if `self` is infinite
+    pub fn is_infinity(&self) -> bool {
+        self.class().is_infinity()
+    }
+    /// return `true` if `self` is zero
+    pub fn is_zero(&self) -> bool {
+        self.class().is_zero()
+    }
+    /// return `true` if `self` is finite
+    pub fn is_finite(&self) -> bool {
+        self.class().is_finite()
+    }
+    /// get the negation of `self`, this is a non-computational operation
+    /// that never signals.
+    pub fn neg(&self) -> Self {
+        Self::from_bits_and_properties(
+            self.bits ^ (1 << self.properties.sign_shift()),
+            self.properties,
+        )
+    }
+    /// get the absolute value of `self`, this is a non-computational operation
+    /// that never signals.
+    pub fn abs(&self) -> Self {
+        Self::from_bits_and_properties(
+            self.bits & !(1 << self.properties.sign_shift()),
+            self.properties,
+        )
+    }
+    /// get the exact value of `self`, returning `None` for infinities and NaNs
+    pub fn to_ratio(&self) -> Option<Ratio<BigInt>> {
+        match self.decode() {
+            Decoded::Finite {
+                sign,
+                significand,
+                exponent,
+            } => {
+                let value = Ratio::from(significand) * ratio_pow10(exponent);
+                Some(match sign {
+                    Sign::Positive => value,
+                    Sign::Negative => -value,
+                })
+            }
+            Decoded::Infinity(_) | Decoded::NaN { .. } => None,
+        }
+    }
+    /// round a rational number into the format specified by `properties`.
+    ///
+    /// exact results use the representation with the quantum exponent closest
+    /// to `preferred_exponent`, inexact results use all `precision` digits.
+    /// zero results are positive with the preferred exponent.
+    pub fn from_ratio_with_preferred_exponent(
+        value: &Ratio<BigInt>,
+        preferred_exponent: i64,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+        properties: DecimalFloatProperties,
+    ) -> Self {
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        if value.is_zero() {
+            return Self::signed_zero_with_exponent(Sign::Positive, preferred_exponent, properties);
+        }
+        let sign = if value.is_negative() {
+            Sign::Negative
+        } else {
+            Sign::Positive
+        };
+        let value = value.abs();
+        let precision = properties.precision as i64;
+        let exponent_min = properties.quantum_exponent_min();
+        let exponent_max = properties.quantum_exponent_max();
+        let significand_limit = pow10(properties.precision);
+        let round = |exponent: i64| -> (BigInt, i64, bool) {
+            let scaled = &value / ratio_pow10(exponent);
+            let (mut significand, remainder) = scaled.numer().div_rem(scaled.denom());
+            let inexact = !remainder.is_zero();
+            let twice_remainder = Ratio::new(remainder * 2, scaled.denom().clone());
+            let round_up = inexact
+                && match rounding_mode.rounds_away_on_tie(significand.is_odd()) {
+                    Some(away_on_tie) => {
+                        twice_remainder > Ratio::one() || (twice_remainder.is_one() && away_on_tie)
+                    }
+                    None => rounding_mode.rounds_away_on_overflow(sign),
+                };
+            let mut exponent = exponent;
+            if round_up {
+                significand += 1;
+                if significand == significand_limit {
+                    significand /= 10;
+                    exponent += 1;
+                }
+            }
+            (significand, exponent, inexact)
+        };
+        let unbounded_exponent = floor_log10(&value) - (precision - 1);
+        let (mut significand, mut exponent, inexact) = round(unbounded_exponent.max(exponent_min));
+        let check_for_underflow = match fp_state.exception_handling_mode {
+            ExceptionHandlingMode::IgnoreExactUnderflow => inexact,
+            ExceptionHandlingMode::SignalExactUnderflow => true,
+        };
+        let min_normal = ratio_pow10(properties.emin());
+        let tiny = check_for_underflow
+            && value < min_normal
+            && match fp_state.tininess_detection_mode {
+                TininessDetectionMode::BeforeRounding => true,
+                TininessDetectionMode::AfterRounding => {
+                    let (significand, exponent, _) = round(unbounded_exponent);
+                    Ratio::from(significand) * ratio_pow10(exponent) < min_normal
+                }
+            };
+        if tiny {
+            if inexact {
+                fp_state.status_flags = fp_state.status_flags.signal_underflow_with_inexact();
+            } else {
+                fp_state.status_flags = fp_state.status_flags.signal_underflow();
+            }
+        } else if exponent > exponent_max {
+            fp_state.status_flags = fp_state.status_flags.signal_overflow_with_inexact();
+            return if rounding_mode.rounds_away_on_overflow(sign) {
+                Self::signed_infinity(sign, properties)
+            } else {
+                Self::signed_max_finite(sign, properties)
+            };
+        } else if inexact {
+            fp_state.status_flags = fp_state.status_flags.signal_inexact();
+        }
+        if significand.is_zero() {
+            return Self::signed_zero_with_exponent(sign, exponent_min, properties);
+        }
+        if !inexact {
+            let preferred_exponent = preferred_exponent.max(exponent_min).min(exponent_max);
+            while exponent > preferred_exponent && &significand * 10 < significand_limit {
+                significand *= 10;
+                exponent -= 1;
+            }
+            while exponent < preferred_exponent && significand.is_multiple_of(&BigInt::from(10)) {
+                significand /= 10;
+                exponent += 1;
+            }
+        }
+        Self::from_finite_parts(
+            sign,
+            &significand.to_biguint().expect("known to be positive"),
+            exponent,
+            properties,
+        )
+        .expect("known to be in range")
+    }
+    /// round a rational number into the format specified by `properties`.
+    ///
+    /// exact results use the quantum exponent closest to zero, so integers
+    /// are represented with an exponent of zero when they fit.
+    pub fn from_ratio(
+        value: &Ratio<BigInt>,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+        properties: DecimalFloatProperties,
+    ) -> Self {
+        Self::from_ratio_with_preferred_exponent(value, 0, rounding_mode, fp_state, properties)
+    }
+    /// handle NaN operands of a binary operation: the result is the first NaN
+    /// operand, quieted. Signaling NaNs signal invalid.
+    fn propagate_nans(&self, rhs: &Self, fp_state: &mut FPState) -> Option<Self> {
+        let self_class = self.class();
+        let rhs_class = rhs.class();
+        fp_state.signal_invalid_for_signaling_operands(&[self_class, rhs_class]);
+        if self_class.is_nan() {
+            Some(self.to_quiet_nan())
+        } else if rhs_class.is_nan() {
+            Some(rhs.to_quiet_nan())
+        } else {
+            None
+        }
+    }
+    /// finish a binary operation with a finite rational result
+    fn finish(
+        value: Ratio<BigInt>,
+        preferred_exponent: i64,
+        zero_sign: Option<Sign>,
+        rounding_mode: RoundingMode,
+        fp_state: &mut FPState,
+        properties: DecimalFloatProperties,
+    ) -> Self {
+        if value.is_zero() {
+            let sign = zero_sign.unwrap_or_else(|| rounding_mode.exact_zero_sign());
+            return Self::signed_zero_with_exponent(sign, preferred_exponent, properties);
+        }
+        Self::from_ratio_with_preferred_exponent(
+            &value,
+            preferred_exponent,
+            Some(rounding_mode),
+            Some(fp_state),
+            properties,
+        )
+    }
+    /// add `self` and `rhs`, the preferred exponent is the smaller of the
+    /// operands' exponents.
+    pub fn add(
+        &self,
+        rhs: &Self,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        assert_eq!(self.properties, rhs.properties);
+        let properties = self.properties;
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        if let Some(retval) = self.propagate_nans(rhs, fp_state) {
+            return retval;
+        }
+        match (self.decode(), rhs.decode()) {
+            (Decoded::Infinity(self_sign), Decoded::Infinity(rhs_sign)) => {
+                if self_sign == rhs_sign {
+                    Self::signed_infinity(self_sign, properties)
+                } else {
+                    fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+                    Self::quiet_nan(properties)
+                }
+            }
+            (Decoded::Infinity(sign), _) | (_, Decoded::Infinity(sign)) => {
+                Self::signed_infinity(sign, properties)
+            }
+            (
+                Decoded::Finite {
+                    sign: self_sign,
+                    significand: self_significand,
+                    exponent: self_exponent,
+                },
+                Decoded::Finite {
+                    sign: rhs_sign,
+                    significand: rhs_significand,
+                    exponent: rhs_exponent,
+                },
+            ) => {
+                let zero_sign = if self_significand.is_zero()
+                    && rhs_significand.is_zero()
+                    && self_sign == rhs_sign
+                {
+                    Some(self_sign)
+                } else {
+                    None
+                };
+                Self::finish(
+                    self.to_ratio().expect("known to be finite")
+                        + rhs.to_ratio().expect("known to be finite"),
+                    self_exponent.min(rhs_exponent),
+                    zero_sign,
+                    rounding_mode,
+                    fp_state,
+                    properties,
+                )
+            }
+            (Decoded::NaN { .. }, _) | (_, Decoded::NaN { .. }) => unreachable!(),
+        }
+    }
+    /// subtract `rhs` from `self`, the preferred exponent is the smaller of
+    /// the operands' exponents.
+    pub fn sub(
+        &self,
+        rhs: &Self,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        if rhs.is_nan() {
+            // don't change the sign of NaNs
+            self.add(rhs, rounding_mode, fp_state)
+        } else {
+            self.add(&rhs.neg(), rounding_mode, fp_state)
+        }
+    }
+    /// multiply `self` and `rhs`, the preferred exponent is the sum of the
+    /// operands' exponents.
+    pub fn mul(
+        &self,
+        rhs: &Self,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        assert_eq!(self.properties, rhs.properties);
+        let properties = self.properties;
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        if let Some(retval) = self.propagate_nans(rhs, fp_state) {
+            return retval;
+        }
+        let sign = self.sign() * rhs.sign();
+        match (self.decode(), rhs.decode()) {
+            (Decoded::Infinity(_), Decoded::Finite { significand, .. })
+            | (Decoded::Finite { significand, .. }, Decoded::Infinity(_))
+                if significand.is_zero() =>
+            {
+                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+                Self::quiet_nan(properties)
+            }
+            (Decoded::Infinity(_), _) | (_, Decoded::Infinity(_)) => {
+                Self::signed_infinity(sign, properties)
+            }
+            (
+                Decoded::Finite {
+                    exponent: self_exponent,
+                    ..
+                },
+                Decoded::Finite {
+                    exponent: rhs_exponent,
+                    ..
+                },
+            ) => Self::finish(
+                self.to_ratio().expect("known to be finite")
+                    * rhs.to_ratio().expect("known to be finite"),
+                self_exponent + rhs_exponent,
+                Some(sign),
+                rounding_mode,
+                fp_state,
+                properties,
+            ),
+            (Decoded::NaN { .. }, _) | (_, Decoded::NaN { .. }) => unreachable!(),
+        }
+    }
+    /// divide `self` by `rhs`, the preferred exponent is the difference of
+    /// the operands' exponents. Finite values divided by infinity give zero
+    /// with the minimum exponent.
+    pub fn div(
+        &self,
+        rhs: &Self,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        assert_eq!(self.properties, rhs.properties);
+        let properties = self.properties;
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        if let Some(retval) = self.propagate_nans(rhs, fp_state) {
+            return retval;
+        }
+        let sign = self.sign() * rhs.sign();
+        match (self.decode(), rhs.decode()) {
+            (Decoded::Infinity(_), Decoded::Infinity(_)) => {
+                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+                Self::quiet_nan(properties)
+            }
+            (Decoded::Infinity(_), _) => Self::signed_infinity(sign, properties),
+            (_, Decoded::Infinity(_)) => {
+                Self::signed_zero_with_exponent(sign, properties.quantum_exponent_min(), properties)
+            }
+            (
+                Decoded::Finite {
+                    significand: self_significand,
+                    exponent: self_exponent,
+                    ..
+                },
+                Decoded::Finite {
+                    significand: rhs_significand,
+                    exponent: rhs_exponent,
+                    ..
+                },
+            ) => {
+                if rhs_significand.is_zero() {
+                    if self_significand.is_zero() {
+                        fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+                        Self::quiet_nan(properties)
+                    } else {
+                        fp_state.status_flags = fp_state.status_flags.signal_division_by_zero();
+                        Self::signed_infinity(sign, properties)
+                    }
+                } else {
+                    Self::finish(
+                        self.to_ratio().expect("known to be finite")
+                            / rhs.to_ratio().expect("known to be finite"),
+                        self_exponent - rhs_exponent,
+                        Some(sign),
+                        rounding_mode,
+                        fp_state,
+                        properties,
+                    )
+                }
+            }
+            (Decoded::NaN { .. }, _) | (_, Decoded::NaN { .. }) => unreachable!(),
+        }
+    }
+}
+
+impl fmt::Debug for DecimalFloat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("DecimalFloat");
+        debug_struct.field("properties", &self.properties);
+        debug_struct.field(
+            "bits",
+            &format_args!(
+                "0x{value:0width$X}",
+                value = self.bits,
+                width = self.properties.width() / 4
+            ),
+        );
+        debug_struct.field("sign", &self.sign());
+        if let Decoded::Finite {
+            significand,
+            exponent,
+            ..
+        } = self.decode()
+        {
+            debug_struct.field("significand", &significand);
+            debug_struct.field("exponent", &exponent);
+        }
+        debug_struct.field("class", &self.class());
+        debug_struct.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StatusFlags;
+    use alloc::vec::Vec;
+
+    fn d64(bits: u64) -> DecimalFloat {
+        DecimalFloat::from_bits_and_properties(bits.into(), DecimalFloatProperties::DECIMAL64)
+    }
+
+    fn from_str(text: &str) -> Ratio<BigInt> {
+        let (mantissa, exponent) = match text.find('E') {
+            Some(index) => (&text[..index], text[index + 1..].parse().unwrap()),
+            None => (text, 0),
+        };
+        let (integer, exponent) = match mantissa.find('.') {
+            Some(index) => {
+                let fraction = &mantissa[index + 1..];
+                let digits: Vec<u8> = mantissa[..index].bytes().chain(fraction.bytes()).collect();
+                (
+                    BigInt::parse_bytes(&digits, 10).unwrap(),
+                    exponent - fraction.len() as i64,
+                )
+            }
+            None => (
+                BigInt::parse_bytes(mantissa.as_bytes(), 10).unwrap(),
+                exponent,
+            ),
+        };
+        Ratio::from(integer) * ratio_pow10(exponent)
+    }
+
+    #[test]
+    fn test_properties() {
+        for &(properties, width, emax, bias) in &[
+            (DecimalFloatProperties::DECIMAL32, 32, 96, 101),
+            (DecimalFloatProperties::DECIMAL64, 64, 384, 398),
+            (DecimalFloatProperties::DECIMAL128, 128, 6144, 6176),
+        ] {
+            assert_eq!(properties.width(), width);
+            assert_eq!(properties.emax(), emax);
+            assert_eq!(properties.emin(), 1 - emax);
+            assert_eq!(properties.exponent_bias(), bias);
+            assert_eq!(
+                DecimalFloatProperties::standard_with_width(width),
+                Some(properties)
+            );
+        }
+        assert_eq!(DecimalFloatProperties::standard_with_width(16), None);
+    }
+
+    #[test]
+    fn test_encoding() {
+        let one = d64(0x31C0_0000_0000_0001);
+        assert_eq!(one.significand(), Some(BigUint::one()));
+        assert_eq!(one.quantum_exponent(None), Some(0));
+        assert_eq!(one.to_ratio(), Some(Ratio::one()));
+        assert_eq!(one.class(), FloatClass::PositiveNormal);
+        assert_eq!(
+            DecimalFloat::from_finite_parts(
+                Sign::Positive,
+                &BigUint::one(),
+                0,
+                DecimalFloatProperties::DECIMAL64
+            ),
+            Some(one)
+        );
+        // significands that don't fit in 53 bits use the other combination field layout
+        let max =
+            DecimalFloat::signed_max_finite(Sign::Negative, DecimalFloatProperties::DECIMAL64);
+        assert_eq!(max.bits(), 0xF7FB_86F2_6FC0_FFFF);
+        assert_eq!(max.quantum_exponent(None), Some(369));
+        assert_eq!(max, d64(0xF7FB_86F2_6FC0_FFFF));
+        assert_eq!(
+            d64(0x7800_0000_0000_0000).class(),
+            FloatClass::PositiveInfinity
+        );
+        assert_eq!(d64(0x7C00_0000_0000_0000).class(), FloatClass::QuietNaN);
+        assert_eq!(d64(0xFE00_0000_0000_0000).class(), FloatClass::SignalingNaN);
+        assert_eq!(
+            d64(0x0000_0000_0000_0001).class(),
+            FloatClass::PositiveSubnormal
+        );
+        assert_eq!(
+            d64(0x0000_0000_0000_0001).to_ratio(),
+            Some(ratio_pow10(-398))
+        );
+        // non-canonical significands are zero
+        assert_eq!(d64(0x6C7F_FFFF_FFFF_FFFF).class(), FloatClass::PositiveZero);
+        assert!(DecimalFloat::from_finite_parts(
+            Sign::Positive,
+            &BigUint::from(10_000_000u32),
+            0,
+            DecimalFloatProperties::DECIMAL32
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_from_ratio() {
+        let properties = DecimalFloatProperties::DECIMAL64;
+        let mut fp_state = FPState::default();
+        let value = DecimalFloat::from_ratio(
+            &Ratio::new(BigInt::one(), BigInt::from(3)),
+            None,
+            Some(&mut fp_state),
+            properties,
+        );
+        assert_eq!(value.bits(), 0x2FCB_D7A6_2540_5555);
+        assert_eq!(value.to_ratio(), Some(from_str("0.3333333333333333")));
+        assert_eq!(fp_state.status_flags, StatusFlags::empty().signal_inexact());
+        let mut fp_state = FPState::default();
+        let value = DecimalFloat::from_ratio(
+            &Ratio::new(BigInt::from(-2), BigInt::from(3)),
+            Some(RoundingMode::TowardZero),
+            Some(&mut fp_state),
+            properties,
+        );
+        assert_eq!(value.to_ratio(), Some(from_str("-0.6666666666666666")));
+        assert_eq!(fp_state.status_flags, StatusFlags::empty().signal_inexact());
+        let mut fp_state = FPState::default();
+        let value = DecimalFloat::from_ratio(
+            &BigInt::from(1234).into(),
+            None,
+            Some(&mut fp_state),
+            properties,
+        );
+        assert_eq!(value.significand(), Some(BigUint::from(1234u32)));
+        assert_eq!(value.quantum_exponent(None), Some(0));
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        let mut fp_state = FPState::default();
+        let value =
+            DecimalFloat::from_ratio(&from_str("1E385"), None, Some(&mut fp_state), properties);
+        assert_eq!(value.class(), FloatClass::PositiveInfinity);
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_overflow_with_inexact()
+        );
+        let mut fp_state = FPState::default();
+        let value =
+            DecimalFloat::from_ratio(&from_str("1.5E-398"), None, Some(&mut fp_state), properties);
+        // ties to even
+        assert_eq!(value.bits(), 0x0000_0000_0000_0002);
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_underflow_with_inexact()
+        );
+        let mut fp_state = FPState::default();
+        let value = DecimalFloat::from_ratio(
+            &from_str("2.5E-398"),
+            Some(RoundingMode::TiesToOdd),
+            Some(&mut fp_state),
+            properties,
+        );
+        assert_eq!(value.bits(), 0x0000_0000_0000_0003);
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_underflow_with_inexact()
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let properties = DecimalFloatProperties::DECIMAL64;
+        let value = |text: &str| DecimalFloat::from_ratio(&from_str(text), None, None, properties);
+        // every operation here is exact, so the preferred exponent is used
+        let cases: &[(&str, &str, u64, u64, u64, u64)] = &[
+            // (a, b, a + b, a - b, a * b, a / b) with `a` and `b` as exact integers
+            (
+                "1",
+                "2",
+                0x31C0_0000_0000_0003,
+                0xB1C0_0000_0000_0001,
+                0x31C0_0000_0000_0002,
+                0x31A0_0000_0000_0005,
+            ),
+            (
+                "10",
+                "4",
+                0x31C0_0000_0000_000E,
+                0x31C0_0000_0000_0006,
+                0x31C0_0000_0000_0028,
+                0x31A0_0000_0000_0019,
+            ),
+        ];
+        for &(a, b, sum, difference, product, quotient) in cases {
+            let a = value(a);
+            let b = value(b);
+            let mut fp_state = FPState::default();
+            assert_eq!(a.add(&b, None, Some(&mut fp_state)).bits(), sum.into());
+            assert_eq!(
+                a.sub(&b, None, Some(&mut fp_state)).bits(),
+                difference.into()
+            );
+            assert_eq!(a.mul(&b, None, Some(&mut fp_state)).bits(), product.into());
+            assert_eq!(a.div(&b, None, Some(&mut fp_state)).bits(), quotient.into());
+            assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        }
+        // 0.1 + 0.2 is exactly 0.3 in decimal
+        let tenth = d64(0x31A0_0000_0000_0001);
+        let fifth = d64(0x31A0_0000_0000_0002);
+        assert_eq!(tenth.add(&fifth, None, None).bits(), 0x31A0_0000_0000_0003);
+        // 1.50 * 2 keeps trailing zeros
+        let product = d64(0x3180_0000_0000_0096).mul(&value("2"), None, None);
+        assert_eq!(product.significand(), Some(BigUint::from(300u32)));
+        assert_eq!(product.quantum_exponent(None), Some(-2));
+        // 1 / 3 is inexact
+        let mut fp_state = FPState::default();
+        let quotient = value("1").div(&value("3"), None, Some(&mut fp_state));
+        assert_eq!(quotient.bits(), 0x2FCB_D7A6_2540_5555);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty().signal_inexact());
+        // x - x is +0, or -0 when rounding toward negative
+        let difference = value("1").sub(&value("1"), None, None);
+        assert_eq!(difference.bits(), 0x31C0_0000_0000_0000);
+        let difference = value("1").sub(&value("1"), Some(RoundingMode::TowardNegative), None);
+        assert_eq!(difference.bits(), 0xB1C0_0000_0000_0000);
+    }
+
+    #[test]
+    fn test_special_values() {
+        let properties = DecimalFloatProperties::DECIMAL64;
+        let one = d64(0x31C0_0000_0000_0001);
+        let zero = DecimalFloat::signed_zero(Sign::Positive, properties);
+        let infinity = DecimalFloat::signed_infinity(Sign::Positive, properties);
+        let mut fp_state = FPState::default();
+        assert_eq!(one.div(&zero, None, Some(&mut fp_state)), infinity);
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_division_by_zero()
+        );
+        let mut fp_state = FPState::default();
+        let result = infinity.sub(&infinity, None, Some(&mut fp_state));
+        assert_eq!(result, DecimalFloat::quiet_nan(properties));
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let mut fp_state = FPState::default();
+        let result = zero.mul(&infinity.neg(), None, Some(&mut fp_state));
+        assert!(result.is_nan());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let mut fp_state = FPState::default();
+        let signaling_nan = d64(0x7E00_0000_0000_002A);
+        let result = one.add(&signaling_nan, None, Some(&mut fp_state));
+        assert_eq!(result.bits(), 0x7C00_0000_0000_002A);
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let mut fp_state = FPState::default();
+        let result = one.div(&infinity, None, Some(&mut fp_state));
+        assert_eq!(result.class(), FloatClass::PositiveZero);
+        assert_eq!(result.quantum_exponent(None), Some(-398));
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+    }
+
+    #[test]
+    fn test_quantum() {
+        let properties = DecimalFloatProperties::DECIMAL64;
+        // 1.50 and 1.5 are in the same cohort but have different quanta
+        let one_point_five_zero = d64(0x3180_0000_0000_0096);
+        let one_point_five = d64(0x31A0_0000_0000_000F);
+        let mut fp_state = FPState::default();
+        assert_eq!(
+            one_point_five_zero.quantum_exponent(Some(&mut fp_state)),
+            Some(-2)
+        );
+        assert_eq!(
+            one_point_five_zero
+                .neg()
+                .quantum(Some(&mut fp_state))
+                .bits(),
+            0x3180_0000_0000_0001
+        );
+        assert_eq!(
+            one_point_five.quantum(Some(&mut fp_state)).bits(),
+            0x31A0_0000_0000_0001
+        );
+        assert!(!one_point_five_zero.same_quantum(&one_point_five));
+        assert!(one_point_five_zero.same_quantum(&d64(0xB180_0000_0000_0000)));
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        let infinity = DecimalFloat::signed_infinity(Sign::Negative, properties);
+        let quiet_nan = DecimalFloat::quiet_nan(properties);
+        let signaling_nan = DecimalFloat::signaling_nan(properties);
+        assert!(infinity.same_quantum(&infinity.neg()));
+        assert!(quiet_nan.same_quantum(&signaling_nan));
+        assert!(!infinity.same_quantum(&quiet_nan));
+        assert!(!one_point_five.same_quantum(&infinity));
+        assert_eq!(
+            infinity.quantum(Some(&mut fp_state)),
+            DecimalFloat::signed_infinity(Sign::Positive, properties)
+        );
+        assert_eq!(quiet_nan.quantum(Some(&mut fp_state)), quiet_nan);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        assert_eq!(signaling_nan.quantum(Some(&mut fp_state)), quiet_nan);
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let mut fp_state = FPState::default();
+        assert_eq!(infinity.quantum_exponent(Some(&mut fp_state)), None);
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+    }
+}
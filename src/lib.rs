@@ -4,17 +4,24 @@
 #![allow(clippy::unneeded_field_pattern)]
 #![allow(clippy::too_many_arguments)]
 #![deny(missing_docs)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+// conversions into `RealAlgebraicNumber` are no-ops when it's `Ratio<BigInt>`
+#![cfg_attr(feature = "rational-only", allow(clippy::useless_conversion))]
 
 //! Soft-float library that intends to be a straightforward reference implementation of IEEE 754
 
+#[cfg(not(feature = "rational-only"))]
 use algebraics::prelude::*;
 use num_bigint::{BigInt, BigUint};
 use num_integer::Integer;
 use num_rational::Ratio;
-use num_traits::{FromPrimitive, NumAssign, NumAssignRef, NumRef, ToPrimitive, Unsigned};
-use std::{
+use num_traits::{FromPrimitive, NumAssign, NumAssignRef, NumRef, Pow, ToPrimitive, Unsigned};
+extern crate alloc;
+
+use alloc::{format, string::String, vec::Vec};
+use core::{
     cmp::Ordering,
-    error::Error,
+    convert::TryFrom,
     fmt,
     ops::{
         Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Deref,
@@ -22,6 +29,54 @@ use std::{
         SubAssign,
     },
 };
+#[cfg(feature = "std")]
+use std::error::Error;
+
+#[cfg(not(any(feature = "algebraics", feature = "rational-only")))]
+compile_error!("either the `algebraics` or the `rational-only` feature must be enabled");
+
+#[cfg(all(feature = "python", feature = "rational-only"))]
+compile_error!("the `python` feature isn't supported with the `rational-only` feature");
+
+/// `rational-only` replaces `RealAlgebraicNumber` with `Ratio<BigInt>`
+#[cfg(feature = "rational-only")]
+type RealAlgebraicNumber = Ratio<BigInt>;
+
+#[cfg(feature = "rational-only")]
+use num_traits::{One as _, Signed as _, Zero as _};
+
+/// the subset of `RealAlgebraicNumber`'s inherent methods that are used on
+/// exact values, implemented for `Ratio<BigInt>`
+#[cfg(feature = "rational-only")]
+trait RationalOnlyExt {
+    fn to_integer_floor(&self) -> BigInt;
+    fn checked_floor_log2(&self) -> Option<i64>;
+}
+
+#[cfg(feature = "rational-only")]
+impl RationalOnlyExt for Ratio<BigInt> {
+    fn to_integer_floor(&self) -> BigInt {
+        self.floor().to_integer()
+    }
+    fn checked_floor_log2(&self) -> Option<i64> {
+        if !self.is_positive() {
+            return None;
+        }
+        let numer = self.numer();
+        let denom = self.denom();
+        let log2 = numer.bits() as i64 - denom.bits() as i64;
+        let is_less_than_power_of_2 = if log2 < 0 {
+            (numer << -log2 as usize) < *denom
+        } else {
+            *numer < (denom << log2 as usize)
+        };
+        if is_less_than_power_of_2 {
+            Some(log2 - 1)
+        } else {
+            Some(log2)
+        }
+    }
+}
 
 #[cfg(feature = "python")]
 use crate::python::PyPlatformProperties;
@@ -30,9 +85,9 @@ use crate::python::PyStatusFlags;
 #[cfg(feature = "python")]
 use crate::python::ToPythonRepr;
 #[cfg(feature = "python")]
-use pyo3::prelude::*;
+use alloc::borrow::Cow;
 #[cfg(feature = "python")]
-use std::borrow::Cow;
+use pyo3::prelude::*;
 
 #[macro_use]
 mod python_macros;
@@ -41,6 +96,8 @@ mod python;
 #[cfg(test)]
 mod test_cases;
 
+pub mod decimal;
+
 python_enum! {
     #[pyenum(module = simple_soft_float, repr = u8, test_fn = test_sign_enum)]
     /// sign of floating-point number
@@ -148,15 +205,61 @@ python_enum! {
         TowardPositive = 3,
         /// round to nearest, ties away from zero
         TiesToAway = 4,
+        /// round to nearest, ties to odd
+        TiesToOdd = 5,
     }
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for RoundingMode {
     fn default() -> Self {
         RoundingMode::TiesToEven
     }
 }
 
+impl RoundingMode {
+    /// for the round-to-nearest modes, return `true` if a value exactly halfway
+    /// between two representable values rounds away from zero, given whether
+    /// the neighbor closer to zero has an odd mantissa.
+    /// returns `None` for the directed rounding modes.
+    pub fn rounds_away_on_tie(self, toward_zero_is_odd: bool) -> Option<bool> {
+        match self {
+            RoundingMode::TiesToEven => Some(toward_zero_is_odd),
+            RoundingMode::TiesToOdd => Some(!toward_zero_is_odd),
+            RoundingMode::TiesToAway => Some(true),
+            RoundingMode::TowardZero
+            | RoundingMode::TowardNegative
+            | RoundingMode::TowardPositive => None,
+        }
+    }
+    /// return `true` if a result with the given `sign` that's too big to be
+    /// represented rounds to infinity instead of the largest finite value.
+    ///
+    /// for the directed rounding modes, every other inexact result is rounded
+    /// in the same direction.
+    pub fn rounds_away_on_overflow(self, sign: Sign) -> bool {
+        match (self, sign) {
+            (RoundingMode::TiesToEven, _)
+            | (RoundingMode::TiesToAway, _)
+            | (RoundingMode::TiesToOdd, _)
+            | (RoundingMode::TowardNegative, Sign::Negative)
+            | (RoundingMode::TowardPositive, Sign::Positive) => true,
+            (RoundingMode::TowardZero, _)
+            | (RoundingMode::TowardNegative, Sign::Positive)
+            | (RoundingMode::TowardPositive, Sign::Negative) => false,
+        }
+    }
+    /// the sign of an exact zero result of adding two nonzero values with
+    /// opposite signs, as required by IEEE 754.
+    pub fn exact_zero_sign(self) -> Sign {
+        if self == RoundingMode::TowardNegative {
+            Sign::Negative
+        } else {
+            Sign::Positive
+        }
+    }
+}
+
 /// IEEE 754 status flags
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
 pub struct StatusFlags(u32);
@@ -508,12 +611,19 @@ python_enum! {
     /// Otherwise, use the default value of `IgnoreExactUnderflow`.
     pub enum ExceptionHandlingMode {
         /// Use the default behavior of ignoring exact underflow.
+        ///
+        /// `UNDERFLOW` is only signaled for tiny results that are also inexact,
+        /// so exact subnormal results (such as `min_subnormal + 0`) don't
+        /// signal any exceptions.
         IgnoreExactUnderflow,
         /// Signal the `UNDERFLOW` exception even if the results are exact.
+        ///
+        /// Exact tiny results signal `UNDERFLOW` without `INEXACT`.
         SignalExactUnderflow,
     }
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for ExceptionHandlingMode {
     fn default() -> ExceptionHandlingMode {
         ExceptionHandlingMode::IgnoreExactUnderflow
@@ -531,12 +641,69 @@ python_enum! {
     }
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for TininessDetectionMode {
     fn default() -> TininessDetectionMode {
         TininessDetectionMode::AfterRounding
     }
 }
 
+python_enum! {
+    #[pyenum(module = simple_soft_float, repr = u8, test_fn = test_flush_to_zero_mode_enum)]
+    /// select which operations flush subnormal results to zero
+    ///
+    /// when an operation flushes, a subnormal result is replaced by a zero
+    /// with the same sign and `UNDERFLOW` and `INEXACT` are signaled. only
+    /// results are flushed, subnormal operands are always used as-is.
+    ///
+    /// | operations                                        | `Disabled` | `Arithmetic` | `ArithmeticAndConversions` |
+    /// |---------------------------------------------------|------------|--------------|----------------------------|
+    /// | `add`, `sub`, `mul`, `div`, `fused_mul_add`       | no         | yes          | yes                        |
+    /// | `scale_b`, `pown`, `hypot`                        | no         | yes          | yes                        |
+    /// | `convert_from_float`, `from_real_algebraic_number`| no         | no           | yes                        |
+    /// | everything else                                   | no         | no           | no                         |
+    ///
+    /// `from_real_algebraic_number` includes everything built on it, such as
+    /// parsing strings and `power_of_two`. operations like `sqrt` can't
+    /// produce subnormal results in the standard formats, and
+    /// `ieee754_remainder` is always exact, so they are never flushed.
+    pub enum FlushToZeroMode {
+        /// subnormal results are never flushed
+        Disabled,
+        /// subnormal results of arithmetic operations are flushed to zero
+        Arithmetic,
+        /// subnormal results of arithmetic operations and conversions are flushed to zero
+        ArithmeticAndConversions,
+    }
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for FlushToZeroMode {
+    fn default() -> FlushToZeroMode {
+        FlushToZeroMode::Disabled
+    }
+}
+
+impl FlushToZeroMode {
+    /// check if subnormal results of operations of kind `op` are flushed to zero
+    pub fn flushes(self, op: OpKind) -> bool {
+        match op {
+            OpKind::Add
+            | OpKind::Sub
+            | OpKind::Mul
+            | OpKind::Div
+            | OpKind::FusedMulAdd
+            | OpKind::ScaleB
+            | OpKind::PowN
+            | OpKind::Hypot => self != FlushToZeroMode::Disabled,
+            OpKind::ConvertFromFloat | OpKind::FromRealAlgebraicNumber => {
+                self == FlushToZeroMode::ArithmeticAndConversions
+            }
+            _ => false,
+        }
+    }
+}
+
 python_enum! {
     #[pyenum(module = simple_soft_float, repr = u8, test_fn = test_binary_nan_propagation_mode_enum)]
     /// Select how NaN payloads should be propagated
@@ -589,6 +756,7 @@ pub enum BinaryNaNPropagationResults {
     Second,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for BinaryNaNPropagationResults {
     fn default() -> Self {
         Self::Canonical
@@ -663,6 +831,7 @@ pub enum UnaryNaNPropagationResults {
     First,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for UnaryNaNPropagationResults {
     fn default() -> Self {
         Self::Canonical
@@ -732,6 +901,7 @@ pub enum TernaryNaNPropagationResults {
     Third,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for TernaryNaNPropagationResults {
     fn default() -> Self {
         Self::Canonical
@@ -825,6 +995,7 @@ python_enum! {
     }
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for TernaryNaNPropagationMode {
     fn default() -> TernaryNaNPropagationMode {
         TernaryNaNPropagationMode::AlwaysCanonical
@@ -1029,6 +1200,7 @@ python_enum! {
     }
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for FMAInfZeroQNaNResult {
     fn default() -> FMAInfZeroQNaNResult {
         FMAInfZeroQNaNResult::FollowNaNPropagationMode
@@ -1048,22 +1220,84 @@ python_enum! {
 }
 
 /// The dynamic state of a floating-point implementation
+///
+/// operations only ever add flags to `status_flags`, they never clear flags
+/// that were already set and never change any of the other fields. debug
+/// builds check this after every operation.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[allow(clippy::manual_non_exhaustive)]
 pub struct FPState {
     /// the dynamic rounding mode -- used whenever the rounding mode is not explicitly overridden
     pub rounding_mode: RoundingMode,
-    /// the cumulative exception status flags
+    /// the cumulative exception status flags; operations only add flags
     pub status_flags: StatusFlags,
     /// the exception handling mode
     pub exception_handling_mode: ExceptionHandlingMode,
     /// the tininess detection mode
     pub tininess_detection_mode: TininessDetectionMode,
+    /// the flush-to-zero mode
+    pub flush_to_zero_mode: FlushToZeroMode,
+    /// the precision control -- if set, the results of `add`, `sub`, `mul`,
+    /// `div`, `sqrt`, and `fused_mul_add` are rounded to this many
+    /// significand bits (including the leading bit) while keeping the
+    /// format's exponent range, like the x87's precision control field.
+    /// other operations and conversions aren't affected, and `Some(0)` is
+    /// treated the same as `None`.
+    pub precision_control: Option<usize>,
+    // FIXME: switch to using #[non_exhaustive] once on stable (rustc 1.40)
+    _non_exhaustive: (),
+}
+
+/// The dynamic modes of a floating-point implementation: everything in
+/// `FPState` except for the status flags.
+///
+/// used with `FPState::save_modes` and `FPState::restore_modes` to implement
+/// IEEE 754's `saveModes` and `restoreModes` operations.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[allow(clippy::manual_non_exhaustive)]
+pub struct FPModes {
+    /// the dynamic rounding mode -- used whenever the rounding mode is not explicitly overridden
+    pub rounding_mode: RoundingMode,
+    /// the exception handling mode
+    pub exception_handling_mode: ExceptionHandlingMode,
+    /// the tininess detection mode
+    pub tininess_detection_mode: TininessDetectionMode,
+    /// the flush-to-zero mode
+    pub flush_to_zero_mode: FlushToZeroMode,
+    /// the precision control -- if set, the results of `add`, `sub`, `mul`,
+    /// `div`, `sqrt`, and `fused_mul_add` are rounded to this many
+    /// significand bits (including the leading bit) while keeping the
+    /// format's exponent range, like the x87's precision control field.
+    /// other operations and conversions aren't affected, and `Some(0)` is
+    /// treated the same as `None`.
+    pub precision_control: Option<usize>,
     // FIXME: switch to using #[non_exhaustive] once on stable (rustc 1.40)
     _non_exhaustive: (),
 }
 
+/// parsing a string into a floating-point value failed because the string
+/// isn't in a supported format
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseError;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid floating-point number string")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ParseError {}
+
+#[cfg(feature = "python")]
+impl From<ParseError> for PyErr {
+    fn from(value: ParseError) -> PyErr {
+        PyErr::new::<pyo3::exceptions::ValueError, _>(format!("{}", value))
+    }
+}
+
 /// `FPState` merging failed due to incompatibility
-#[derive(Clone, Debug, Default)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct FPStateMergeFailed;
 
 impl fmt::Display for FPStateMergeFailed {
@@ -1072,6 +1306,7 @@ impl fmt::Display for FPStateMergeFailed {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for FPStateMergeFailed {}
 
 #[cfg(feature = "python")]
@@ -1082,7 +1317,26 @@ impl From<FPStateMergeFailed> for PyErr {
 }
 
 impl FPState {
+    /// signal `INVALID` if any operand would signal when used in a computational operation
+    fn signal_invalid_for_signaling_operands(&mut self, operand_classes: &[FloatClass]) {
+        if operand_classes
+            .iter()
+            .any(|class| class.would_signal_as_operand())
+        {
+            self.status_flags = self.status_flags.signal_invalid_operation();
+        }
+    }
+    /// get the default `FPState` with all status flags set, useful for testing
+    pub fn with_all_flags_set() -> Self {
+        Self {
+            status_flags: StatusFlags::all(),
+            ..Self::default()
+        }
+    }
     /// combine two `FPState` values into one, assigning the result to `self`
+    ///
+    /// the status flags are combined with bitwise or. returns an error,
+    /// leaving `self` unchanged, if any of the other fields differ.
     pub fn checked_merge_assign(&mut self, rhs: Self) -> Result<(), FPStateMergeFailed> {
         let status_flags = self.status_flags.merge(rhs.status_flags);
         let same = Self {
@@ -1100,19 +1354,306 @@ impl FPState {
         }
     }
     /// combine two `FPState` values into one, assigning the result to `self`
+    ///
+    /// # Panics
+    ///
+    /// Panics if any fields other than `status_flags` differ, use
+    /// `checked_merge_assign` to get an error instead.
     pub fn merge_assign(&mut self, rhs: Self) {
         self.checked_merge_assign(rhs).unwrap();
     }
     /// combine two `FPState` values into one, returning the result
+    ///
+    /// the status flags are combined with bitwise or. returns an error if
+    /// any of the other fields differ.
     pub fn checked_merge(mut self, rhs: Self) -> Result<Self, FPStateMergeFailed> {
         self.checked_merge_assign(rhs)?;
         Ok(self)
     }
     /// combine two `FPState` values into one, returning the result
+    ///
+    /// # Panics
+    ///
+    /// Panics if any fields other than `status_flags` differ, use
+    /// `checked_merge` to get an error instead.
     pub fn merge(mut self, rhs: Self) -> Self {
         self.merge_assign(rhs);
         self
     }
+    /// get the modes (everything except for the status flags), like IEEE
+    /// 754's `saveModes`
+    pub fn save_modes(&self) -> FPModes {
+        let FPState {
+            rounding_mode,
+            status_flags: _,
+            exception_handling_mode,
+            tininess_detection_mode,
+            flush_to_zero_mode,
+            precision_control,
+            _non_exhaustive: _,
+        } = *self;
+        FPModes {
+            rounding_mode,
+            exception_handling_mode,
+            tininess_detection_mode,
+            flush_to_zero_mode,
+            precision_control,
+            _non_exhaustive: (),
+        }
+    }
+    /// set the modes (everything except for the status flags) from `modes`,
+    /// like IEEE 754's `restoreModes`. the status flags are left unchanged.
+    pub fn restore_modes(&mut self, modes: FPModes) {
+        let FPModes {
+            rounding_mode,
+            exception_handling_mode,
+            tininess_detection_mode,
+            flush_to_zero_mode,
+            precision_control,
+            _non_exhaustive: _,
+        } = modes;
+        self.rounding_mode = rounding_mode;
+        self.exception_handling_mode = exception_handling_mode;
+        self.tininess_detection_mode = tininess_detection_mode;
+        self.flush_to_zero_mode = flush_to_zero_mode;
+        self.precision_control = precision_control;
+    }
+}
+
+/// the `FPState` used by an operation.
+///
+/// in debug builds, checks when dropped that the operation only added to the
+/// status flags and didn't change the modes. in release builds this is just a
+/// reference.
+struct FPStateInvariantsGuard<'a> {
+    fp_state: &'a mut FPState,
+    #[cfg(debug_assertions)]
+    initial: FPState,
+}
+
+impl<'a> FPStateInvariantsGuard<'a> {
+    #[inline]
+    fn new(fp_state: &'a mut FPState) -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            initial: *fp_state,
+            fp_state,
+        }
+    }
+}
+
+impl Deref for FPStateInvariantsGuard<'_> {
+    type Target = FPState;
+    #[inline]
+    fn deref(&self) -> &FPState {
+        self.fp_state
+    }
+}
+
+impl DerefMut for FPStateInvariantsGuard<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut FPState {
+        self.fp_state
+    }
+}
+
+#[cfg(debug_assertions)]
+impl Drop for FPStateInvariantsGuard<'_> {
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        {
+            if std::thread::panicking() {
+                return;
+            }
+        }
+        let initial_flags = self.initial.status_flags;
+        let final_flags = self.fp_state.status_flags;
+        assert_eq!(
+            final_flags.merge(initial_flags),
+            final_flags,
+            "operation cleared status flags: {:?} -> {:?}",
+            initial_flags,
+            final_flags
+        );
+        assert_eq!(
+            self.fp_state.save_modes(),
+            self.initial.save_modes(),
+            "operation changed the floating-point modes"
+        );
+    }
+}
+
+/// a record of one operation, passed to the callback of `TracingFPState`
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct OperationRecord {
+    /// the kind of operation
+    pub op_kind: OpKind,
+    /// the bits of the floating-point operands
+    pub operands: Vec<BigUint>,
+    /// the bits of the result, or `None` if the result isn't a floating-point value
+    pub result: Option<BigUint>,
+    /// the status flags that were signaled by the operation and weren't
+    /// already set before it
+    pub new_flags: StatusFlags,
+}
+
+/// an `FPState` that calls a callback with an `OperationRecord` after each
+/// operation run through it, for debugging why a computation produced an
+/// unexpected value or status flag.
+///
+/// the `trace_*` methods (such as `trace_add`) run the operation and build
+/// the record from their own arguments. operations without a `trace_*`
+/// method can be run using `trace` or `trace_non_float`, where the record's
+/// `op_kind` and `operands` are supplied by the caller.
+///
+/// this is separate from `FPState` so `FPState` can stay `Copy`.
+pub struct TracingFPState<F: FnMut(&OperationRecord)> {
+    /// the `FPState` used for the traced operations
+    pub fp_state: FPState,
+    callback: F,
+}
+
+impl<F: FnMut(&OperationRecord)> TracingFPState<F> {
+    /// create a new `TracingFPState`
+    pub fn new(fp_state: FPState, callback: F) -> Self {
+        Self { fp_state, callback }
+    }
+    /// get the `FPState`, discarding the callback
+    pub fn into_fp_state(self) -> FPState {
+        self.fp_state
+    }
+    /// run `op` with `self.fp_state`, then call the callback with the
+    /// operation's record.
+    ///
+    /// `op_kind` and `operands` are recorded as given, they aren't checked
+    /// against what `op` actually does, so prefer the `trace_*` methods when
+    /// there is one for the operation.
+    pub fn trace<FT: FloatTraits>(
+        &mut self,
+        op_kind: OpKind,
+        operands: &[&Float<FT>],
+        op: impl FnOnce(&mut FPState) -> Float<FT>,
+    ) -> Float<FT> {
+        self.record(op_kind, operands, op, |result| {
+            Some(result.bits_as_biguint())
+        })
+    }
+    /// like `trace`, except for operations that don't return a
+    /// floating-point value. the record's `result` is `None`.
+    pub fn trace_non_float<FT: FloatTraits, R>(
+        &mut self,
+        op_kind: OpKind,
+        operands: &[&Float<FT>],
+        op: impl FnOnce(&mut FPState) -> R,
+    ) -> R {
+        self.record(op_kind, operands, op, |_| None)
+    }
+    /// run and record `lhs.add(rhs, rounding_mode, ...)`
+    pub fn trace_add<FT: FloatTraits>(
+        &mut self,
+        lhs: &Float<FT>,
+        rhs: &Float<FT>,
+        rounding_mode: Option<RoundingMode>,
+    ) -> Float<FT> {
+        self.trace(OpKind::Add, &[lhs, rhs], |fp_state| {
+            lhs.add(rhs, rounding_mode, Some(fp_state))
+        })
+    }
+    /// run and record `lhs.sub(rhs, rounding_mode, ...)`
+    pub fn trace_sub<FT: FloatTraits>(
+        &mut self,
+        lhs: &Float<FT>,
+        rhs: &Float<FT>,
+        rounding_mode: Option<RoundingMode>,
+    ) -> Float<FT> {
+        self.trace(OpKind::Sub, &[lhs, rhs], |fp_state| {
+            lhs.sub(rhs, rounding_mode, Some(fp_state))
+        })
+    }
+    /// run and record `lhs.mul(rhs, rounding_mode, ...)`
+    pub fn trace_mul<FT: FloatTraits>(
+        &mut self,
+        lhs: &Float<FT>,
+        rhs: &Float<FT>,
+        rounding_mode: Option<RoundingMode>,
+    ) -> Float<FT> {
+        self.trace(OpKind::Mul, &[lhs, rhs], |fp_state| {
+            lhs.mul(rhs, rounding_mode, Some(fp_state))
+        })
+    }
+    /// run and record `lhs.div(rhs, rounding_mode, ...)`
+    pub fn trace_div<FT: FloatTraits>(
+        &mut self,
+        lhs: &Float<FT>,
+        rhs: &Float<FT>,
+        rounding_mode: Option<RoundingMode>,
+    ) -> Float<FT> {
+        self.trace(OpKind::Div, &[lhs, rhs], |fp_state| {
+            lhs.div(rhs, rounding_mode, Some(fp_state))
+        })
+    }
+    /// run and record `value.fused_mul_add(factor, term, rounding_mode, ...)`
+    pub fn trace_fused_mul_add<FT: FloatTraits>(
+        &mut self,
+        value: &Float<FT>,
+        factor: &Float<FT>,
+        term: &Float<FT>,
+        rounding_mode: Option<RoundingMode>,
+    ) -> Float<FT> {
+        self.trace(OpKind::FusedMulAdd, &[value, factor, term], |fp_state| {
+            value.fused_mul_add(factor, term, rounding_mode, Some(fp_state))
+        })
+    }
+    /// run and record `value.sqrt(rounding_mode, ...)`
+    #[cfg(not(feature = "rational-only"))]
+    pub fn trace_sqrt<FT: FloatTraits>(
+        &mut self,
+        value: &Float<FT>,
+        rounding_mode: Option<RoundingMode>,
+    ) -> Float<FT> {
+        self.trace(OpKind::Sqrt, &[value], |fp_state| {
+            value.sqrt(rounding_mode, Some(fp_state))
+        })
+    }
+    /// run and record `lhs.compare_quiet(rhs, ...)`
+    pub fn trace_compare_quiet<FT: FloatTraits>(
+        &mut self,
+        lhs: &Float<FT>,
+        rhs: &Float<FT>,
+    ) -> Option<Ordering> {
+        self.trace_non_float(OpKind::Compare, &[lhs, rhs], |fp_state| {
+            lhs.compare_quiet(rhs, Some(fp_state))
+        })
+    }
+    /// run and record `lhs.compare_signaling(rhs, ...)`
+    pub fn trace_compare_signaling<FT: FloatTraits>(
+        &mut self,
+        lhs: &Float<FT>,
+        rhs: &Float<FT>,
+    ) -> Option<Ordering> {
+        self.trace_non_float(OpKind::Compare, &[lhs, rhs], |fp_state| {
+            lhs.compare_signaling(rhs, Some(fp_state))
+        })
+    }
+    fn record<FT: FloatTraits, R>(
+        &mut self,
+        op_kind: OpKind,
+        operands: &[&Float<FT>],
+        op: impl FnOnce(&mut FPState) -> R,
+        result_bits: impl FnOnce(&R) -> Option<BigUint>,
+    ) -> R {
+        let old_flags = self.fp_state.status_flags;
+        let result = op(&mut self.fp_state);
+        let new_flags =
+            StatusFlags::from_bits_truncate(self.fp_state.status_flags.bits() & !old_flags.bits());
+        (self.callback)(&OperationRecord {
+            op_kind,
+            operands: operands.iter().map(|v| v.bits_as_biguint()).collect(),
+            result: result_bits(&result),
+            new_flags,
+        });
+        result
+    }
 }
 
 python_enum! {
@@ -1224,6 +1765,15 @@ impl FloatClass {
     pub fn is_signaling_nan(self) -> bool {
         self == FloatClass::SignalingNaN
     }
+    /// return `true` if a value of this class signals `INVALID` when used as
+    /// an operand of a computational operation.
+    ///
+    /// this is the same as `is_signaling_nan`, since unsupported encodings
+    /// are classified as signaling NaNs.
+    #[inline]
+    pub fn would_signal_as_operand(self) -> bool {
+        self.is_signaling_nan()
+    }
     /// return `true` if `self` is infinity
     #[inline]
     pub fn is_infinity(self) -> bool {
@@ -1252,26 +1802,26 @@ impl FloatClass {
     /// return `true` if `self` is finite (not NaN or infinity)
     #[inline]
     pub fn is_finite(self) -> bool {
-        match self {
+        matches!(
+            self,
             FloatClass::NegativeZero
-            | FloatClass::NegativeSubnormal
-            | FloatClass::NegativeNormal
-            | FloatClass::PositiveZero
-            | FloatClass::PositiveSubnormal
-            | FloatClass::PositiveNormal => true,
-            _ => false,
-        }
+                | FloatClass::NegativeSubnormal
+                | FloatClass::NegativeNormal
+                | FloatClass::PositiveZero
+                | FloatClass::PositiveSubnormal
+                | FloatClass::PositiveNormal
+        )
     }
     /// return `true` if `self` is subnormal or zero
     #[inline]
     pub fn is_subnormal_or_zero(self) -> bool {
-        match self {
+        matches!(
+            self,
             FloatClass::NegativeZero
-            | FloatClass::NegativeSubnormal
-            | FloatClass::PositiveZero
-            | FloatClass::PositiveSubnormal => true,
-            _ => false,
-        }
+                | FloatClass::NegativeSubnormal
+                | FloatClass::PositiveZero
+                | FloatClass::PositiveSubnormal
+        )
     }
 }
 
@@ -1320,6 +1870,7 @@ impl QuietNaNFormat {
     }
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for QuietNaNFormat {
     fn default() -> QuietNaNFormat {
         QuietNaNFormat::Standard
@@ -1328,6 +1879,7 @@ impl Default for QuietNaNFormat {
 
 /// properties of a floating-point implementation
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[allow(clippy::manual_non_exhaustive)]
 pub struct PlatformProperties {
     /// sign of the canonical NaN
     pub canonical_nan_sign: Sign,
@@ -1348,9 +1900,9 @@ pub struct PlatformProperties {
     pub round_to_integral_nan_propagation_mode: UnaryNaNPropagationMode,
     /// NaN payload propagation mode for `next_up_or_down`, `next_up`, and `next_down`
     pub next_up_or_down_nan_propagation_mode: UnaryNaNPropagationMode,
-    /// NaN payload propagation mode for `scale_b`
+    /// NaN payload propagation mode for `scale_b` and `pown`
     pub scale_b_nan_propagation_mode: UnaryNaNPropagationMode,
-    /// NaN payload propagation mode for `sqrt`
+    /// NaN payload propagation mode for `sqrt` and `cbrt`
     pub sqrt_nan_propagation_mode: UnaryNaNPropagationMode,
     /// NaN payload propagation mode for float-to-float conversions
     pub float_to_float_conversion_nan_propagation_mode: FloatToFloatConversionNaNPropagationMode,
@@ -1602,10 +2154,38 @@ impl PlatformProperties {
             QuietNaNFormat::MIPSLegacy
         }
     }
+    /// construct a `PlatformProperties` from `self` in "default NaN" mode,
+    /// like ARM's `FPSCR.DN` bit, where every NaN result is the canonical NaN.
+    ///
+    /// all the `*_nan_propagation_mode` fields are set to `AlwaysCanonical`,
+    /// and `fma_inf_zero_qnan_result` is changed from
+    /// `PropagateAndGenerateInvalid` to `CanonicalAndGenerateInvalid`, so
+    /// whether `INVALID_OPERATION` is signaled stays the same.
+    pub fn with_default_nan_mode(self) -> Self {
+        let fma_inf_zero_qnan_result = match self.fma_inf_zero_qnan_result {
+            FMAInfZeroQNaNResult::PropagateAndGenerateInvalid => {
+                FMAInfZeroQNaNResult::CanonicalAndGenerateInvalid
+            }
+            result => result,
+        };
+        Self {
+            std_bin_ops_nan_propagation_mode: BinaryNaNPropagationMode::AlwaysCanonical,
+            fma_nan_propagation_mode: TernaryNaNPropagationMode::AlwaysCanonical,
+            fma_inf_zero_qnan_result,
+            round_to_integral_nan_propagation_mode: UnaryNaNPropagationMode::AlwaysCanonical,
+            next_up_or_down_nan_propagation_mode: UnaryNaNPropagationMode::AlwaysCanonical,
+            scale_b_nan_propagation_mode: UnaryNaNPropagationMode::AlwaysCanonical,
+            sqrt_nan_propagation_mode: UnaryNaNPropagationMode::AlwaysCanonical,
+            float_to_float_conversion_nan_propagation_mode:
+                FloatToFloatConversionNaNPropagationMode::AlwaysCanonical,
+            rsqrt_nan_propagation_mode: UnaryNaNPropagationMode::AlwaysCanonical,
+            ..self
+        }
+    }
 }
 
 /// `FloatProperties` values incompatible: must be equal
-#[derive(Clone, Debug, Default)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct FloatPropertiesIncompatible;
 
 impl fmt::Display for FloatPropertiesIncompatible {
@@ -1614,6 +2194,7 @@ impl fmt::Display for FloatPropertiesIncompatible {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for FloatPropertiesIncompatible {}
 
 #[cfg(feature = "python")]
@@ -1630,6 +2211,8 @@ pub struct FloatProperties {
     mantissa_width: usize,
     has_implicit_leading_bit: bool,
     has_sign_bit: bool,
+    has_inf_nan: bool,
+    unnormals_are_invalid: bool,
     platform_properties: PlatformProperties,
 }
 
@@ -1657,6 +2240,8 @@ impl FloatProperties {
             mantissa_width,
             has_implicit_leading_bit,
             has_sign_bit,
+            has_inf_nan: true,
+            unnormals_are_invalid: false,
             platform_properties,
         }
     }
@@ -1668,6 +2253,8 @@ impl FloatProperties {
             mantissa_width,
             has_implicit_leading_bit: true,
             has_sign_bit: true,
+            has_inf_nan: true,
+            unnormals_are_invalid: false,
             platform_properties: PlatformProperties::default(),
         }
     }
@@ -1683,10 +2270,14 @@ impl FloatProperties {
             mantissa_width,
             has_implicit_leading_bit: true,
             has_sign_bit: true,
+            has_inf_nan: true,
+            unnormals_are_invalid: false,
             platform_properties,
         }
     }
     /// `FloatProperties` for standard [__binary16__ format](https://en.wikipedia.org/wiki/Half-precision_floating-point_format)
+    ///
+    /// see `arm_alt_half` for ARM's variant without infinities and NaNs.
     pub const STANDARD_16: Self =
         Self::standard_16_with_platform_properties(PlatformProperties::default());
     /// `FloatProperties` for standard [__binary32__ format](https://en.wikipedia.org/wiki/Single-precision_floating-point_format)
@@ -1722,7 +2313,44 @@ impl FloatProperties {
     ) -> Self {
         Self::new_with_platform_properties(15, 112, platform_properties)
     }
+    /// `FloatProperties` for the [x87 80-bit extended precision format](https://en.wikipedia.org/wiki/Extended_precision#x86_extended_precision_format),
+    /// which has an explicit integer bit instead of an implicit leading bit.
+    ///
+    /// unnormals are invalid operands, like on the 80387 and later, see
+    /// `unnormals_are_invalid`.
+    pub const fn x87_extended_with_platform_properties(
+        platform_properties: PlatformProperties,
+    ) -> Self {
+        Self::new_with_extended_flags(15, 64, false, true, platform_properties)
+            .with_unnormals_are_invalid(true)
+    }
+    /// `FloatProperties` for the [x87 80-bit extended precision format](https://en.wikipedia.org/wiki/Extended_precision#x86_extended_precision_format),
+    /// which has an explicit integer bit instead of an implicit leading bit
+    pub const fn x87_extended() -> Self {
+        Self::x87_extended_with_platform_properties(PlatformProperties::default())
+    }
+    /// `FloatProperties` for ARM's [alternative half-precision format](https://en.wikipedia.org/wiki/Half-precision_floating-point_format#ARM_alternative_half-precision),
+    /// selected by the `AHP` bit of the VFP `FPSCR` register.
+    ///
+    /// it has the same layout as `STANDARD_16`, but doesn't have infinities
+    /// or NaNs: the all-ones exponent field is used for normal values,
+    /// extending the largest finite value to `131008`, and overflow saturates
+    /// to the largest finite value.
+    pub const fn arm_alt_half_with_platform_properties(
+        platform_properties: PlatformProperties,
+    ) -> Self {
+        Self::standard_16_with_platform_properties(platform_properties).with_has_inf_nan(false)
+    }
+    /// `FloatProperties` for ARM's [alternative half-precision format](https://en.wikipedia.org/wiki/Half-precision_floating-point_format#ARM_alternative_half-precision),
+    /// see `arm_alt_half_with_platform_properties`.
+    pub const fn arm_alt_half() -> Self {
+        Self::arm_alt_half_with_platform_properties(PlatformProperties::default())
+    }
     /// construct `FloatProperties` for standard `width`-bit binary interchange format, if it exists
+    ///
+    /// the standard formats are the 16, 32, 64, and 128-bit formats, and
+    /// every multiple of 32 bits wider than 128 bits, which use IEEE 754's
+    /// formula `exponent_width = round(4 * log2(width)) - 13`.
     #[inline]
     pub fn standard_with_platform_properties(
         width: usize,
@@ -1750,8 +2378,12 @@ impl FloatProperties {
                 platform_properties,
             )),
             _ => {
-                if width > 128 && width.is_multiple_of(&32) {
-                    let exponent_width = ((width as f64).log2() * 4.0).round() as usize - 13;
+                if width > 128 && Integer::is_multiple_of(&width, &32) {
+                    // `round(4 * log2(width))` is `n` when
+                    // `2^(2 * n - 1) <= width^8 < 2^(2 * n + 1)`, which is
+                    // half the bit length of `width^8` rounded down. `width^8`
+                    // is never an odd power of 2, so there are no ties.
+                    let exponent_width = BigUint::from(width).pow(8u32).bits() / 2 - 13;
                     Some(Self::new_with_platform_properties(
                         exponent_width,
                         width - exponent_width - 1,
@@ -1768,6 +2400,18 @@ impl FloatProperties {
     pub fn standard(width: usize) -> Option<Self> {
         Self::standard_with_platform_properties(width, PlatformProperties::default())
     }
+    /// the widths of the standard binary interchange formats that don't need
+    /// the formula for widths above 128 bits
+    pub const BASE_STANDARD_WIDTHS: [usize; 4] = [16, 32, 64, 128];
+    /// iterate through the widths for which `standard` returns `Some`, in
+    /// increasing order. the iterator is infinite, since there's a standard
+    /// format for every multiple of 32 bits wider than 128 bits.
+    pub fn standard_widths() -> impl Iterator<Item = usize> {
+        Self::BASE_STANDARD_WIDTHS
+            .iter()
+            .copied()
+            .chain((160..).step_by(32))
+    }
     /// check if `self` is a standard binary interchange format.
     #[inline]
     pub fn is_standard(self) -> bool {
@@ -1794,6 +2438,41 @@ impl FloatProperties {
     pub const fn has_sign_bit(self) -> bool {
         self.has_sign_bit
     }
+    /// if the floating-point format has infinities and NaNs.
+    ///
+    /// formats without infinities and NaNs use the all-ones exponent field
+    /// for normal numbers, and saturate to the largest finite value on overflow.
+    #[inline]
+    pub const fn has_inf_nan(self) -> bool {
+        self.has_inf_nan
+    }
+    /// construct a `FloatProperties` from `self` with `has_inf_nan` replaced.
+    #[inline]
+    pub const fn with_has_inf_nan(self, has_inf_nan: bool) -> Self {
+        Self {
+            has_inf_nan,
+            ..self
+        }
+    }
+    /// if unnormals (including pseudo-zeros), pseudo-infinities, and
+    /// pseudo-NaNs are invalid operands in the floating-point format, so they
+    /// are classified as signaling NaNs. otherwise they are classified by
+    /// their value, like on the 8087 and 80287.
+    ///
+    /// only formats without an implicit leading bit have these encodings. this
+    /// is `true` for `x87_extended`, matching the 80387 and later.
+    #[inline]
+    pub const fn unnormals_are_invalid(self) -> bool {
+        self.unnormals_are_invalid
+    }
+    /// construct a `FloatProperties` from `self` with `unnormals_are_invalid` replaced.
+    #[inline]
+    pub const fn with_unnormals_are_invalid(self, unnormals_are_invalid: bool) -> Self {
+        Self {
+            unnormals_are_invalid,
+            ..self
+        }
+    }
     /// get the `PlatformProperties`
     #[inline]
     pub const fn platform_properties(self) -> PlatformProperties {
@@ -1882,6 +2561,22 @@ impl FloatProperties {
     pub fn mantissa_field_msb_mask<Bits: FloatBitsType>(self) -> Bits {
         Bits::one() << self.mantissa_field_msb_shift()
     }
+    /// get the amount by which the floating-point bits should be shifted right
+    /// in order to extract the MSB of the fraction (the part of the mantissa
+    /// field after the radix point). The fraction's MSB selects between quiet
+    /// and signaling NaNs.
+    ///
+    /// this is the same as `mantissa_field_msb_shift` for formats with an implicit leading bit.
+    #[inline]
+    pub const fn fraction_msb_shift(self) -> usize {
+        self.fraction_width() - 1
+    }
+    /// get the bitwise mask for the fraction's MSB (before shifting to extract).
+    ///
+    /// this is the same as `mantissa_field_msb_mask` for formats with an implicit leading bit.
+    pub fn fraction_msb_mask<Bits: FloatBitsType>(self) -> Bits {
+        Bits::one() << self.fraction_msb_shift()
+    }
     /// get the amount by which the exponent field is offset from the
     /// mathematical exponent for normal floating-point numbers.
     ///
@@ -1896,6 +2591,9 @@ impl FloatProperties {
         }
     }
     /// get the value used in the exponent field for infinities and NaNs
+    ///
+    /// if the format doesn't have infinities and NaNs, this exponent field
+    /// value is used for normal floating-point numbers instead.
     pub fn exponent_inf_nan<Bits: FloatBitsType>(self) -> Bits {
         (Bits::one() << self.exponent_width) - Bits::one()
     }
@@ -1917,7 +2615,203 @@ impl FloatProperties {
     /// floating-point numbers are related by the following equation:
     /// `mathematical_exponent + exponent_bias == exponent_field`
     pub fn exponent_max_normal<Bits: FloatBitsType>(self) -> Bits {
-        self.exponent_inf_nan::<Bits>() - Bits::one()
+        if self.has_inf_nan {
+            self.exponent_inf_nan::<Bits>() - Bits::one()
+        } else {
+            self.exponent_inf_nan::<Bits>()
+        }
+    }
+    /// `const` version of `exponent_bias` for formats where
+    /// `exponent_width <= 64`, so the result fits in a `u64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `exponent_width > 64`.
+    pub const fn exponent_bias_u64(self) -> u64 {
+        assert!(self.exponent_width <= 64, "exponent_width too big for u64");
+        if self.exponent_width == 0 {
+            0
+        } else {
+            (1 << (self.exponent_width - 1)) - 1
+        }
+    }
+    /// `const` version of `exponent_inf_nan` for formats where
+    /// `exponent_width <= 64`, so the result fits in a `u64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `exponent_width > 64`.
+    pub const fn exponent_inf_nan_u64(self) -> u64 {
+        assert!(self.exponent_width <= 64, "exponent_width too big for u64");
+        if self.exponent_width == 0 {
+            0
+        } else {
+            u64::MAX >> (64 - self.exponent_width)
+        }
+    }
+    /// `const` version of `exponent_max_normal` for formats where
+    /// `exponent_width <= 64`, so the result fits in a `u64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `exponent_width > 64`.
+    pub const fn exponent_max_normal_u64(self) -> u64 {
+        if self.has_inf_nan {
+            self.exponent_inf_nan_u64() - 1
+        } else {
+            self.exponent_inf_nan_u64()
+        }
+    }
+    /// get the minimum and maximum mathematical exponents of normal
+    /// floating-point numbers, the exponents of `min_positive_normal` and of
+    /// `max_finite_value`'s leading bit.
+    ///
+    /// `(-126, 127)` for `STANDARD_32`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the exponents don't fit in `i64`.
+    pub fn normal_exponent_range(self) -> (i64, i64) {
+        let exponent_bias: BigInt = self.exponent_bias::<BigUint>().into();
+        let to_exponent = |exponent_field: BigUint| {
+            (BigInt::from(exponent_field) - &exponent_bias)
+                .to_i64()
+                .expect("exponent doesn't fit in i64")
+        };
+        (
+            to_exponent(self.exponent_min_normal()),
+            to_exponent(self.exponent_max_normal()),
+        )
+    }
+    /// get the minimum and maximum mathematical exponents of all finite
+    /// non-zero values, including subnormals: the exponents of
+    /// `min_positive_subnormal` and of `max_finite_value`'s leading bit.
+    ///
+    /// `(-149, 127)` for `STANDARD_32`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the exponents don't fit in `i64`.
+    pub fn mathematical_exponent_range(self) -> (i64, i64) {
+        let (min_normal, max_normal) = self.normal_exponent_range();
+        let fraction_width = self
+            .fraction_width()
+            .to_i64()
+            .expect("fraction_width doesn't fit in i64");
+        (
+            min_normal
+                .checked_sub(fraction_width)
+                .expect("exponent doesn't fit in i64"),
+            max_normal,
+        )
+    }
+    /// get the largest finite value of this format
+    pub fn max_finite_value(self) -> Ratio<BigInt> {
+        Float::signed_max_normal_with_traits(Sign::Positive, self)
+            .to_ratio()
+            .expect("known to be finite")
+    }
+    /// get the smallest positive normal value of this format
+    pub fn min_positive_normal(self) -> Ratio<BigInt> {
+        let mut value = Float::positive_zero_with_traits(self);
+        value.set_mantissa_field(self.mantissa_field_normal_min());
+        value.set_exponent_field(self.exponent_min_normal());
+        value.to_ratio().expect("known to be finite")
+    }
+    /// get the smallest positive subnormal value of this format
+    pub fn min_positive_subnormal(self) -> Ratio<BigInt> {
+        Float::signed_min_subnormal_with_traits(Sign::Positive, self)
+            .to_ratio()
+            .expect("known to be finite")
+    }
+    /// get the unit roundoff, `2^-(fraction_width + 1)`: the bound on the
+    /// relative error of rounding to nearest (ignoring underflow and
+    /// overflow). It's half of the machine epsilon.
+    pub fn unit_roundoff(self) -> Ratio<BigInt> {
+        Ratio::new(BigInt::one(), BigInt::one() << (self.fraction_width() + 1))
+    }
+    /// get the approximate number of decimal orders of magnitude between
+    /// `min_positive_subnormal` and `max_finite_value`, which is
+    /// `log10(max_finite_value / min_positive_subnormal)`.
+    #[cfg(feature = "std")]
+    pub fn dynamic_range_decades(self) -> f64 {
+        let range = (self.max_finite_value() / self.min_positive_subnormal()).to_integer();
+        // keep as many bits as fit in `f64` so the conversion can't overflow
+        let shift = range.bits().saturating_sub(f64::MANTISSA_DIGITS as usize);
+        let truncated_range = (range >> shift).to_f64().expect("known to fit in f64");
+        (truncated_range.log2() + shift as f64) * std::f64::consts::LOG10_2
+    }
+    /// get the format with the same exponent range but only
+    /// `significand_bits` bits of precision (including the leading bit)
+    fn with_significand_bits(self, significand_bits: usize) -> Self {
+        assert!(significand_bits != 0, "significand_bits must be non-zero");
+        let mantissa_width = if self.has_implicit_leading_bit() {
+            significand_bits - 1
+        } else {
+            significand_bits
+        };
+        FloatProperties::new_with_extended_flags(
+            self.exponent_width(),
+            mantissa_width,
+            self.has_implicit_leading_bit(),
+            self.has_sign_bit(),
+            self.platform_properties(),
+        )
+        .with_has_inf_nan(self.has_inf_nan())
+        .with_unnormals_are_invalid(self.unnormals_are_invalid())
+    }
+    /// check if this format can represent `value` exactly, ignoring the sign
+    fn can_represent_magnitude(self, value: &Ratio<BigInt>) -> bool {
+        if value.abs() > self.max_finite_value() {
+            return false;
+        }
+        let quanta = value.abs() / self.min_positive_subnormal();
+        if !quanta.is_integer() {
+            return false;
+        }
+        let mut significand = quanta.to_integer();
+        while !significand.is_zero() && significand.is_even() {
+            significand >>= 1;
+        }
+        significand.bits() <= self.fraction_width() + 1
+    }
+    /// check if this format can exactly represent the product of every pair
+    /// of values, the first in format `a` and the second in format `b`, such
+    /// as when picking an accumulator format for mixed-precision arithmetic.
+    ///
+    /// the product of the largest finite values needs the most precision and
+    /// range, and the product of the smallest subnormals needs the smallest
+    /// exponent, so only those are checked. negative products need a sign
+    /// bit and infinite or NaN products need infinities and NaNs.
+    pub fn can_represent_exact_product(self, a: FloatProperties, b: FloatProperties) -> bool {
+        if (a.has_sign_bit() || b.has_sign_bit()) && !self.has_sign_bit() {
+            return false;
+        }
+        if (a.has_inf_nan() || b.has_inf_nan()) && !self.has_inf_nan() {
+            return false;
+        }
+        self.can_represent_magnitude(&(a.max_finite_value() * b.max_finite_value()))
+            && self
+                .can_represent_magnitude(&(a.min_positive_subnormal() * b.min_positive_subnormal()))
+    }
+    /// check if this format can exactly represent the sum of every pair of
+    /// values, the first in format `a` and the second in format `b`.
+    ///
+    /// the sum of the largest finite values needs the most range, and the
+    /// sums of the largest finite value of one format and the smallest
+    /// subnormal of the other need the most precision, so only those are
+    /// checked. negative sums need a sign bit and infinite or NaN sums need
+    /// infinities and NaNs.
+    pub fn can_represent_exact_sum(self, a: FloatProperties, b: FloatProperties) -> bool {
+        if (a.has_sign_bit() || b.has_sign_bit()) && !self.has_sign_bit() {
+            return false;
+        }
+        if (a.has_inf_nan() || b.has_inf_nan()) && !self.has_inf_nan() {
+            return false;
+        }
+        self.can_represent_magnitude(&(a.max_finite_value() + b.max_finite_value()))
+            && self.can_represent_magnitude(&(a.max_finite_value() + b.min_positive_subnormal()))
+            && self.can_represent_magnitude(&(a.min_positive_subnormal() + b.max_finite_value()))
     }
     /// get the mask for the whole floating-point format
     pub fn overall_mask<Bits: FloatBitsType>(self) -> Bits {
@@ -1931,6 +2825,8 @@ impl FloatProperties {
             .field("mantissa_width", &self.mantissa_width())
             .field("has_implicit_leading_bit", &self.has_implicit_leading_bit())
             .field("has_sign_bit", &self.has_sign_bit())
+            .field("has_inf_nan", &self.has_inf_nan())
+            .field("unnormals_are_invalid", &self.unnormals_are_invalid())
             .field("platform_properties", &self.platform_properties())
             .field("quiet_nan_format", &self.quiet_nan_format())
             .field("width", &self.width())
@@ -2032,6 +2928,15 @@ pub struct F128Traits;
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
 pub struct F128WithPlatformPropertiesTraits(pub PlatformProperties);
 
+/// `FloatTraits` where `Bits = u128` and `properties` returns `FloatProperties::x87_extended()`
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug, Default)]
+pub struct F80Traits;
+
+/// `FloatTraits` where `Bits = u128` and `properties` returns
+/// `FloatProperties::x87_extended_with_platform_properties(self.0)`
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
+pub struct F80WithPlatformPropertiesTraits(pub PlatformProperties);
+
 impl FloatTraits for FloatProperties {
     type Bits = BigUint;
     fn properties(&self) -> FloatProperties {
@@ -2095,6 +3000,20 @@ impl FloatTraits for F128WithPlatformPropertiesTraits {
     }
 }
 
+impl FloatTraits for F80Traits {
+    type Bits = u128;
+    fn properties(&self) -> FloatProperties {
+        FloatProperties::x87_extended()
+    }
+}
+
+impl FloatTraits for F80WithPlatformPropertiesTraits {
+    type Bits = u128;
+    fn properties(&self) -> FloatProperties {
+        FloatProperties::x87_extended_with_platform_properties(self.0)
+    }
+}
+
 struct RoundedMantissa {
     inexact: bool,
     exponent: i64,
@@ -2146,55 +3065,29 @@ impl RoundedMantissa {
                 upper_float_mantissa >>= 1;
                 upper_float_exponent += 1;
             }
-            match (rounding_mode, sign) {
-                (RoundingMode::TiesToEven, _) | (RoundingMode::TiesToAway, _) => {
-                    match remainder_in_ulps.cmp(&RealAlgebraicNumber::from(Ratio::new(1, 2))) {
-                        Ordering::Less => Self {
-                            inexact: true,
-                            exponent: lower_float_exponent,
-                            mantissa: lower_float_mantissa,
-                        },
-                        Ordering::Equal => {
-                            if rounding_mode == RoundingMode::TiesToAway
-                                || lower_float_mantissa.is_odd()
-                            {
-                                Self {
-                                    inexact: true,
-                                    exponent: upper_float_exponent,
-                                    mantissa: upper_float_mantissa,
-                                }
-                            } else {
-                                Self {
-                                    inexact: true,
-                                    exponent: lower_float_exponent,
-                                    mantissa: lower_float_mantissa,
-                                }
-                            }
-                        }
-                        Ordering::Greater => Self {
-                            inexact: true,
-                            exponent: upper_float_exponent,
-                            mantissa: upper_float_mantissa,
-                        },
+            let round_up = match rounding_mode.rounds_away_on_tie(lower_float_mantissa.is_odd()) {
+                Some(away_on_tie) => {
+                    match remainder_in_ulps.cmp(&Ratio::new(BigInt::one(), BigInt::from(2)).into())
+                    {
+                        Ordering::Less => false,
+                        Ordering::Equal => away_on_tie,
+                        Ordering::Greater => true,
                     }
                 }
-                (RoundingMode::TowardZero, _) => Self {
-                    inexact: true,
-                    exponent: lower_float_exponent,
-                    mantissa: lower_float_mantissa,
-                },
-                (RoundingMode::TowardNegative, Sign::Negative)
-                | (RoundingMode::TowardPositive, Sign::Positive) => Self {
+                None => rounding_mode.rounds_away_on_overflow(sign),
+            };
+            if round_up {
+                Self {
                     inexact: true,
                     exponent: upper_float_exponent,
                     mantissa: upper_float_mantissa,
-                },
-                (RoundingMode::TowardNegative, Sign::Positive)
-                | (RoundingMode::TowardPositive, Sign::Negative) => Self {
+                }
+            } else {
+                Self {
                     inexact: true,
                     exponent: lower_float_exponent,
                     mantissa: lower_float_mantissa,
-                },
+                }
             }
         }
     }
@@ -2211,6 +3104,7 @@ python_enum! {
     }
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for UpOrDown {
     fn default() -> Self {
         Self::Up
@@ -2226,6 +3120,193 @@ impl From<UpOrDown> for Sign {
     }
 }
 
+python_enum! {
+    #[pyenum(module = simple_soft_float, repr = u8, test_fn = test_op_kind_enum)]
+    /// the kinds of floating-point operations, used by `OpKind::possible_flags`
+    pub enum OpKind {
+        /// `add`
+        Add,
+        /// `sub`
+        Sub,
+        /// `mul`
+        Mul,
+        /// `div`
+        Div,
+        /// `ieee754_remainder`
+        IEEE754Remainder,
+        /// `fused_mul_add`
+        FusedMulAdd,
+        /// `round_to_integer` and the `to_*` integer conversions
+        RoundToInteger,
+        /// `round_to_integral`
+        RoundToIntegral,
+        /// `next_up_or_down`, `next_up`, and `next_down`
+        NextUpOrDown,
+        /// `log_b`
+        LogB,
+        /// `scale_b`
+        ScaleB,
+        /// `sqrt`
+        Sqrt,
+        /// `rsqrt`
+        RSqrt,
+        /// `pown`
+        PowN,
+        /// `cbrt`
+        Cbrt,
+        /// `hypot`
+        Hypot,
+        /// `neg`, `abs`, and `copy_sign`
+        SignOp,
+        /// `compare`, `compare_quiet`, and `compare_signaling`
+        Compare,
+        /// float-to-float conversions
+        ConvertFromFloat,
+        /// integer-to-float conversions
+        ConvertFromInt,
+        /// `from_real_algebraic_number`
+        FromRealAlgebraicNumber,
+    }
+}
+
+impl OpKind {
+    /// get the union of all the `StatusFlags` that the operation can signal,
+    /// for any inputs, `FPState`, and `PlatformProperties`.
+    pub fn possible_flags(self) -> StatusFlags {
+        let invalid_operation = StatusFlags::empty().signal_invalid_operation();
+        let division_by_zero = StatusFlags::empty().signal_division_by_zero();
+        let overflow = StatusFlags::empty().signal_overflow_with_inexact();
+        let underflow = StatusFlags::empty().signal_underflow_with_inexact();
+        let inexact = StatusFlags::empty().signal_inexact();
+        match self {
+            OpKind::Add
+            | OpKind::Sub
+            | OpKind::Mul
+            | OpKind::FusedMulAdd
+            | OpKind::ScaleB
+            | OpKind::Hypot
+            | OpKind::ConvertFromFloat => invalid_operation.merge(overflow).merge(underflow),
+            OpKind::Div | OpKind::PowN => invalid_operation
+                .merge(division_by_zero)
+                .merge(overflow)
+                .merge(underflow),
+            OpKind::IEEE754Remainder => {
+                invalid_operation.merge(StatusFlags::empty().signal_underflow())
+            }
+            OpKind::RoundToInteger | OpKind::RoundToIntegral | OpKind::Sqrt | OpKind::Cbrt => {
+                invalid_operation.merge(inexact)
+            }
+            OpKind::RSqrt => invalid_operation.merge(division_by_zero).merge(inexact),
+            OpKind::NextUpOrDown | OpKind::LogB | OpKind::Compare => invalid_operation,
+            OpKind::SignOp => StatusFlags::empty(),
+            OpKind::ConvertFromInt => overflow,
+            OpKind::FromRealAlgebraicNumber => overflow.merge(underflow),
+        }
+    }
+}
+
+/// a floating-point operation that produces a `Float` from `Float` operands,
+/// along with any non-`Float` arguments it takes, used by `Float::apply` and
+/// `DynamicFloat::apply`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FloatOp {
+    /// `add`
+    Add,
+    /// `sub`
+    Sub,
+    /// `mul`
+    Mul,
+    /// `div`
+    Div,
+    /// `ieee754_remainder`
+    IEEE754Remainder,
+    /// `fused_mul_add`
+    FusedMulAdd,
+    /// `round_to_integral` with `exact` set to `false`, rounding with the
+    /// contained `RoundingMode` instead of the one passed to `apply`
+    RoundToIntegral(RoundingMode),
+    /// `round_to_integral` with `exact` set to `true`
+    RoundToIntegralExact,
+    /// `next_up`
+    NextUp,
+    /// `next_down`
+    NextDown,
+    /// `scale_b`
+    ScaleB(i64),
+    /// `sqrt`
+    #[cfg(not(feature = "rational-only"))]
+    Sqrt,
+    /// `rsqrt`
+    #[cfg(not(feature = "rational-only"))]
+    RSqrt,
+    /// `pown`
+    PowN(i64),
+    /// `cbrt`
+    #[cfg(not(feature = "rational-only"))]
+    Cbrt,
+    /// `hypot`
+    #[cfg(not(feature = "rational-only"))]
+    Hypot,
+    /// `neg`
+    Neg,
+    /// `abs`
+    Abs,
+    /// `copy_sign`
+    CopySign,
+}
+
+impl FloatOp {
+    /// get the number of `Float` operands the operation takes, including `self`
+    /// in `Float::apply`
+    pub fn operand_count(self) -> usize {
+        match self {
+            FloatOp::RoundToIntegral(_)
+            | FloatOp::RoundToIntegralExact
+            | FloatOp::NextUp
+            | FloatOp::NextDown
+            | FloatOp::ScaleB(_)
+            | FloatOp::PowN(_)
+            | FloatOp::Neg
+            | FloatOp::Abs => 1,
+            #[cfg(not(feature = "rational-only"))]
+            FloatOp::Sqrt | FloatOp::RSqrt | FloatOp::Cbrt => 1,
+            FloatOp::Add
+            | FloatOp::Sub
+            | FloatOp::Mul
+            | FloatOp::Div
+            | FloatOp::IEEE754Remainder
+            | FloatOp::CopySign => 2,
+            #[cfg(not(feature = "rational-only"))]
+            FloatOp::Hypot => 2,
+            FloatOp::FusedMulAdd => 3,
+        }
+    }
+    /// get the `OpKind` of the operation
+    pub fn kind(self) -> OpKind {
+        match self {
+            FloatOp::Add => OpKind::Add,
+            FloatOp::Sub => OpKind::Sub,
+            FloatOp::Mul => OpKind::Mul,
+            FloatOp::Div => OpKind::Div,
+            FloatOp::IEEE754Remainder => OpKind::IEEE754Remainder,
+            FloatOp::FusedMulAdd => OpKind::FusedMulAdd,
+            FloatOp::RoundToIntegral(_) | FloatOp::RoundToIntegralExact => OpKind::RoundToIntegral,
+            FloatOp::NextUp | FloatOp::NextDown => OpKind::NextUpOrDown,
+            FloatOp::ScaleB(_) => OpKind::ScaleB,
+            #[cfg(not(feature = "rational-only"))]
+            FloatOp::Sqrt => OpKind::Sqrt,
+            #[cfg(not(feature = "rational-only"))]
+            FloatOp::RSqrt => OpKind::RSqrt,
+            FloatOp::PowN(_) => OpKind::PowN,
+            #[cfg(not(feature = "rational-only"))]
+            FloatOp::Cbrt => OpKind::Cbrt,
+            #[cfg(not(feature = "rational-only"))]
+            FloatOp::Hypot => OpKind::Hypot,
+            FloatOp::Neg | FloatOp::Abs | FloatOp::CopySign => OpKind::SignOp,
+        }
+    }
+}
+
 /// the floating-point type with the specified `FloatTraits`
 #[derive(Copy, Clone)]
 pub struct Float<FT: FloatTraits> {
@@ -2249,7 +3330,7 @@ macro_rules! impl_from_int_type {
             traits: FT,
         ) -> Self {
             Self::from_real_algebraic_number_with_traits(
-                &value.into(),
+                &BigInt::from(value).into(),
                 rounding_mode,
                 fp_state,
                 traits,
@@ -2279,7 +3360,9 @@ macro_rules! impl_to_int_type {
             fp_state: Option<&mut FPState>,
         ) -> Option<$int> {
             let mut default_fp_state = FPState::default();
-            let fp_state = fp_state.unwrap_or(&mut default_fp_state);
+            let mut fp_state_guard =
+                FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+            let fp_state: &mut FPState = &mut fp_state_guard;
             let old_status_flags = fp_state.status_flags;
             if let Some(retval) = self
                 .round_to_integer(exact, rounding_mode, Some(fp_state))
@@ -2329,6 +3412,21 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
     pub fn traits(&self) -> &FT {
         &self.traits
     }
+    /// reinterpret the bits of `self` using the format specified by `traits`,
+    /// returning `None` if the bits don't fit in the new format.
+    ///
+    /// this is a bit reinterpretation (like type-punning a register), not a
+    /// value conversion: use `convert_to_float_with_traits` to convert values.
+    pub fn reinterpret_bits_as<FT2: FloatTraits<Bits = Bits>>(
+        self,
+        traits: FT2,
+    ) -> Option<Float<FT2>> {
+        if traits.properties().overall_mask::<Bits>() & &self.bits == self.bits {
+            Some(Float::from_bits_and_traits(self.bits, traits))
+        } else {
+            None
+        }
+    }
     /// get the bits and `FloatTraits`
     pub fn into_bits_and_traits(self) -> (Bits, FT) {
         (self.bits, self.traits)
@@ -2341,6 +3439,68 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
     pub fn into_traits(self) -> FT {
         self.traits
     }
+    /// get the underlying bits as a little-endian byte array.
+    ///
+    /// the returned array has `ceil(width / 8)` bytes, the unused bits in the last byte are zero.
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        let byte_count = self.properties().width().div_ceil(8);
+        let bits: BigInt = self.bits.clone().into();
+        let (_, mut retval) = bits.to_bytes_le();
+        retval.resize(byte_count, 0);
+        retval
+    }
+    /// get the underlying bits as a big-endian byte array.
+    ///
+    /// the returned array has `ceil(width / 8)` bytes, the unused bits in the first byte are zero.
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        let mut retval = self.to_le_bytes();
+        retval.reverse();
+        retval
+    }
+    /// construct `Float` from a little-endian byte array.
+    ///
+    /// returns `None` if `bytes` doesn't have exactly `ceil(width / 8)` bytes
+    /// or if the value is out of range.
+    pub fn from_le_bytes_with_traits(bytes: &[u8], traits: FT) -> Option<Self> {
+        let properties = traits.properties();
+        if bytes.len() != properties.width().div_ceil(8) {
+            return None;
+        }
+        let bits = Bits::from_bigint(&BigUint::from_bytes_le(bytes).into())?;
+        if properties.overall_mask::<Bits>() & &bits != bits {
+            return None;
+        }
+        Some(Self::from_bits_and_traits(bits, traits))
+    }
+    /// construct `Float` from a little-endian byte array.
+    ///
+    /// returns `None` if `bytes` doesn't have exactly `ceil(width / 8)` bytes
+    /// or if the value is out of range.
+    pub fn from_le_bytes(bytes: &[u8]) -> Option<Self>
+    where
+        FT: Default,
+    {
+        Self::from_le_bytes_with_traits(bytes, FT::default())
+    }
+    /// construct `Float` from a big-endian byte array.
+    ///
+    /// returns `None` if `bytes` doesn't have exactly `ceil(width / 8)` bytes
+    /// or if the value is out of range.
+    pub fn from_be_bytes_with_traits(bytes: &[u8], traits: FT) -> Option<Self> {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        Self::from_le_bytes_with_traits(&bytes, traits)
+    }
+    /// construct `Float` from a big-endian byte array.
+    ///
+    /// returns `None` if `bytes` doesn't have exactly `ceil(width / 8)` bytes
+    /// or if the value is out of range.
+    pub fn from_be_bytes(bytes: &[u8]) -> Option<Self>
+    where
+        FT: Default,
+    {
+        Self::from_be_bytes_with_traits(bytes, FT::default())
+    }
     /// get the `FloatProperties`
     pub fn properties(&self) -> FloatProperties {
         self.traits.properties()
@@ -2368,6 +3528,11 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
         BitOrAssign::<&Bits>::bitor_assign(&mut self.bits, &bits);
         self.xor_bits(bits)
     }
+    fn bits_as_biguint(&self) -> BigUint {
+        Into::<BigInt>::into(self.bits.clone())
+            .to_biguint()
+            .expect("bits are never negative")
+    }
     /// set the sign
     pub fn set_sign(&mut self, sign: Sign) {
         let properties = self.properties();
@@ -2442,34 +3607,205 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
             self.and_not_bits(properties.mantissa_field_msb_mask());
         }
     }
+    /// get the sign, exponent field, and mantissa field all at once
+    pub fn decompose_fields(&self) -> (Sign, Bits, Bits) {
+        (self.sign(), self.exponent_field(), self.mantissa_field())
+    }
+    /// construct `Float` from the sign, exponent field, and mantissa field.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `exponent_field` or `mantissa_field` doesn't fit in its
+    /// field, or if `sign` is negative and the format doesn't have a sign bit.
+    pub fn compose_fields_with_traits(
+        sign: Sign,
+        exponent_field: Bits,
+        mantissa_field: Bits,
+        traits: FT,
+    ) -> Self {
+        let properties = traits.properties();
+        // check before shifting so high bits can't be shifted out of `Bits`
+        assert!(
+            exponent_field <= properties.exponent_inf_nan::<Bits>(),
+            "exponent out of range"
+        );
+        assert!(
+            mantissa_field <= properties.mantissa_field_max::<Bits>(),
+            "mantissa out of range"
+        );
+        let mut retval = Self::positive_zero_with_traits(traits);
+        retval.set_sign(sign);
+        retval.set_exponent_field(exponent_field);
+        retval.set_mantissa_field(mantissa_field);
+        retval
+    }
+    /// construct `Float` from the sign, exponent field, and mantissa field.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `exponent_field` or `mantissa_field` doesn't fit in its
+    /// field, or if `sign` is negative and the format doesn't have a sign bit.
+    pub fn compose_fields(sign: Sign, exponent_field: Bits, mantissa_field: Bits) -> Self
+    where
+        FT: Default,
+    {
+        Self::compose_fields_with_traits(sign, exponent_field, mantissa_field, FT::default())
+    }
+    /// get the bits of `self` in the IEEE 754 interchange format layout
+    /// (IEEE 754-2019 section 3.4): from MSB to LSB, the sign bit (if the
+    /// format has one), then the biased exponent field, then the mantissa
+    /// field (the trailing significand field, including the explicit leading
+    /// bit for formats without an implicit leading bit).
+    ///
+    /// all the formats that are currently supported store their fields in
+    /// that order, so this is the same as `bits()`, but it's the conversion
+    /// to use when exchanging values with hardware or files that might
+    /// store fields in a different order internally.
+    pub fn to_interchange_bits(&self) -> BigUint {
+        let properties = self.properties();
+        let (sign, exponent_field, mantissa_field) = self.decompose_fields();
+        let mut retval: BigUint = Into::<BigInt>::into(exponent_field)
+            .to_biguint()
+            .expect("known to be non-negative")
+            << properties.mantissa_width();
+        retval |= Into::<BigInt>::into(mantissa_field)
+            .to_biguint()
+            .expect("known to be non-negative");
+        if sign == Sign::Negative {
+            retval |= BigUint::one() << (properties.exponent_width() + properties.mantissa_width());
+        }
+        retval
+    }
+    /// construct `Float` from bits in the IEEE 754 interchange format layout.
+    ///
+    /// returns `None` if `bits` doesn't fit in `traits.properties().width()`
+    /// bits. see `to_interchange_bits`.
+    pub fn from_interchange_bits_with_traits(bits: &BigUint, traits: FT) -> Option<Self> {
+        let properties = traits.properties();
+        if bits.bits() > properties.width() {
+            return None;
+        }
+        let field = |shift: usize, width: usize| {
+            let mask = (BigUint::one() << width) - 1u32;
+            Bits::from_bigint(&((bits >> shift) & mask).into()).expect("field fits in Bits")
+        };
+        let mantissa_field = field(0, properties.mantissa_width());
+        let exponent_field = field(properties.mantissa_width(), properties.exponent_width());
+        let sign = if properties.has_sign_bit()
+            && !field(properties.exponent_width() + properties.mantissa_width(), 1).is_zero()
+        {
+            Sign::Negative
+        } else {
+            Sign::Positive
+        };
+        Some(Self::compose_fields_with_traits(
+            sign,
+            exponent_field,
+            mantissa_field,
+            traits,
+        ))
+    }
+    /// construct `Float` from bits in the IEEE 754 interchange format layout.
+    ///
+    /// see `from_interchange_bits_with_traits`.
+    pub fn from_interchange_bits(bits: &BigUint) -> Option<Self>
+    where
+        FT: Default,
+    {
+        Self::from_interchange_bits_with_traits(bits, FT::default())
+    }
+    fn fraction_msb(&self) -> bool {
+        let properties = self.properties();
+        !(properties.fraction_msb_mask::<Bits>() & &self.bits).is_zero()
+    }
+    fn set_fraction_msb(&mut self, fraction_msb: bool) {
+        let properties = self.properties();
+        if fraction_msb {
+            self.or_bits(properties.fraction_msb_mask());
+        } else {
+            self.and_not_bits(properties.fraction_msb_mask());
+        }
+    }
+    /// returns `true` if the fraction's MSB is in the state that makes a NaN
+    /// quiet, which is set for `QuietNaNFormat::Standard` and clear for
+    /// `QuietNaNFormat::MIPSLegacy`.
+    ///
+    /// doesn't check if `self` is a NaN.
+    pub fn is_quiet_bit_set(&self) -> bool {
+        self.properties()
+            .quiet_nan_format()
+            .is_nan_quiet(self.fraction_msb())
+    }
+    /// set the fraction's MSB to the state that makes a NaN quiet (if `quiet`
+    /// is `true`) or signaling (if `quiet` is `false`), handling the
+    /// inverted meaning for `QuietNaNFormat::MIPSLegacy`.
+    ///
+    /// doesn't check if `self` is a NaN. note that if the rest of the
+    /// fraction is zero, changing the quiet bit of a NaN can turn it into an
+    /// infinity.
+    pub fn set_quiet_bit(&mut self, quiet: bool) {
+        match self.properties().quiet_nan_format() {
+            QuietNaNFormat::Standard => self.set_fraction_msb(quiet),
+            QuietNaNFormat::MIPSLegacy => self.set_fraction_msb(!quiet),
+        }
+    }
+    /// set the explicit leading bit for infinities and NaNs in formats
+    /// without an implicit leading bit
+    fn set_inf_nan_explicit_leading_bit(&mut self) {
+        let properties = self.properties();
+        if !properties.has_implicit_leading_bit() {
+            self.or_bits(properties.mantissa_field_normal_min());
+        }
+    }
     /// calculate the `FloatClass`
+    ///
+    /// for formats without an implicit leading bit (such as x87's extended
+    /// format), if `FloatProperties::unnormals_are_invalid` is set, matching
+    /// the 80387 and later, the encodings where the exponent field is
+    /// non-zero but the explicit leading bit is clear are invalid operands
+    /// and are classified as signaling NaNs: pseudo-infinities and pseudo-NaNs
+    /// (where the exponent field is all ones) and unnormals (where it isn't).
+    /// otherwise, matching the 8087 and 80287, they are classified by their
+    /// value, so pseudo-infinities are infinities and pseudo-NaNs are NaNs.
+    /// see `is_unsupported_encoding`.
+    ///
+    /// pseudo-denormals (see `is_pseudo_denormal`) are classified as subnormal,
+    /// since x87 treats them like denormals.
     pub fn class(&self) -> FloatClass {
         let properties = self.properties();
         let sign = self.sign();
-        let mut exponent_field = self.exponent_field();
-        let mut mantissa_field = self.mantissa_field();
+        let exponent_field = self.exponent_field();
+        let mantissa_field = self.mantissa_field();
         let retval = if exponent_field == properties.exponent_zero_subnormal() {
             if mantissa_field.is_zero() {
                 FloatClass::PositiveZero
             } else {
                 FloatClass::PositiveSubnormal
             }
-        } else if exponent_field == properties.exponent_inf_nan() {
-            if mantissa_field.is_zero() {
-                FloatClass::PositiveInfinity
-            } else if properties
-                .quiet_nan_format()
-                .is_nan_quiet(self.mantissa_field_msb())
+        } else if properties.has_inf_nan() && exponent_field == properties.exponent_inf_nan() {
+            let explicit_leading_bit = properties.mantissa_field_normal_min::<Bits>();
+            if properties.unnormals_are_invalid()
+                && (mantissa_field.clone() & &explicit_leading_bit) != explicit_leading_bit
             {
+                FloatClass::SignalingNaN
+            } else if mantissa_field.is_zero() || mantissa_field == explicit_leading_bit {
+                FloatClass::PositiveInfinity
+            } else if self.is_quiet_bit_set() {
                 FloatClass::QuietNaN
             } else {
                 FloatClass::SignalingNaN
             }
-        } else if properties.has_implicit_leading_bit() {
+        } else if properties.has_implicit_leading_bit()
+            || !(properties.mantissa_field_normal_min::<Bits>() & &mantissa_field).is_zero()
+        {
             FloatClass::PositiveNormal
+        } else if properties.unnormals_are_invalid() {
+            FloatClass::SignalingNaN
         } else if mantissa_field.is_zero() {
             FloatClass::PositiveZero
         } else {
+            let mut exponent_field = exponent_field;
+            let mut mantissa_field = mantissa_field;
             loop {
                 if (properties.mantissa_field_msb_mask::<Bits>() & &mantissa_field).is_zero() {
                     mantissa_field <<= 1;
@@ -2487,6 +3823,96 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
             Sign::Negative => -retval,
         }
     }
+    /// return `true` if `self` is a pseudo-denormal: the exponent field is
+    /// zero but the explicit leading bit is set. only possible in formats
+    /// without an implicit leading bit.
+    ///
+    /// x87 accepts pseudo-denormals as operands, treating them as though the
+    /// exponent field was one.
+    pub fn is_pseudo_denormal(&self) -> bool {
+        let properties = self.properties();
+        !properties.has_implicit_leading_bit()
+            && self.exponent_field() == properties.exponent_zero_subnormal()
+            && !(properties.mantissa_field_normal_min::<Bits>() & self.mantissa_field()).is_zero()
+    }
+    /// return `true` if the explicit leading bit is clear even though the
+    /// exponent field is non-zero. only possible in formats without an
+    /// implicit leading bit.
+    fn is_missing_explicit_leading_bit(&self) -> bool {
+        let properties = self.properties();
+        !properties.has_implicit_leading_bit()
+            && self.exponent_field() != properties.exponent_zero_subnormal()
+            && (properties.mantissa_field_normal_min::<Bits>() & self.mantissa_field()).is_zero()
+    }
+    /// return `true` if `self` is an encoding that can only occur in formats
+    /// without an implicit leading bit, where the exponent field is non-zero
+    /// but the explicit leading bit is clear, and that is an invalid operand
+    /// because `FloatProperties::unnormals_are_invalid` is set:
+    /// * pseudo-infinities and pseudo-NaNs, which have the maximum exponent
+    ///   field.
+    /// * unnormals (including pseudo-zeros), which have any other non-zero
+    ///   exponent field.
+    ///
+    /// these are classified as signaling NaNs and converting them to a quiet
+    /// NaN produces the default NaN.
+    pub fn is_unsupported_encoding(&self) -> bool {
+        self.properties().unnormals_are_invalid() && self.is_missing_explicit_leading_bit()
+    }
+    /// return `true` if `self` is in its canonical encoding.
+    ///
+    /// for formats without an implicit leading bit, the explicit leading bit
+    /// must be set exactly when the exponent field is non-zero, so this
+    /// returns `false` for pseudo-denormals, unnormals, pseudo-infinities,
+    /// and pseudo-NaNs. all encodings of formats with an implicit leading bit
+    /// are canonical.
+    pub fn is_canonical(&self) -> bool {
+        !self.is_pseudo_denormal() && !self.is_missing_explicit_leading_bit()
+    }
+    /// get the canonical encoding of `self`, like IEEE 754's `canonicalize`.
+    ///
+    /// pseudo-denormals, and unnormals, pseudo-infinities, and quiet
+    /// pseudo-NaNs that aren't unsupported encodings, are converted to the
+    /// canonical encoding of the same value. like other computational
+    /// operations, signaling NaNs (including the unsupported encodings, see
+    /// `is_unsupported_encoding`) signal invalid operation and are converted
+    /// using `into_quiet_nan`, other quiet NaNs are returned unchanged so
+    /// their payloads are preserved. for formats with an implicit leading
+    /// bit, all other values are returned unchanged.
+    pub fn canonicalize(&self, fp_state: Option<&mut FPState>) -> Self {
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        if self.would_signal_as_operand() {
+            fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+            return self.to_quiet_nan();
+        }
+        let mut retval = self.clone();
+        if self.is_pseudo_denormal() {
+            retval.set_exponent_field(self.properties().exponent_min_normal());
+        } else if self.is_missing_explicit_leading_bit() && !self.is_finite() {
+            // pseudo-infinities and quiet pseudo-NaNs
+            retval.set_inf_nan_explicit_leading_bit();
+        } else if self.is_missing_explicit_leading_bit() {
+            let properties = self.properties();
+            let mut exponent_field = self.exponent_field();
+            let mut mantissa_field = self.mantissa_field();
+            while exponent_field != properties.exponent_min_normal()
+                && !mantissa_field.is_zero()
+                && (properties.mantissa_field_msb_mask::<Bits>() & &mantissa_field).is_zero()
+            {
+                mantissa_field <<= 1;
+                exponent_field -= Bits::one();
+            }
+            if (properties.mantissa_field_msb_mask::<Bits>() & &mantissa_field).is_zero() {
+                // subnormal or zero
+                exponent_field = properties.exponent_zero_subnormal();
+            }
+            retval.set_exponent_field(exponent_field);
+            retval.set_mantissa_field(mantissa_field);
+        }
+        retval
+    }
     /// return `true` if `self.class()` is `NegativeInfinity`
     #[inline]
     pub fn is_negative_infinity(&self) -> bool {
@@ -2537,6 +3963,15 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
     pub fn is_signaling_nan(&self) -> bool {
         self.class().is_signaling_nan()
     }
+    /// return `true` if using `self` as an operand of a computational
+    /// operation (such as `add` or `sqrt`) signals `INVALID`.
+    ///
+    /// this is `true` for signaling NaNs, including the unsupported encodings
+    /// (see `is_unsupported_encoding`) which are classified as signaling NaNs.
+    #[inline]
+    pub fn would_signal_as_operand(&self) -> bool {
+        self.class().would_signal_as_operand()
+    }
     /// return `true` if `self` is infinity
     #[inline]
     pub fn is_infinity(&self) -> bool {
@@ -2572,9 +4007,171 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
     pub fn is_subnormal_or_zero(&self) -> bool {
         self.class().is_subnormal_or_zero()
     }
+    /// return `true` if `self` is exactly `2^k` for some integer `k`.
+    ///
+    /// negative values, zeros, infinities, and NaNs all return `false`.
+    /// computed directly from the fields, without going through `to_ratio`.
+    pub fn is_power_of_two(&self) -> bool {
+        let mantissa_field = self.mantissa_field();
+        match self.class() {
+            FloatClass::PositiveNormal => {
+                mantissa_field == self.properties().mantissa_field_normal_min::<Bits>()
+            }
+            FloatClass::PositiveSubnormal => {
+                // the value is the mantissa field times a fixed power of two,
+                // pseudo-denormals included, so check for a single set bit
+                (mantissa_field.clone() & (mantissa_field - Bits::one())).is_zero()
+            }
+            _ => false,
+        }
+    }
     /// get the mathematical value of `self` as a `Ratio<BigInt>`.
     /// if `self` is NaN or infinite, returns `None`.
     pub fn to_ratio(&self) -> Option<Ratio<BigInt>> {
+        let (sign, numerator, denominator) = self.to_ratio_parts()?;
+        let retval = Ratio::new_raw(numerator.into(), denominator.into());
+        match sign {
+            Sign::Positive => Some(retval),
+            Sign::Negative => Some(-retval),
+        }
+    }
+    /// get the mathematical value of `self` as a sign, numerator, and
+    /// denominator, with the fraction in lowest terms. `-0.0` returns a
+    /// negative sign.
+    /// if `self` is NaN or infinite, returns `None`.
+    pub fn to_ratio_parts(&self) -> Option<(Sign, BigUint, BigUint)> {
+        let (sign, mut mantissa, mut exponent) = self.to_scientific_ratio()?;
+        if mantissa.is_zero() {
+            return Some((sign, mantissa, BigUint::one()));
+        }
+        // reduce to lowest terms, the denominator is a power of 2
+        while exponent.is_negative() && mantissa.is_even() {
+            mantissa >>= 1;
+            exponent += 1;
+        }
+        Some(if exponent.is_negative() {
+            let shift = (-exponent)
+                .to_usize()
+                .expect("exponent doesn't fit in usize");
+            (sign, mantissa, BigUint::one() << shift)
+        } else {
+            let shift = exponent.to_usize().expect("exponent doesn't fit in usize");
+            (sign, mantissa << shift, BigUint::one())
+        })
+    }
+    /// get the rational with the smallest denominator that rounds back to
+    /// `self` when rounding to nearest with ties to even, which is useful for
+    /// recognizing what "nice" value a bit pattern likely represents, such as
+    /// `1/10` for the `F32` closest to `0.1`. if several rationals have the
+    /// smallest denominator, the one with the smallest magnitude is returned.
+    ///
+    /// zeros return zero.
+    /// if `self` is NaN or infinite, returns `None`.
+    pub fn to_simplest_rational_within_ulp(&self) -> Option<Ratio<BigInt>> {
+        let value = self.to_ratio()?;
+        if value.is_zero() {
+            return Some(value);
+        }
+        // the interval is symmetric, so search using the magnitude
+        let magnitude = self.abs();
+        let magnitude_value = value.abs();
+        let two = BigInt::from(2);
+        let lower =
+            (&magnitude_value + magnitude.next_down(None).to_ratio_or_overflow_threshold()) / &two;
+        let upper =
+            (&magnitude_value + magnitude.next_up(None).to_ratio_or_overflow_threshold()) / &two;
+        // ties round to `self` exactly when its mantissa is even
+        let inclusive = self.mantissa_field().is_even();
+        let retval = Self::simplest_rational_in_interval(lower, inclusive, Some(upper), inclusive);
+        match self.sign() {
+            Sign::Positive => Some(retval),
+            Sign::Negative => Some(-retval),
+        }
+    }
+    /// find the rational with the smallest denominator (and then the smallest
+    /// numerator) between `lower` and `upper`, where `0 <= lower < upper` and
+    /// an `upper` of `None` is infinity.
+    ///
+    /// this is a descent of the Stern-Brocot tree, done by following the
+    /// continued-fraction expansions of both bounds until they differ.
+    fn simplest_rational_in_interval(
+        mut lower: Ratio<BigInt>,
+        mut lower_inclusive: bool,
+        mut upper: Option<Ratio<BigInt>>,
+        mut upper_inclusive: bool,
+    ) -> Ratio<BigInt> {
+        let mut terms = Vec::new();
+        let mut retval = loop {
+            let lower_floor = lower.floor();
+            let integer = if lower_inclusive && lower.is_integer() {
+                lower.clone()
+            } else {
+                &lower_floor + BigInt::one()
+            };
+            let upper_value = match upper {
+                Some(upper) if integer > upper || (integer == upper && !upper_inclusive) => upper,
+                _ => break integer,
+            };
+            // both bounds have the same integer part, so continue with the
+            // reciprocals of their fractional parts, which swaps them
+            let lower_fraction = lower - &lower_floor;
+            let upper_fraction = upper_value - &lower_floor;
+            terms.push(lower_floor);
+            lower = upper_fraction.recip();
+            upper = if lower_fraction.is_zero() {
+                None
+            } else {
+                Some(lower_fraction.recip())
+            };
+            core::mem::swap(&mut lower_inclusive, &mut upper_inclusive);
+        };
+        for term in terms.into_iter().rev() {
+            retval = term + retval.recip();
+        }
+        retval
+    }
+    /// get the value of `self` as the unevaluated sum `hi + lo` of two
+    /// `f64`s (a double-double), such as for passing `F128` values to
+    /// double-double libraries.
+    ///
+    /// `hi` is the `f64` nearest to `self` and `lo` is the `f64` nearest to
+    /// the rest, `self - hi`, both rounded with ties to even. zeros are
+    /// returned as `(self, 0.0)`, keeping the sign of zero in `hi`.
+    /// returns `None` if `self` is NaN or infinite, if `hi` overflows, or if
+    /// `hi + lo` isn't exactly `self` because `self` needs more than two
+    /// `f64`s.
+    pub fn to_double_double(&self) -> Option<(f64, f64)> {
+        let value = self.to_ratio()?;
+        if value.is_zero() {
+            let hi = match self.sign() {
+                Sign::Positive => 0.0,
+                Sign::Negative => -0.0,
+            };
+            return Some((hi, 0.0));
+        }
+        let nearest_f64 = |value: &Ratio<BigInt>| {
+            let mut fp_state = FPState::default();
+            let retval = F64::from_real_algebraic_number(
+                &value.clone().into(),
+                Some(RoundingMode::TiesToEven),
+                Some(&mut fp_state),
+            );
+            retval.to_ratio().map(|ratio| (retval, ratio))
+        };
+        let (hi, hi_value) = nearest_f64(&value)?;
+        let rest = value - hi_value;
+        let (lo, lo_value) = nearest_f64(&rest)?;
+        if lo_value != rest {
+            return None;
+        }
+        Some((hi.into(), lo.into()))
+    }
+    /// get the mathematical value of `self` as `(sign, mantissa, exponent)`
+    /// where the value is `sign * mantissa * 2^exponent`. `mantissa` is the
+    /// integer significand including the implicit leading bit (if any), so
+    /// it isn't normalized and `exponent` is the exponent of its LSB.
+    /// if `self` is NaN or infinite, returns `None`.
+    pub fn to_scientific_ratio(&self) -> Option<(Sign, BigUint, i64)> {
         if !self.is_finite() {
             return None;
         }
@@ -2582,17 +4179,20 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
         let sign = self.sign();
         let exponent_field = self.exponent_field();
         let mantissa_field = self.mantissa_field();
-        let mut mantissa: BigInt = mantissa_field.into();
+        let mut mantissa = Into::<BigInt>::into(mantissa_field)
+            .to_biguint()
+            .expect("mantissa_field is unsigned");
         let mut exponent = exponent_field
             .to_i64()
             .expect("exponent_field doesn't fit in i64");
-        if self.is_subnormal_or_zero() {
+        if exponent_field == properties.exponent_zero_subnormal() {
+            // subnormal, zero, or pseudo-denormal
             exponent = properties
                 .exponent_min_normal::<Bits>()
                 .to_i64()
                 .expect("exponent_field doesn't fit in i64");
         } else if properties.has_implicit_leading_bit() {
-            mantissa |= BigInt::one() << properties.fraction_width();
+            mantissa |= BigUint::one() << properties.fraction_width();
         }
         exponent -= properties
             .exponent_bias::<Bits>()
@@ -2602,19 +4202,64 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
             .fraction_width()
             .to_i64()
             .expect("fraction_width doesn't fit in i64");
-        let mut retval = if exponent.is_negative() {
-            let shift = (-exponent)
-                .to_usize()
-                .expect("exponent doesn't fit in usize");
-            Ratio::new(mantissa, BigInt::one() << shift)
-        } else {
-            Ratio::from(mantissa << exponent.to_usize().expect("exponent doesn't fit in usize"))
-        };
-        if sign == Sign::Negative {
-            retval = -retval;
+        Some((sign, mantissa, exponent))
+    }
+    /// get the value of `self` as an integer multiple of the smallest
+    /// positive subnormal value, `min_positive_subnormal`: the fixed-point
+    /// representation of `self` on the densest grid of the format.
+    ///
+    /// the results are ordered the same as the finite values, and the
+    /// difference of two results is the number of minimum-subnormal steps
+    /// between the values. both zeros return zero.
+    /// if `self` is NaN or infinite, returns `None`.
+    pub fn to_ulp_grid_integer(&self) -> Option<BigInt> {
+        let (sign, mantissa, exponent) = self.to_scientific_ratio()?;
+        let (exponent_min, _) = self.properties().mathematical_exponent_range();
+        let shift = (exponent - exponent_min)
+            .to_usize()
+            .expect("exponent is at least the minimum exponent");
+        let magnitude = BigInt::from(mantissa << shift);
+        Some(match sign {
+            Sign::Positive => magnitude,
+            Sign::Negative => -magnitude,
+        })
+    }
+    /// get the significand of `self` as an integer, including the implicit
+    /// leading bit (if any).
+    /// if `self` is NaN or infinite, returns `None`.
+    fn significand(&self) -> Option<BigUint> {
+        self.to_scientific_ratio()
+            .map(|(_, significand, _)| significand)
+    }
+    /// get the number of trailing zero bits in the significand of `self`,
+    /// which includes the implicit leading bit (if any).
+    /// zeros have all `fraction_width() + 1` significand bits zero.
+    /// if `self` is NaN or infinite, returns `None`.
+    pub fn mantissa_trailing_zeros(&self) -> Option<usize> {
+        let mut significand = self.significand()?;
+        if significand.is_zero() {
+            return Some(self.properties().fraction_width() + 1);
+        }
+        let mut retval = 0;
+        while significand.is_even() {
+            significand >>= 1;
+            retval += 1;
         }
         Some(retval)
     }
+    /// get the number of significant bits `self` uses: the number of bits
+    /// from the most significant one bit through the least significant one
+    /// bit of the significand. `self` can be converted exactly to a format
+    /// with at least that much precision, if the exponent is within range.
+    /// zeros use no bits.
+    /// if `self` is NaN or infinite, returns `None`.
+    pub fn significand_bit_length(&self) -> Option<usize> {
+        let significand = self.significand()?;
+        if significand.is_zero() {
+            return Some(0);
+        }
+        Some(significand.bits() - self.mantissa_trailing_zeros()?)
+    }
     /// get the mathematical value of `self` as a `RealAlgebraicNumber`.
     /// if `self` is NaN or infinite, returns `None`.
     pub fn to_real_algebraic_number(&self) -> Option<RealAlgebraicNumber> {
@@ -2664,6 +4309,7 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
         let properties = traits.properties();
         let mut retval = Self::positive_zero_with_traits(traits);
         retval.set_exponent_field(properties.exponent_inf_nan::<Bits>());
+        retval.set_inf_nan_explicit_leading_bit();
         retval
     }
     /// get the positive infinity value
@@ -2678,6 +4324,7 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
         let properties = traits.properties();
         let mut retval = Self::negative_zero_with_traits(traits);
         retval.set_exponent_field(properties.exponent_inf_nan::<Bits>());
+        retval.set_inf_nan_explicit_leading_bit();
         retval
     }
     /// get the negative infinity value
@@ -2701,18 +4348,37 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
     {
         Self::signed_infinity_with_traits(sign, FT::default())
     }
+    /// get the result for an exactly infinite result of a finite operand,
+    /// such as division by zero: the infinity with sign `sign`, or the
+    /// largest finite value with sign `sign` if the format doesn't have
+    /// infinities.
+    fn signed_infinite_result_with_traits(sign: Sign, traits: FT) -> Self {
+        if traits.properties().has_inf_nan() {
+            Self::signed_infinity_with_traits(sign, traits)
+        } else {
+            Self::signed_max_normal_with_traits(sign, traits)
+        }
+    }
     /// get the canonical quiet NaN, which is also just the canonical NaN
+    ///
+    /// the sign is `canonical_nan_sign` from `PlatformProperties`.
+    ///
+    /// returns positive zero if the format doesn't have NaNs.
     pub fn quiet_nan_with_traits(traits: FT) -> Self {
         let properties = traits.properties();
+        if !properties.has_inf_nan() {
+            return Self::positive_zero_with_traits(traits);
+        }
         let mut retval = Self::positive_zero_with_traits(traits);
+        if properties.has_sign_bit() {
+            retval.set_sign(properties.platform_properties().canonical_nan_sign);
+        }
         retval.set_exponent_field(properties.exponent_inf_nan::<Bits>());
-        match properties.quiet_nan_format() {
-            QuietNaNFormat::Standard => retval.set_mantissa_field_msb(true),
-            QuietNaNFormat::MIPSLegacy => {
-                retval.set_mantissa_field(properties.mantissa_field_max());
-                retval.set_mantissa_field_msb(false);
-            }
+        if properties.quiet_nan_format() == QuietNaNFormat::MIPSLegacy {
+            retval.set_mantissa_field(properties.mantissa_field_max());
         }
+        retval.set_quiet_bit(true);
+        retval.set_inf_nan_explicit_leading_bit();
         retval
     }
     /// get the canonical quiet NaN, which is also just the canonical NaN
@@ -2722,15 +4388,75 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
     {
         Self::quiet_nan_with_traits(FT::default())
     }
+    /// get the default NaN: the NaN generated by invalid operations and by
+    /// NaN propagation modes that produce the canonical NaN.
+    ///
+    /// it's built from the `canonical_nan_*` fields of `PlatformProperties`.
+    ///
+    /// returns positive zero if the format doesn't have NaNs.
+    pub fn default_nan_with_traits(traits: FT) -> Self {
+        let properties = traits.properties();
+        if !properties.has_inf_nan() {
+            return Self::positive_zero_with_traits(traits);
+        }
+        let platform_properties = properties.platform_properties();
+        let mut retval = Self::positive_zero_with_traits(traits);
+        if properties.has_sign_bit() {
+            retval.set_sign(platform_properties.canonical_nan_sign);
+        }
+        retval.set_exponent_field(properties.exponent_inf_nan::<Bits>());
+        let msb_mask = properties.fraction_msb_mask::<Bits>();
+        let mut mantissa = Bits::zero();
+        if platform_properties.canonical_nan_mantissa_msb {
+            mantissa |= msb_mask.clone();
+        }
+        if platform_properties.canonical_nan_mantissa_second_to_msb {
+            mantissa |= msb_mask.clone() >> 1;
+        }
+        if platform_properties.canonical_nan_mantissa_rest {
+            mantissa |= ((Bits::one() << properties.fraction_width()) - Bits::one()) >> 2;
+        }
+        retval.set_mantissa_field(mantissa);
+        retval.set_inf_nan_explicit_leading_bit();
+        retval
+    }
+    /// get the default NaN: the NaN generated by invalid operations and by
+    /// NaN propagation modes that produce the canonical NaN.
+    pub fn default_nan() -> Self
+    where
+        FT: Default,
+    {
+        Self::default_nan_with_traits(FT::default())
+    }
+    /// check if `self` is exactly the default NaN (see `default_nan`),
+    /// including its sign and payload. This is what conformance tests check
+    /// for after invalid operations, unlike `is_quiet_nan`, which accepts any
+    /// quiet NaN.
+    ///
+    /// always `false` if the format doesn't have NaNs.
+    pub fn matches_platform_default_nan(&self) -> bool {
+        self.is_nan() && self.bits == Self::default_nan_with_traits(self.traits.clone()).bits
+    }
     /// get the canonical signaling NaN
+    ///
+    /// the sign is `canonical_nan_sign` from `PlatformProperties`.
+    ///
+    /// returns positive zero if the format doesn't have NaNs.
     pub fn signaling_nan_with_traits(traits: FT) -> Self {
         let properties = traits.properties();
+        if !properties.has_inf_nan() {
+            return Self::positive_zero_with_traits(traits);
+        }
         let mut retval = Self::positive_zero_with_traits(traits);
+        if properties.has_sign_bit() {
+            retval.set_sign(properties.platform_properties().canonical_nan_sign);
+        }
         retval.set_exponent_field(properties.exponent_inf_nan::<Bits>());
         match properties.quiet_nan_format() {
             QuietNaNFormat::Standard => retval.set_mantissa_field(Bits::one()),
-            QuietNaNFormat::MIPSLegacy => retval.set_mantissa_field_msb(true),
+            QuietNaNFormat::MIPSLegacy => retval.set_quiet_bit(false),
         }
+        retval.set_inf_nan_explicit_leading_bit();
         retval
     }
     /// get the canonical signaling NaN
@@ -2740,21 +4466,135 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
     {
         Self::signaling_nan_with_traits(FT::default())
     }
-    /// convert `self` into a quiet NaN
-    pub fn into_quiet_nan(mut self) -> Self {
-        let properties = self.properties();
-        self.set_exponent_field(properties.exponent_inf_nan::<Bits>());
-        // FIXME: handle nan propagation properly
-        match properties.quiet_nan_format() {
-            QuietNaNFormat::Standard => self.set_mantissa_field_msb(true),
-            QuietNaNFormat::MIPSLegacy => return Self::quiet_nan_with_traits(self.traits),
-        }
-        self
+    /// construct a signaling NaN with sign `sign` and `payload` stored in the
+    /// bits of the fraction below the quiet bit, the same bits that
+    /// `nan_box_with_traits` uses, such as for creating specific signaling NaN
+    /// operands for tests.
+    ///
+    /// returns `None` if the format doesn't have NaNs, `sign` is negative and
+    /// the format doesn't have a sign bit, `payload` is more than
+    /// `nan_box_payload_max_with_traits`, or the result wouldn't be a
+    /// signaling NaN. with `QuietNaNFormat::Standard` a payload of zero would
+    /// be an infinity, so it returns `None`; with `QuietNaNFormat::MIPSLegacy`
+    /// the quiet bit is set for signaling NaNs, so every payload works.
+    pub fn signaling_nan_with_payload_with_traits(
+        sign: Sign,
+        payload: Bits,
+        traits: FT,
+    ) -> Option<Self> {
+        let properties = traits.properties();
+        if !properties.has_inf_nan()
+            || (sign == Sign::Negative && !properties.has_sign_bit())
+            || payload > Self::nan_box_payload_max_with_traits(traits.clone())
+        {
+            return None;
+        }
+        let mut retval = Self::signed_zero_with_traits(sign, traits);
+        retval.set_exponent_field(properties.exponent_inf_nan::<Bits>());
+        retval.set_mantissa_field(payload);
+        retval.set_quiet_bit(false);
+        retval.set_inf_nan_explicit_leading_bit();
+        if retval.is_signaling_nan() && !retval.is_unsupported_encoding() {
+            Some(retval)
+        } else {
+            None
+        }
+    }
+    /// construct a signaling NaN with a specific sign and payload.
+    ///
+    /// see `signaling_nan_with_payload_with_traits`.
+    pub fn signaling_nan_with_payload(sign: Sign, payload: Bits) -> Option<Self>
+    where
+        FT: Default,
+    {
+        Self::signaling_nan_with_payload_with_traits(sign, payload, FT::default())
+    }
+    /// convert `self` into a quiet NaN
+    pub fn into_quiet_nan(mut self) -> Self {
+        let properties = self.properties();
+        if !properties.has_inf_nan() {
+            return Self::quiet_nan_with_traits(self.traits);
+        }
+        if self.is_unsupported_encoding() {
+            return Self::default_nan_with_traits(self.traits);
+        }
+        self.set_exponent_field(properties.exponent_inf_nan::<Bits>());
+        // FIXME: handle nan propagation properly
+        match properties.quiet_nan_format() {
+            QuietNaNFormat::Standard => self.set_quiet_bit(true),
+            QuietNaNFormat::MIPSLegacy => return Self::quiet_nan_with_traits(self.traits),
+        }
+        self.set_inf_nan_explicit_leading_bit();
+        self
     }
     /// convert `self` into a quiet NaN
     pub fn to_quiet_nan(&self) -> Self {
         self.clone().into_quiet_nan()
     }
+    /// get the largest payload that `nan_box_with_traits` can store: all the
+    /// bits of the fraction below the quiet bit.
+    pub fn nan_box_payload_max_with_traits(traits: FT) -> Bits {
+        let properties = traits.properties();
+        properties.fraction_msb_mask::<Bits>() - Bits::one()
+    }
+    /// get the largest payload that `nan_box` can store.
+    pub fn nan_box_payload_max() -> Bits
+    where
+        FT: Default,
+    {
+        Self::nan_box_payload_max_with_traits(FT::default())
+    }
+    /// store `payload` in the bits below the quiet bit of a positive quiet
+    /// NaN, such as for NaN-boxing pointers in a language runtime. use
+    /// `nan_unbox` to get the payload back. whether arithmetic keeps the
+    /// payload depends on the NaN propagation modes in `PlatformProperties`.
+    ///
+    /// returns `None` if the format doesn't have NaNs or `payload` is more
+    /// than `nan_box_payload_max_with_traits`.
+    ///
+    /// with `QuietNaNFormat::MIPSLegacy`, quiet NaNs have the quiet bit clear,
+    /// so a payload of zero would be an infinity and returns `None`. also,
+    /// `into_quiet_nan` and NaN propagation produce the canonical NaN for
+    /// that format, so only `nan_box` itself should be used to create boxed
+    /// values.
+    pub fn nan_box_with_traits(payload: Bits, traits: FT) -> Option<Self> {
+        let properties = traits.properties();
+        if !properties.has_inf_nan()
+            || payload > Self::nan_box_payload_max_with_traits(traits.clone())
+        {
+            return None;
+        }
+        let mut retval = Self::positive_zero_with_traits(traits);
+        retval.set_exponent_field(properties.exponent_inf_nan::<Bits>());
+        retval.set_mantissa_field(payload);
+        retval.set_quiet_bit(true);
+        retval.set_inf_nan_explicit_leading_bit();
+        if retval.class() == FloatClass::QuietNaN {
+            Some(retval)
+        } else {
+            None
+        }
+    }
+    /// store `payload` in a quiet NaN.
+    ///
+    /// see `nan_box_with_traits`.
+    pub fn nan_box(payload: Bits) -> Option<Self>
+    where
+        FT: Default,
+    {
+        Self::nan_box_with_traits(payload, FT::default())
+    }
+    /// get the payload stored by `nan_box_with_traits`: the bits of the
+    /// fraction below the quiet bit.
+    ///
+    /// returns `None` if `self` isn't a quiet NaN. the sign is ignored.
+    pub fn nan_unbox(&self) -> Option<Bits> {
+        if self.class() != FloatClass::QuietNaN && self.class() != -FloatClass::QuietNaN {
+            return None;
+        }
+        let payload_max = Self::nan_box_payload_max_with_traits(self.traits.clone());
+        Some(self.mantissa_field() & payload_max)
+    }
     /// get the largest finite value with sign `sign`
     pub fn signed_max_normal_with_traits(sign: Sign, traits: FT) -> Self {
         let properties = traits.properties();
@@ -2785,6 +4625,17 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
     {
         Self::signed_min_subnormal_with_traits(sign, FT::default())
     }
+    /// replace `self` with a zero of the same sign, signaling `UNDERFLOW`
+    /// and `INEXACT`, if `self` is subnormal and `fp_state`'s
+    /// `flush_to_zero_mode` flushes the results of operations of kind `op`
+    fn flush_to_zero(self, op: OpKind, fp_state: &mut FPState) -> Self {
+        if self.is_subnormal() && fp_state.flush_to_zero_mode.flushes(op) {
+            fp_state.status_flags = fp_state.status_flags.signal_underflow_with_inexact();
+            Self::signed_zero_with_traits(self.sign(), self.traits)
+        } else {
+            self
+        }
+    }
     /// round from a `RealAlgebraicNumber` into a floating-point value.
     pub fn from_real_algebraic_number_with_traits(
         value: &RealAlgebraicNumber,
@@ -2793,7 +4644,76 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
         traits: FT,
     ) -> Self {
         let mut default_fp_state = FPState::default();
-        let fp_state = fp_state.unwrap_or(&mut default_fp_state);
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        Self::round_from_real_algebraic_number_with_traits(
+            value,
+            rounding_mode,
+            Some(fp_state),
+            traits,
+        )
+        .flush_to_zero(OpKind::FromRealAlgebraicNumber, fp_state)
+    }
+    /// get the reduced-precision format that `fp_state`'s precision control
+    /// rounds results in format `properties` to, or `None` if precision
+    /// control doesn't affect `properties`.
+    fn precision_control_properties(
+        properties: FloatProperties,
+        fp_state: &FPState,
+    ) -> Option<FloatProperties> {
+        match fp_state.precision_control {
+            Some(precision_control)
+                if precision_control != 0
+                    && precision_control < properties.fraction_width() + 1 =>
+            {
+                Some(properties.with_significand_bits(precision_control))
+            }
+            _ => None,
+        }
+    }
+    /// round the result of an arithmetic operation (`add`, `sub`, `mul`,
+    /// `div`, `sqrt`, or `fused_mul_add`), which, unlike the other
+    /// operations, are subject to `fp_state`'s precision control.
+    fn round_arithmetic_result(
+        value: &RealAlgebraicNumber,
+        rounding_mode: RoundingMode,
+        fp_state: &mut FPState,
+        traits: FT,
+    ) -> Self {
+        if let Some(reduced_properties) =
+            Self::precision_control_properties(traits.properties(), fp_state)
+        {
+            let reduced = Float::round_from_real_algebraic_number_with_traits(
+                value,
+                Some(rounding_mode),
+                Some(fp_state),
+                reduced_properties,
+            );
+            // converting back is exact, since `traits` has more precision
+            Self::convert_from_float_with_traits(&reduced, None, None, traits)
+        } else {
+            Self::round_from_real_algebraic_number_with_traits(
+                value,
+                Some(rounding_mode),
+                Some(fp_state),
+                traits,
+            )
+        }
+    }
+    /// like `from_real_algebraic_number_with_traits`, except that the result
+    /// is never flushed to zero, used to implement the other operations, which
+    /// do their own flushing.
+    fn round_from_real_algebraic_number_with_traits(
+        value: &RealAlgebraicNumber,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+        traits: FT,
+    ) -> Self {
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
         let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
         let properties = traits.properties();
         let sign = if value.is_positive() {
@@ -2823,18 +4743,13 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
             - exponent_bias_i64;
         if exponent > exponent_max {
             fp_state.status_flags = fp_state.status_flags.signal_overflow_with_inexact();
-            match (rounding_mode, sign) {
-                (RoundingMode::TowardNegative, Sign::Positive)
-                | (RoundingMode::TowardPositive, Sign::Negative)
-                | (RoundingMode::TowardZero, _) => {
-                    return Self::signed_max_normal_with_traits(sign, traits);
-                }
-                (RoundingMode::TowardNegative, Sign::Negative)
-                | (RoundingMode::TowardPositive, Sign::Positive)
-                | (RoundingMode::TiesToEven, _)
-                | (RoundingMode::TiesToAway, _) => {
-                    return Self::signed_infinity_with_traits(sign, traits);
-                }
+            if !properties.has_inf_nan() {
+                return Self::signed_max_normal_with_traits(sign, traits);
+            }
+            if rounding_mode.rounds_away_on_overflow(sign) {
+                return Self::signed_infinity_with_traits(sign, traits);
+            } else {
+                return Self::signed_max_normal_with_traits(sign, traits);
             }
         }
         let exponent_min = properties
@@ -2893,6 +4808,9 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
         } else if retval_exponent > exponent_max {
             assert!(inexact);
             fp_state.status_flags = fp_state.status_flags.signal_overflow_with_inexact();
+            if !properties.has_inf_nan() {
+                return Self::signed_max_normal_with_traits(sign, traits);
+            }
             return Self::signed_infinity_with_traits(sign, traits);
         } else if inexact {
             fp_state.status_flags = fp_state.status_flags.signal_inexact();
@@ -2929,1935 +4847,11060 @@ impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> Float<FT> {
     {
         Self::from_real_algebraic_number_with_traits(value, rounding_mode, fp_state, FT::default())
     }
-    fn add_or_sub(
-        &self,
-        rhs: &Self,
-        rounding_mode: Option<RoundingMode>,
+    /// get the two values that `value` rounds to when rounding toward
+    /// negative and toward positive, along with how `value` compares to the
+    /// midpoint between them, so custom rounding policies (such as stochastic
+    /// rounding) can be implemented on top.
+    ///
+    /// if `value` is exactly representable, both neighbors are equal and the
+    /// ordering is `Equal`. if `value` is beyond the largest finite value, the
+    /// outer neighbor is infinity (for formats with infinities) and the
+    /// midpoint is the overflow threshold used by rounding to nearest.
+    pub fn round_neighbors_with_traits(
+        value: &RealAlgebraicNumber,
+        traits: FT,
+    ) -> (Self, Self, Ordering) {
+        let round = |rounding_mode| {
+            Self::from_real_algebraic_number_with_traits(
+                value,
+                Some(rounding_mode),
+                None,
+                traits.clone(),
+            )
+        };
+        let down = round(RoundingMode::TowardNegative);
+        let up = round(RoundingMode::TowardPositive);
+        let midpoint = (down.to_ratio_or_overflow_threshold()
+            + up.to_ratio_or_overflow_threshold())
+            / BigInt::from(2);
+        let ordering = value.cmp(&midpoint.into());
+        (down, up, ordering)
+    }
+    /// return `true` if rounding `value` gives different results depending on
+    /// the rounding mode, which happens exactly when `value` lies strictly
+    /// between two neighbors (see `round_neighbors_with_traits`), including
+    /// between the largest finite value and infinity. returns `false` if
+    /// `value` is exactly representable.
+    ///
+    /// useful for deciding which test vectors need to be checked with every
+    /// rounding mode.
+    pub fn is_rounding_mode_sensitive_with_traits(value: &RealAlgebraicNumber, traits: FT) -> bool {
+        let (down, up, _) = Self::round_neighbors_with_traits(value, traits);
+        down.bits() != up.bits()
+    }
+    /// return `true` if rounding `value` gives different results depending on
+    /// the rounding mode.
+    ///
+    /// see `is_rounding_mode_sensitive_with_traits`.
+    pub fn is_rounding_mode_sensitive(value: &RealAlgebraicNumber) -> bool
+    where
+        FT: Default,
+    {
+        Self::is_rounding_mode_sensitive_with_traits(value, FT::default())
+    }
+    /// like `to_ratio`, except that infinities are replaced with the value just
+    /// past the largest finite value. not meaningful for NaNs.
+    fn to_ratio_or_overflow_threshold(&self) -> Ratio<BigInt> {
+        self.to_ratio().unwrap_or_else(|| {
+            let properties = self.properties();
+            let exponent_max =
+                properties.exponent_max_normal::<BigUint>() - properties.exponent_bias::<BigUint>();
+            let exponent_max = exponent_max
+                .to_usize()
+                .expect("exponent_max doesn't fit in usize");
+            let threshold = Ratio::from(BigInt::one() << (exponent_max + 1));
+            match self.sign() {
+                Sign::Positive => threshold,
+                Sign::Negative => -threshold,
+            }
+        })
+    }
+    /// round from a `RealAlgebraicNumber` into a floating-point value using
+    /// stochastic rounding: if `value` isn't exactly representable, round up
+    /// to the upper neighbor (see `round_neighbors_with_traits`) with
+    /// probability equal to how far `value` is from the lower neighbor
+    /// relative to the distance between the neighbors, otherwise round down.
+    ///
+    /// the probability is exact: `rng` is used to lazily generate the bits
+    /// of a uniform random number in `[0, 1)` until it can be compared with
+    /// the fractional position. `fp_state`'s rounding mode is ignored, the
+    /// status flags are signaled the same as rounding toward the selected
+    /// neighbor.
+    #[cfg(feature = "rand")]
+    pub fn from_real_algebraic_number_stochastic_with_traits<R: rand_core::RngCore + ?Sized>(
+        value: &RealAlgebraicNumber,
+        rng: &mut R,
         fp_state: Option<&mut FPState>,
-        is_sub: bool,
+        traits: FT,
     ) -> Self {
-        assert_eq!(self.traits, rhs.traits);
-        let properties = self.properties();
-        let mut default_fp_state = FPState::default();
-        let fp_state = fp_state.unwrap_or(&mut default_fp_state);
-        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
-        let self_class = self.class();
-        let mut rhs_class = rhs.class();
-        if is_sub {
-            rhs_class = -rhs_class;
-        }
-        match (self_class, rhs_class) {
-            (FloatClass::SignalingNaN, _)
-            | (FloatClass::QuietNaN, _)
-            | (_, FloatClass::SignalingNaN)
-            | (_, FloatClass::QuietNaN) => {
-                if self_class.is_signaling_nan() || rhs_class.is_signaling_nan() {
-                    fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-                }
-                match properties
-                    .platform_properties
-                    .std_bin_ops_nan_propagation_mode
-                    .calculate_propagation_results(self_class, rhs_class)
-                {
-                    BinaryNaNPropagationResults::First => self.to_quiet_nan(),
-                    BinaryNaNPropagationResults::Second => rhs.to_quiet_nan(),
-                    BinaryNaNPropagationResults::Canonical => {
-                        Self::quiet_nan_with_traits(self.traits.clone())
+        let (down, up, _) = Self::round_neighbors_with_traits(value, traits.clone());
+        let rounding_mode = if down.bits() == up.bits() {
+            // exact, no randomness needed
+            RoundingMode::TowardNegative
+        } else {
+            let down_value: RealAlgebraicNumber = down.to_ratio_or_overflow_threshold().into();
+            let up_value: RealAlgebraicNumber = up.to_ratio_or_overflow_threshold().into();
+            let mut position = (value - &down_value) / (up_value - &down_value);
+            let scale = RealAlgebraicNumber::from(BigInt::one() << 64);
+            loop {
+                let scaled = position * &scale;
+                let scaled_floor = scaled.to_integer_floor();
+                match BigInt::from(rng.next_u64()).cmp(&scaled_floor) {
+                    Ordering::Less => break RoundingMode::TowardPositive,
+                    Ordering::Greater => break RoundingMode::TowardNegative,
+                    Ordering::Equal => {
+                        position = scaled - RealAlgebraicNumber::from(scaled_floor);
+                        if position.is_zero() {
+                            break RoundingMode::TowardNegative;
+                        }
                     }
                 }
             }
-            (FloatClass::NegativeInfinity, FloatClass::PositiveInfinity)
-            | (FloatClass::PositiveInfinity, FloatClass::NegativeInfinity) => {
-                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-                Self::quiet_nan_with_traits(self.traits.clone())
-            }
-            (FloatClass::PositiveInfinity, _) | (_, FloatClass::PositiveInfinity) => {
-                Self::positive_infinity_with_traits(self.traits.clone())
+        };
+        Self::from_real_algebraic_number_with_traits(value, Some(rounding_mode), fp_state, traits)
+    }
+    /// round from a `RealAlgebraicNumber` into a floating-point value using
+    /// stochastic rounding.
+    ///
+    /// see `from_real_algebraic_number_stochastic_with_traits`.
+    #[cfg(feature = "rand")]
+    pub fn from_real_algebraic_number_stochastic<R: rand_core::RngCore + ?Sized>(
+        value: &RealAlgebraicNumber,
+        rng: &mut R,
+        fp_state: Option<&mut FPState>,
+    ) -> Self
+    where
+        FT: Default,
+    {
+        Self::from_real_algebraic_number_stochastic_with_traits(value, rng, fp_state, FT::default())
+    }
+    /// get the two values that `value` rounds to when rounding toward
+    /// negative and toward positive, along with how `value` compares to the
+    /// midpoint between them.
+    ///
+    /// see `round_neighbors_with_traits`.
+    pub fn round_neighbors(value: &RealAlgebraicNumber) -> (Self, Self, Ordering)
+    where
+        FT: Default,
+    {
+        Self::round_neighbors_with_traits(value, FT::default())
+    }
+    /// convert `self` to a hexadecimal string, like IEEE 754's `convertToHexCharacter`.
+    ///
+    /// finite values are formatted like `-0x1.8p+1`, subnormals use a leading
+    /// digit of `0` and the minimum exponent, infinities are formatted as
+    /// `inf` or `-inf`, and NaNs are formatted as `nan(0x<mantissa_field>)` or
+    /// `snan(0x<mantissa_field>)`, preceded by `-` if the sign bit is set.
+    pub fn to_hex_string(&self) -> String {
+        let properties = self.properties();
+        let class = self.class();
+        let sign = match self.sign() {
+            Sign::Positive => "",
+            Sign::Negative => "-",
+        };
+        if class.is_infinity() {
+            return format!("{}inf", sign);
+        } else if class.is_nan() {
+            let name = if class.is_signaling_nan() {
+                "snan"
+            } else {
+                "nan"
+            };
+            return format!("{}{}(0x{:x})", sign, name, self.mantissa_field());
+        }
+        let fraction_width = properties.fraction_width();
+        let exponent_field = self.exponent_field();
+        let exponent_bias = properties.exponent_bias::<Bits>();
+        let mut mantissa: BigInt = self.mantissa_field().into();
+        let exponent = if class.is_zero() {
+            0
+        } else if exponent_field == properties.exponent_zero_subnormal() {
+            properties
+                .exponent_min_normal::<Bits>()
+                .to_i64()
+                .expect("exponent_min_normal doesn't fit in i64")
+                - exponent_bias
+                    .to_i64()
+                    .expect("exponent_bias doesn't fit in i64")
+        } else {
+            if properties.has_implicit_leading_bit() {
+                mantissa |= BigInt::one() << fraction_width;
             }
-            (FloatClass::NegativeInfinity, _) | (_, FloatClass::NegativeInfinity) => {
-                Self::negative_infinity_with_traits(self.traits.clone())
+            exponent_field
+                .to_i64()
+                .expect("exponent_field doesn't fit in i64")
+                - exponent_bias
+                    .to_i64()
+                    .expect("exponent_bias doesn't fit in i64")
+        };
+        let integer_part = &mantissa >> fraction_width;
+        let fraction_digit_count = fraction_width.div_ceil(4);
+        let fraction = (mantissa - (&integer_part << fraction_width))
+            << (fraction_digit_count * 4 - fraction_width);
+        let fraction = format!("{:0width$x}", fraction, width = fraction_digit_count);
+        let fraction = fraction.trim_end_matches('0');
+        let point = if fraction.is_empty() { "" } else { "." };
+        format!(
+            "{}0x{:x}{}{}p{:+}",
+            sign, integer_part, point, fraction, exponent
+        )
+    }
+    /// convert `self` to a string of digits in base `radix`, like
+    /// `-101.011` or `0.1`.
+    ///
+    /// finite values are written in positional notation with lowercase
+    /// letters for digits above `9` and without trailing zeros after the
+    /// point. infinities and NaNs are spelled the same as by
+    /// `to_hex_string`.
+    ///
+    /// if `max_digits` is `Some`, the value is rounded to that many
+    /// significant digits, ties to even. otherwise the exact value is written
+    /// when `radix` is even (every finite binary value has a terminating
+    /// expansion in that case), and when `radix` is odd, enough significant
+    /// digits are written to identify the value uniquely. this is formatting,
+    /// so no status flags are signaled.
+    ///
+    /// # Panics
+    ///
+    /// panics if `radix` isn't in `2..=36` or `max_digits` is `Some(0)`.
+    pub fn to_string_radix(&self, radix: u32, max_digits: Option<usize>) -> String {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix must be in the range 2..=36"
+        );
+        assert_ne!(max_digits, Some(0), "max_digits must be at least 1");
+        let class = self.class();
+        if class.is_infinity() || class.is_nan() {
+            return self.to_hex_string();
+        }
+        let (sign, numerator, denominator) = self.to_ratio_parts().expect("known to be finite");
+        let sign = match sign {
+            Sign::Positive => "",
+            Sign::Negative => "-",
+        };
+        let radix_big = BigUint::from(radix);
+        // the written value is `digits * radix^-fraction_digit_count`
+        let (mut digits, mut fraction_digit_count): (BigUint, i64) = if numerator.is_zero() {
+            (BigUint::zero(), 0)
+        } else if max_digits.is_none() && radix.is_even() {
+            let mut digits = numerator;
+            let mut fraction_digit_count = 0;
+            while !(&digits % &denominator).is_zero() {
+                digits *= &radix_big;
+                fraction_digit_count += 1;
             }
-            (FloatClass::PositiveZero, FloatClass::PositiveZero) => {
-                Self::positive_zero_with_traits(self.traits.clone())
+            (digits / denominator, fraction_digit_count)
+        } else {
+            let max_digits = max_digits.unwrap_or_else(|| {
+                // radix^(n - 1) > 2^precision is enough to round-trip
+                let limit = BigUint::one() << (self.properties().fraction_width() + 1);
+                let mut power = BigUint::one();
+                let mut digit_count = 1;
+                while power <= limit {
+                    power *= &radix_big;
+                    digit_count += 1;
+                }
+                digit_count
+            });
+            // find `exponent` such that `radix^exponent <= value < radix^(exponent + 1)`
+            let integer_part = &numerator / &denominator;
+            let exponent: i64 = if integer_part.is_zero() {
+                let mut scaled = numerator.clone();
+                let mut exponent = 0;
+                while scaled < denominator {
+                    scaled *= &radix_big;
+                    exponent -= 1;
+                }
+                exponent
+            } else {
+                integer_part.to_str_radix(radix).len() as i64 - 1
+            };
+            let fraction_digit_count = max_digits as i64 - 1 - exponent;
+            let (scaled_numerator, scaled_denominator) = if fraction_digit_count < 0 {
+                let scale = num_traits::pow(radix_big.clone(), (-fraction_digit_count) as usize);
+                (numerator, denominator * scale)
+            } else {
+                let scale = num_traits::pow(radix_big.clone(), fraction_digit_count as usize);
+                (numerator * scale, denominator)
+            };
+            let (mut digits, remainder) = scaled_numerator.div_rem(&scaled_denominator);
+            match (remainder << 1).cmp(&scaled_denominator) {
+                Ordering::Less => {}
+                Ordering::Equal if digits.is_even() => {}
+                Ordering::Equal | Ordering::Greater => digits += 1u32,
             }
-            (FloatClass::NegativeZero, FloatClass::NegativeZero) => {
-                Self::negative_zero_with_traits(self.traits.clone())
+            (digits, fraction_digit_count)
+        };
+        if fraction_digit_count < 0 {
+            digits *= num_traits::pow(radix_big, (-fraction_digit_count) as usize);
+            fraction_digit_count = 0;
+        }
+        let fraction_digit_count = fraction_digit_count as usize;
+        let digits = digits.to_str_radix(radix);
+        let digits = if digits.len() <= fraction_digit_count {
+            format!("{:0>width$}", digits, width = fraction_digit_count + 1)
+        } else {
+            digits
+        };
+        let (integer_part, fraction) = digits.split_at(digits.len() - fraction_digit_count);
+        let fraction = fraction.trim_end_matches('0');
+        let point = if fraction.is_empty() { "" } else { "." };
+        format!("{}{}{}{}", sign, integer_part, point, fraction)
+    }
+    /// parse a hexadecimal string, like IEEE 754's `convertFromHexCharacter`.
+    ///
+    /// accepts the strings produced by `to_hex_string`, as well as `infinity`,
+    /// a leading `+`, uppercase letters, and a missing `p` exponent.
+    /// the value is rounded using `rounding_mode`.
+    ///
+    /// returns `None` if the string is invalid.
+    pub fn from_hex_str_with_traits(
+        text: &str,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+        traits: FT,
+    ) -> Option<Self> {
+        let properties = traits.properties();
+        let (sign, rest) = if let Some(rest) = text.strip_prefix('-') {
+            (Sign::Negative, rest)
+        } else {
+            (Sign::Positive, text.strip_prefix('+').unwrap_or(text))
+        };
+        let rest = rest.to_ascii_lowercase();
+        if properties.has_inf_nan() {
+            if rest == "inf" || rest == "infinity" {
+                if sign == Sign::Negative && !properties.has_sign_bit() {
+                    return None;
+                }
+                return Some(Self::signed_infinity_with_traits(sign, traits));
             }
-            _ => {
-                let lhs_value = self.to_real_algebraic_number().expect("known to be finite");
-                let rhs_value = rhs.to_real_algebraic_number().expect("known to be finite");
-                let result = if is_sub {
-                    lhs_value - rhs_value
+            let (nan, payload) = if let Some(payload) = rest.strip_prefix("snan") {
+                (Some(FloatClass::SignalingNaN), payload)
+            } else if let Some(payload) = rest.strip_prefix("nan") {
+                (Some(FloatClass::QuietNaN), payload)
+            } else {
+                (None, "")
+            };
+            if let Some(nan_class) = nan {
+                if sign == Sign::Negative && !properties.has_sign_bit() {
+                    return None;
+                }
+                let mut retval = if nan_class == FloatClass::SignalingNaN {
+                    Self::signaling_nan_with_traits(traits)
                 } else {
-                    lhs_value + rhs_value
+                    Self::quiet_nan_with_traits(traits)
                 };
-                if result.is_zero() {
-                    match rounding_mode {
-                        RoundingMode::TiesToEven
-                        | RoundingMode::TiesToAway
-                        | RoundingMode::TowardPositive
-                        | RoundingMode::TowardZero => {
-                            Self::positive_zero_with_traits(self.traits.clone())
-                        }
-                        RoundingMode::TowardNegative => {
-                            Self::negative_zero_with_traits(self.traits.clone())
-                        }
+                if properties.has_sign_bit() {
+                    retval.set_sign(sign);
+                }
+                if !payload.is_empty() {
+                    let payload = payload.strip_prefix("(0x")?.strip_suffix(')')?;
+                    let payload = BigUint::parse_bytes(payload.as_bytes(), 16)?;
+                    retval.set_mantissa_field(Bits::from_bigint(&payload.into())?);
+                    if retval.class() != nan_class {
+                        return None;
                     }
-                } else {
-                    Self::from_real_algebraic_number_with_traits(
-                        &result,
-                        Some(rounding_mode),
-                        Some(fp_state),
-                        self.traits.clone(),
-                    )
                 }
+                return Some(retval);
             }
         }
-    }
-    /// add floating-point numbers
-    pub fn add(
-        &self,
-        rhs: &Self,
-        rounding_mode: Option<RoundingMode>,
-        fp_state: Option<&mut FPState>,
-    ) -> Self {
-        self.add_or_sub(rhs, rounding_mode, fp_state, false)
-    }
-    /// subtract floating-point numbers
-    pub fn sub(
-        &self,
-        rhs: &Self,
-        rounding_mode: Option<RoundingMode>,
-        fp_state: Option<&mut FPState>,
-    ) -> Self {
-        self.add_or_sub(rhs, rounding_mode, fp_state, true)
-    }
-    /// multiply floating-point numbers
-    pub fn mul(
-        &self,
-        rhs: &Self,
+        let rest = rest.strip_prefix("0x")?;
+        let (digits, exponent) = match rest.find('p') {
+            Some(index) => (&rest[..index], rest[index + 1..].parse::<i64>().ok()?),
+            None => (rest, 0),
+        };
+        let (integer_digits, fraction_digits) = match digits.find('.') {
+            Some(index) => (&digits[..index], &digits[index + 1..]),
+            None => (digits, ""),
+        };
+        let all_digits = format!("{}{}", integer_digits, fraction_digits);
+        if all_digits.is_empty() || !all_digits.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+            return None;
+        }
+        let mantissa = BigInt::parse_bytes(all_digits.as_bytes(), 16)?;
+        if mantissa.is_zero() {
+            if !properties.has_sign_bit() {
+                return Some(Self::positive_zero_with_traits(traits));
+            }
+            return Some(Self::signed_zero_with_traits(sign, traits));
+        }
+        let exponent_bias = properties
+            .exponent_bias::<Bits>()
+            .to_i64()
+            .expect("exponent_bias doesn't fit in i64");
+        let exponent_max = properties
+            .exponent_max_normal::<Bits>()
+            .to_i64()
+            .expect("exponent_max_normal doesn't fit in i64")
+            - exponent_bias;
+        let exponent_min = properties
+            .exponent_min_normal::<Bits>()
+            .to_i64()
+            .expect("exponent_min_normal doesn't fit in i64")
+            - exponent_bias;
+        let fraction_digit_count = fraction_digits.len().to_i64()?;
+        let exponent = exponent.checked_sub(fraction_digit_count.checked_mul(4)?)?;
+        // clamp the exponent to a range that still rounds to the same value,
+        // to avoid creating huge numbers
+        let digit_bits = all_digits.len().to_i64()?.checked_mul(4)?;
+        let exponent = exponent.min(exponent_max + 2).max(
+            exponent_min
+                - properties
+                    .fraction_width()
+                    .to_i64()
+                    .expect("fraction_width doesn't fit in i64")
+                - 2
+                - digit_bits,
+        );
+        let mut value = if exponent < 0 {
+            Ratio::new(mantissa, BigInt::one() << (-exponent).to_usize()?)
+        } else {
+            Ratio::from(mantissa << exponent.to_usize()?)
+        };
+        if sign == Sign::Negative {
+            value = -value;
+        }
+        Some(Self::from_real_algebraic_number_with_traits(
+            &value.into(),
+            rounding_mode,
+            fp_state,
+            traits,
+        ))
+    }
+    /// parse a hexadecimal string, like IEEE 754's `convertFromHexCharacter`.
+    ///
+    /// see `from_hex_str_with_traits`.
+    pub fn from_hex_str(
+        text: &str,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Option<Self>
+    where
+        FT: Default,
+    {
+        Self::from_hex_str_with_traits(text, rounding_mode, fp_state, FT::default())
+    }
+    /// compute `mantissa * 10^exponent` exactly, where `mantissa` has at most
+    /// `digit_count` decimal digits.
+    ///
+    /// `exponent` is first clamped to a range that still rounds to the same
+    /// value, to avoid creating huge numbers.
+    fn decimal_to_ratio(
+        mantissa: BigInt,
+        exponent: BigInt,
+        digit_count: usize,
+        properties: FloatProperties,
+    ) -> Ratio<BigInt> {
+        if mantissa.is_zero() {
+            return Ratio::zero();
+        }
+        // `10^x` is at least `2^(3 * x)` for positive `x` and at most that
+        // for negative `x`.
+        let digit_count = BigInt::from(digit_count);
+        let exponent_bias: BigInt = properties.exponent_bias::<Bits>().into();
+        let exponent_max_normal: BigInt = properties.exponent_max_normal::<Bits>().into();
+        let exponent_min_normal: BigInt = properties.exponent_min_normal::<Bits>().into();
+        let exponent_max = exponent_max_normal - &exponent_bias;
+        let exponent_min =
+            exponent_min_normal - &exponent_bias - BigInt::from(properties.fraction_width()) - 2i32;
+        let three = BigInt::from(3);
+        let upper_limit = Integer::div_ceil(&(exponent_max + 2i32), &three);
+        let lower_limit = exponent_min.div_floor(&three) - digit_count;
+        let exponent = exponent
+            .min(upper_limit)
+            .max(lower_limit)
+            .to_i64()
+            .expect("clamped exponent fits in i64");
+        let scale = num_traits::pow(
+            BigInt::from(10),
+            exponent
+                .unsigned_abs()
+                .to_usize()
+                .expect("clamped exponent fits in usize"),
+        );
+        if exponent < 0 {
+            Ratio::new(mantissa, scale)
+        } else {
+            Ratio::from(mantissa * scale)
+        }
+    }
+    /// compute `sign * digits * 10^decimal_exponent` exactly and round it
+    /// once, the structured form of a decimal number like the ones produced
+    /// by decimal parsers. zero keeps its sign.
+    pub fn from_decimal_parts_with_traits(
+        sign: Sign,
+        digits: &BigUint,
+        decimal_exponent: i64,
         rounding_mode: Option<RoundingMode>,
         fp_state: Option<&mut FPState>,
+        traits: FT,
     ) -> Self {
-        assert_eq!(self.traits, rhs.traits);
-        let properties = self.properties();
-        let mut default_fp_state = FPState::default();
-        let fp_state = fp_state.unwrap_or(&mut default_fp_state);
-        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
-        let self_class = self.class();
-        let rhs_class = rhs.class();
-        let result_sign = self.sign() * rhs.sign();
-        if self_class.is_nan() || rhs_class.is_nan() {
-            if self_class.is_signaling_nan() || rhs_class.is_signaling_nan() {
-                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+        let properties = traits.properties();
+        if digits.is_zero() {
+            if !properties.has_sign_bit() {
+                return Self::positive_zero_with_traits(traits);
             }
-            match properties
-                .platform_properties
-                .std_bin_ops_nan_propagation_mode
-                .calculate_propagation_results(self_class, rhs_class)
-            {
-                BinaryNaNPropagationResults::First => self.to_quiet_nan(),
-                BinaryNaNPropagationResults::Second => rhs.to_quiet_nan(),
-                BinaryNaNPropagationResults::Canonical => {
-                    Self::quiet_nan_with_traits(self.traits.clone())
+            return Self::signed_zero_with_traits(sign, traits);
+        }
+        // a number with `n` bits has at most `n` decimal digits
+        let value = Self::decimal_to_ratio(
+            digits.clone().into(),
+            decimal_exponent.into(),
+            digits.bits(),
+            properties,
+        );
+        let value = match sign {
+            Sign::Positive => value,
+            Sign::Negative => -value,
+        };
+        Self::from_real_algebraic_number_with_traits(&value.into(), rounding_mode, fp_state, traits)
+    }
+    /// compute `sign * digits * 10^decimal_exponent` exactly and round it
+    /// once.
+    ///
+    /// see `from_decimal_parts_with_traits`.
+    pub fn from_decimal_parts(
+        sign: Sign,
+        digits: &BigUint,
+        decimal_exponent: i64,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self
+    where
+        FT: Default,
+    {
+        Self::from_decimal_parts_with_traits(
+            sign,
+            digits,
+            decimal_exponent,
+            rounding_mode,
+            fp_state,
+            FT::default(),
+        )
+    }
+    /// parse an exact rational number and round it once.
+    ///
+    /// accepts an optional sign followed by either a fraction like `1/3` or
+    /// `-22/7` (the denominator must be a non-zero unsigned integer), or a
+    /// decimal number like `12`, `0.1`, or `1.5e-7`. Since the value is
+    /// parsed exactly, `1/3` rounds correctly instead of adding an extra
+    /// rounding from a truncated decimal expansion. zero keeps its sign.
+    pub fn from_rational_str_with_traits(
+        text: &str,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+        traits: FT,
+    ) -> Result<Self, ParseError> {
+        fn parse_unsigned(digits: &str) -> Result<BigInt, ParseError> {
+            if digits.is_empty() || !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+                return Err(ParseError);
+            }
+            BigInt::parse_bytes(digits.as_bytes(), 10).ok_or(ParseError)
+        }
+        let properties = traits.properties();
+        let (sign, rest) = if let Some(rest) = text.strip_prefix('-') {
+            (Sign::Negative, rest)
+        } else {
+            (Sign::Positive, text.strip_prefix('+').unwrap_or(text))
+        };
+        let value = if let Some(index) = rest.find('/') {
+            let denominator = parse_unsigned(&rest[index + 1..])?;
+            if denominator.is_zero() {
+                return Err(ParseError);
+            }
+            Ratio::new(parse_unsigned(&rest[..index])?, denominator)
+        } else {
+            let (digits, exponent) = match rest.find(['e', 'E']) {
+                Some(index) => {
+                    let exponent_text = &rest[index + 1..];
+                    let (exponent_sign, exponent_digits) =
+                        if let Some(digits) = exponent_text.strip_prefix('-') {
+                            (-1, digits)
+                        } else {
+                            (1, exponent_text.strip_prefix('+').unwrap_or(exponent_text))
+                        };
+                    (
+                        &rest[..index],
+                        parse_unsigned(exponent_digits)? * exponent_sign,
+                    )
                 }
+                None => (rest, BigInt::zero()),
+            };
+            let (integer_digits, fraction_digits) = match digits.find('.') {
+                Some(index) => (&digits[..index], &digits[index + 1..]),
+                None => (digits, ""),
+            };
+            if integer_digits.is_empty() && fraction_digits.is_empty() {
+                return Err(ParseError);
             }
-        } else if (self_class.is_infinity() && rhs_class.is_zero())
-            || (self_class.is_zero() && rhs_class.is_infinity())
-        {
-            fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-            Self::quiet_nan_with_traits(self.traits.clone())
-        } else if self_class.is_zero() || rhs_class.is_zero() {
-            Self::signed_zero_with_traits(result_sign, self.traits.clone())
-        } else if self_class.is_infinity() || rhs_class.is_infinity() {
-            Self::signed_infinity_with_traits(result_sign, self.traits.clone())
+            let all_digits = format!("{}{}", integer_digits, fraction_digits);
+            let mantissa = parse_unsigned(&all_digits)?;
+            let exponent = exponent - BigInt::from(fraction_digits.len());
+            Self::decimal_to_ratio(mantissa, exponent, all_digits.len(), properties)
+        };
+        if value.is_zero() {
+            if !properties.has_sign_bit() {
+                return Ok(Self::positive_zero_with_traits(traits));
+            }
+            return Ok(Self::signed_zero_with_traits(sign, traits));
+        }
+        let value = match sign {
+            Sign::Positive => value,
+            Sign::Negative => -value,
+        };
+        Ok(Self::from_real_algebraic_number_with_traits(
+            &value.into(),
+            rounding_mode,
+            fp_state,
+            traits,
+        ))
+    }
+    /// parse an exact rational number and round it once.
+    ///
+    /// see `from_rational_str_with_traits`.
+    pub fn from_rational_str(
+        text: &str,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Result<Self, ParseError>
+    where
+        FT: Default,
+    {
+        Self::from_rational_str_with_traits(text, rounding_mode, fp_state, FT::default())
+    }
+    /// parse a number written in base `radix`, like the strings produced by
+    /// `to_string_radix`, and round it once.
+    ///
+    /// accepts an optional sign followed by digits with an optional point,
+    /// like `-101.011` or `z.z`. letters are digits `10` to `35` and may be
+    /// uppercase. when `radix` is 2 or 16, an optional binary exponent like
+    /// `p-3` or `P+7` may follow, so `1.8p1` in base 16 is `3`. strings that
+    /// aren't valid digits in base `radix` but are an infinity or NaN as
+    /// accepted by `from_hex_str_with_traits` are parsed as such. zero keeps
+    /// its sign.
+    ///
+    /// # Panics
+    ///
+    /// panics if `radix` isn't in `2..=36`.
+    pub fn from_str_radix_with_traits(
+        text: &str,
+        radix: u32,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+        traits: FT,
+    ) -> Result<Self, ParseError> {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix must be in the range 2..=36"
+        );
+        let properties = traits.properties();
+        let (sign, rest) = if let Some(rest) = text.strip_prefix('-') {
+            (Sign::Negative, rest)
         } else {
-            let lhs_value = self.to_real_algebraic_number().expect("known to be finite");
-            let rhs_value = rhs.to_real_algebraic_number().expect("known to be finite");
-            Self::from_real_algebraic_number_with_traits(
-                &(lhs_value * rhs_value),
-                Some(rounding_mode),
-                Some(fp_state),
-                self.traits.clone(),
+            (Sign::Positive, text.strip_prefix('+').unwrap_or(text))
+        };
+        let (digits, exponent) = match rest.find(['p', 'P']) {
+            Some(index) if radix == 2 || radix == 16 => (
+                &rest[..index],
+                rest[index + 1..].parse::<i64>().map_err(|_| ParseError)?,
+            ),
+            _ => (rest, 0),
+        };
+        let (integer_digits, fraction_digits) = match digits.find('.') {
+            Some(index) => (&digits[..index], &digits[index + 1..]),
+            None => (digits, ""),
+        };
+        let all_digits = format!("{}{}", integer_digits, fraction_digits);
+        if all_digits.is_empty() || !all_digits.chars().all(|digit| digit.is_digit(radix)) {
+            let name = rest.to_ascii_lowercase();
+            if name == "inf"
+                || name == "infinity"
+                || name.starts_with("nan")
+                || name.starts_with("snan")
+            {
+                return Self::from_hex_str_with_traits(text, rounding_mode, fp_state, traits)
+                    .ok_or(ParseError);
+            }
+            return Err(ParseError);
+        }
+        let mantissa = BigInt::parse_bytes(all_digits.as_bytes(), radix).ok_or(ParseError)?;
+        if mantissa.is_zero() {
+            if !properties.has_sign_bit() {
+                return Ok(Self::positive_zero_with_traits(traits));
+            }
+            return Ok(Self::signed_zero_with_traits(sign, traits));
+        }
+        let mut value = if radix.is_power_of_two() {
+            let exponent_bias = properties
+                .exponent_bias::<Bits>()
+                .to_i64()
+                .expect("exponent_bias doesn't fit in i64");
+            let exponent_max = properties
+                .exponent_max_normal::<Bits>()
+                .to_i64()
+                .expect("exponent_max_normal doesn't fit in i64")
+                - exponent_bias;
+            let exponent_min = properties
+                .exponent_min_normal::<Bits>()
+                .to_i64()
+                .expect("exponent_min_normal doesn't fit in i64")
+                - exponent_bias;
+            let digit_width = i64::from(radix.trailing_zeros());
+            let fraction_bits = fraction_digits
+                .len()
+                .to_i64()
+                .and_then(|count| count.checked_mul(digit_width))
+                .ok_or(ParseError)?;
+            let exponent = exponent.checked_sub(fraction_bits).ok_or(ParseError)?;
+            // clamp the exponent to a range that still rounds to the same
+            // value, to avoid creating huge numbers
+            let digit_bits = all_digits
+                .len()
+                .to_i64()
+                .and_then(|count| count.checked_mul(digit_width))
+                .ok_or(ParseError)?;
+            let exponent = exponent.min(exponent_max + 2).max(
+                exponent_min
+                    - properties
+                        .fraction_width()
+                        .to_i64()
+                        .expect("fraction_width doesn't fit in i64")
+                    - 2
+                    - digit_bits,
+            );
+            if exponent < 0 {
+                Ratio::new(mantissa, BigInt::one() << (-exponent) as usize)
+            } else {
+                Ratio::from(mantissa << exponent as usize)
+            }
+        } else {
+            Ratio::new(
+                mantissa,
+                num_traits::pow(BigInt::from(radix), fraction_digits.len()),
             )
+        };
+        if sign == Sign::Negative {
+            value = -value;
+        }
+        Ok(Self::from_real_algebraic_number_with_traits(
+            &value.into(),
+            rounding_mode,
+            fp_state,
+            traits,
+        ))
+    }
+    /// parse a number written in base `radix` and round it once.
+    ///
+    /// see `from_str_radix_with_traits`.
+    pub fn from_str_radix(
+        text: &str,
+        radix: u32,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Result<Self, ParseError>
+    where
+        FT: Default,
+    {
+        Self::from_str_radix_with_traits(text, radix, rounding_mode, fp_state, FT::default())
+    }
+    /// check that formatting `self` with `to_hex_string` and parsing it back
+    /// with `from_hex_str_with_traits` produces identical bits.
+    ///
+    /// does nothing unless `debug_assertions` are enabled.
+    /// non-canonical encodings (such as unnormals in formats without an
+    /// implicit leading bit) don't round trip.
+    pub fn debug_assert_hex_roundtrip(&self) {
+        if cfg!(debug_assertions) {
+            let text = self.to_hex_string();
+            let parsed = Self::from_hex_str_with_traits(
+                &text,
+                Some(RoundingMode::TiesToEven),
+                None,
+                self.traits.clone(),
+            );
+            assert!(
+                parsed.as_ref().is_some_and(|parsed| parsed.bit_eq(self)),
+                "hex round trip failed: {:?} -> {:?} -> {:?}",
+                self,
+                text,
+                parsed
+            );
         }
     }
-    /// divide floating-point numbers
-    pub fn div(
+    /// the fast path of `add_or_sub` for finite operands with exponents within
+    /// `fraction_width` of each other: the sum is computed exactly using
+    /// integer arithmetic on the significands, then rounded using the guard
+    /// and sticky bits. returns `None` if the rounded sum overflows or isn't
+    /// a normal number or zero, leaving overflow and underflow to the general
+    /// path.
+    fn add_or_sub_aligned(
         &self,
         rhs: &Self,
-        rounding_mode: Option<RoundingMode>,
-        fp_state: Option<&mut FPState>,
-    ) -> Self {
-        assert_eq!(self.traits, rhs.traits);
+        rounding_mode: RoundingMode,
+        fp_state: &mut FPState,
+        is_sub: bool,
+    ) -> Option<Self> {
         let properties = self.properties();
-        let mut default_fp_state = FPState::default();
-        let fp_state = fp_state.unwrap_or(&mut default_fp_state);
-        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
-        let self_class = self.class();
-        let rhs_class = rhs.class();
-        let result_sign = self.sign() * rhs.sign();
-        if self_class.is_nan() || rhs_class.is_nan() {
-            if self_class.is_signaling_nan() || rhs_class.is_signaling_nan() {
-                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+        let (lhs_sign, lhs_significand, lhs_exponent) = self.to_scientific_ratio()?;
+        let (mut rhs_sign, rhs_significand, rhs_exponent) = rhs.to_scientific_ratio()?;
+        if is_sub {
+            rhs_sign = -rhs_sign;
+        }
+        let fraction_width = properties.fraction_width() as i64;
+        if (lhs_exponent - rhs_exponent).abs() > fraction_width {
+            return None;
+        }
+        let mut exponent = lhs_exponent.min(rhs_exponent);
+        let aligned = |sign: Sign, significand: BigUint, significand_exponent: i64| {
+            let value = BigInt::from(significand) << (significand_exponent - exponent) as usize;
+            match sign {
+                Sign::Positive => value,
+                Sign::Negative => -value,
             }
-            match properties
-                .platform_properties
-                .std_bin_ops_nan_propagation_mode
-                .calculate_propagation_results(self_class, rhs_class)
-            {
-                BinaryNaNPropagationResults::First => self.to_quiet_nan(),
-                BinaryNaNPropagationResults::Second => rhs.to_quiet_nan(),
-                BinaryNaNPropagationResults::Canonical => {
-                    Self::quiet_nan_with_traits(self.traits.clone())
+        };
+        let sum = aligned(lhs_sign, lhs_significand, lhs_exponent)
+            + aligned(rhs_sign, rhs_significand, rhs_exponent);
+        if sum.is_zero() {
+            return Some(Self::signed_zero_with_traits(
+                rounding_mode.exact_zero_sign(),
+                self.traits.clone(),
+            ));
+        }
+        let sign = if sum.is_negative() {
+            Sign::Negative
+        } else {
+            Sign::Positive
+        };
+        if sign == Sign::Negative && !properties.has_sign_bit() {
+            return None;
+        }
+        let mut significand = sum.abs();
+        let precision = properties.fraction_width() + 1;
+        let bit_count = significand.bits();
+        let mut inexact = false;
+        if bit_count > precision {
+            let shift = bit_count - precision;
+            let guard_bit = (&significand >> (shift - 1)).is_odd();
+            let sticky_bits_mask: BigInt = (BigInt::one() << (shift - 1)) - 1;
+            let sticky = !(&significand & sticky_bits_mask).is_zero();
+            significand >>= shift;
+            exponent += shift as i64;
+            if guard_bit || sticky {
+                inexact = true;
+                let round_up = match rounding_mode.rounds_away_on_tie(significand.is_odd()) {
+                    Some(away_on_tie) => guard_bit && (sticky || away_on_tie),
+                    None => rounding_mode.rounds_away_on_overflow(sign),
+                };
+                if round_up {
+                    significand += 1;
+                    if significand.bits() > precision {
+                        significand >>= 1;
+                        exponent += 1;
+                    }
                 }
             }
-        } else if (self_class.is_infinity() && rhs_class.is_infinity())
-            || (self_class.is_zero() && rhs_class.is_zero())
-        {
-            fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-            Self::quiet_nan_with_traits(self.traits.clone())
-        } else if self_class.is_zero() || rhs_class.is_infinity() {
-            Self::signed_zero_with_traits(result_sign, self.traits.clone())
-        } else if self_class.is_infinity() {
-            Self::signed_infinity_with_traits(result_sign, self.traits.clone())
-        } else if rhs_class.is_zero() {
-            fp_state.status_flags = fp_state.status_flags.signal_division_by_zero();
-            Self::signed_infinity_with_traits(result_sign, self.traits.clone())
         } else {
-            let lhs_value = self.to_real_algebraic_number().expect("known to be finite");
-            let rhs_value = rhs.to_real_algebraic_number().expect("known to be finite");
-            Self::from_real_algebraic_number_with_traits(
-                &(lhs_value / rhs_value),
-                Some(rounding_mode),
-                Some(fp_state),
-                self.traits.clone(),
-            )
+            // cancellation can be normalized without limit, since subnormal
+            // results are left to the general path
+            let shift = precision - bit_count;
+            significand <<= shift;
+            exponent -= shift as i64;
+        }
+        let exponent_bias = properties
+            .exponent_bias::<Bits>()
+            .to_i64()
+            .expect("exponent bias doesn't fit in i64");
+        let exponent_field = exponent + fraction_width + exponent_bias;
+        let exponent_min_normal = properties
+            .exponent_min_normal::<Bits>()
+            .to_i64()
+            .expect("exponent_min_normal doesn't fit in i64");
+        let exponent_max_normal = properties
+            .exponent_max_normal::<Bits>()
+            .to_i64()
+            .expect("exponent_max_normal doesn't fit in i64");
+        if exponent_field < exponent_min_normal || exponent_field > exponent_max_normal {
+            return None;
+        }
+        if properties.has_implicit_leading_bit() {
+            significand -= BigInt::one() << properties.fraction_width();
         }
+        if inexact {
+            fp_state.status_flags = fp_state.status_flags.signal_inexact();
+        }
+        Some(Self::compose_fields_with_traits(
+            sign,
+            Bits::from_i64(exponent_field).expect("known to fit"),
+            Bits::from_bigint(&significand).expect("known to fit"),
+            self.traits.clone(),
+        ))
     }
-    /// compute the IEEE 754 remainder of two floating-point numbers
-    pub fn ieee754_remainder(
+    fn add_or_sub(
         &self,
         rhs: &Self,
         rounding_mode: Option<RoundingMode>,
         fp_state: Option<&mut FPState>,
+        is_sub: bool,
     ) -> Self {
         assert_eq!(self.traits, rhs.traits);
         let properties = self.properties();
         let mut default_fp_state = FPState::default();
-        let fp_state = fp_state.unwrap_or(&mut default_fp_state);
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
         let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
         let self_class = self.class();
-        let rhs_class = rhs.class();
-        if self_class.is_nan() || rhs_class.is_nan() {
-            if self_class.is_signaling_nan() || rhs_class.is_signaling_nan() {
-                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-            }
-            match properties
-                .platform_properties
-                .std_bin_ops_nan_propagation_mode
-                .calculate_propagation_results(self_class, rhs_class)
-            {
-                BinaryNaNPropagationResults::First => self.to_quiet_nan(),
-                BinaryNaNPropagationResults::Second => rhs.to_quiet_nan(),
-                BinaryNaNPropagationResults::Canonical => {
-                    Self::quiet_nan_with_traits(self.traits.clone())
+        let mut rhs_class = rhs.class();
+        if is_sub {
+            rhs_class = -rhs_class;
+        }
+        let op = if is_sub { OpKind::Sub } else { OpKind::Add };
+        let retval = match (self_class, rhs_class) {
+            (FloatClass::SignalingNaN, _)
+            | (FloatClass::QuietNaN, _)
+            | (_, FloatClass::SignalingNaN)
+            | (_, FloatClass::QuietNaN) => {
+                fp_state.signal_invalid_for_signaling_operands(&[self_class, rhs_class]);
+                match properties
+                    .platform_properties
+                    .std_bin_ops_nan_propagation_mode
+                    .calculate_propagation_results(self_class, rhs_class)
+                {
+                    BinaryNaNPropagationResults::First => self.to_quiet_nan(),
+                    BinaryNaNPropagationResults::Second => rhs.to_quiet_nan(),
+                    BinaryNaNPropagationResults::Canonical => {
+                        Self::default_nan_with_traits(self.traits.clone())
+                    }
                 }
             }
-        } else if self_class.is_infinity() || rhs_class.is_zero() {
-            fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-            Self::quiet_nan_with_traits(self.traits.clone())
-        } else if rhs_class.is_infinity() {
-            if self_class.is_zero() {
-                Self::signed_zero_with_traits(self.sign(), self.traits.clone())
-            } else {
-                Self::from_real_algebraic_number_with_traits(
-                    &self.to_real_algebraic_number().expect("known to be finite"),
-                    Some(rounding_mode),
-                    Some(fp_state),
-                    self.traits.clone(),
-                )
+            (FloatClass::NegativeInfinity, FloatClass::PositiveInfinity)
+            | (FloatClass::PositiveInfinity, FloatClass::NegativeInfinity) => {
+                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+                Self::default_nan_with_traits(self.traits.clone())
             }
-        } else {
-            let lhs_value = self.to_real_algebraic_number().expect("known to be finite");
-            let rhs_value = rhs.to_real_algebraic_number().expect("known to be finite");
-            let quotient = &lhs_value / &rhs_value;
-            let floor_quotient = quotient.to_integer_floor();
-            let fract_quotient = quotient - RealAlgebraicNumber::from(floor_quotient.clone());
-            let selected_quotient = match fract_quotient.cmp(&Ratio::new(1, 2).into()) {
-                Ordering::Less => floor_quotient,
-                Ordering::Greater => floor_quotient + 1,
-                Ordering::Equal => {
-                    if floor_quotient.is_even() {
-                        floor_quotient
-                    } else {
-                        floor_quotient + 1
+            (FloatClass::PositiveInfinity, _) | (_, FloatClass::PositiveInfinity) => {
+                Self::positive_infinity_with_traits(self.traits.clone())
+            }
+            (FloatClass::NegativeInfinity, _) | (_, FloatClass::NegativeInfinity) => {
+                Self::negative_infinity_with_traits(self.traits.clone())
+            }
+            (FloatClass::PositiveZero, FloatClass::PositiveZero) => {
+                Self::positive_zero_with_traits(self.traits.clone())
+            }
+            (FloatClass::NegativeZero, FloatClass::NegativeZero) => {
+                Self::negative_zero_with_traits(self.traits.clone())
+            }
+            _ => {
+                // the fast path rounds to the full precision, which can be
+                // more than the precision control allows
+                if Self::precision_control_properties(properties, fp_state).is_none() {
+                    if let Some(result) =
+                        self.add_or_sub_aligned(rhs, rounding_mode, fp_state, is_sub)
+                    {
+                        return result.flush_to_zero(op, fp_state);
                     }
                 }
-            };
-            let remainder = lhs_value - rhs_value * RealAlgebraicNumber::from(selected_quotient);
-            if remainder.is_zero() {
-                Self::signed_zero_with_traits(self.sign(), self.traits.clone())
-            } else {
-                Self::from_real_algebraic_number_with_traits(
-                    &remainder,
-                    Some(rounding_mode),
-                    Some(fp_state),
-                    self.traits.clone(),
-                )
+                let lhs_value = self.to_real_algebraic_number().expect("known to be finite");
+                let rhs_value = rhs.to_real_algebraic_number().expect("known to be finite");
+                let result = if is_sub {
+                    lhs_value - rhs_value
+                } else {
+                    lhs_value + rhs_value
+                };
+                if result.is_zero() {
+                    Self::signed_zero_with_traits(
+                        rounding_mode.exact_zero_sign(),
+                        self.traits.clone(),
+                    )
+                } else {
+                    Self::round_arithmetic_result(
+                        &result,
+                        rounding_mode,
+                        fp_state,
+                        self.traits.clone(),
+                    )
+                }
             }
-        }
+        };
+        retval.flush_to_zero(op, fp_state)
     }
-    /// calculate the result of `(self * factor) + term` rounding only once, returning the result
-    pub fn fused_mul_add(
+    /// add floating-point numbers
+    pub fn add(
         &self,
-        factor: &Self,
-        term: &Self,
+        rhs: &Self,
         rounding_mode: Option<RoundingMode>,
         fp_state: Option<&mut FPState>,
     ) -> Self {
-        assert_eq!(self.traits, factor.traits);
-        assert_eq!(self.traits, term.traits);
-        let properties = self.properties();
-        let mut default_fp_state = FPState::default();
-        let fp_state = fp_state.unwrap_or(&mut default_fp_state);
-        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
-        let self_class = self.class();
-        let factor_class = factor.class();
-        let term_class = term.class();
-        let product_sign = self.sign() * factor.sign();
-        let is_infinity_times_zero = (self_class.is_infinity() && factor_class.is_zero())
-            || (self_class.is_zero() && factor_class.is_infinity());
-        if self_class.is_nan() || factor_class.is_nan() || term_class.is_nan() {
-            if self_class.is_signaling_nan()
-                || factor_class.is_signaling_nan()
-                || term_class.is_signaling_nan()
-            {
-                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-            }
-            if is_infinity_times_zero && term_class.is_quiet_nan() {
-                match properties.platform_properties.fma_inf_zero_qnan_result {
-                    FMAInfZeroQNaNResult::CanonicalAndGenerateInvalid => {
-                        fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-                        return Self::quiet_nan_with_traits(self.traits.clone());
-                    }
-                    FMAInfZeroQNaNResult::PropagateAndGenerateInvalid => {
-                        fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-                        return term.clone();
-                    }
-                    FMAInfZeroQNaNResult::FollowNaNPropagationMode => {}
-                }
-            }
-            match properties
-                .platform_properties
-                .fma_nan_propagation_mode
-                .calculate_propagation_results(self_class, factor_class, term_class)
-            {
-                TernaryNaNPropagationResults::First => self.to_quiet_nan(),
-                TernaryNaNPropagationResults::Second => factor.to_quiet_nan(),
-                TernaryNaNPropagationResults::Third => term.to_quiet_nan(),
-                TernaryNaNPropagationResults::Canonical => {
-                    Self::quiet_nan_with_traits(self.traits.clone())
-                }
-            }
-        } else if is_infinity_times_zero
-            || ((self_class.is_infinity() || factor_class.is_infinity())
-                && term_class.is_infinity()
-                && product_sign != term.sign())
-        {
-            fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-            Self::quiet_nan_with_traits(self.traits.clone())
-        } else if (self_class.is_zero() || factor_class.is_zero())
-            && term_class.is_zero()
-            && product_sign == term.sign()
-        {
-            Self::signed_zero_with_traits(product_sign, self.traits.clone())
-        } else if term_class.is_infinity() {
-            Self::signed_infinity_with_traits(term.sign(), self.traits.clone())
-        } else if self_class.is_infinity() || factor_class.is_infinity() {
-            Self::signed_infinity_with_traits(product_sign, self.traits.clone())
-        } else {
-            let self_value = self.to_real_algebraic_number().expect("known to be finite");
-            let factor_value = factor
-                .to_real_algebraic_number()
-                .expect("known to be finite");
-            let term_value = term.to_real_algebraic_number().expect("known to be finite");
-            let result = self_value * factor_value + term_value;
-            if result.is_zero() {
-                match rounding_mode {
-                    RoundingMode::TiesToEven
-                    | RoundingMode::TiesToAway
-                    | RoundingMode::TowardPositive
-                    | RoundingMode::TowardZero => {
-                        Self::positive_zero_with_traits(self.traits.clone())
-                    }
-                    RoundingMode::TowardNegative => {
-                        Self::negative_zero_with_traits(self.traits.clone())
-                    }
-                }
-            } else {
-                Self::from_real_algebraic_number_with_traits(
-                    &result,
-                    Some(rounding_mode),
-                    Some(fp_state),
-                    self.traits.clone(),
-                )
+        self.add_or_sub(rhs, rounding_mode, fp_state, false)
+    }
+    /// subtract floating-point numbers
+    pub fn sub(
+        &self,
+        rhs: &Self,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        self.add_or_sub(rhs, rounding_mode, fp_state, true)
+    }
+    /// compute `|self - rhs|`, rounding only once.
+    ///
+    /// the exact difference is made non-negative before rounding, so the
+    /// directed rounding modes round the magnitude of the result rather than
+    /// the signed difference. a zero result is always `+0`. NaNs propagate the
+    /// same as `sub`.
+    pub fn abs_diff(
+        &self,
+        rhs: &Self,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        let mut retval = match self.compare_quiet(rhs, None) {
+            Some(Ordering::Less) => rhs.sub(self, rounding_mode, fp_state),
+            Some(Ordering::Equal) | Some(Ordering::Greater) | None => {
+                self.sub(rhs, rounding_mode, fp_state)
             }
+        };
+        if retval.is_zero() && self.properties().has_sign_bit() {
+            retval.set_sign(Sign::Positive);
         }
+        retval
     }
-    /// round `self` to an integer, returning the result as an integer or `None`
-    pub fn round_to_integer(
+    /// compute `(self + rhs) / 2` exactly and round only once, so the result
+    /// doesn't overflow and isn't double-rounded.
+    ///
+    /// a zero result has the same sign as it would for `add`, so
+    /// `midpoint(x, x)` is always `x`. if either operand is NaN or infinite,
+    /// the result (including which flags are signaled) is the same as for
+    /// `add`, so `midpoint(inf, -inf)` is NaN and signals invalid operation.
+    pub fn midpoint(
         &self,
-        exact: bool,
+        rhs: &Self,
         rounding_mode: Option<RoundingMode>,
         fp_state: Option<&mut FPState>,
-    ) -> Option<BigInt> {
+    ) -> Self {
+        assert_eq!(self.traits, rhs.traits);
         let mut default_fp_state = FPState::default();
-        let fp_state = fp_state.unwrap_or(&mut default_fp_state);
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
         let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
-        match self.class() {
-            FloatClass::SignalingNaN => {
-                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-                return None;
-            }
-            class if !class.is_finite() => {
-                return None;
-            }
-            _ => {}
+        if !self.is_finite() || !rhs.is_finite() || (self.is_zero() && rhs.is_zero()) {
+            return self.add(rhs, Some(rounding_mode), Some(fp_state));
         }
-        let value = self.to_real_algebraic_number().expect("known to be finite");
-        let lower_value = value.to_integer_floor();
-        let remainder = value - RealAlgebraicNumber::from(lower_value.clone());
-        if remainder.is_zero() {
-            return Some(lower_value);
+        let self_value = self.to_real_algebraic_number().expect("known to be finite");
+        let rhs_value = rhs.to_real_algebraic_number().expect("known to be finite");
+        let result = (self_value + rhs_value) / RealAlgebraicNumber::from(BigInt::from(2));
+        if result.is_zero() {
+            Self::signed_zero_with_traits(rounding_mode.exact_zero_sign(), self.traits.clone())
+        } else {
+            Self::round_from_real_algebraic_number_with_traits(
+                &result,
+                Some(rounding_mode),
+                Some(fp_state),
+                self.traits.clone(),
+            )
         }
-        if exact {
-            fp_state.status_flags = fp_state.status_flags.signal_inexact();
+    }
+    /// compute the sum of all of `values`, rounding only once, like IEEE 754's
+    /// `sum` reduction operation.
+    ///
+    /// the sum is calculated exactly before rounding, so the result and the
+    /// signaled flags don't depend on the order of `values`. to keep that true
+    /// when there are multiple NaN operands, a NaN result is always the
+    /// default NaN rather than a propagated operand. the sum of no values is
+    /// `+0`.
+    pub fn sum_with_traits(
+        values: &[Self],
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+        traits: FT,
+    ) -> Self {
+        Self::sum_or_mean_with_traits(values, rounding_mode, fp_state, traits, false)
+    }
+    fn sum_or_mean_with_traits(
+        values: &[Self],
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+        traits: FT,
+        is_mean: bool,
+    ) -> Self {
+        for value in values {
+            assert_eq!(value.traits, traits);
         }
-        let upper_value = &lower_value + 1;
-        match rounding_mode {
-            RoundingMode::TiesToAway | RoundingMode::TiesToEven => {
-                match remainder.cmp(&Ratio::new(1, 2).into()) {
-                    Ordering::Less => Some(lower_value),
-                    Ordering::Equal => {
-                        if rounding_mode == RoundingMode::TiesToEven {
-                            if lower_value.is_even() {
-                                Some(lower_value)
-                            } else {
-                                Some(upper_value)
-                            }
-                        } else {
-                            assert_eq!(rounding_mode, RoundingMode::TiesToAway);
-                            if lower_value.is_negative() {
-                                Some(lower_value)
-                            } else {
-                                Some(upper_value)
-                            }
-                        }
-                    }
-                    Ordering::Greater => Some(upper_value),
-                }
-            }
-            RoundingMode::TowardPositive => Some(upper_value),
-            RoundingMode::TowardNegative => Some(lower_value),
-            RoundingMode::TowardZero => {
-                if lower_value.is_negative() {
-                    Some(upper_value)
-                } else {
-                    Some(lower_value)
-                }
+        let terms = values.iter().map(|value| {
+            let class = value.class();
+            if class.is_finite() && !class.is_zero() {
+                (class, value.to_ratio().expect("known to be finite"))
+            } else {
+                (class, Ratio::zero())
             }
-        }
+        });
+        let divisor = if is_mean { Some(values.len()) } else { None };
+        Self::exact_reduction_with_traits(terms, divisor, rounding_mode, fp_state, traits)
     }
-    /// round `self` to an integer, returning the result as a `Float`
-    pub fn round_to_integral(
-        &self,
-        exact: bool,
+    /// round the exact sum of `terms`, optionally divided by `divisor`.
+    ///
+    /// each term is the class of the value being summed along with its value
+    /// if it's finite and nonzero.
+    fn exact_reduction_with_traits(
+        terms: impl Iterator<Item = (FloatClass, Ratio<BigInt>)>,
+        divisor: Option<usize>,
         rounding_mode: Option<RoundingMode>,
         fp_state: Option<&mut FPState>,
+        traits: FT,
     ) -> Self {
-        let properties = self.properties();
         let mut default_fp_state = FPState::default();
-        let fp_state = fp_state.unwrap_or(&mut default_fp_state);
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
         let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
-        let class = self.class();
-        if class.is_nan() {
-            if class.is_signaling_nan() {
-                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+        let mut is_empty = true;
+        let mut has_nan = false;
+        let mut has_signaling_nan = false;
+        let mut has_positive_infinity = false;
+        let mut has_negative_infinity = false;
+        let mut zero_sign = None;
+        let mut all_zeros_with_same_sign = true;
+        let mut sum = Ratio::<BigInt>::zero();
+        for (class, value) in terms {
+            is_empty = false;
+            match class {
+                FloatClass::SignalingNaN => {
+                    has_nan = true;
+                    has_signaling_nan = true;
+                }
+                FloatClass::QuietNaN => has_nan = true,
+                FloatClass::PositiveInfinity => has_positive_infinity = true,
+                FloatClass::NegativeInfinity => has_negative_infinity = true,
+                FloatClass::PositiveZero | FloatClass::NegativeZero => {
+                    let sign = if class == FloatClass::PositiveZero {
+                        Sign::Positive
+                    } else {
+                        Sign::Negative
+                    };
+                    if *zero_sign.get_or_insert(sign) != sign {
+                        all_zeros_with_same_sign = false;
+                    }
+                }
+                _ => {
+                    all_zeros_with_same_sign = false;
+                    sum += value;
+                }
             }
-            match properties
-                .platform_properties()
-                .round_to_integral_nan_propagation_mode
-                .calculate_propagation_results(class)
-            {
-                UnaryNaNPropagationResults::Canonical => {
-                    Self::quiet_nan_with_traits(self.traits.clone())
+        }
+        if has_signaling_nan {
+            fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+        }
+        if has_nan {
+            Self::default_nan_with_traits(traits)
+        } else if (divisor.is_some() && is_empty)
+            || (has_positive_infinity && has_negative_infinity)
+        {
+            fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+            Self::default_nan_with_traits(traits)
+        } else if has_positive_infinity {
+            Self::positive_infinity_with_traits(traits)
+        } else if has_negative_infinity {
+            Self::negative_infinity_with_traits(traits)
+        } else if sum.is_zero() {
+            match zero_sign {
+                _ if is_empty => Self::positive_zero_with_traits(traits),
+                Some(sign) if all_zeros_with_same_sign => {
+                    Self::signed_zero_with_traits(sign, traits)
                 }
-                UnaryNaNPropagationResults::First => self.to_quiet_nan(),
+                _ => Self::signed_zero_with_traits(rounding_mode.exact_zero_sign(), traits),
             }
-        } else if class.is_infinity() {
-            Self::signed_infinity_with_traits(self.sign(), self.traits.clone())
         } else {
-            let value = self
-                .round_to_integer(exact, Some(rounding_mode), Some(fp_state))
-                .expect("known to be finite");
-            if value.is_zero() {
-                Self::signed_zero_with_traits(self.sign(), self.traits.clone())
-            } else {
-                Self::from_real_algebraic_number_with_traits(
-                    &value.into(),
-                    Some(rounding_mode),
-                    Some(fp_state),
-                    self.traits.clone(),
-                )
-            }
+            let result = match divisor {
+                Some(divisor) => sum / BigInt::from(divisor),
+                None => sum,
+            };
+            Self::round_from_real_algebraic_number_with_traits(
+                &result.into(),
+                Some(rounding_mode),
+                Some(fp_state),
+                traits,
+            )
         }
     }
-    /// normalize `self`.
-    /// This is a no-op for all floating-point formats where
-    /// `has_implicit_leading_bit` is `true` (which includes all standard
-    /// floating-point formats).
-    pub fn normalize(&mut self) {
-        let properties = self.properties();
-        if properties.has_implicit_leading_bit() {
-            return;
-        }
-        let mut exponent_field = self.exponent_field();
-        let exponent_zero_subnormal = properties.exponent_zero_subnormal();
-        if exponent_field == properties.exponent_inf_nan()
-            || exponent_field == exponent_zero_subnormal
-        {
-            return;
-        }
-        let mut mantissa_field = self.mantissa_field();
-        if mantissa_field.is_zero() {
-            self.set_exponent_field(exponent_zero_subnormal);
-            return;
-        }
-        let mantissa_field_msb = Bits::one() << properties.fraction_width();
-        while (mantissa_field.clone() & &mantissa_field_msb).is_zero() {
-            if exponent_field == exponent_zero_subnormal {
-                break;
-            }
-            exponent_field -= Bits::one();
-            mantissa_field <<= 1;
+    /// compute the sum of all of `values`, rounding only once.
+    ///
+    /// see `sum_with_traits`.
+    pub fn sum(
+        values: &[Self],
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self
+    where
+        FT: Default,
+    {
+        Self::sum_with_traits(values, rounding_mode, fp_state, FT::default())
+    }
+    /// compute the arithmetic mean of `values`, `sum / values.len()`, rounding
+    /// only once.
+    ///
+    /// the special cases are the same as `sum_with_traits`: any NaN gives the
+    /// default NaN, `+inf` and `-inf` together give the default NaN and signal
+    /// invalid operation, and the sign of a zero result is the same as for
+    /// `sum_with_traits`. the mean of no values is the default NaN and signals
+    /// invalid operation.
+    pub fn mean_with_traits(
+        values: &[Self],
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+        traits: FT,
+    ) -> Self {
+        Self::sum_or_mean_with_traits(values, rounding_mode, fp_state, traits, true)
+    }
+    /// compute the arithmetic mean of `values`, rounding only once.
+    ///
+    /// see `mean_with_traits`.
+    pub fn mean(
+        values: &[Self],
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self
+    where
+        FT: Default,
+    {
+        Self::mean_with_traits(values, rounding_mode, fp_state, FT::default())
+    }
+    /// compute the dot product of `lhs` and `rhs`, the sum of
+    /// `lhs[i] * rhs[i]`, rounding only once, like IEEE 754's `dot`
+    /// reduction operation.
+    ///
+    /// the products and their sum are calculated exactly before rounding, so
+    /// the result and the signaled flags don't depend on the order of the
+    /// pairs. the special cases are the same as `sum_with_traits`, where
+    /// `0 * inf` gives the default NaN and signals invalid operation. the dot
+    /// product of no values is `+0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lhs` and `rhs` have different lengths.
+    pub fn dot_with_traits(
+        lhs: &[Self],
+        rhs: &[Self],
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+        traits: FT,
+    ) -> Self {
+        assert_eq!(lhs.len(), rhs.len(), "dot product operands' lengths differ");
+        for value in lhs.iter().chain(rhs) {
+            assert_eq!(value.traits, traits);
         }
-        self.set_exponent_field(exponent_field);
-        self.set_mantissa_field(mantissa_field);
+        let terms = lhs.iter().zip(rhs).map(|(lhs, rhs)| {
+            let lhs_class = lhs.class();
+            let rhs_class = rhs.class();
+            let sign = lhs.sign() * rhs.sign();
+            let class = if lhs_class.is_nan() || rhs_class.is_nan() {
+                if lhs_class.is_signaling_nan() || rhs_class.is_signaling_nan() {
+                    FloatClass::SignalingNaN
+                } else {
+                    FloatClass::QuietNaN
+                }
+            } else if lhs_class.is_infinity() || rhs_class.is_infinity() {
+                if lhs_class.is_zero() || rhs_class.is_zero() {
+                    // `0 * inf` is invalid, the same as a signaling NaN
+                    FloatClass::SignalingNaN
+                } else if sign == Sign::Positive {
+                    FloatClass::PositiveInfinity
+                } else {
+                    FloatClass::NegativeInfinity
+                }
+            } else if lhs_class.is_zero() || rhs_class.is_zero() {
+                if sign == Sign::Positive {
+                    FloatClass::PositiveZero
+                } else {
+                    FloatClass::NegativeZero
+                }
+            } else {
+                let value = lhs.to_ratio().expect("known to be finite")
+                    * rhs.to_ratio().expect("known to be finite");
+                return (FloatClass::PositiveNormal, value);
+            };
+            (class, Ratio::zero())
+        });
+        Self::exact_reduction_with_traits(terms, None, rounding_mode, fp_state, traits)
     }
-    /// compute the result of `next_up` or `next_down`
-    pub fn next_up_or_down(&self, up_or_down: UpOrDown, fp_state: Option<&mut FPState>) -> Self {
-        let properties = self.properties();
-        let mut default_fp_state = FPState::default();
-        let fp_state = fp_state.unwrap_or(&mut default_fp_state);
-        match (self.class(), up_or_down) {
-            (class, _) if class.is_nan() => {
-                if class.is_signaling_nan() {
-                    fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+    /// compute the dot product of `lhs` and `rhs`, rounding only once.
+    ///
+    /// see `dot_with_traits`.
+    pub fn dot(
+        lhs: &[Self],
+        rhs: &[Self],
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self
+    where
+        FT: Default,
+    {
+        Self::dot_with_traits(lhs, rhs, rounding_mode, fp_state, FT::default())
+    }
+    /// return `true` if reducing `values` signals the same `StatusFlags` for
+    /// every rotation of `values`, both in order and in reverse order.
+    ///
+    /// the reduction is `sum` of `values`, or `dot` of `values` and `weights`
+    /// if `weights` is given, where each weight stays paired with its value
+    /// when reordering. every order starts from a copy of `fp_state` (or the
+    /// default `FPState`), which isn't modified.
+    ///
+    /// `sum` and `dot` round only once, so this checks their invariant that
+    /// the flags don't depend on the order, unlike reducing one operation at
+    /// a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weights` is given and has a different length than `values`.
+    #[cfg(test)]
+    fn flags_match_across_orders(
+        values: &[Self],
+        weights: Option<&[Self]>,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&FPState>,
+    ) -> bool
+    where
+        FT: Default,
+    {
+        let fp_state = fp_state.copied().unwrap_or_default();
+        let reduce = |values: &[Self], weights: Option<&[Self]>| {
+            let mut fp_state = fp_state;
+            match weights {
+                Some(weights) => Self::dot(values, weights, rounding_mode, Some(&mut fp_state)),
+                None => Self::sum(values, rounding_mode, Some(&mut fp_state)),
+            };
+            fp_state.status_flags
+        };
+        let expected = reduce(values, weights);
+        let mut values = values.to_vec();
+        let mut weights = weights.map(<[Self]>::to_vec);
+        for _ in 0..2 {
+            for _ in 0..values.len() {
+                if reduce(&values, weights.as_deref()) != expected {
+                    return false;
                 }
-                match properties
-                    .platform_properties()
-                    .next_up_or_down_nan_propagation_mode
-                    .calculate_propagation_results(class)
-                {
-                    UnaryNaNPropagationResults::Canonical => {
-                        Self::quiet_nan_with_traits(self.traits.clone())
-                    }
-                    UnaryNaNPropagationResults::First => self.to_quiet_nan(),
+                values.rotate_left(1);
+                if let Some(weights) = &mut weights {
+                    weights.rotate_left(1);
                 }
             }
-            (FloatClass::NegativeInfinity, UpOrDown::Up)
-            | (FloatClass::PositiveInfinity, UpOrDown::Down) => {
-                Self::signed_max_normal_with_traits(self.sign(), self.traits.clone())
-            }
-            (FloatClass::NegativeInfinity, UpOrDown::Down)
-            | (FloatClass::PositiveInfinity, UpOrDown::Up) => self.clone(),
-            (class, _) if class.is_zero() => {
-                Self::signed_min_subnormal_with_traits(up_or_down.into(), self.traits.clone())
+            values.reverse();
+            if let Some(weights) = &mut weights {
+                weights.reverse();
             }
-            _ => {
-                let mut retval = self.clone();
-                retval.normalize();
-                let mantissa = self.mantissa_field();
-                let is_larger_magnitude = Sign::from(up_or_down) == self.sign();
-                if is_larger_magnitude {
-                    if mantissa == properties.mantissa_field_max() {
-                        let exponent = self.exponent_field();
-                        if exponent == properties.exponent_max_normal() {
-                            Self::signed_infinity_with_traits(self.sign(), self.traits.clone())
-                        } else {
-                            let mut retval = self.clone();
-                            retval.set_mantissa_field(properties.mantissa_field_normal_min());
-                            retval.set_exponent_field(exponent + Bits::one());
-                            retval
-                        }
-                    } else {
-                        let mut retval = self.clone();
-                        retval.set_mantissa_field(mantissa + Bits::one());
-                        retval
-                    }
-                } else if mantissa <= properties.mantissa_field_normal_min() {
-                    let exponent = self.exponent_field();
-                    if exponent == properties.exponent_zero_subnormal() {
-                        assert!(!mantissa.is_zero());
-                        let mut retval = self.clone();
-                        retval.set_mantissa_field(mantissa - Bits::one());
-                        retval
-                    } else {
-                        let mut retval = self.clone();
-                        retval.set_mantissa_field(properties.mantissa_field_max());
-                        retval.set_exponent_field(exponent - Bits::one());
-                        retval
-                    }
+        }
+        true
+    }
+    /// return `true` if `self` is a normal `±2^k`, checked cheaply from the
+    /// fields so `mul` and `div` can test for their fast path.
+    fn is_normal_power_of_two(&self, class: FloatClass) -> bool {
+        class.is_normal() && self.mantissa_field() == self.properties().mantissa_field_normal_min()
+    }
+    /// compute `self * power_of_two` (or `self / power_of_two` if `divide`),
+    /// the fast path of `mul` and `div` when `power_of_two` is a normal
+    /// `±2^k`. `self` must be finite and nonzero, and precision control
+    /// must not apply.
+    ///
+    /// when `self` is normal and the result stays in the normal range, the
+    /// result is built by adjusting the exponent field, otherwise this falls
+    /// back to `scale_b`, which handles rounding.
+    fn mul_by_normal_power_of_two(
+        &self,
+        self_class: FloatClass,
+        power_of_two: &Self,
+        divide: bool,
+        rounding_mode: RoundingMode,
+        fp_state: &mut FPState,
+    ) -> Self {
+        let properties = self.properties();
+        let mut retval = self.clone();
+        retval.set_sign(self.sign() * power_of_two.sign());
+        if self_class.is_normal() && self.mantissa_field() >= properties.mantissa_field_normal_min()
+        {
+            let offset = || -> Option<i64> {
+                let power_of_two_exponent = power_of_two.exponent_field().to_i64()?;
+                let exponent_bias = properties.exponent_bias::<Bits>().to_i64()?;
+                if divide {
+                    exponent_bias.checked_sub(power_of_two_exponent)
                 } else {
-                    let mut retval = self.clone();
-                    retval.set_mantissa_field(mantissa - Bits::one());
-                    retval
+                    power_of_two_exponent.checked_sub(exponent_bias)
+                }
+            };
+            if let Some(offset) = offset() {
+                if retval.offset_normal_exponent_field(offset) {
+                    return retval;
                 }
             }
         }
+        let mut scale = power_of_two
+            .log_b(None)
+            .expect("known to be finite and nonzero");
+        if divide {
+            scale = -scale;
+        }
+        retval.scale_b(scale, Some(rounding_mode), Some(fp_state))
     }
-    /// compute the least floating-point number that compares greater than `self`
-    pub fn next_up(&self, fp_state: Option<&mut FPState>) -> Self {
-        self.next_up_or_down(UpOrDown::Up, fp_state)
-    }
-    /// compute the greatest floating-point number that compares less than `self`
-    pub fn next_down(&self, fp_state: Option<&mut FPState>) -> Self {
-        self.next_up_or_down(UpOrDown::Down, fp_state)
-    }
-    /// get the floor of the log base 2 of the absolute value of `self`
-    pub fn log_b(&self, fp_state: Option<&mut FPState>) -> Option<BigInt> {
-        let mut default_fp_state = FPState::default();
-        let fp_state = fp_state.unwrap_or(&mut default_fp_state);
+    /// add `offset` to the exponent field of `self`, which must be normal
+    /// with its leading bit set, in place. returns `false` without modifying
+    /// `self` if the result wouldn't be normal.
+    fn offset_normal_exponent_field(&mut self, offset: i64) -> bool {
         let properties = self.properties();
-        let class = self.class();
-        if !class.is_finite() || class.is_zero() {
-            fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-            return None;
-        }
-        let exponent_field: BigInt = self.exponent_field().into();
-        let exponent_bias: BigInt = properties.exponent_bias::<Bits>().into();
-        let exponent_zero_subnormal: BigInt = properties.exponent_zero_subnormal::<Bits>().into();
-        let mut exponent =
-            if properties.has_implicit_leading_bit() && exponent_field != exponent_zero_subnormal {
-                return Some(exponent_field - exponent_bias);
-            } else if exponent_field == exponent_zero_subnormal {
-                properties.exponent_min_normal::<Bits>().into() - exponent_bias
-            } else {
-                exponent_field - exponent_bias
-            };
-        let mut mantissa = self.mantissa_field();
-        while (mantissa.clone() >> properties.fraction_width()).is_zero() {
-            mantissa <<= 1;
-            exponent -= 1;
+        let exponent_field =
+            || -> Option<i64> { self.exponent_field().to_i64()?.checked_add(offset) };
+        let exponent_range = || -> Option<(i64, i64)> {
+            Some((
+                properties.exponent_min_normal::<Bits>().to_i64()?,
+                properties.exponent_max_normal::<Bits>().to_i64()?,
+            ))
+        };
+        if let (Some(exponent_field), Some((exponent_min, exponent_max))) =
+            (exponent_field(), exponent_range())
+        {
+            if exponent_field >= exponent_min && exponent_field <= exponent_max {
+                self.set_exponent_field(
+                    Bits::from_i64(exponent_field).expect("known to fit in exponent field"),
+                );
+                return true;
+            }
         }
-        Some(exponent)
+        false
     }
-    /// get `self * 2^scale`
-    pub fn scale_b(
+    /// multiply floating-point numbers
+    pub fn mul(
         &self,
-        mut scale: BigInt,
+        rhs: &Self,
         rounding_mode: Option<RoundingMode>,
         fp_state: Option<&mut FPState>,
     ) -> Self {
+        assert_eq!(self.traits, rhs.traits);
+        let properties = self.properties();
         let mut default_fp_state = FPState::default();
-        let fp_state = fp_state.unwrap_or(&mut default_fp_state);
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
         let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
-        let properties = self.properties();
-        let class = self.class();
-        if class.is_nan() {
-            if class.is_signaling_nan() {
-                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-            }
+        let self_class = self.class();
+        let rhs_class = rhs.class();
+        let result_sign = self.sign() * rhs.sign();
+        // the fast path's exact results can have more precision than the
+        // precision control allows
+        let use_fast_path = Self::precision_control_properties(properties, fp_state).is_none();
+        let retval = if self_class.is_nan() || rhs_class.is_nan() {
+            fp_state.signal_invalid_for_signaling_operands(&[self_class, rhs_class]);
             match properties
-                .platform_properties()
-                .scale_b_nan_propagation_mode
-                .calculate_propagation_results(class)
+                .platform_properties
+                .std_bin_ops_nan_propagation_mode
+                .calculate_propagation_results(self_class, rhs_class)
             {
-                UnaryNaNPropagationResults::Canonical => {
-                    Self::quiet_nan_with_traits(self.traits.clone())
+                BinaryNaNPropagationResults::First => self.to_quiet_nan(),
+                BinaryNaNPropagationResults::Second => rhs.to_quiet_nan(),
+                BinaryNaNPropagationResults::Canonical => {
+                    Self::default_nan_with_traits(self.traits.clone())
                 }
-                UnaryNaNPropagationResults::First => self.to_quiet_nan(),
             }
-        } else if class.is_infinity() {
-            Self::signed_infinity_with_traits(self.sign(), self.traits.clone())
-        } else if class.is_zero() {
-            Self::signed_zero_with_traits(self.sign(), self.traits.clone())
+        } else if (self_class.is_infinity() && rhs_class.is_zero())
+            || (self_class.is_zero() && rhs_class.is_infinity())
+        {
+            fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+            Self::default_nan_with_traits(self.traits.clone())
+        } else if self_class.is_zero() || rhs_class.is_zero() {
+            Self::signed_zero_with_traits(result_sign, self.traits.clone())
+        } else if self_class.is_infinity() || rhs_class.is_infinity() {
+            Self::signed_infinity_with_traits(result_sign, self.traits.clone())
+        } else if use_fast_path && rhs.is_normal_power_of_two(rhs_class) {
+            self.mul_by_normal_power_of_two(self_class, rhs, false, rounding_mode, fp_state)
+        } else if use_fast_path && self.is_normal_power_of_two(self_class) {
+            rhs.mul_by_normal_power_of_two(rhs_class, self, false, rounding_mode, fp_state)
         } else {
-            let exponent_max_normal: BigInt = properties.exponent_max_normal::<Bits>().into();
-            let exponent_min_normal: BigInt = properties.exponent_min_normal::<Bits>().into();
-            let scale_limit: BigInt =
-                (exponent_max_normal - exponent_min_normal + properties.fraction_width() + 1) * 2;
-            scale = scale.max(-&scale_limit);
-            scale = scale.min(scale_limit);
-            let mut value = self.to_real_algebraic_number().expect("known to be finite");
-            if scale.is_positive() {
-                value *= RealAlgebraicNumber::from(
-                    BigInt::one() << scale.to_usize().expect("rhs won't fit in usize"),
-                );
-            } else {
-                value /= RealAlgebraicNumber::from(
-                    BigInt::one() << (-scale).to_usize().expect("-rhs won't fit in usize"),
-                );
-            }
-            Self::from_real_algebraic_number_with_traits(
-                &value,
-                Some(rounding_mode),
-                Some(fp_state),
+            let lhs_value = self.to_real_algebraic_number().expect("known to be finite");
+            let rhs_value = rhs.to_real_algebraic_number().expect("known to be finite");
+            Self::round_arithmetic_result(
+                &(lhs_value * rhs_value),
+                rounding_mode,
+                fp_state,
                 self.traits.clone(),
             )
-        }
+        };
+        retval.flush_to_zero(OpKind::Mul, fp_state)
     }
-    /// get the square-root of `self`
-    pub fn sqrt(
+    /// divide floating-point numbers
+    pub fn div(
         &self,
+        rhs: &Self,
         rounding_mode: Option<RoundingMode>,
         fp_state: Option<&mut FPState>,
     ) -> Self {
+        assert_eq!(self.traits, rhs.traits);
         let properties = self.properties();
         let mut default_fp_state = FPState::default();
-        let fp_state = fp_state.unwrap_or(&mut default_fp_state);
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
         let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
-        let class = self.class();
-        if class.is_nan() {
-            if class.is_signaling_nan() {
-                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-            }
-            match properties
-                .platform_properties()
-                .sqrt_nan_propagation_mode
-                .calculate_propagation_results(class)
+        let self_class = self.class();
+        let rhs_class = rhs.class();
+        let result_sign = self.sign() * rhs.sign();
+        // the fast path's exact results can have more precision than the
+        // precision control allows
+        let use_fast_path = Self::precision_control_properties(properties, fp_state).is_none();
+        let retval = if self_class.is_nan() || rhs_class.is_nan() {
+            fp_state.signal_invalid_for_signaling_operands(&[self_class, rhs_class]);
+            match properties
+                .platform_properties
+                .std_bin_ops_nan_propagation_mode
+                .calculate_propagation_results(self_class, rhs_class)
             {
-                UnaryNaNPropagationResults::Canonical => {
-                    Self::quiet_nan_with_traits(self.traits.clone())
+                BinaryNaNPropagationResults::First => self.to_quiet_nan(),
+                BinaryNaNPropagationResults::Second => rhs.to_quiet_nan(),
+                BinaryNaNPropagationResults::Canonical => {
+                    Self::default_nan_with_traits(self.traits.clone())
                 }
-                UnaryNaNPropagationResults::First => self.to_quiet_nan(),
             }
-        } else if class.is_zero() {
-            Self::signed_zero_with_traits(self.sign(), self.traits.clone())
-        } else if class.is_positive_infinity() {
-            Self::positive_infinity_with_traits(self.traits.clone())
-        } else if self.sign() == Sign::Negative {
+        } else if (self_class.is_infinity() && rhs_class.is_infinity())
+            || (self_class.is_zero() && rhs_class.is_zero())
+        {
             fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-            Self::quiet_nan_with_traits(self.traits.clone())
+            Self::default_nan_with_traits(self.traits.clone())
+        } else if self_class.is_zero() || rhs_class.is_infinity() {
+            Self::signed_zero_with_traits(result_sign, self.traits.clone())
+        } else if self_class.is_infinity() {
+            Self::signed_infinity_with_traits(result_sign, self.traits.clone())
+        } else if rhs_class.is_zero() {
+            fp_state.status_flags = fp_state.status_flags.signal_division_by_zero();
+            Self::signed_infinite_result_with_traits(result_sign, self.traits.clone())
+        } else if use_fast_path && rhs.is_normal_power_of_two(rhs_class) {
+            // dividing by `±2^k` is the same as multiplying by `±2^-k`, which
+            // is much faster than the general division below.
+            self.mul_by_normal_power_of_two(self_class, rhs, true, rounding_mode, fp_state)
         } else {
-            let value = self.to_real_algebraic_number().expect("known to be finite");
-            Self::from_real_algebraic_number_with_traits(
-                &value.pow((1, 2)),
-                Some(rounding_mode),
-                Some(fp_state),
+            let lhs_value = self.to_real_algebraic_number().expect("known to be finite");
+            let rhs_value = rhs.to_real_algebraic_number().expect("known to be finite");
+            Self::round_arithmetic_result(
+                &(lhs_value / rhs_value),
+                rounding_mode,
+                fp_state,
                 self.traits.clone(),
             )
-        }
+        };
+        retval.flush_to_zero(OpKind::Div, fp_state)
     }
-    /// convert `src` to the floating-point format specified by `traits`.
-    pub fn convert_from_float_with_traits<SrcFT: FloatTraits>(
-        src: &Float<SrcFT>,
-        rounding_mode: Option<RoundingMode>,
-        fp_state: Option<&mut FPState>,
-        traits: FT,
-    ) -> Self {
-        let src_properties = src.properties();
-        let dest_properties = traits.properties();
-        let mut default_fp_state = FPState::default();
-        let fp_state = fp_state.unwrap_or(&mut default_fp_state);
-        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
-        let class = src.class();
-        if class.is_nan() {
-            if class.is_signaling_nan() {
-                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-            }
-            let mut retval = Self::quiet_nan_with_traits(traits);
-            match dest_properties
-                .platform_properties
-                .float_to_float_conversion_nan_propagation_mode
-            {
-                FloatToFloatConversionNaNPropagationMode::AlwaysCanonical => retval,
-                FloatToFloatConversionNaNPropagationMode::RetainMostSignificantBits => {
-                    let mut mantissa: BigInt = src.mantissa_field().into();
-                    let retained_bits = src_properties
-                        .mantissa_width()
-                        .min(dest_properties.mantissa_width());
-                    mantissa >>= src_properties.mantissa_width() - retained_bits;
-                    mantissa <<= dest_properties.mantissa_width() - retained_bits;
-                    retval.set_mantissa_field(
-                        Bits::from_bigint(&mantissa).expect("mantissa doesn't fit"),
-                    );
-                    retval.to_quiet_nan()
-                }
-            }
-        } else if class.is_infinity() {
-            Self::signed_infinity_with_traits(src.sign(), traits)
-        } else if class.is_zero() {
-            Self::signed_zero_with_traits(src.sign(), traits)
+    fn check_traits_compatibility(&self, rhs: &Self) -> Result<(), FloatPropertiesIncompatible> {
+        if self.traits == rhs.traits {
+            Ok(())
         } else {
-            let value = src.to_real_algebraic_number().expect("known to be finite");
-            Self::from_real_algebraic_number_with_traits(
-                &value,
-                Some(rounding_mode),
-                Some(fp_state),
-                traits,
-            )
+            Err(FloatPropertiesIncompatible)
         }
     }
-    /// convert `src` to the floating-point format specified by `FT::default()` where `Self` is `Float<FT>`.
-    pub fn convert_from_float<SrcFT: FloatTraits>(
-        src: &Float<SrcFT>,
+    /// add floating-point numbers, returning `Err` instead of panicking if
+    /// `self` and `rhs` have different traits
+    pub fn try_add(
+        &self,
+        rhs: &Self,
         rounding_mode: Option<RoundingMode>,
         fp_state: Option<&mut FPState>,
-    ) -> Self
-    where
-        FT: Default,
-    {
-        Self::convert_from_float_with_traits(src, rounding_mode, fp_state, FT::default())
+    ) -> Result<Self, FloatPropertiesIncompatible> {
+        self.check_traits_compatibility(rhs)?;
+        Ok(self.add(rhs, rounding_mode, fp_state))
     }
-    /// convert `self` to the floating-point format specified by `traits`.
-    pub fn convert_to_float_with_traits<DestFT: FloatTraits>(
+    /// subtract floating-point numbers, returning `Err` instead of panicking if
+    /// `self` and `rhs` have different traits
+    pub fn try_sub(
         &self,
+        rhs: &Self,
         rounding_mode: Option<RoundingMode>,
         fp_state: Option<&mut FPState>,
-        traits: DestFT,
-    ) -> Float<DestFT> {
-        Float::convert_from_float_with_traits(self, rounding_mode, fp_state, traits)
+    ) -> Result<Self, FloatPropertiesIncompatible> {
+        self.check_traits_compatibility(rhs)?;
+        Ok(self.sub(rhs, rounding_mode, fp_state))
     }
-    /// convert `self` to the floating-point format specified by `DestFT::default()`.
-    pub fn convert_to_float<DestFT: FloatTraits + Default>(
+    /// multiply floating-point numbers, returning `Err` instead of panicking if
+    /// `self` and `rhs` have different traits
+    pub fn try_mul(
         &self,
+        rhs: &Self,
         rounding_mode: Option<RoundingMode>,
         fp_state: Option<&mut FPState>,
-    ) -> Float<DestFT> {
-        Float::convert_from_float(self, rounding_mode, fp_state)
-    }
-    /// negate and assign the result back to `self`.
-    /// identical to `self.toggle_sign()`
-    pub fn neg_assign(&mut self) {
-        self.toggle_sign();
-    }
-    /// compute the negation of `self`
-    pub fn neg(&self) -> Self {
-        let mut retval = self.clone();
-        retval.neg_assign();
-        retval
-    }
-    /// compute the absolute-value and assign the result back to `self`.
-    /// identical to `self.set_sign(Sign::Positive)`
-    pub fn abs_assign(&mut self) {
-        self.set_sign(Sign::Positive);
-    }
-    /// compute the absolute-value
-    pub fn abs(&self) -> Self {
-        let mut retval = self.clone();
-        retval.abs_assign();
-        retval
-    }
-    /// set `self`'s sign to the sign of `sign_src`
-    pub fn copy_sign_assign<FT2: FloatTraits>(&mut self, sign_src: &Float<FT2>) {
-        self.set_sign(sign_src.sign());
-    }
-    /// construct a `Float` from `self` but with the sign of `sign_src`
-    pub fn copy_sign<FT2: FloatTraits>(&self, sign_src: &Float<FT2>) -> Self {
-        let mut retval = self.clone();
-        retval.set_sign(sign_src.sign());
-        retval
+    ) -> Result<Self, FloatPropertiesIncompatible> {
+        self.check_traits_compatibility(rhs)?;
+        Ok(self.mul(rhs, rounding_mode, fp_state))
     }
-    /// compare two `Float` values
-    pub fn compare(
+    /// divide floating-point numbers, returning `Err` instead of panicking if
+    /// `self` and `rhs` have different traits
+    pub fn try_div(
         &self,
         rhs: &Self,
-        quiet: bool,
+        rounding_mode: Option<RoundingMode>,
         fp_state: Option<&mut FPState>,
-    ) -> Option<Ordering> {
-        let self_class = self.class();
-        let rhs_class = rhs.class();
-        if self_class.is_nan() || rhs_class.is_nan() {
-            if !quiet || self_class.is_signaling_nan() || rhs_class.is_signaling_nan() {
-                if let Some(fp_state) = fp_state {
-                    fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-                }
-            }
+    ) -> Result<Self, FloatPropertiesIncompatible> {
+        self.check_traits_compatibility(rhs)?;
+        Ok(self.div(rhs, rounding_mode, fp_state))
+    }
+    /// run `op` with a fresh `FPState`, returning its result only if it
+    /// didn't signal inexact, overflow, or underflow.
+    fn exact_result(op: impl FnOnce(&mut FPState) -> Self) -> Option<Self> {
+        let mut fp_state = FPState::default();
+        let result = op(&mut fp_state);
+        let status_flags = fp_state.status_flags;
+        if status_flags.inexact() || status_flags.overflow() || status_flags.underflow() {
             None
-        } else if self_class.is_infinity() || rhs_class.is_infinity() {
-            if self_class == rhs_class {
-                Some(Ordering::Equal)
-            } else if self_class.is_positive_infinity() || rhs_class.is_negative_infinity() {
-                Some(Ordering::Greater)
-            } else {
-                Some(Ordering::Less)
-            }
         } else {
-            Some(
-                self.to_ratio()
-                    .expect("known to be finite")
-                    .cmp(&rhs.to_ratio().expect("known to be finite")),
-            )
+            Some(result)
         }
     }
-    /// compare two `Float` values
-    pub fn compare_quiet(&self, rhs: &Self, fp_state: Option<&mut FPState>) -> Option<Ordering> {
-        self.compare(rhs, true, fp_state)
+    /// add floating-point numbers, returning `None` if the sum had to be
+    /// rounded.
+    ///
+    /// infinite and NaN results that don't need rounding (such as from
+    /// adding `+inf` and `-inf`) are returned as `Some`. `rounding_mode` only
+    /// affects the sign of zero results.
+    pub fn add_exact(&self, rhs: &Self, rounding_mode: Option<RoundingMode>) -> Option<Self> {
+        Self::exact_result(|fp_state| self.add(rhs, rounding_mode, Some(fp_state)))
+    }
+    /// subtract floating-point numbers, returning `None` if the difference had
+    /// to be rounded.
+    ///
+    /// see `add_exact`.
+    pub fn sub_exact(&self, rhs: &Self, rounding_mode: Option<RoundingMode>) -> Option<Self> {
+        Self::exact_result(|fp_state| self.sub(rhs, rounding_mode, Some(fp_state)))
     }
-    /// compare two `Float` values
-    pub fn compare_signaling(
+    /// multiply floating-point numbers, returning `None` if the product had to
+    /// be rounded.
+    ///
+    /// see `add_exact`.
+    pub fn mul_exact(&self, rhs: &Self, rounding_mode: Option<RoundingMode>) -> Option<Self> {
+        Self::exact_result(|fp_state| self.mul(rhs, rounding_mode, Some(fp_state)))
+    }
+    /// divide floating-point numbers, returning `None` if the quotient had to
+    /// be rounded.
+    ///
+    /// division by zero produces an exact infinity, so it returns `Some`.
+    /// see `add_exact`.
+    pub fn div_exact(&self, rhs: &Self, rounding_mode: Option<RoundingMode>) -> Option<Self> {
+        Self::exact_result(|fp_state| self.div(rhs, rounding_mode, Some(fp_state)))
+    }
+    /// compute the exact product of `self` and `rhs` in the wider
+    /// floating-point format specified by `wide_traits`, returning `None` if
+    /// the product isn't exactly representable in that format.
+    ///
+    /// a format with at least twice the significand bits and enough exponent
+    /// range always holds the product exactly, such as `F32 * F32` into
+    /// `F64`, so this gives error-free products. infinite and NaN products are
+    /// computed using `mul` and then converted, so they are `Some` only if
+    /// they are exactly representable in the wide format, see
+    /// `is_exactly_representable_in`.
+    pub fn mul_exact_to<WideFT: FloatTraits>(
         &self,
         rhs: &Self,
-        fp_state: Option<&mut FPState>,
-    ) -> Option<Ordering> {
-        self.compare(rhs, false, fp_state)
+        wide_traits: WideFT,
+    ) -> Option<Float<WideFT>> {
+        assert_eq!(self.traits, rhs.traits);
+        if !self.is_finite() || !rhs.is_finite() {
+            let product = self.mul(rhs, None, None);
+            if !product.is_exactly_representable_in(&wide_traits) {
+                return None;
+            }
+            return Some(Float::convert_from_float_with_traits(
+                &product,
+                None,
+                None,
+                wide_traits,
+            ));
+        }
+        let product_sign = self.sign() * rhs.sign();
+        let value = self.to_real_algebraic_number().expect("known to be finite")
+            * rhs.to_real_algebraic_number().expect("known to be finite");
+        if value.is_zero() {
+            if product_sign == Sign::Negative && !wide_traits.properties().has_sign_bit() {
+                return None;
+            }
+            return Some(Float::signed_zero_with_traits(product_sign, wide_traits));
+        }
+        let mut fp_state = FPState::default();
+        let result = Float::from_real_algebraic_number_with_traits(
+            &value,
+            None,
+            Some(&mut fp_state),
+            wide_traits,
+        );
+        let status_flags = fp_state.status_flags;
+        if status_flags.inexact()
+            || status_flags.overflow()
+            || status_flags.underflow()
+            || status_flags.invalid_operation()
+        {
+            None
+        } else {
+            Some(result)
+        }
     }
-    impl_from_int_type!(from_bigint_with_traits, from_bigint, BigInt);
-    impl_from_int_type!(from_biguint_with_traits, from_biguint, BigUint);
-    impl_from_int_type!(from_u8_with_traits, from_u8, u8);
-    impl_from_int_type!(from_u16_with_traits, from_u16, u16);
-    impl_from_int_type!(from_u32_with_traits, from_u32, u32);
-    impl_from_int_type!(from_u64_with_traits, from_u64, u64);
-    impl_from_int_type!(from_u128_with_traits, from_u128, u128);
-    impl_from_int_type!(from_usize_with_traits, from_usize, usize);
-    impl_from_int_type!(from_i8_with_traits, from_i8, i8);
-    impl_from_int_type!(from_i16_with_traits, from_i16, i16);
-    impl_from_int_type!(from_i32_with_traits, from_i32, i32);
-    impl_from_int_type!(from_i64_with_traits, from_i64, i64);
-    impl_from_int_type!(from_i128_with_traits, from_i128, i128);
-    impl_from_int_type!(from_isize_with_traits, from_isize, isize);
-    impl_to_int_type!(to_bigint, into, BigInt);
-    impl_to_int_type!(to_biguint, to_biguint, BigUint);
-    impl_to_int_type!(to_u8, to_u8, u8);
-    impl_to_int_type!(to_u16, to_u16, u16);
-    impl_to_int_type!(to_u32, to_u32, u32);
-    impl_to_int_type!(to_u64, to_u64, u64);
-    impl_to_int_type!(to_u128, to_u128, u128);
-    impl_to_int_type!(to_usize, to_usize, usize);
-    impl_to_int_type!(to_i8, to_i8, i8);
-    impl_to_int_type!(to_i16, to_i16, i16);
-    impl_to_int_type!(to_i32, to_i32, i32);
-    impl_to_int_type!(to_i64, to_i64, i64);
-    impl_to_int_type!(to_i128, to_i128, i128);
-    impl_to_int_type!(to_isize, to_isize, isize);
-    /// reciprocal square root -- computes `1 / sqrt(self)`
-    pub fn rsqrt(
+    /// compute the IEEE 754 remainder of two floating-point numbers
+    pub fn ieee754_remainder(
         &self,
+        rhs: &Self,
         rounding_mode: Option<RoundingMode>,
         fp_state: Option<&mut FPState>,
     ) -> Self {
+        assert_eq!(self.traits, rhs.traits);
         let properties = self.properties();
         let mut default_fp_state = FPState::default();
-        let fp_state = fp_state.unwrap_or(&mut default_fp_state);
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
         let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
-        let class = self.class();
-        if class.is_nan() {
-            if class.is_signaling_nan() {
-                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-            }
+        let self_class = self.class();
+        let rhs_class = rhs.class();
+        if self_class.is_nan() || rhs_class.is_nan() {
+            fp_state.signal_invalid_for_signaling_operands(&[self_class, rhs_class]);
             match properties
-                .platform_properties()
-                .rsqrt_nan_propagation_mode
-                .calculate_propagation_results(class)
+                .platform_properties
+                .std_bin_ops_nan_propagation_mode
+                .calculate_propagation_results(self_class, rhs_class)
             {
-                UnaryNaNPropagationResults::Canonical => {
-                    Self::quiet_nan_with_traits(self.traits.clone())
+                BinaryNaNPropagationResults::First => self.to_quiet_nan(),
+                BinaryNaNPropagationResults::Second => rhs.to_quiet_nan(),
+                BinaryNaNPropagationResults::Canonical => {
+                    Self::default_nan_with_traits(self.traits.clone())
                 }
-                UnaryNaNPropagationResults::First => self.to_quiet_nan(),
             }
-        } else if class.is_zero() {
-            fp_state.status_flags = fp_state.status_flags.signal_division_by_zero();
-            Self::signed_infinity_with_traits(self.sign(), self.traits.clone())
-        } else if class.is_positive_infinity() {
-            Self::positive_zero_with_traits(self.traits.clone())
-        } else if self.sign() == Sign::Negative {
+        } else if self_class.is_infinity() || rhs_class.is_zero() {
             fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
-            Self::quiet_nan_with_traits(self.traits.clone())
+            Self::default_nan_with_traits(self.traits.clone())
+        } else if rhs_class.is_infinity() {
+            if self_class.is_zero() {
+                Self::signed_zero_with_traits(self.sign(), self.traits.clone())
+            } else {
+                Self::round_from_real_algebraic_number_with_traits(
+                    &self.to_real_algebraic_number().expect("known to be finite"),
+                    Some(rounding_mode),
+                    Some(fp_state),
+                    self.traits.clone(),
+                )
+            }
         } else {
-            let value = self.to_real_algebraic_number().expect("known to be finite");
-            Self::from_real_algebraic_number_with_traits(
-                &value.recip().pow((1, 2)),
+            let lhs_value = self.to_real_algebraic_number().expect("known to be finite");
+            let rhs_value = rhs.to_real_algebraic_number().expect("known to be finite");
+            let quotient = &lhs_value / &rhs_value;
+            let floor_quotient = quotient.to_integer_floor();
+            let fract_quotient = quotient - RealAlgebraicNumber::from(floor_quotient.clone());
+            let selected_quotient =
+                match fract_quotient.cmp(&Ratio::new(BigInt::one(), BigInt::from(2)).into()) {
+                    Ordering::Less => floor_quotient,
+                    Ordering::Greater => floor_quotient + 1,
+                    Ordering::Equal => {
+                        if floor_quotient.is_even() {
+                            floor_quotient
+                        } else {
+                            floor_quotient + 1
+                        }
+                    }
+                };
+            let remainder = lhs_value - rhs_value * RealAlgebraicNumber::from(selected_quotient);
+            if remainder.is_zero() {
+                Self::signed_zero_with_traits(self.sign(), self.traits.clone())
+            } else {
+                Self::round_from_real_algebraic_number_with_traits(
+                    &remainder,
+                    Some(rounding_mode),
+                    Some(fp_state),
+                    self.traits.clone(),
+                )
+            }
+        }
+    }
+    /// calculate the result of `(self * factor) + term` rounding only once, returning the result
+    pub fn fused_mul_add(
+        &self,
+        factor: &Self,
+        term: &Self,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        assert_eq!(self.traits, factor.traits);
+        assert_eq!(self.traits, term.traits);
+        self.fused_mul_add_with_result_traits(
+            factor,
+            term,
+            self.traits.clone(),
+            rounding_mode,
+            fp_state,
+            |propagation_result, quiet, _fp_state| {
+                let nan = match propagation_result {
+                    TernaryNaNPropagationResults::First => self,
+                    TernaryNaNPropagationResults::Second => factor,
+                    TernaryNaNPropagationResults::Third => term,
+                    TernaryNaNPropagationResults::Canonical => {
+                        unreachable!("canonical NaNs aren't propagated")
+                    }
+                };
+                if quiet {
+                    nan.to_quiet_nan()
+                } else {
+                    nan.clone()
+                }
+            },
+        )
+    }
+    /// calculate the result of `(self * factor) + term` rounding only once
+    /// into the format specified by `result_traits`, returning the result.
+    ///
+    /// unlike `fused_mul_add`, the operands don't need to have the same
+    /// format: each operand is converted exactly to a rational before
+    /// computing, so there is no intermediate rounding. The result's platform
+    /// properties decide NaN propagation; a propagated NaN is quieted and then
+    /// converted to the result format. The result is `Float<RT>` rather than
+    /// `Self` so a result format that's wider than the operands can be used.
+    pub fn fused_mul_add_mixed<FT2: FloatTraits, FT3: FloatTraits, RT: FloatTraits>(
+        &self,
+        factor: &Float<FT2>,
+        term: &Float<FT3>,
+        result_traits: RT,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Float<RT> {
+        self.fused_mul_add_with_result_traits(
+            factor,
+            term,
+            result_traits.clone(),
+            rounding_mode,
+            fp_state,
+            |propagation_result, quiet, fp_state| {
+                // the NaNs are quieted first, so converting them doesn't signal again
+                fn convert<SrcFT: FloatTraits, RT: FloatTraits>(
+                    nan: &Float<SrcFT>,
+                    quiet: bool,
+                    fp_state: &mut FPState,
+                    result_traits: RT,
+                ) -> Float<RT> {
+                    let nan = if quiet {
+                        nan.to_quiet_nan()
+                    } else {
+                        nan.clone()
+                    };
+                    Float::convert_from_float_with_traits(&nan, None, Some(fp_state), result_traits)
+                }
+                match propagation_result {
+                    TernaryNaNPropagationResults::First => {
+                        convert(self, quiet, fp_state, result_traits)
+                    }
+                    TernaryNaNPropagationResults::Second => {
+                        convert(factor, quiet, fp_state, result_traits)
+                    }
+                    TernaryNaNPropagationResults::Third => {
+                        convert(term, quiet, fp_state, result_traits)
+                    }
+                    TernaryNaNPropagationResults::Canonical => {
+                        unreachable!("canonical NaNs aren't propagated")
+                    }
+                }
+            },
+        )
+    }
+    /// the shared implementation of `fused_mul_add` and
+    /// `fused_mul_add_mixed`. `result_traits` decides NaN propagation.
+    ///
+    /// NaN operands are propagated by calling `propagate_nan` with the
+    /// operand to propagate (never `Canonical`), and whether it needs to be
+    /// quieted: a quiet NaN third operand propagated by
+    /// `FMAInfZeroQNaNResult::PropagateAndGenerateInvalid` is returned as-is.
+    fn fused_mul_add_with_result_traits<FT2: FloatTraits, FT3: FloatTraits, RT: FloatTraits>(
+        &self,
+        factor: &Float<FT2>,
+        term: &Float<FT3>,
+        result_traits: RT,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+        propagate_nan: impl FnOnce(TernaryNaNPropagationResults, bool, &mut FPState) -> Float<RT>,
+    ) -> Float<RT> {
+        let result_properties = result_traits.properties();
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        let self_class = self.class();
+        let factor_class = factor.class();
+        let term_class = term.class();
+        let product_sign = self.sign() * factor.sign();
+        let is_infinity_times_zero = (self_class.is_infinity() && factor_class.is_zero())
+            || (self_class.is_zero() && factor_class.is_infinity());
+        let retval = if self_class.is_nan() || factor_class.is_nan() || term_class.is_nan() {
+            fp_state.signal_invalid_for_signaling_operands(&[self_class, factor_class, term_class]);
+            if is_infinity_times_zero && term_class.is_quiet_nan() {
+                match result_properties
+                    .platform_properties
+                    .fma_inf_zero_qnan_result
+                {
+                    FMAInfZeroQNaNResult::CanonicalAndGenerateInvalid => {
+                        fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+                        return Float::default_nan_with_traits(result_traits);
+                    }
+                    FMAInfZeroQNaNResult::PropagateAndGenerateInvalid => {
+                        fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+                        return propagate_nan(TernaryNaNPropagationResults::Third, false, fp_state);
+                    }
+                    FMAInfZeroQNaNResult::FollowNaNPropagationMode => {}
+                }
+            }
+            match result_properties
+                .platform_properties
+                .fma_nan_propagation_mode
+                .calculate_propagation_results(self_class, factor_class, term_class)
+            {
+                TernaryNaNPropagationResults::Canonical => {
+                    Float::default_nan_with_traits(result_traits)
+                }
+                propagation_result => propagate_nan(propagation_result, true, fp_state),
+            }
+        } else if is_infinity_times_zero
+            || ((self_class.is_infinity() || factor_class.is_infinity())
+                && term_class.is_infinity()
+                && product_sign != term.sign())
+        {
+            fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+            Float::default_nan_with_traits(result_traits)
+        } else if (self_class.is_zero() || factor_class.is_zero())
+            && term_class.is_zero()
+            && product_sign == term.sign()
+        {
+            Float::signed_zero_with_traits(product_sign, result_traits)
+        } else if term_class.is_infinity() {
+            Float::signed_infinity_with_traits(term.sign(), result_traits)
+        } else if self_class.is_infinity() || factor_class.is_infinity() {
+            Float::signed_infinity_with_traits(product_sign, result_traits)
+        } else {
+            let self_value = self.to_real_algebraic_number().expect("known to be finite");
+            let factor_value = factor
+                .to_real_algebraic_number()
+                .expect("known to be finite");
+            let term_value = term.to_real_algebraic_number().expect("known to be finite");
+            let result = self_value * factor_value + term_value;
+            if result.is_zero() {
+                Float::signed_zero_with_traits(rounding_mode.exact_zero_sign(), result_traits)
+            } else {
+                Float::round_arithmetic_result(&result, rounding_mode, fp_state, result_traits)
+            }
+        };
+        retval.flush_to_zero(OpKind::FusedMulAdd, fp_state)
+    }
+    /// linearly interpolate between `self` and `other`, computing
+    /// `self + (other - self) * t` exactly and rounding only once.
+    ///
+    /// for finite operands, the result is monotonic in `t`, is exactly `self`
+    /// when `t` is zero and exactly `other` when `t` is one, and is between
+    /// `self` and `other` (inclusive) when `t` is between zero and one.
+    /// a zero result has the sign of `self` when `t` is zero, the sign of
+    /// `other` when `t` is one, otherwise it's the same as for `add`.
+    ///
+    /// if any operand is NaN or infinite, the result (including which flags
+    /// are signaled) is the same as evaluating `self + (other - self) * t`
+    /// using the individual operations.
+    pub fn lerp(
+        &self,
+        other: &Self,
+        t: &Self,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        assert_eq!(self.traits, other.traits);
+        assert_eq!(self.traits, t.traits);
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        if !self.is_finite() || !other.is_finite() || !t.is_finite() {
+            let difference = other.sub(self, Some(rounding_mode), Some(fp_state));
+            let product = difference.mul(t, Some(rounding_mode), Some(fp_state));
+            return self.add(&product, Some(rounding_mode), Some(fp_state));
+        }
+        let self_value = self.to_real_algebraic_number().expect("known to be finite");
+        let other_value = other
+            .to_real_algebraic_number()
+            .expect("known to be finite");
+        let t_value = t.to_real_algebraic_number().expect("known to be finite");
+        let result = &self_value + (other_value - &self_value) * t_value;
+        if !result.is_zero() {
+            Self::round_from_real_algebraic_number_with_traits(
+                &result,
                 Some(rounding_mode),
                 Some(fp_state),
                 self.traits.clone(),
             )
+        } else if t.is_zero() {
+            self.clone()
+        } else if t.compare_with_bigint(&BigInt::one(), true, None) == Some(Ordering::Equal) {
+            other.clone()
+        } else if self.is_zero() && other.is_zero() && self.sign() == other.sign() {
+            self.clone()
+        } else {
+            Self::signed_zero_with_traits(rounding_mode.exact_zero_sign(), self.traits.clone())
+        }
+    }
+    /// round `self` to an integer, returning the result as an integer or `None`
+    pub fn round_to_integer(
+        &self,
+        exact: bool,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Option<BigInt> {
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        match self.class() {
+            FloatClass::SignalingNaN => {
+                fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+                return None;
+            }
+            class if !class.is_finite() => {
+                return None;
+            }
+            _ => {}
+        }
+        let value = self.to_real_algebraic_number().expect("known to be finite");
+        let lower_value = value.to_integer_floor();
+        let remainder = value - RealAlgebraicNumber::from(lower_value.clone());
+        if remainder.is_zero() {
+            return Some(lower_value);
+        }
+        if exact {
+            fp_state.status_flags = fp_state.status_flags.signal_inexact();
+        }
+        let upper_value = &lower_value + 1;
+        let (sign, toward_zero_value, away_from_zero_value) = if lower_value.is_negative() {
+            (Sign::Negative, upper_value, lower_value)
+        } else {
+            (Sign::Positive, lower_value, upper_value)
+        };
+        let round_away = match rounding_mode.rounds_away_on_tie(toward_zero_value.is_odd()) {
+            Some(away_on_tie) => {
+                let half = Ratio::new(BigInt::one(), BigInt::from(2)).into();
+                let remainder = match sign {
+                    Sign::Positive => remainder,
+                    Sign::Negative => RealAlgebraicNumber::from(BigInt::one()) - remainder,
+                };
+                match remainder.cmp(&half) {
+                    Ordering::Less => false,
+                    Ordering::Equal => away_on_tie,
+                    Ordering::Greater => true,
+                }
+            }
+            None => rounding_mode.rounds_away_on_overflow(sign),
+        };
+        if round_away {
+            Some(away_from_zero_value)
+        } else {
+            Some(toward_zero_value)
+        }
+    }
+    /// round `self` to an integer, returning the result as a `Float`
+    pub fn round_to_integral(
+        &self,
+        exact: bool,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        let properties = self.properties();
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        let class = self.class();
+        if class.is_nan() {
+            fp_state.signal_invalid_for_signaling_operands(&[class]);
+            match properties
+                .platform_properties()
+                .round_to_integral_nan_propagation_mode
+                .calculate_propagation_results(class)
+            {
+                UnaryNaNPropagationResults::Canonical => {
+                    Self::default_nan_with_traits(self.traits.clone())
+                }
+                UnaryNaNPropagationResults::First => self.to_quiet_nan(),
+            }
+        } else if class.is_infinity() {
+            Self::signed_infinity_with_traits(self.sign(), self.traits.clone())
+        } else {
+            let value = self
+                .round_to_integer(exact, Some(rounding_mode), Some(fp_state))
+                .expect("known to be finite");
+            if value.is_zero() {
+                Self::signed_zero_with_traits(self.sign(), self.traits.clone())
+            } else {
+                Self::round_from_real_algebraic_number_with_traits(
+                    &value.into(),
+                    Some(rounding_mode),
+                    Some(fp_state),
+                    self.traits.clone(),
+                )
+            }
+        }
+    }
+    /// round `self` as if its significand (including the leading bit) had
+    /// only `significand_bits` bits, keeping the same format. Useful for
+    /// modeling reduced-precision arithmetic such as TF32 inside of an F32
+    /// register.
+    ///
+    /// the exponent range isn't reduced, so this is different from
+    /// converting to a narrower standard format and back. The result can
+    /// round up to infinity, signaling overflow, and tiny results can signal
+    /// underflow since the subnormal spacing is also coarser. NaNs are
+    /// quieted, signaling invalid for signaling NaNs. `self` is returned
+    /// unchanged if `significand_bits` is at least the format's precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `significand_bits` is zero.
+    pub fn round_to_precision(
+        &self,
+        significand_bits: usize,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        assert!(significand_bits != 0, "significand_bits must be non-zero");
+        let properties = self.properties();
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let class = self.class();
+        if class.is_nan() {
+            fp_state.signal_invalid_for_signaling_operands(&[class]);
+            return self.to_quiet_nan();
+        }
+        if !class.is_finite() || class.is_zero() || significand_bits > properties.fraction_width() {
+            return self.clone();
+        }
+        let reduced_properties = properties.with_significand_bits(significand_bits);
+        let value = self.to_real_algebraic_number().expect("known to be finite");
+        let reduced = Float::round_from_real_algebraic_number_with_traits(
+            &value,
+            rounding_mode,
+            Some(fp_state),
+            reduced_properties,
+        );
+        // converting back is exact, since `properties` has more precision
+        Self::convert_from_float_with_traits(&reduced, None, None, self.traits.clone())
+    }
+    /// round `self` to the precision and exponent range of the format
+    /// specified by `other`, keeping the same format. This models computing
+    /// in wide registers while rounding results to a narrower format, like
+    /// storing to memory and reloading, without changing the storage format
+    /// the way `convert_to_float` does.
+    ///
+    /// unlike `round_to_precision`, the exponent range is also reduced, so
+    /// the result can overflow or be rounded to a subnormal of `other`'s
+    /// format. (the x87's precision control only reduces the precision, so
+    /// use `round_to_precision` to model it exactly.) If `other`'s format
+    /// isn't narrower, the result is rounded again to fit `self`'s format.
+    /// NaNs are quieted, signaling invalid for signaling NaNs, and
+    /// infinities and zeros are returned unchanged.
+    pub fn round_to_format_precision<FT2: FloatTraits>(
+        &self,
+        other: &FT2,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        let class = self.class();
+        if class.is_nan() {
+            fp_state.signal_invalid_for_signaling_operands(&[class]);
+            return self.to_quiet_nan();
+        }
+        if !class.is_finite() || class.is_zero() {
+            return self.clone();
+        }
+        let value = self.to_real_algebraic_number().expect("known to be finite");
+        let rounded = Float::round_from_real_algebraic_number_with_traits(
+            &value,
+            Some(rounding_mode),
+            Some(fp_state),
+            other.clone(),
+        );
+        Self::convert_from_float_with_traits(
+            &rounded,
+            Some(rounding_mode),
+            Some(fp_state),
+            self.traits.clone(),
+        )
+    }
+    /// normalize `self`.
+    /// This is a no-op for all floating-point formats where
+    /// `has_implicit_leading_bit` is `true` (which includes all standard
+    /// floating-point formats).
+    pub fn normalize(&mut self) {
+        let properties = self.properties();
+        if properties.has_implicit_leading_bit() {
+            return;
+        }
+        let mut exponent_field = self.exponent_field();
+        let exponent_zero_subnormal = properties.exponent_zero_subnormal();
+        if exponent_field == properties.exponent_inf_nan()
+            || exponent_field == exponent_zero_subnormal
+        {
+            return;
+        }
+        let mut mantissa_field = self.mantissa_field();
+        if mantissa_field.is_zero() {
+            self.set_exponent_field(exponent_zero_subnormal);
+            return;
+        }
+        let mantissa_field_msb = Bits::one() << properties.fraction_width();
+        while (mantissa_field.clone() & &mantissa_field_msb).is_zero() {
+            if exponent_field == exponent_zero_subnormal {
+                break;
+            }
+            exponent_field -= Bits::one();
+            mantissa_field <<= 1;
+        }
+        self.set_exponent_field(exponent_field);
+        self.set_mantissa_field(mantissa_field);
+    }
+    /// compute the result of `next_up` or `next_down`
+    pub fn next_up_or_down(&self, up_or_down: UpOrDown, fp_state: Option<&mut FPState>) -> Self {
+        let mut retval = self.clone();
+        retval.next_up_or_down_assign(up_or_down, fp_state);
+        retval
+    }
+    /// assign the result of `next_up` or `next_down` to `self`. finite
+    /// nonzero values are updated by adjusting their fields in place.
+    fn next_up_or_down_assign(&mut self, up_or_down: UpOrDown, fp_state: Option<&mut FPState>) {
+        let properties = self.properties();
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        match (self.class(), up_or_down) {
+            (class, _) if class.is_nan() => {
+                fp_state.signal_invalid_for_signaling_operands(&[class]);
+                match properties
+                    .platform_properties()
+                    .next_up_or_down_nan_propagation_mode
+                    .calculate_propagation_results(class)
+                {
+                    UnaryNaNPropagationResults::Canonical => {
+                        *self = Self::default_nan_with_traits(self.traits.clone());
+                    }
+                    UnaryNaNPropagationResults::First => *self = self.to_quiet_nan(),
+                }
+            }
+            (FloatClass::NegativeInfinity, UpOrDown::Up)
+            | (FloatClass::PositiveInfinity, UpOrDown::Down) => {
+                *self = Self::signed_max_normal_with_traits(self.sign(), self.traits.clone());
+            }
+            (FloatClass::NegativeInfinity, UpOrDown::Down)
+            | (FloatClass::PositiveInfinity, UpOrDown::Up) => {}
+            (class, _) if class.is_zero() => {
+                *self =
+                    Self::signed_min_subnormal_with_traits(up_or_down.into(), self.traits.clone());
+            }
+            _ => {
+                let mantissa = self.mantissa_field();
+                let is_larger_magnitude = Sign::from(up_or_down) == self.sign();
+                if is_larger_magnitude {
+                    if mantissa == properties.mantissa_field_max() {
+                        let exponent = self.exponent_field();
+                        if exponent == properties.exponent_max_normal() {
+                            *self = Self::signed_infinite_result_with_traits(
+                                self.sign(),
+                                self.traits.clone(),
+                            );
+                        } else {
+                            self.set_mantissa_field(properties.mantissa_field_normal_min());
+                            self.set_exponent_field(exponent + Bits::one());
+                        }
+                    } else {
+                        self.set_mantissa_field(mantissa + Bits::one());
+                    }
+                } else if mantissa <= properties.mantissa_field_normal_min() {
+                    let exponent = self.exponent_field();
+                    if exponent == properties.exponent_zero_subnormal() {
+                        assert!(!mantissa.is_zero());
+                        self.set_mantissa_field(mantissa - Bits::one());
+                    } else {
+                        self.set_mantissa_field(properties.mantissa_field_max());
+                        self.set_exponent_field(exponent - Bits::one());
+                    }
+                } else {
+                    self.set_mantissa_field(mantissa - Bits::one());
+                }
+            }
+        }
+    }
+    /// compute the least floating-point number that compares greater than `self`
+    pub fn next_up(&self, fp_state: Option<&mut FPState>) -> Self {
+        self.next_up_or_down(UpOrDown::Up, fp_state)
+    }
+    /// compute the greatest floating-point number that compares less than `self`
+    pub fn next_down(&self, fp_state: Option<&mut FPState>) -> Self {
+        self.next_up_or_down(UpOrDown::Down, fp_state)
+    }
+    /// compute the result of `next_integer_up` or `next_integer_down`
+    pub fn next_integer_up_or_down(
+        &self,
+        up_or_down: UpOrDown,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        let class = self.class();
+        if !class.is_finite() {
+            return self.next_up_or_down(up_or_down, fp_state);
+        }
+        let value = self.to_real_algebraic_number().expect("known to be finite");
+        let (integer, rounding_mode) = match up_or_down {
+            UpOrDown::Up => (
+                value.to_integer_floor() + BigInt::one(),
+                RoundingMode::TowardPositive,
+            ),
+            UpOrDown::Down => (
+                -(-value).to_integer_floor() - BigInt::one(),
+                RoundingMode::TowardNegative,
+            ),
+        };
+        if integer.is_zero() {
+            // stepping from the other side of zero keeps the sign, like `ceil` and `floor`
+            return Self::signed_zero_with_traits(self.sign(), self.traits.clone());
+        }
+        // when the integer isn't representable, the spacing between floats is
+        // more than 1, so rounding away from `self` gives the next float,
+        // which is already an integer. The rounding is exact otherwise, so the
+        // flags are discarded.
+        Self::round_from_real_algebraic_number_with_traits(
+            &RealAlgebraicNumber::from(integer),
+            Some(rounding_mode),
+            Some(&mut FPState::default()),
+            self.traits.clone(),
+        )
+    }
+    /// compute the least integer-valued floating-point number that compares greater than `self`
+    pub fn next_integer_up(&self, fp_state: Option<&mut FPState>) -> Self {
+        self.next_integer_up_or_down(UpOrDown::Up, fp_state)
+    }
+    /// compute the greatest integer-valued floating-point number that compares less than `self`
+    pub fn next_integer_down(&self, fp_state: Option<&mut FPState>) -> Self {
+        self.next_integer_up_or_down(UpOrDown::Down, fp_state)
+    }
+    /// get the floor of the log base 2 of the absolute value of `self`
+    pub fn log_b(&self, fp_state: Option<&mut FPState>) -> Option<BigInt> {
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let properties = self.properties();
+        let class = self.class();
+        if !class.is_finite() || class.is_zero() {
+            fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+            return None;
+        }
+        let exponent_field: BigInt = self.exponent_field().into();
+        let exponent_bias: BigInt = properties.exponent_bias::<Bits>().into();
+        let exponent_zero_subnormal: BigInt = properties.exponent_zero_subnormal::<Bits>().into();
+        let mut exponent =
+            if properties.has_implicit_leading_bit() && exponent_field != exponent_zero_subnormal {
+                return Some(exponent_field - exponent_bias);
+            } else if exponent_field == exponent_zero_subnormal {
+                properties.exponent_min_normal::<Bits>().into() - exponent_bias
+            } else {
+                exponent_field - exponent_bias
+            };
+        let mut mantissa = self.mantissa_field();
+        while (mantissa.clone() >> properties.fraction_width()).is_zero() {
+            mantissa <<= 1;
+            exponent -= 1;
+        }
+        Some(exponent)
+    }
+    /// get `self * 2^scale`
+    pub fn scale_b(
+        &self,
+        mut scale: BigInt,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        let properties = self.properties();
+        let class = self.class();
+        let retval = if class.is_nan() {
+            fp_state.signal_invalid_for_signaling_operands(&[class]);
+            match properties
+                .platform_properties()
+                .scale_b_nan_propagation_mode
+                .calculate_propagation_results(class)
+            {
+                UnaryNaNPropagationResults::Canonical => {
+                    Self::default_nan_with_traits(self.traits.clone())
+                }
+                UnaryNaNPropagationResults::First => self.to_quiet_nan(),
+            }
+        } else if class.is_infinity() {
+            Self::signed_infinity_with_traits(self.sign(), self.traits.clone())
+        } else if class.is_zero() {
+            Self::signed_zero_with_traits(self.sign(), self.traits.clone())
+        } else {
+            let (exponent_min, exponent_max) = properties.mathematical_exponent_range();
+            let scale_limit: BigInt = (BigInt::from(exponent_max) - exponent_min + 1) * 2;
+            scale = scale.max(-&scale_limit);
+            scale = scale.min(scale_limit);
+            let mut value = self.to_real_algebraic_number().expect("known to be finite");
+            if scale.is_positive() {
+                value *= RealAlgebraicNumber::from(
+                    BigInt::one() << scale.to_usize().expect("rhs won't fit in usize"),
+                );
+            } else {
+                value /= RealAlgebraicNumber::from(
+                    BigInt::one() << (-scale).to_usize().expect("-rhs won't fit in usize"),
+                );
+            }
+            Self::round_from_real_algebraic_number_with_traits(
+                &value,
+                Some(rounding_mode),
+                Some(fp_state),
+                self.traits.clone(),
+            )
+        };
+        retval.flush_to_zero(OpKind::ScaleB, fp_state)
+    }
+    #[cfg(not(feature = "rational-only"))]
+    /// get the square-root of `self`
+    pub fn sqrt(
+        &self,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        let properties = self.properties();
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        let class = self.class();
+        if class.is_nan() {
+            fp_state.signal_invalid_for_signaling_operands(&[class]);
+            match properties
+                .platform_properties()
+                .sqrt_nan_propagation_mode
+                .calculate_propagation_results(class)
+            {
+                UnaryNaNPropagationResults::Canonical => {
+                    Self::default_nan_with_traits(self.traits.clone())
+                }
+                UnaryNaNPropagationResults::First => self.to_quiet_nan(),
+            }
+        } else if class.is_zero() {
+            Self::signed_zero_with_traits(self.sign(), self.traits.clone())
+        } else if class.is_positive_infinity() {
+            Self::positive_infinity_with_traits(self.traits.clone())
+        } else if self.sign() == Sign::Negative {
+            fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+            Self::default_nan_with_traits(self.traits.clone())
+        } else {
+            let value = self.to_real_algebraic_number().expect("known to be finite");
+            Self::round_arithmetic_result(
+                &value.pow((1, 2)),
+                rounding_mode,
+                fp_state,
+                self.traits.clone(),
+            )
+        }
+    }
+    #[cfg(not(feature = "rational-only"))]
+    fn newton_steps(
+        &self,
+        initial: &Self,
+        steps: usize,
+        exact_result: impl FnOnce(&RealAlgebraicNumber) -> RealAlgebraicNumber,
+        step: impl Fn(&RealAlgebraicNumber, &RealAlgebraicNumber) -> RealAlgebraicNumber,
+    ) -> Vec<(Self, RealAlgebraicNumber)> {
+        let value = match self.to_real_algebraic_number() {
+            Some(value) if value.is_positive() => value,
+            _ => return Vec::new(),
+        };
+        let mut estimate = match initial.to_real_algebraic_number() {
+            Some(estimate) if estimate.is_positive() => estimate,
+            _ => return Vec::new(),
+        };
+        let exact_result = exact_result(&value);
+        let mut retval = Vec::with_capacity(steps);
+        for _ in 0..steps {
+            let next = Self::from_real_algebraic_number_with_traits(
+                &step(&value, &estimate),
+                None,
+                None,
+                self.traits.clone(),
+            );
+            estimate = match next.to_real_algebraic_number() {
+                Some(estimate) if estimate.is_positive() => estimate,
+                _ => break,
+            };
+            retval.push((next, &estimate - &exact_result));
+        }
+        retval
+    }
+    #[cfg(not(feature = "rational-only"))]
+    /// run `steps` iterations of Newton's method for the square-root of
+    /// `self`, `x' = (x + self / x) / 2`, starting from the estimate
+    /// `initial`.
+    ///
+    /// each iteration is computed exactly then rounded to `self`'s format
+    /// using `TiesToEven`, matching a refinement step with a single rounding.
+    /// returns each iteration's value along with its exact error
+    /// (`value - sqrt(self)`).
+    ///
+    /// this is an analysis tool for checking how sqrt estimates converge, it
+    /// isn't used by `sqrt`. returns an empty `Vec` if `self` or `initial`
+    /// isn't finite and positive, and stops early if an iteration rounds to
+    /// a value that isn't finite and positive.
+    pub fn sqrt_newton_steps(
+        &self,
+        initial: &Self,
+        steps: usize,
+    ) -> Vec<(Self, RealAlgebraicNumber)> {
+        self.newton_steps(
+            initial,
+            steps,
+            |value| value.pow((1, 2)),
+            |value, x| (x + value / x) / RealAlgebraicNumber::from(2),
+        )
+    }
+    #[cfg(not(feature = "rational-only"))]
+    /// run `steps` iterations of Newton's method for the reciprocal
+    /// square-root of `self`, `x' = x * (3 - self * x * x) / 2`, starting
+    /// from the estimate `initial`.
+    ///
+    /// returns each iteration's value along with its exact error
+    /// (`value - 1 / sqrt(self)`), see `sqrt_newton_steps`.
+    pub fn rsqrt_newton_steps(
+        &self,
+        initial: &Self,
+        steps: usize,
+    ) -> Vec<(Self, RealAlgebraicNumber)> {
+        self.newton_steps(
+            initial,
+            steps,
+            |value| value.recip().pow((1, 2)),
+            |value, x| {
+                x * (RealAlgebraicNumber::from(3) - value * x * x) / RealAlgebraicNumber::from(2)
+            },
+        )
+    }
+    /// convert `src` to the floating-point format specified by `traits`.
+    pub fn convert_from_float_with_traits<SrcFT: FloatTraits>(
+        src: &Float<SrcFT>,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+        traits: FT,
+    ) -> Self {
+        let src_properties = src.properties();
+        let dest_properties = traits.properties();
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        let class = src.class();
+        let retval = if !dest_properties.has_inf_nan() && (class.is_nan() || class.is_infinity()) {
+            // infinities saturate and NaNs convert to zero, both are invalid
+            fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+            if class.is_nan() {
+                Self::positive_zero_with_traits(traits)
+            } else {
+                Self::signed_max_normal_with_traits(src.sign(), traits)
+            }
+        } else if class.is_nan() {
+            fp_state.signal_invalid_for_signaling_operands(&[class]);
+            match dest_properties
+                .platform_properties
+                .float_to_float_conversion_nan_propagation_mode
+            {
+                FloatToFloatConversionNaNPropagationMode::AlwaysCanonical => {
+                    Self::default_nan_with_traits(traits)
+                }
+                FloatToFloatConversionNaNPropagationMode::RetainMostSignificantBits => {
+                    let mut retval = Self::quiet_nan_with_traits(traits);
+                    let mut mantissa: BigInt = src.mantissa_field().into();
+                    // only the fraction is retained, explicit leading bits aren't part of the payload
+                    mantissa &= (BigInt::one() << src_properties.fraction_width()) - 1;
+                    let retained_bits = src_properties
+                        .fraction_width()
+                        .min(dest_properties.fraction_width());
+                    mantissa >>= src_properties.fraction_width() - retained_bits;
+                    mantissa <<= dest_properties.fraction_width() - retained_bits;
+                    retval.set_mantissa_field(
+                        Bits::from_bigint(&mantissa).expect("mantissa doesn't fit"),
+                    );
+                    retval.to_quiet_nan()
+                }
+            }
+        } else if class.is_infinity() {
+            Self::signed_infinity_with_traits(src.sign(), traits)
+        } else if class.is_zero() {
+            Self::signed_zero_with_traits(src.sign(), traits)
+        } else {
+            let value = src.to_real_algebraic_number().expect("known to be finite");
+            Self::round_from_real_algebraic_number_with_traits(
+                &value,
+                Some(rounding_mode),
+                Some(fp_state),
+                traits,
+            )
+        };
+        retval.flush_to_zero(OpKind::ConvertFromFloat, fp_state)
+    }
+    /// convert `src` to the floating-point format specified by `FT::default()` where `Self` is `Float<FT>`.
+    pub fn convert_from_float<SrcFT: FloatTraits>(
+        src: &Float<SrcFT>,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self
+    where
+        FT: Default,
+    {
+        Self::convert_from_float_with_traits(src, rounding_mode, fp_state, FT::default())
+    }
+    /// convert `self` to the floating-point format specified by `traits`.
+    pub fn convert_to_float_with_traits<DestFT: FloatTraits>(
+        &self,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+        traits: DestFT,
+    ) -> Float<DestFT> {
+        Float::convert_from_float_with_traits(self, rounding_mode, fp_state, traits)
+    }
+    /// convert `self` to the floating-point format specified by `DestFT::default()`.
+    pub fn convert_to_float<DestFT: FloatTraits + Default>(
+        &self,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Float<DestFT> {
+        Float::convert_from_float(self, rounding_mode, fp_state)
+    }
+    /// return `true` if converting `self` to the floating-point format
+    /// specified by `traits` is lossless: it signals none of `INEXACT`,
+    /// `OVERFLOW`, `UNDERFLOW`, or `INVALID_OPERATION` (such as for negative
+    /// values in formats without a sign bit).
+    ///
+    /// the trial conversion uses the default `FPState`, so exact results are
+    /// never reported as underflowing.
+    ///
+    /// infinities and NaNs must also convert back to the same bits, so NaNs
+    /// whose payload doesn't fit in the target format aren't representable.
+    /// signaling NaNs are never representable, since converting them signals
+    /// `INVALID_OPERATION`.
+    pub fn is_exactly_representable_in<FT2: FloatTraits>(&self, traits: &FT2) -> bool {
+        let mut fp_state = FPState::default();
+        let converted: Float<FT2> =
+            self.convert_to_float_with_traits(None, Some(&mut fp_state), traits.clone());
+        let status_flags = fp_state.status_flags;
+        if status_flags.inexact()
+            || status_flags.overflow()
+            || status_flags.underflow()
+            || status_flags.invalid_operation()
+        {
+            return false;
+        }
+        self.is_finite()
+            || converted
+                .convert_to_float_with_traits(None, None, self.traits.clone())
+                .bit_eq(self)
+    }
+    /// saturate infinities to the largest finite value with the same sign,
+    /// returning the result and whether `self` was clamped.
+    ///
+    /// infinities signal overflow and inexact when clamped. finite values
+    /// and NaNs (including signaling NaNs) are returned unchanged without
+    /// signaling anything.
+    pub fn clamp_to_representable(&self, fp_state: Option<&mut FPState>) -> (Self, bool) {
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        if self.is_infinity() {
+            fp_state.status_flags = fp_state.status_flags.signal_overflow_with_inexact();
+            (
+                Self::signed_max_normal_with_traits(self.sign(), self.traits.clone()),
+                true,
+            )
+        } else {
+            (self.clone(), false)
+        }
+    }
+    /// negate and assign the result back to `self`.
+    /// identical to `self.toggle_sign()`
+    ///
+    /// `neg`, `abs`, and `copy_sign` are IEEE 754's non-computational
+    /// `negate`, `abs`, and `copySign` operations: they only change the sign
+    /// bit and never signal exceptions, so signaling NaNs are passed through
+    /// unchanged (not quieted) and don't signal invalid operation.
+    pub fn neg_assign(&mut self) {
+        self.toggle_sign();
+    }
+    /// compute the negation of `self`. never signals, see `neg_assign`.
+    pub fn neg(&self) -> Self {
+        let mut retval = self.clone();
+        retval.neg_assign();
+        retval
+    }
+    /// compute the absolute-value and assign the result back to `self`.
+    /// identical to `self.set_sign(Sign::Positive)`
+    pub fn abs_assign(&mut self) {
+        self.set_sign(Sign::Positive);
+    }
+    /// compute the absolute-value. never signals, see `neg_assign`.
+    pub fn abs(&self) -> Self {
+        let mut retval = self.clone();
+        retval.abs_assign();
+        retval
+    }
+    /// set `self`'s sign to the sign of `sign_src`
+    pub fn copy_sign_assign<FT2: FloatTraits>(&mut self, sign_src: &Float<FT2>) {
+        self.set_sign(sign_src.sign());
+    }
+    /// construct a `Float` from `self` but with the sign of `sign_src`.
+    /// never signals, even if `self` or `sign_src` is a signaling NaN, see
+    /// `neg_assign`.
+    pub fn copy_sign<FT2: FloatTraits>(&self, sign_src: &Float<FT2>) -> Self {
+        let mut retval = self.clone();
+        retval.set_sign(sign_src.sign());
+        retval
+    }
+    /// assign the least floating-point number that compares greater than
+    /// `self` to `self`, see `next_up`. finite nonzero values are updated
+    /// by adjusting their fields in place.
+    pub fn next_up_assign(&mut self, fp_state: Option<&mut FPState>) {
+        self.next_up_or_down_assign(UpOrDown::Up, fp_state);
+    }
+    /// assign the greatest floating-point number that compares less than
+    /// `self` to `self`, see `next_down`. finite nonzero values are updated
+    /// by adjusting their fields in place.
+    pub fn next_down_assign(&mut self, fp_state: Option<&mut FPState>) {
+        self.next_up_or_down_assign(UpOrDown::Down, fp_state);
+    }
+    /// assign `self * 2^scale` to `self`, see `scale_b`. if `self` and the
+    /// result are both normal, the exponent field is adjusted in place.
+    pub fn scale_b_assign(
+        &mut self,
+        scale: BigInt,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) {
+        let properties = self.properties();
+        if self.class().is_normal()
+            && self.mantissa_field() >= properties.mantissa_field_normal_min()
+        {
+            if let Some(offset) = scale.to_i64() {
+                if self.offset_normal_exponent_field(offset) {
+                    return;
+                }
+            }
+        }
+        *self = self.scale_b(scale, rounding_mode, fp_state);
+    }
+    /// compare two `Float` values numerically.
+    ///
+    /// NaNs are unordered with everything (including themselves) and
+    /// `-0.0` compares equal to `+0.0`. Use `bit_eq` to compare the
+    /// encodings instead.
+    pub fn compare(
+        &self,
+        rhs: &Self,
+        quiet: bool,
+        fp_state: Option<&mut FPState>,
+    ) -> Option<Ordering> {
+        let self_class = self.class();
+        let rhs_class = rhs.class();
+        if self_class.is_nan() || rhs_class.is_nan() {
+            if !quiet || self_class.would_signal_as_operand() || rhs_class.would_signal_as_operand()
+            {
+                if let Some(fp_state) = fp_state {
+                    fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+                }
+            }
+            None
+        } else if self_class.is_infinity() || rhs_class.is_infinity() {
+            if self_class == rhs_class {
+                Some(Ordering::Equal)
+            } else if self_class.is_positive_infinity() || rhs_class.is_negative_infinity() {
+                Some(Ordering::Greater)
+            } else {
+                Some(Ordering::Less)
+            }
+        } else {
+            Some(
+                self.to_ratio()
+                    .expect("known to be finite")
+                    .cmp(&rhs.to_ratio().expect("known to be finite")),
+            )
+        }
+    }
+    /// compare two `Float` values
+    pub fn compare_quiet(&self, rhs: &Self, fp_state: Option<&mut FPState>) -> Option<Ordering> {
+        self.compare(rhs, true, fp_state)
+    }
+    /// compare two `Float` values
+    pub fn compare_signaling(
+        &self,
+        rhs: &Self,
+        fp_state: Option<&mut FPState>,
+    ) -> Option<Ordering> {
+        self.compare(rhs, false, fp_state)
+    }
+    /// compare `self` numerically with the exact integer `rhs`.
+    ///
+    /// `rhs` isn't converted to a `Float` first, so it isn't rounded: a value
+    /// slightly above `2^53` compares correctly even when this format can't
+    /// represent it. infinities compare greater or less than every integer,
+    /// NaNs are handled the same as `compare`.
+    pub fn compare_with_bigint(
+        &self,
+        rhs: &BigInt,
+        quiet: bool,
+        fp_state: Option<&mut FPState>,
+    ) -> Option<Ordering> {
+        self.compare_with_ratio(&Ratio::from(rhs.clone()), quiet, fp_state)
+    }
+    /// compare `self` numerically with the exact rational number `rhs`.
+    ///
+    /// this allows checking exact relationships, such as whether a rounded
+    /// result is less than `1/3`, without rounding `rhs` to a `Float`.
+    /// infinities compare greater or less than every rational number, NaNs
+    /// are handled the same as `compare`.
+    pub fn compare_with_ratio(
+        &self,
+        rhs: &Ratio<BigInt>,
+        quiet: bool,
+        fp_state: Option<&mut FPState>,
+    ) -> Option<Ordering> {
+        let class = self.class();
+        if class.is_nan() {
+            if !quiet || class.would_signal_as_operand() {
+                if let Some(fp_state) = fp_state {
+                    fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+                }
+            }
+            None
+        } else if class.is_positive_infinity() {
+            Some(Ordering::Greater)
+        } else if class.is_negative_infinity() {
+            Some(Ordering::Less)
+        } else {
+            Some(self.to_ratio().expect("known to be finite").cmp(rhs))
+        }
+    }
+    fn minimum_or_maximum(
+        &self,
+        rhs: &Self,
+        fp_state: Option<&mut FPState>,
+        is_maximum: bool,
+    ) -> Self {
+        assert_eq!(self.traits, rhs.traits);
+        let properties = self.properties();
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let self_class = self.class();
+        let rhs_class = rhs.class();
+        if self_class.is_nan() || rhs_class.is_nan() {
+            fp_state.signal_invalid_for_signaling_operands(&[self_class, rhs_class]);
+            return match properties
+                .platform_properties
+                .std_bin_ops_nan_propagation_mode
+                .calculate_propagation_results(self_class, rhs_class)
+            {
+                BinaryNaNPropagationResults::First => self.to_quiet_nan(),
+                BinaryNaNPropagationResults::Second => rhs.to_quiet_nan(),
+                BinaryNaNPropagationResults::Canonical => {
+                    Self::default_nan_with_traits(self.traits.clone())
+                }
+            };
+        }
+        let ordering = match self.compare_quiet(rhs, None).expect("known to not be NaN") {
+            Ordering::Equal => match (self.sign(), rhs.sign()) {
+                (Sign::Negative, Sign::Positive) => Ordering::Less,
+                (Sign::Positive, Sign::Negative) => Ordering::Greater,
+                _ => Ordering::Equal,
+            },
+            ordering => ordering,
+        };
+        if (ordering == Ordering::Less) == is_maximum {
+            rhs.clone()
+        } else {
+            self.clone()
+        }
+    }
+    /// compute the minimum of `self` and `rhs`, like IEEE 754's `minimum`
+    /// operation.
+    ///
+    /// NaNs propagate, and `-0.0` is less than `+0.0`.
+    pub fn minimum(&self, rhs: &Self, fp_state: Option<&mut FPState>) -> Self {
+        self.minimum_or_maximum(rhs, fp_state, false)
+    }
+    /// compute the maximum of `self` and `rhs`, like IEEE 754's `maximum`
+    /// operation.
+    ///
+    /// NaNs propagate, and `-0.0` is less than `+0.0`.
+    pub fn maximum(&self, rhs: &Self, fp_state: Option<&mut FPState>) -> Self {
+        self.minimum_or_maximum(rhs, fp_state, true)
+    }
+    /// clamp `self` to the range `[lo, hi]`, computed as
+    /// `lo.maximum(&hi.minimum(self))`.
+    ///
+    /// if any of `self`, `lo`, or `hi` is NaN, the result is NaN, since
+    /// `minimum` and `maximum` propagate NaNs. `-0.0` is less than `+0.0`.
+    ///
+    /// debug builds panic if `lo > hi`.
+    pub fn clamp(&self, lo: &Self, hi: &Self, fp_state: Option<&mut FPState>) -> Self {
+        debug_assert_ne!(
+            lo.compare_quiet(hi, None),
+            Some(Ordering::Greater),
+            "clamp called with lo > hi"
+        );
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let upper_clamped = hi.minimum(self, Some(fp_state));
+        lo.maximum(&upper_clamped, Some(fp_state))
+    }
+    /// check if `self` and `other` have identical bits and `FloatProperties`.
+    ///
+    /// unlike `compare`, this is a structural comparison: NaNs with the same
+    /// encoding are equal and `-0.0` is not equal to `+0.0`.
+    pub fn bit_eq(&self, other: &Self) -> bool {
+        self.properties() == other.properties() && self.bits == other.bits
+    }
+    /// get the position of `self` among the non-NaN values of its format in
+    /// IEEE 754's `totalOrder`, where `+0.0` is at `0` and `-0.0` is at `-1`.
+    fn total_order_position(&self) -> BigInt {
+        let mut magnitude = self.clone();
+        magnitude.set_sign(Sign::Positive);
+        let magnitude: BigInt = magnitude.bits.into();
+        match self.sign() {
+            Sign::Positive => magnitude,
+            Sign::Negative => -magnitude - 1,
+        }
+    }
+    /// count the floating-point values in the range `[self, hi]`, both ends
+    /// included, by their positions in IEEE 754's `totalOrder`.
+    ///
+    /// `-0.0` and `+0.0` are counted separately, and infinities are counted
+    /// when they're an endpoint. for formats with an explicit leading bit,
+    /// every encoding is counted, even ones with the same value as another
+    /// encoding. returns zero if `self > hi`, and `None` if either endpoint is
+    /// NaN.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `hi` have different `FloatProperties`.
+    pub fn count_representable_in_range(&self, hi: &Self) -> Option<BigInt> {
+        assert_eq!(
+            self.properties(),
+            hi.properties(),
+            "count_representable_in_range called with different formats"
+        );
+        if self.is_nan() || hi.is_nan() {
+            return None;
+        }
+        let count: BigInt = hi.total_order_position() - self.total_order_position() + 1;
+        Some(count.max(BigInt::zero()))
+    }
+    /// pick a uniformly-random floating-point value in the range
+    /// `[self, hi]`, both ends included, where every value counted by
+    /// `count_representable_in_range` is equally likely.
+    ///
+    /// a random position in IEEE 754's `totalOrder` is picked by rejection
+    /// sampling, so ranges that cross zero or include subnormals are handled
+    /// the same as any other range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `hi` have different `FloatProperties`, if either
+    /// is NaN, or if `self > hi`.
+    #[cfg(feature = "rand")]
+    pub fn sample_uniform_representable<R: rand_core::RngCore + ?Sized>(
+        &self,
+        hi: &Self,
+        rng: &mut R,
+    ) -> Self {
+        let count = self
+            .count_representable_in_range(hi)
+            .expect("sample_uniform_representable called with a NaN endpoint");
+        assert!(
+            count.is_positive(),
+            "sample_uniform_representable called with lo > hi"
+        );
+        let max_offset = (count - 1i32)
+            .to_biguint()
+            .expect("known to be non-negative");
+        let bit_count = max_offset.bits();
+        let offset = loop {
+            let mut offset = BigUint::zero();
+            let mut remaining_bits = bit_count;
+            while remaining_bits > 0 {
+                let chunk_bits = remaining_bits.min(64);
+                let chunk = rng.next_u64() >> (64 - chunk_bits);
+                offset = (offset << chunk_bits) | BigUint::from(chunk);
+                remaining_bits -= chunk_bits;
+            }
+            if offset <= max_offset {
+                break offset;
+            }
+        };
+        let position = self.total_order_position() + BigInt::from(offset);
+        let (sign, magnitude) = if position.is_negative() {
+            (Sign::Negative, -position - 1i32)
+        } else {
+            (Sign::Positive, position)
+        };
+        let mut retval = Self::from_bits_and_traits(
+            Bits::from_bigint(&magnitude).expect("known to fit"),
+            self.traits.clone(),
+        );
+        retval.set_sign(sign);
+        retval
+    }
+    impl_from_int_type!(from_bigint_with_traits, from_bigint, BigInt);
+    impl_from_int_type!(from_biguint_with_traits, from_biguint, BigUint);
+    impl_from_int_type!(from_u8_with_traits, from_u8, u8);
+    impl_from_int_type!(from_u16_with_traits, from_u16, u16);
+    impl_from_int_type!(from_u32_with_traits, from_u32, u32);
+    impl_from_int_type!(from_u64_with_traits, from_u64, u64);
+    impl_from_int_type!(from_u128_with_traits, from_u128, u128);
+    impl_from_int_type!(from_usize_with_traits, from_usize, usize);
+    impl_from_int_type!(from_i8_with_traits, from_i8, i8);
+    impl_from_int_type!(from_i16_with_traits, from_i16, i16);
+    impl_from_int_type!(from_i32_with_traits, from_i32, i32);
+    impl_from_int_type!(from_i64_with_traits, from_i64, i64);
+    impl_from_int_type!(from_i128_with_traits, from_i128, i128);
+    impl_from_int_type!(from_isize_with_traits, from_isize, isize);
+    /// get `2^exponent`, which is exact unless `exponent` is outside the
+    /// range of normal and subnormal values, in which case the result is
+    /// rounded, signaling overflow or underflow.
+    pub fn power_of_two_with_traits(
+        exponent: i64,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+        traits: FT,
+    ) -> Self {
+        let properties = traits.properties();
+        let exponent_bias = properties
+            .exponent_bias::<Bits>()
+            .to_i64()
+            .expect("exponent_bias doesn't fit in i64");
+        let exponent_max = properties
+            .exponent_max_normal::<Bits>()
+            .to_i64()
+            .expect("exponent_max_normal doesn't fit in i64")
+            - exponent_bias;
+        let exponent_min = properties
+            .exponent_min_normal::<Bits>()
+            .to_i64()
+            .expect("exponent_min_normal doesn't fit in i64")
+            - exponent_bias
+            - properties.fraction_width() as i64;
+        // every exponent outside of `exponent_min - 2..=exponent_max + 1`
+        // rounds the same as the closest one inside it, clamp so we don't
+        // compute huge numbers
+        let exponent = exponent.max(exponent_min - 2).min(exponent_max + 1);
+        let value = if exponent < 0 {
+            Ratio::new(BigInt::one(), BigInt::one() << (-exponent) as usize)
+        } else {
+            Ratio::from(BigInt::one() << exponent as usize)
+        };
+        Self::from_real_algebraic_number_with_traits(&value.into(), rounding_mode, fp_state, traits)
+    }
+    /// get `2^exponent`, which is exact unless `exponent` is outside the
+    /// range of normal and subnormal values, in which case the result is
+    /// rounded, signaling overflow or underflow.
+    pub fn power_of_two(
+        exponent: i64,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self
+    where
+        FT: Default,
+    {
+        Self::power_of_two_with_traits(exponent, rounding_mode, fp_state, FT::default())
+    }
+    impl_to_int_type!(to_bigint, into, BigInt);
+    impl_to_int_type!(to_biguint, to_biguint, BigUint);
+    impl_to_int_type!(to_u8, to_u8, u8);
+    impl_to_int_type!(to_u16, to_u16, u16);
+    impl_to_int_type!(to_u32, to_u32, u32);
+    impl_to_int_type!(to_u64, to_u64, u64);
+    impl_to_int_type!(to_u128, to_u128, u128);
+    impl_to_int_type!(to_usize, to_usize, usize);
+    impl_to_int_type!(to_i8, to_i8, i8);
+    impl_to_int_type!(to_i16, to_i16, i16);
+    impl_to_int_type!(to_i32, to_i32, i32);
+    impl_to_int_type!(to_i64, to_i64, i64);
+    impl_to_int_type!(to_i128, to_i128, i128);
+    impl_to_int_type!(to_isize, to_isize, isize);
+    #[cfg(not(feature = "rational-only"))]
+    /// reciprocal square root -- computes `1 / sqrt(self)`
+    pub fn rsqrt(
+        &self,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        let properties = self.properties();
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        let class = self.class();
+        if class.is_nan() {
+            fp_state.signal_invalid_for_signaling_operands(&[class]);
+            match properties
+                .platform_properties()
+                .rsqrt_nan_propagation_mode
+                .calculate_propagation_results(class)
+            {
+                UnaryNaNPropagationResults::Canonical => {
+                    Self::default_nan_with_traits(self.traits.clone())
+                }
+                UnaryNaNPropagationResults::First => self.to_quiet_nan(),
+            }
+        } else if class.is_zero() {
+            fp_state.status_flags = fp_state.status_flags.signal_division_by_zero();
+            Self::signed_infinite_result_with_traits(self.sign(), self.traits.clone())
+        } else if class.is_positive_infinity() {
+            Self::positive_zero_with_traits(self.traits.clone())
+        } else if self.sign() == Sign::Negative {
+            fp_state.status_flags = fp_state.status_flags.signal_invalid_operation();
+            Self::default_nan_with_traits(self.traits.clone())
+        } else {
+            let value = self.to_real_algebraic_number().expect("known to be finite");
+            Self::round_from_real_algebraic_number_with_traits(
+                &value.recip().pow((1, 2)),
+                Some(rounding_mode),
+                Some(fp_state),
+                self.traits.clone(),
+            )
+        }
+    }
+    /// round the positive value `value` down or up to `precision`
+    /// significant bits
+    fn round_ratio_to_precision(
+        value: &Ratio<BigInt>,
+        precision: usize,
+        round_up: bool,
+    ) -> Ratio<BigInt> {
+        let log2 = value.numer().bits() as i64 - value.denom().bits() as i64;
+        let shift = precision as i64 - log2;
+        let scale = BigInt::one() << shift.unsigned_abs() as usize;
+        let scaled = if shift < 0 {
+            value / &scale
+        } else {
+            value * &scale
+        };
+        let scaled = if round_up {
+            scaled.ceil()
+        } else {
+            scaled.floor()
+        };
+        if shift < 0 {
+            scaled * scale
+        } else {
+            scaled / scale
+        }
+    }
+    /// get lower and upper bounds of `base^exponent`, where `base` is between
+    /// the positive values `base_bounds`, keeping `precision` significant bits
+    /// for the intermediate values.
+    ///
+    /// returns `None` if the result is known to be greater than `limit` or
+    /// less than `1 / limit`.
+    fn pown_bounds(
+        base_bounds: (Ratio<BigInt>, Ratio<BigInt>),
+        mut exponent: u64,
+        precision: usize,
+        limit: &Ratio<BigInt>,
+    ) -> Option<(Ratio<BigInt>, Ratio<BigInt>)> {
+        let multiply = |a: &(Ratio<BigInt>, Ratio<BigInt>), b: &(Ratio<BigInt>, Ratio<BigInt>)| {
+            let bounds = (
+                Self::round_ratio_to_precision(&(&a.0 * &b.0), precision, false),
+                Self::round_ratio_to_precision(&(&a.1 * &b.1), precision, true),
+            );
+            // the powers of `base` only get further from one, so once past a
+            // limit, the result is too
+            if bounds.0 > *limit || bounds.1 < limit.recip() {
+                None
+            } else {
+                Some(bounds)
+            }
+        };
+        let mut result = (Ratio::one(), Ratio::one());
+        let mut power = base_bounds;
+        loop {
+            if exponent & 1 != 0 {
+                result = multiply(&result, &power)?;
+            }
+            exponent >>= 1;
+            if exponent == 0 {
+                return Some(result);
+            }
+            power = multiply(&power, &power)?;
+        }
+    }
+    /// compute `self` raised to the integer power `exponent` (IEEE 754 `pown`)
+    ///
+    /// the result is correctly rounded. the exact result is computed when it's
+    /// small enough, otherwise bounds with increasing precision are computed
+    /// until they round to the same value.
+    pub fn pown(
+        &self,
+        exponent: i64,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        let properties = self.properties();
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        let class = self.class();
+        let result_sign = if exponent.is_odd() {
+            self.sign()
+        } else {
+            Sign::Positive
+        };
+        let retval = if class.would_signal_as_operand() || (class.is_nan() && exponent != 0) {
+            fp_state.signal_invalid_for_signaling_operands(&[class]);
+            match properties
+                .platform_properties()
+                .scale_b_nan_propagation_mode
+                .calculate_propagation_results(class)
+            {
+                UnaryNaNPropagationResults::Canonical => {
+                    Self::default_nan_with_traits(self.traits.clone())
+                }
+                UnaryNaNPropagationResults::First => self.to_quiet_nan(),
+            }
+        } else if exponent == 0 {
+            Self::round_from_real_algebraic_number_with_traits(
+                &RealAlgebraicNumber::one(),
+                Some(rounding_mode),
+                Some(fp_state),
+                self.traits.clone(),
+            )
+        } else if class.is_zero() {
+            if exponent < 0 {
+                fp_state.status_flags = fp_state.status_flags.signal_division_by_zero();
+                Self::signed_infinite_result_with_traits(result_sign, self.traits.clone())
+            } else {
+                Self::signed_zero_with_traits(result_sign, self.traits.clone())
+            }
+        } else if class.is_infinity() {
+            if exponent < 0 {
+                Self::signed_zero_with_traits(result_sign, self.traits.clone())
+            } else {
+                Self::signed_infinity_with_traits(result_sign, self.traits.clone())
+            }
+        } else {
+            let (_, mut mantissa, mut mantissa_exponent) =
+                self.to_scientific_ratio().expect("known to be finite");
+            while mantissa.is_even() {
+                mantissa >>= 1;
+                mantissa_exponent += 1;
+            }
+            // all results with magnitudes outside of `2^-limit..=2^limit`
+            // round the same as the values at the limits
+            let (exponent_min, exponent_max) = properties.mathematical_exponent_range();
+            let limit = (i128::from(exponent_max) - i128::from(exponent_min) + 1) * 2;
+            let power_of_two = |log2: i128| {
+                let shift = log2.abs().to_usize().expect("limit doesn't fit in usize");
+                if log2 < 0 {
+                    Ratio::new(BigInt::one(), BigInt::one() << shift)
+                } else {
+                    Ratio::from_integer(BigInt::one() << shift)
+                }
+            };
+            let abs_exponent = exponent.unsigned_abs();
+            let significand_bits = mantissa.bits() as i128;
+            // `2^lower <= abs(self) < 2^(lower + 1)`
+            let lower = i128::from(mantissa_exponent) + significand_bits - 1;
+            let (lower_log2, upper_log2) = if exponent > 0 {
+                (
+                    lower * i128::from(exponent),
+                    (lower + 1) * i128::from(exponent),
+                )
+            } else {
+                (
+                    (lower + 1) * i128::from(exponent),
+                    lower * i128::from(exponent),
+                )
+            };
+            let value = if mantissa.is_one() {
+                // powers of two (including ±1) have exact powers of two as results
+                Some(power_of_two(
+                    (lower * i128::from(exponent)).max(-limit).min(limit),
+                ))
+            } else if lower_log2 > limit {
+                Some(power_of_two(limit))
+            } else if upper_log2 < -limit {
+                Some(power_of_two(-limit))
+            } else if significand_bits * i128::from(abs_exponent) <= limit * 2 {
+                let (numer, denom) = self.abs().to_ratio().expect("known to be finite").into();
+                let abs_exponent = abs_exponent
+                    .to_usize()
+                    .expect("exponent doesn't fit in usize");
+                let value = Ratio::new(
+                    Pow::pow(&numer, abs_exponent),
+                    Pow::pow(&denom, abs_exponent),
+                );
+                Some(if exponent < 0 { value.recip() } else { value })
+            } else {
+                None
+            };
+            let round = |value: Ratio<BigInt>, fp_state: &mut FPState| {
+                let value = match result_sign {
+                    Sign::Positive => value,
+                    Sign::Negative => -value,
+                };
+                Self::round_from_real_algebraic_number_with_traits(
+                    &value.into(),
+                    Some(rounding_mode),
+                    Some(fp_state),
+                    self.traits.clone(),
+                )
+            };
+            if let Some(value) = value {
+                round(value, fp_state)
+            } else {
+                // the exact result is too big to calculate, but it isn't
+                // representable and isn't halfway between representable
+                // values, so calculate narrower and narrower bounds until
+                // both bounds round to the same value
+                let abs_value = self.abs().to_ratio().expect("known to be finite");
+                let base = if exponent < 0 {
+                    abs_value.recip()
+                } else {
+                    abs_value
+                };
+                let mut precision = properties.fraction_width() + 2 * 64 + 16;
+                loop {
+                    let base_bounds = (
+                        Self::round_ratio_to_precision(&base, precision, false),
+                        Self::round_ratio_to_precision(&base, precision, true),
+                    );
+                    let (lower_bound, upper_bound) = match Self::pown_bounds(
+                        base_bounds,
+                        abs_exponent,
+                        precision,
+                        &power_of_two(limit),
+                    ) {
+                        Some(bounds) => bounds,
+                        None if base > Ratio::one() => break round(power_of_two(limit), fp_state),
+                        None => break round(power_of_two(-limit), fp_state),
+                    };
+                    let mut lower_fp_state = *fp_state;
+                    let lower_result = round(lower_bound, &mut lower_fp_state);
+                    let mut upper_fp_state = *fp_state;
+                    let upper_result = round(upper_bound, &mut upper_fp_state);
+                    if lower_result.bits() == upper_result.bits()
+                        && lower_fp_state.status_flags == upper_fp_state.status_flags
+                    {
+                        fp_state.status_flags = lower_fp_state.status_flags;
+                        break lower_result;
+                    }
+                    precision *= 2;
+                }
+            }
+        };
+        retval.flush_to_zero(OpKind::PowN, fp_state)
+    }
+    #[cfg(not(feature = "rational-only"))]
+    /// get the cube-root of `self`
+    pub fn cbrt(
+        &self,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        let properties = self.properties();
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        let class = self.class();
+        if class.is_nan() {
+            fp_state.signal_invalid_for_signaling_operands(&[class]);
+            match properties
+                .platform_properties()
+                .sqrt_nan_propagation_mode
+                .calculate_propagation_results(class)
+            {
+                UnaryNaNPropagationResults::Canonical => {
+                    Self::default_nan_with_traits(self.traits.clone())
+                }
+                UnaryNaNPropagationResults::First => self.to_quiet_nan(),
+            }
+        } else if class.is_zero() {
+            Self::signed_zero_with_traits(self.sign(), self.traits.clone())
+        } else if class.is_infinity() {
+            Self::signed_infinity_with_traits(self.sign(), self.traits.clone())
+        } else {
+            let value = self
+                .abs()
+                .to_real_algebraic_number()
+                .expect("known to be finite")
+                .pow((1, 3));
+            let value = match self.sign() {
+                Sign::Positive => value,
+                Sign::Negative => -value,
+            };
+            Self::round_from_real_algebraic_number_with_traits(
+                &value,
+                Some(rounding_mode),
+                Some(fp_state),
+                self.traits.clone(),
+            )
+        }
+    }
+    #[cfg(not(feature = "rational-only"))]
+    /// compute `sqrt(self * self + rhs * rhs)` rounding only once, returning the result
+    pub fn hypot(
+        &self,
+        rhs: &Self,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        assert_eq!(self.traits, rhs.traits);
+        let properties = self.properties();
+        let mut default_fp_state = FPState::default();
+        let mut fp_state_guard =
+            FPStateInvariantsGuard::new(fp_state.unwrap_or(&mut default_fp_state));
+        let fp_state: &mut FPState = &mut fp_state_guard;
+        let rounding_mode = rounding_mode.unwrap_or(fp_state.rounding_mode);
+        let self_class = self.class();
+        let rhs_class = rhs.class();
+        let retval = if self_class.would_signal_as_operand()
+            || rhs_class.would_signal_as_operand()
+            || ((self_class.is_nan() || rhs_class.is_nan())
+                && !self_class.is_infinity()
+                && !rhs_class.is_infinity())
+        {
+            fp_state.signal_invalid_for_signaling_operands(&[self_class, rhs_class]);
+            match properties
+                .platform_properties
+                .std_bin_ops_nan_propagation_mode
+                .calculate_propagation_results(self_class, rhs_class)
+            {
+                BinaryNaNPropagationResults::First => self.to_quiet_nan(),
+                BinaryNaNPropagationResults::Second => rhs.to_quiet_nan(),
+                BinaryNaNPropagationResults::Canonical => {
+                    Self::default_nan_with_traits(self.traits.clone())
+                }
+            }
+        } else if self_class.is_infinity() || rhs_class.is_infinity() {
+            // IEEE 754 specifies `hypot(inf, QNaN)` is `inf`
+            Self::positive_infinity_with_traits(self.traits.clone())
+        } else if self_class.is_zero() && rhs_class.is_zero() {
+            Self::positive_zero_with_traits(self.traits.clone())
+        } else {
+            let lhs_value = self.to_real_algebraic_number().expect("known to be finite");
+            let rhs_value = rhs.to_real_algebraic_number().expect("known to be finite");
+            let sum_of_squares = &lhs_value * &lhs_value + &rhs_value * &rhs_value;
+            Self::round_from_real_algebraic_number_with_traits(
+                &sum_of_squares.pow((1, 2)),
+                Some(rounding_mode),
+                Some(fp_state),
+                self.traits.clone(),
+            )
+        };
+        retval.flush_to_zero(OpKind::Hypot, fp_state)
+    }
+    /// apply `op` to `self` followed by `operands`, returning the result.
+    ///
+    /// `rounding_mode` and `fp_state` are passed through to the called
+    /// function when it takes them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `operands.len() + 1 != op.operand_count()`.
+    pub fn apply(
+        &self,
+        op: FloatOp,
+        operands: &[&Self],
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<&mut FPState>,
+    ) -> Self {
+        assert_eq!(
+            operands.len() + 1,
+            op.operand_count(),
+            "wrong number of operands for {:?}",
+            op
+        );
+        match op {
+            FloatOp::Add => self.add(operands[0], rounding_mode, fp_state),
+            FloatOp::Sub => self.sub(operands[0], rounding_mode, fp_state),
+            FloatOp::Mul => self.mul(operands[0], rounding_mode, fp_state),
+            FloatOp::Div => self.div(operands[0], rounding_mode, fp_state),
+            FloatOp::IEEE754Remainder => {
+                self.ieee754_remainder(operands[0], rounding_mode, fp_state)
+            }
+            FloatOp::FusedMulAdd => {
+                self.fused_mul_add(operands[0], operands[1], rounding_mode, fp_state)
+            }
+            FloatOp::RoundToIntegral(rounding_mode) => {
+                self.round_to_integral(false, Some(rounding_mode), fp_state)
+            }
+            FloatOp::RoundToIntegralExact => self.round_to_integral(true, rounding_mode, fp_state),
+            FloatOp::NextUp => self.next_up(fp_state),
+            FloatOp::NextDown => self.next_down(fp_state),
+            FloatOp::ScaleB(scale) => self.scale_b(scale.into(), rounding_mode, fp_state),
+            #[cfg(not(feature = "rational-only"))]
+            FloatOp::Sqrt => self.sqrt(rounding_mode, fp_state),
+            #[cfg(not(feature = "rational-only"))]
+            FloatOp::RSqrt => self.rsqrt(rounding_mode, fp_state),
+            FloatOp::PowN(exponent) => self.pown(exponent, rounding_mode, fp_state),
+            #[cfg(not(feature = "rational-only"))]
+            FloatOp::Cbrt => self.cbrt(rounding_mode, fp_state),
+            #[cfg(not(feature = "rational-only"))]
+            FloatOp::Hypot => self.hypot(operands[0], rounding_mode, fp_state),
+            FloatOp::Neg => self.neg(),
+            FloatOp::Abs => self.abs(),
+            FloatOp::CopySign => self.copy_sign(operands[0]),
+        }
+    }
+}
+
+impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> fmt::Debug for Float<FT> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let properties = self.properties();
+        let mut debug_struct = f.debug_struct("Float");
+        debug_struct.field("traits", &self.traits);
+        debug_struct.field(
+            "bits",
+            &format_args!(
+                "0x{value:0width$X}",
+                value = self.bits(),
+                width = properties.width().div_ceil(4)
+            ),
+        );
+        if properties.has_sign_bit() {
+            debug_struct.field("sign", &self.sign());
+        }
+        debug_struct.field(
+            "exponent_field",
+            &format_args!(
+                "0x{value:0width$X}",
+                value = self.exponent_field(),
+                width = properties.exponent_width().div_ceil(4)
+            ),
+        );
+        debug_struct.field(
+            "mantissa_field",
+            &format_args!(
+                "0x{value:0width$X}",
+                value = self.mantissa_field(),
+                width = properties.mantissa_width().div_ceil(4)
+            ),
+        );
+        debug_struct.field("class", &self.class());
+        debug_struct.finish()
+    }
+}
+
+/// standard 16-bit float
+pub type F16 = Float<F16Traits>;
+/// standard 32-bit float
+pub type F32 = Float<F32Traits>;
+/// standard 64-bit float
+pub type F64 = Float<F64Traits>;
+/// standard 128-bit float
+pub type F128 = Float<F128Traits>;
+/// x87 80-bit extended precision float
+pub type F80 = Float<F80Traits>;
+
+/// standard 16-bit float
+pub type F16WithPlatformProperties = Float<F16WithPlatformPropertiesTraits>;
+/// standard 32-bit float
+pub type F32WithPlatformProperties = Float<F32WithPlatformPropertiesTraits>;
+/// standard 64-bit float
+pub type F64WithPlatformProperties = Float<F64WithPlatformPropertiesTraits>;
+/// standard 128-bit float
+pub type F128WithPlatformProperties = Float<F128WithPlatformPropertiesTraits>;
+/// x87 80-bit extended precision float
+pub type F80WithPlatformProperties = Float<F80WithPlatformPropertiesTraits>;
+
+impl From<f32> for F32 {
+    /// convert by copying the bits, so NaN payloads are preserved
+    fn from(value: f32) -> Self {
+        Self::from_bits(value.to_bits())
+    }
+}
+
+impl From<F32> for f32 {
+    /// convert by copying the bits, so NaN payloads are preserved
+    fn from(value: F32) -> Self {
+        f32::from_bits(value.into_bits())
+    }
+}
+
+impl From<f64> for F64 {
+    /// convert by copying the bits, so NaN payloads are preserved
+    fn from(value: f64) -> Self {
+        Self::from_bits(value.to_bits())
+    }
+}
+
+impl From<F64> for f64 {
+    /// convert by copying the bits, so NaN payloads are preserved
+    fn from(value: F64) -> Self {
+        f64::from_bits(value.into_bits())
+    }
+}
+
+/// a finite value was too large to convert to the destination format
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConversionOverflowed;
+
+impl fmt::Display for ConversionOverflowed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("float conversion overflowed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ConversionOverflowed {}
+
+impl TryFrom<F64> for F32 {
+    type Error = ConversionOverflowed;
+    /// convert using `convert_from_float` with the default `FPState`, which
+    /// rounds to nearest, ties to even. returns an error if a finite value
+    /// overflows. values that aren't exactly representable are rounded
+    /// rather than returning an error, use `is_exactly_representable_in` to
+    /// check for that.
+    fn try_from(value: F64) -> Result<Self, Self::Error> {
+        let mut fp_state = FPState::default();
+        let retval = Self::convert_from_float(&value, None, Some(&mut fp_state));
+        if fp_state.status_flags.overflow() {
+            Err(ConversionOverflowed)
+        } else {
+            Ok(retval)
+        }
+    }
+}
+
+/// `Float` with attached `FPState` and dynamically settable `FloatProperties`
+///
+/// the operations that take an `FPState` in `Float` use `self`'s `FPState`
+/// instead, merged with the other `DynamicFloat` operands' `FPState`s.
+/// operations that produce a floating-point value return a `DynamicFloat`
+/// holding the updated `FPState`, and operations that produce anything else,
+/// such as `log_b`, `round_to_integer`, and `compare`, return the result
+/// along with the updated `FPState`. operations that never signal are
+/// available on `value` through `Deref`.
+#[derive(Clone, Debug)]
+#[allow(clippy::manual_non_exhaustive)]
+pub struct DynamicFloat {
+    /// floating-point state
+    pub fp_state: FPState,
+    /// floating-point value; also accessible through `*self`
+    pub value: Float<FloatProperties>,
+    /// the exact value which `value` approximates, if exact value tracking is
+    /// enabled; see `with_exact_value_tracking`.
+    pub exact_value: Option<RealAlgebraicNumber>,
+    _private: (),
+}
+
+impl Deref for DynamicFloat {
+    type Target = Float<FloatProperties>;
+    /// returns `&self.value`
+    fn deref(&self) -> &Float<FloatProperties> {
+        &self.value
+    }
+}
+
+impl DerefMut for DynamicFloat {
+    /// returns `&mut self.value`
+    fn deref_mut(&mut self) -> &mut Float<FloatProperties> {
+        &mut self.value
+    }
+}
+
+impl From<Float<FloatProperties>> for DynamicFloat {
+    fn from(value: Float<FloatProperties>) -> Self {
+        Self {
+            fp_state: FPState::default(),
+            value,
+            exact_value: None,
+            _private: (),
+        }
+    }
+}
+
+impl From<DynamicFloat> for Float<FloatProperties> {
+    fn from(value: DynamicFloat) -> Self {
+        value.value
+    }
+}
+
+macro_rules! impl_dynamic_float_fn {
+    (@exact $operands:expr) => {
+        None
+    };
+    (@exact $operands:expr, $exact:expr) => {
+        Self::calculate_exact_value($operands, $exact)
+    };
+    (
+        $(#[doc = $doc:literal])+
+        $fn_name:ident, $called_fn_name:ident,
+        (&self$(, $args:ident: $arg_types:ty)*)
+        $(, exact = $exact:expr)?
+    ) => {
+        impl DynamicFloat {
+            $(#[doc = $doc])+
+            pub fn $fn_name(
+                &self,
+                $($args: $arg_types,)*
+            ) -> Self {
+                let mut fp_state = self.fp_state;
+                let value = self
+                    .value
+                    .$called_fn_name($($args,)* Some(&mut fp_state));
+                Self {
+                    fp_state,
+                    value,
+                    exact_value: impl_dynamic_float_fn!(@exact &[self] $(, $exact)?),
+                    _private: (),
+                }
+            }
+        }
+    };
+    (
+        $(#[doc = $doc:literal])+
+        $fn_name:ident, $checked_fn_name:ident, $called_fn_name:ident,
+        (&self$(, $before_args:ident: $before_arg_types:ty)*),
+        ($($float_args:ident: &Self),*),
+        ($($after_args:ident: $after_arg_types:ty),*)
+        $(, exact = $exact:expr)?
+    ) => {
+        impl DynamicFloat {
+            $(#[doc = $doc])+
+            pub fn $fn_name(
+                &self,
+                $($before_args: $before_args_type,)*
+                $($float_args: &Self,)*
+                $($after_args: $after_arg_types,)*
+            ) -> Self {
+                let mut fp_state = self.fp_state;
+                $(fp_state.merge_assign($float_args.fp_state);)*
+                let value = self
+                    .value
+                    .$called_fn_name($($before_args,)* $(&$float_args.value,)* $($after_args,)* Some(&mut fp_state));
+                Self {
+                    fp_state,
+                    value,
+                    exact_value: impl_dynamic_float_fn!(@exact &[self, $($float_args),*] $(, $exact)?),
+                    _private: (),
+                }
+            }
+            $(#[doc = $doc])+
+            pub fn $checked_fn_name(
+                &self,
+                $($before_args: $before_args_type,)*
+                $($float_args: &Self,)*
+                $($after_args: $after_arg_types,)*
+            ) -> Result<Self, FPStateMergeFailed> {
+                let mut fp_state = self.fp_state;
+                $(fp_state.checked_merge_assign($float_args.fp_state)?;)*
+                let value = self
+                    .value
+                    .$called_fn_name($($before_args,)* $(&$float_args.value,)* $($after_args,)* Some(&mut fp_state));
+                Ok(Self {
+                    fp_state,
+                    value,
+                    exact_value: impl_dynamic_float_fn!(@exact &[self, $($float_args),*] $(, $exact)?),
+                    _private: (),
+                })
+            }
+        }
+    };
+}
+
+macro_rules! impl_dynamic_float_from_int_type {
+    ($from_int_with_traits:ident, $from_int:ident, $int:ident) => {
+        impl DynamicFloat {
+            /// convert from integer to floating-point.
+            /// `rounding_mode` only used for this conversion.
+            pub fn $from_int(
+                value: $int,
+                rounding_mode: Option<RoundingMode>,
+                fp_state: Option<FPState>,
+                properties: FloatProperties,
+            ) -> Self {
+                let mut fp_state = fp_state.unwrap_or_default();
+                let value = Float::$from_int_with_traits(
+                    value,
+                    rounding_mode,
+                    Some(&mut fp_state),
+                    properties,
+                );
+                Self {
+                    fp_state,
+                    value,
+                    exact_value: None,
+                    _private: (),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_dynamic_float_to_int_type {
+    ($name:ident, $int:ident) => {
+        impl DynamicFloat {
+            /// convert `self` to an integer, returning the result as a tuple of an integer or `None`, and `FPState`
+            ///
+            /// the result is `None` and invalid operation is signaled if
+            /// `self` is NaN or the rounded value doesn't fit in the integer
+            /// type.
+            pub fn $name(
+                &self,
+                exact: bool,
+                rounding_mode: Option<RoundingMode>,
+            ) -> (Option<$int>, FPState) {
+                let mut fp_state = self.fp_state;
+                let result = self.value.$name(exact, rounding_mode, Some(&mut fp_state));
+                (result, fp_state)
+            }
+        }
+    };
+}
+
+impl DynamicFloat {
+    /// create from `properties`
+    pub fn new(properties: FloatProperties) -> Self {
+        Self {
+            fp_state: FPState::default(),
+            value: Float::from_bits_and_traits(BigUint::zero(), properties),
+            exact_value: None,
+            _private: (),
+        }
+    }
+    /// create from `bits` and `properties`
+    pub fn from_bits(bits: BigUint, properties: FloatProperties) -> Option<Self> {
+        if bits <= properties.overall_mask::<BigUint>() {
+            Some(Self {
+                fp_state: FPState::default(),
+                value: Float::from_bits_and_traits(bits, properties),
+                exact_value: None,
+                _private: (),
+            })
+        } else {
+            None
+        }
+    }
+    /// create from `bits` in the IEEE 754 interchange format layout and
+    /// `properties`.
+    ///
+    /// see `Float::from_interchange_bits_with_traits`.
+    pub fn from_interchange_bits(bits: &BigUint, properties: FloatProperties) -> Option<Self> {
+        Some(Self {
+            fp_state: FPState::default(),
+            value: Float::from_interchange_bits_with_traits(bits, properties)?,
+            exact_value: None,
+            _private: (),
+        })
+    }
+    /// get the bits of `self`
+    pub fn into_bits(self) -> BigUint {
+        self.value.into_bits()
+    }
+    /// convert `self` to a `Float` with statically-known `FloatTraits` by
+    /// copying the bits, returning `None` if `self.properties()` doesn't match
+    /// `FT::default().properties()`. `self.fp_state` is discarded.
+    pub fn to_static_float<FT: FloatTraits + Default>(&self) -> Option<Float<FT>> {
+        let traits = FT::default();
+        if traits.properties() != self.properties() {
+            return None;
+        }
+        let bits = FT::Bits::from_bigint(&self.bits().clone().into())
+            .expect("bits known to fit in the destination type");
+        Some(Float::from_bits_and_traits(bits, traits))
+    }
+    /// enable exact value tracking, starting from the mathematical value of
+    /// `self.value`.
+    ///
+    /// while tracking is enabled, `add`, `sub`, `mul`, `div`, `fused_mul_add`,
+    /// `sqrt`, `hypot`, the sign operations, and conversions between formats
+    /// compute `exact_value` of their result from the `exact_value` of their
+    /// operands without rounding, so it is the value the whole computation
+    /// would have produced with infinite precision.
+    ///
+    /// tracking is disabled (`exact_value` becomes `None`) by all other
+    /// operations, when any operand isn't tracking, and when the exact result
+    /// isn't a real number, such as when dividing by zero. modifying `value`
+    /// directly doesn't update `exact_value`.
+    ///
+    /// since `RealAlgebraicNumber` arithmetic is much slower than rounded
+    /// arithmetic, tracking is disabled by default.
+    pub fn with_exact_value_tracking(mut self) -> Self {
+        self.exact_value = self.value.to_real_algebraic_number();
+        self
+    }
+    fn calculate_exact_value(
+        operands: &[&Self],
+        f: impl FnOnce(&[&RealAlgebraicNumber]) -> Option<RealAlgebraicNumber>,
+    ) -> Option<RealAlgebraicNumber> {
+        let operands: Option<Vec<&RealAlgebraicNumber>> = operands
+            .iter()
+            .map(|operand| operand.exact_value.as_ref())
+            .collect();
+        f(&operands?)
+    }
+    /// create from a little-endian byte array and `properties`
+    pub fn from_le_bytes(bytes: &[u8], properties: FloatProperties) -> Option<Self> {
+        Float::from_le_bytes_with_traits(bytes, properties).map(Into::into)
+    }
+    /// create from a big-endian byte array and `properties`
+    pub fn from_be_bytes(bytes: &[u8], properties: FloatProperties) -> Option<Self> {
+        Float::from_be_bytes_with_traits(bytes, properties).map(Into::into)
+    }
+    /// get the positive zero value
+    pub fn positive_zero(properties: FloatProperties) -> Self {
+        Float::positive_zero_with_traits(properties).into()
+    }
+    /// get the negative zero value
+    pub fn negative_zero(properties: FloatProperties) -> Self {
+        Float::negative_zero_with_traits(properties).into()
+    }
+    /// get the zero with sign `sign`
+    pub fn signed_zero(sign: Sign, properties: FloatProperties) -> Self {
+        Float::signed_zero_with_traits(sign, properties).into()
+    }
+    /// get the positive infinity value
+    pub fn positive_infinity(properties: FloatProperties) -> Self {
+        Float::positive_infinity_with_traits(properties).into()
+    }
+    /// get the negative infinity value
+    pub fn negative_infinity(properties: FloatProperties) -> Self {
+        Float::negative_infinity_with_traits(properties).into()
+    }
+    /// get the infinity with sign `sign`
+    pub fn signed_infinity(sign: Sign, properties: FloatProperties) -> Self {
+        Float::signed_infinity_with_traits(sign, properties).into()
+    }
+    /// get the canonical quiet NaN, which is also just the canonical NaN
+    pub fn quiet_nan(properties: FloatProperties) -> Self {
+        Float::quiet_nan_with_traits(properties).into()
+    }
+    /// get the default NaN: the NaN generated by invalid operations and by
+    /// NaN propagation modes that produce the canonical NaN.
+    pub fn default_nan(properties: FloatProperties) -> Self {
+        Float::default_nan_with_traits(properties).into()
+    }
+    /// get the canonical signaling NaN
+    pub fn signaling_nan(properties: FloatProperties) -> Self {
+        Float::signaling_nan_with_traits(properties).into()
+    }
+    /// construct a signaling NaN with a specific sign and payload.
+    ///
+    /// see `Float::signaling_nan_with_payload_with_traits`.
+    pub fn signaling_nan_with_payload(
+        sign: Sign,
+        payload: BigUint,
+        properties: FloatProperties,
+    ) -> Option<Self> {
+        Float::signaling_nan_with_payload_with_traits(sign, payload, properties).map(Into::into)
+    }
+    /// convert `self` into a quiet NaN
+    pub fn into_quiet_nan(self) -> Self {
+        let Self {
+            fp_state,
+            value,
+            exact_value: _,
+            _private: _,
+        } = self;
+        Self {
+            fp_state,
+            value: value.into_quiet_nan(),
+            exact_value: None,
+            _private: (),
+        }
+    }
+    /// convert `self` into a quiet NaN
+    pub fn to_quiet_nan(&self) -> Self {
+        let Self {
+            fp_state,
+            ref value,
+            exact_value: _,
+            _private: _,
+        } = *self;
+        Self {
+            fp_state,
+            value: value.to_quiet_nan(),
+            exact_value: None,
+            _private: (),
+        }
+    }
+    /// get the largest finite value with sign `sign`
+    pub fn signed_max_normal(sign: Sign, properties: FloatProperties) -> Self {
+        Float::signed_max_normal_with_traits(sign, properties).into()
+    }
+    /// get the subnormal value closest to zero with sign `sign`
+    pub fn signed_min_subnormal(sign: Sign, properties: FloatProperties) -> Self {
+        Float::signed_min_subnormal_with_traits(sign, properties).into()
+    }
+    /// round from a `RealAlgebraicNumber` into a floating-point value.
+    /// `rounding_mode` only used for this conversion
+    pub fn from_real_algebraic_number(
+        value: &RealAlgebraicNumber,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<FPState>,
+        properties: FloatProperties,
+    ) -> Self {
+        let mut fp_state = fp_state.unwrap_or_default();
+        let value = Float::from_real_algebraic_number_with_traits(
+            value,
+            rounding_mode,
+            Some(&mut fp_state),
+            properties,
+        );
+        Self {
+            fp_state,
+            value,
+            exact_value: None,
+            _private: (),
+        }
+    }
+}
+impl DynamicFloat {
+    /// parse a hexadecimal string, like IEEE 754's `convertFromHexCharacter`.
+    ///
+    /// see `Float::from_hex_str_with_traits`.
+    pub fn from_hex_str(
+        text: &str,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<FPState>,
+        properties: FloatProperties,
+    ) -> Option<Self> {
+        let mut fp_state = fp_state.unwrap_or_default();
+        let value =
+            Float::from_hex_str_with_traits(text, rounding_mode, Some(&mut fp_state), properties)?;
+        Some(Self {
+            fp_state,
+            value,
+            exact_value: None,
+            _private: (),
+        })
+    }
+    /// parse an exact rational number and round it once.
+    ///
+    /// see `Float::from_rational_str_with_traits`.
+    pub fn from_rational_str(
+        text: &str,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<FPState>,
+        properties: FloatProperties,
+    ) -> Result<Self, ParseError> {
+        let mut fp_state = fp_state.unwrap_or_default();
+        let value = Float::from_rational_str_with_traits(
+            text,
+            rounding_mode,
+            Some(&mut fp_state),
+            properties,
+        )?;
+        Ok(Self {
+            fp_state,
+            value,
+            exact_value: None,
+            _private: (),
+        })
+    }
+    /// compute `sign * digits * 10^decimal_exponent` exactly and round it
+    /// once.
+    ///
+    /// see `Float::from_decimal_parts_with_traits`.
+    pub fn from_decimal_parts(
+        sign: Sign,
+        digits: &BigUint,
+        decimal_exponent: i64,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<FPState>,
+        properties: FloatProperties,
+    ) -> Self {
+        let mut fp_state = fp_state.unwrap_or_default();
+        let value = Float::from_decimal_parts_with_traits(
+            sign,
+            digits,
+            decimal_exponent,
+            rounding_mode,
+            Some(&mut fp_state),
+            properties,
+        );
+        Self {
+            fp_state,
+            value,
+            exact_value: None,
+            _private: (),
+        }
+    }
+    /// parse a number written in base `radix` and round it once.
+    ///
+    /// see `Float::from_str_radix_with_traits`.
+    pub fn from_str_radix(
+        text: &str,
+        radix: u32,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<FPState>,
+        properties: FloatProperties,
+    ) -> Result<Self, ParseError> {
+        let mut fp_state = fp_state.unwrap_or_default();
+        let value = Float::from_str_radix_with_traits(
+            text,
+            radix,
+            rounding_mode,
+            Some(&mut fp_state),
+            properties,
+        )?;
+        Ok(Self {
+            fp_state,
+            value,
+            exact_value: None,
+            _private: (),
+        })
+    }
+}
+
+impl DynamicFloat {
+    /// compute the sum of all of `values`, rounding only once, returning the result.
+    ///
+    /// see `Float::sum_with_traits`.
+    pub fn sum(
+        values: &[Self],
+        rounding_mode: Option<RoundingMode>,
+        properties: FloatProperties,
+    ) -> Self {
+        Self::checked_sum(values, rounding_mode, properties).unwrap()
+    }
+    /// compute the sum of all of `values`, rounding only once, returning the result.
+    ///
+    /// see `Float::sum_with_traits`.
+    pub fn checked_sum(
+        values: &[Self],
+        rounding_mode: Option<RoundingMode>,
+        properties: FloatProperties,
+    ) -> Result<Self, FPStateMergeFailed> {
+        let mut fp_state = values.first().map(|v| v.fp_state).unwrap_or_default();
+        for value in values {
+            fp_state.checked_merge_assign(value.fp_state)?;
+        }
+        let float_values: Vec<_> = values.iter().map(|v| v.value.clone()).collect();
+        let value = Float::sum_with_traits(
+            &float_values,
+            rounding_mode,
+            Some(&mut fp_state),
+            properties,
+        );
+        Ok(Self {
+            fp_state,
+            value,
+            exact_value: None,
+            _private: (),
+        })
+    }
+    /// compute the arithmetic mean of `values`, rounding only once, returning the result.
+    ///
+    /// see `Float::mean_with_traits`.
+    pub fn mean(
+        values: &[Self],
+        rounding_mode: Option<RoundingMode>,
+        properties: FloatProperties,
+    ) -> Self {
+        Self::checked_mean(values, rounding_mode, properties).unwrap()
+    }
+    /// compute the arithmetic mean of `values`, rounding only once, returning the result.
+    ///
+    /// see `Float::mean_with_traits`.
+    pub fn checked_mean(
+        values: &[Self],
+        rounding_mode: Option<RoundingMode>,
+        properties: FloatProperties,
+    ) -> Result<Self, FPStateMergeFailed> {
+        let mut fp_state = values.first().map(|v| v.fp_state).unwrap_or_default();
+        for value in values {
+            fp_state.checked_merge_assign(value.fp_state)?;
+        }
+        let float_values: Vec<_> = values.iter().map(|v| v.value.clone()).collect();
+        let value = Float::mean_with_traits(
+            &float_values,
+            rounding_mode,
+            Some(&mut fp_state),
+            properties,
+        );
+        Ok(Self {
+            fp_state,
+            value,
+            exact_value: None,
+            _private: (),
+        })
+    }
+    /// apply `op` to `self` followed by `operands`, returning the result.
+    ///
+    /// see `Float::apply`.
+    pub fn apply(
+        &self,
+        op: FloatOp,
+        operands: &[&Self],
+        rounding_mode: Option<RoundingMode>,
+    ) -> Self {
+        self.checked_apply(op, operands, rounding_mode).unwrap()
+    }
+    /// apply `op` to `self` followed by `operands`, returning the result.
+    ///
+    /// see `Float::apply`.
+    pub fn checked_apply(
+        &self,
+        op: FloatOp,
+        operands: &[&Self],
+        rounding_mode: Option<RoundingMode>,
+    ) -> Result<Self, FPStateMergeFailed> {
+        let mut fp_state = self.fp_state;
+        for operand in operands {
+            fp_state.checked_merge_assign(operand.fp_state)?;
+        }
+        let float_operands: Vec<_> = operands.iter().map(|v| &v.value).collect();
+        let value = self
+            .value
+            .apply(op, &float_operands, rounding_mode, Some(&mut fp_state));
+        Ok(Self {
+            fp_state,
+            value,
+            exact_value: None,
+            _private: (),
+        })
+    }
+}
+
+impl_dynamic_float_fn!(
+    /// add two `DynamicFloat` values, returning the result
+    add_with_rounding_mode,
+    checked_add_with_rounding_mode,
+    add,
+    (&self),
+    (rhs: &Self),
+    (rounding_mode: Option<RoundingMode>),
+    exact = |v| Some(v[0] + v[1])
+);
+impl_dynamic_float_fn!(
+    /// subtract two `DynamicFloat` values, returning the result
+    sub_with_rounding_mode,
+    checked_sub_with_rounding_mode,
+    sub,
+    (&self),
+    (rhs: &Self),
+    (rounding_mode: Option<RoundingMode>),
+    exact = |v| Some(v[0] - v[1])
+);
+impl_dynamic_float_fn!(
+    /// compute `|self - rhs|` rounding only once, returning the result
+    abs_diff,
+    checked_abs_diff,
+    abs_diff,
+    (&self),
+    (rhs: &Self),
+    (rounding_mode: Option<RoundingMode>),
+    exact = |v| Some((v[0] - v[1]).abs())
+);
+impl_dynamic_float_fn!(
+    /// get the canonical encoding of `self`, like IEEE 754's `canonicalize`
+    canonicalize,
+    canonicalize,
+    (&self),
+    exact = |v| Some(v[0].clone())
+);
+impl_dynamic_float_fn!(
+    /// compute `(self + rhs) / 2` rounding only once, returning the result
+    midpoint,
+    checked_midpoint,
+    midpoint,
+    (&self),
+    (rhs: &Self),
+    (rounding_mode: Option<RoundingMode>),
+    exact = |v| Some((v[0] + v[1]) / RealAlgebraicNumber::from(BigInt::from(2)))
+);
+impl_dynamic_float_fn!(
+    /// compute the minimum of two `DynamicFloat` values, returning the result
+    minimum,
+    checked_minimum,
+    minimum,
+    (&self),
+    (rhs: &Self),
+    ()
+);
+impl_dynamic_float_fn!(
+    /// compute the maximum of two `DynamicFloat` values, returning the result
+    maximum,
+    checked_maximum,
+    maximum,
+    (&self),
+    (rhs: &Self),
+    ()
+);
+impl_dynamic_float_fn!(
+    /// clamp `self` to the range `[lo, hi]`, returning the result
+    clamp,
+    checked_clamp,
+    clamp,
+    (&self),
+    (lo: &Self, hi: &Self),
+    ()
+);
+impl_dynamic_float_fn!(
+    /// linearly interpolate between `self` and `other`, rounding only once, returning the result
+    lerp,
+    checked_lerp,
+    lerp,
+    (&self),
+    (other: &Self, t: &Self),
+    (rounding_mode: Option<RoundingMode>),
+    exact = |v| Some(v[0] + (v[1] - v[0]) * v[2])
+);
+impl_dynamic_float_fn!(
+    /// multiply two `DynamicFloat` values, returning the result
+    mul_with_rounding_mode,
+    checked_mul_with_rounding_mode,
+    mul,
+    (&self),
+    (rhs: &Self),
+    (rounding_mode: Option<RoundingMode>),
+    exact = |v| Some(v[0] * v[1])
+);
+impl_dynamic_float_fn!(
+    /// divide two `DynamicFloat` values, returning the result
+    div_with_rounding_mode,
+    checked_div_with_rounding_mode,
+    div,
+    (&self),
+    (rhs: &Self),
+    (rounding_mode: Option<RoundingMode>),
+    exact = |v| if v[1].is_zero() { None } else { Some(v[0] / v[1]) }
+);
+impl_dynamic_float_fn!(
+    /// calculate the IEEE 754 remainder of two `DynamicFloat` values, returning the result
+    ieee754_remainder,
+    checked_ieee754_remainder,
+    ieee754_remainder,
+    (&self),
+    (rhs: &Self),
+    (rounding_mode: Option<RoundingMode>)
+);
+impl_dynamic_float_fn!(
+    /// calculate the result of `(self * factor) + term` rounding only once, returning the result
+    fused_mul_add,
+    checked_fused_mul_add,
+    fused_mul_add,
+    (&self),
+    (factor: &Self, term: &Self),
+    (rounding_mode: Option<RoundingMode>),
+    exact = |v| Some(v[0] * v[1] + v[2])
+);
+
+impl DynamicFloat {
+    /// round `self` to an integer, returning the result as a tuple of an integer or `None`, and `FPState`
+    pub fn round_to_integer(
+        &self,
+        exact: bool,
+        rounding_mode: Option<RoundingMode>,
+    ) -> (Option<BigInt>, FPState) {
+        let mut fp_state = self.fp_state;
+        let value = self
+            .value
+            .round_to_integer(exact, rounding_mode, Some(&mut fp_state));
+        (value, fp_state)
+    }
+}
+
+impl_dynamic_float_fn!(
+    /// round `self` to an integer, returning the result as a `DynamicFloat`
+    round_to_integral,
+    round_to_integral,
+    (&self, exact: bool, rounding_mode: Option<RoundingMode>)
+);
+impl_dynamic_float_fn!(
+    /// round `self` as if its significand had only `significand_bits` bits,
+    /// keeping the same format
+    round_to_precision,
+    round_to_precision,
+    (&self, significand_bits: usize, rounding_mode: Option<RoundingMode>)
+);
+impl_dynamic_float_fn!(
+    /// round `self` to the precision and exponent range of the format
+    /// specified by `other`, keeping the same format
+    round_to_format_precision,
+    round_to_format_precision,
+    (&self, other: &FloatProperties, rounding_mode: Option<RoundingMode>)
+);
+impl_dynamic_float_fn!(
+    /// compute the result of `next_up` or `next_down`
+    next_up_or_down,
+    next_up_or_down,
+    (&self, up_or_down: UpOrDown)
+);
+impl_dynamic_float_fn!(
+    /// compute the least floating-point number that compares greater than `self`
+    next_up,
+    next_up,
+    (&self)
+);
+impl_dynamic_float_fn!(
+    /// compute the greatest floating-point number that compares less than `self`
+    next_down,
+    next_down,
+    (&self)
+);
+impl_dynamic_float_fn!(
+    /// compute the result of `next_integer_up` or `next_integer_down`
+    next_integer_up_or_down,
+    next_integer_up_or_down,
+    (&self, up_or_down: UpOrDown)
+);
+impl_dynamic_float_fn!(
+    /// compute the least integer-valued floating-point number that compares greater than `self`
+    next_integer_up,
+    next_integer_up,
+    (&self)
+);
+impl_dynamic_float_fn!(
+    /// compute the greatest integer-valued floating-point number that compares less than `self`
+    next_integer_down,
+    next_integer_down,
+    (&self)
+);
+
+impl DynamicFloat {
+    /// get the floor of the log base 2 of the absolute value of `self`
+    pub fn log_b(&self) -> (Option<BigInt>, FPState) {
+        let mut fp_state = self.fp_state;
+        let value = self.value.log_b(Some(&mut fp_state));
+        (value, fp_state)
+    }
+}
+
+impl DynamicFloat {
+    /// saturate infinities to the largest finite value with the same sign,
+    /// returning the result and whether `self` was clamped
+    pub fn clamp_to_representable(&self) -> (Self, bool) {
+        let mut fp_state = self.fp_state;
+        let (value, clamped) = self.value.clamp_to_representable(Some(&mut fp_state));
+        let exact_value = if clamped {
+            None
+        } else {
+            self.exact_value.clone()
+        };
+        (
+            Self {
+                fp_state,
+                value,
+                exact_value,
+                _private: (),
+            },
+            clamped,
+        )
+    }
+}
+
+impl_dynamic_float_fn!(
+    /// get `self * 2^scale`
+    scale_b,
+    scale_b,
+    (&self, scale: BigInt, rounding_mode: Option<RoundingMode>)
+);
+#[cfg(not(feature = "rational-only"))]
+impl_dynamic_float_fn!(
+    /// get the square-root of `self`
+    sqrt,
+    sqrt,
+    (&self, rounding_mode: Option<RoundingMode>),
+    exact = |v| if v[0].is_negative() {
+        None
+    } else {
+        Some(v[0].pow((1, 2)))
+    }
+);
+
+impl DynamicFloat {
+    /// convert `src` to the floating-point format specified by `properties`.
+    pub fn convert_from_dynamic_float(
+        src: &Self,
+        rounding_mode: Option<RoundingMode>,
+        properties: FloatProperties,
+    ) -> Self {
+        let mut fp_state = src.fp_state;
+        let value = Float::convert_from_float_with_traits(
+            &src.value,
+            rounding_mode,
+            Some(&mut fp_state),
+            properties,
+        );
+        Self {
+            fp_state,
+            value,
+            exact_value: src.exact_value.clone(),
+            _private: (),
+        }
+    }
+    /// convert `src` to the floating-point format specified by `properties`.
+    pub fn convert_from_float<SrcFT: FloatTraits>(
+        src: &Float<SrcFT>,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<FPState>,
+        properties: FloatProperties,
+    ) -> Self {
+        let mut fp_state = fp_state.unwrap_or_default();
+        let value = Float::convert_from_float_with_traits(
+            src,
+            rounding_mode,
+            Some(&mut fp_state),
+            properties,
+        );
+        Self {
+            fp_state,
+            value,
+            exact_value: None,
+            _private: (),
+        }
+    }
+    /// convert `self` to the floating-point format specified by `properties`.
+    pub fn convert_to_dynamic_float(
+        &self,
+        rounding_mode: Option<RoundingMode>,
+        properties: FloatProperties,
+    ) -> Self {
+        Self::convert_from_dynamic_float(self, rounding_mode, properties)
+    }
+    /// compute the absolute value of `self`
+    pub fn abs(&self) -> Self {
+        let mut retval = self.clone();
+        retval.abs_assign();
+        retval.exact_value = retval.exact_value.map(|v| v.abs());
+        retval
+    }
+    /// construct a `DynamicFloat` from `self` but with the sign of `sign_src`
+    pub fn copy_sign<FT2: FloatTraits>(&self, sign_src: &Float<FT2>) -> Self {
+        let mut retval = self.clone();
+        if retval.sign() != sign_src.sign() {
+            retval.exact_value = retval.exact_value.map(|v| -v);
+        }
+        retval.set_sign(sign_src.sign());
+        retval
+    }
+    /// compare two `DynamicFloat` values
+    pub fn compare(&self, rhs: &Self, quiet: bool) -> (Option<Ordering>, FPState) {
+        let mut fp_state = self.fp_state;
+        fp_state.merge_assign(rhs.fp_state);
+        let result = self.value.compare(&rhs.value, quiet, Some(&mut fp_state));
+        (result, fp_state)
+    }
+    /// compare two `DynamicFloat` values
+    pub fn checked_compare(
+        &self,
+        rhs: &Self,
+        quiet: bool,
+    ) -> Result<(Option<Ordering>, FPState), FPStateMergeFailed> {
+        let mut fp_state = self.fp_state;
+        fp_state.checked_merge_assign(rhs.fp_state)?;
+        let result = self.value.compare(&rhs.value, quiet, Some(&mut fp_state));
+        Ok((result, fp_state))
+    }
+    /// compare `self` numerically with the exact integer `rhs`
+    pub fn compare_with_bigint(&self, rhs: &BigInt, quiet: bool) -> (Option<Ordering>, FPState) {
+        let mut fp_state = self.fp_state;
+        let result = self
+            .value
+            .compare_with_bigint(rhs, quiet, Some(&mut fp_state));
+        (result, fp_state)
+    }
+    /// compare `self` numerically with the exact rational number `rhs`
+    pub fn compare_with_ratio(
+        &self,
+        rhs: &Ratio<BigInt>,
+        quiet: bool,
+    ) -> (Option<Ordering>, FPState) {
+        let mut fp_state = self.fp_state;
+        let result = self
+            .value
+            .compare_with_ratio(rhs, quiet, Some(&mut fp_state));
+        (result, fp_state)
+    }
+    /// compare two `DynamicFloat` values
+    pub fn compare_quiet(&self, rhs: &Self) -> (Option<Ordering>, FPState) {
+        let mut fp_state = self.fp_state;
+        fp_state.merge_assign(rhs.fp_state);
+        let result = self.value.compare_quiet(&rhs.value, Some(&mut fp_state));
+        (result, fp_state)
+    }
+    /// compare two `DynamicFloat` values
+    pub fn checked_compare_quiet(
+        &self,
+        rhs: &Self,
+    ) -> Result<(Option<Ordering>, FPState), FPStateMergeFailed> {
+        let mut fp_state = self.fp_state;
+        fp_state.checked_merge_assign(rhs.fp_state)?;
+        let result = self.value.compare_quiet(&rhs.value, Some(&mut fp_state));
+        Ok((result, fp_state))
+    }
+    /// compare two `DynamicFloat` values
+    pub fn compare_signaling(&self, rhs: &Self) -> (Option<Ordering>, FPState) {
+        let mut fp_state = self.fp_state;
+        fp_state.merge_assign(rhs.fp_state);
+        let result = self
+            .value
+            .compare_signaling(&rhs.value, Some(&mut fp_state));
+        (result, fp_state)
+    }
+    /// compare two `DynamicFloat` values
+    pub fn checked_compare_signaling(
+        &self,
+        rhs: &Self,
+    ) -> Result<(Option<Ordering>, FPState), FPStateMergeFailed> {
+        let mut fp_state = self.fp_state;
+        fp_state.checked_merge_assign(rhs.fp_state)?;
+        let result = self
+            .value
+            .compare_signaling(&rhs.value, Some(&mut fp_state));
+        Ok((result, fp_state))
+    }
+}
+
+impl_dynamic_float_from_int_type!(from_bigint_with_traits, from_bigint, BigInt);
+impl_dynamic_float_from_int_type!(from_biguint_with_traits, from_biguint, BigUint);
+impl_dynamic_float_from_int_type!(from_u8_with_traits, from_u8, u8);
+impl_dynamic_float_from_int_type!(from_u16_with_traits, from_u16, u16);
+impl_dynamic_float_from_int_type!(from_u32_with_traits, from_u32, u32);
+impl_dynamic_float_from_int_type!(from_u64_with_traits, from_u64, u64);
+impl_dynamic_float_from_int_type!(from_u128_with_traits, from_u128, u128);
+impl_dynamic_float_from_int_type!(from_usize_with_traits, from_usize, usize);
+impl_dynamic_float_from_int_type!(from_i8_with_traits, from_i8, i8);
+impl_dynamic_float_from_int_type!(from_i16_with_traits, from_i16, i16);
+impl_dynamic_float_from_int_type!(from_i32_with_traits, from_i32, i32);
+impl_dynamic_float_from_int_type!(from_i64_with_traits, from_i64, i64);
+impl_dynamic_float_from_int_type!(from_i128_with_traits, from_i128, i128);
+impl_dynamic_float_from_int_type!(from_isize_with_traits, from_isize, isize);
+
+impl DynamicFloat {
+    /// get `2^exponent`, which is exact unless `exponent` is outside the
+    /// range of normal and subnormal values.
+    /// `rounding_mode` only used for this conversion.
+    pub fn power_of_two(
+        exponent: i64,
+        rounding_mode: Option<RoundingMode>,
+        fp_state: Option<FPState>,
+        properties: FloatProperties,
+    ) -> Self {
+        let mut fp_state = fp_state.unwrap_or_default();
+        let value = Float::power_of_two_with_traits(
+            exponent,
+            rounding_mode,
+            Some(&mut fp_state),
+            properties,
+        );
+        Self {
+            fp_state,
+            value,
+            exact_value: None,
+            _private: (),
+        }
+    }
+}
+impl_dynamic_float_to_int_type!(to_bigint, BigInt);
+impl_dynamic_float_to_int_type!(to_biguint, BigUint);
+impl_dynamic_float_to_int_type!(to_u8, u8);
+impl_dynamic_float_to_int_type!(to_u16, u16);
+impl_dynamic_float_to_int_type!(to_u32, u32);
+impl_dynamic_float_to_int_type!(to_u64, u64);
+impl_dynamic_float_to_int_type!(to_u128, u128);
+impl_dynamic_float_to_int_type!(to_usize, usize);
+impl_dynamic_float_to_int_type!(to_i8, i8);
+impl_dynamic_float_to_int_type!(to_i16, i16);
+impl_dynamic_float_to_int_type!(to_i32, i32);
+impl_dynamic_float_to_int_type!(to_i64, i64);
+impl_dynamic_float_to_int_type!(to_i128, i128);
+impl_dynamic_float_to_int_type!(to_isize, isize);
+#[cfg(not(feature = "rational-only"))]
+impl_dynamic_float_fn!(
+    /// compute reciprocal square-root (`1.0 / sqrt(self)`)
+    rsqrt,
+    rsqrt,
+    (&self, rounding_mode: Option<RoundingMode>)
+);
+impl_dynamic_float_fn!(
+    /// compute `self` raised to the integer power `exponent`
+    pown,
+    pown,
+    (&self, exponent: i64, rounding_mode: Option<RoundingMode>)
+);
+#[cfg(not(feature = "rational-only"))]
+impl_dynamic_float_fn!(
+    /// get the cube-root of `self`
+    cbrt,
+    cbrt,
+    (&self, rounding_mode: Option<RoundingMode>)
+);
+#[cfg(not(feature = "rational-only"))]
+impl_dynamic_float_fn!(
+    /// compute `sqrt(self * self + rhs * rhs)` rounding only once, returning the result
+    hypot,
+    checked_hypot,
+    hypot,
+    (&self),
+    (rhs: &Self),
+    (rounding_mode: Option<RoundingMode>),
+    exact = |v| Some((v[0] * v[0] + v[1] * v[1]).pow((1, 2)))
+);
+
+macro_rules! impl_dynamic_float_binary_op_trait {
+    ($op_trait:ident, $op:ident, $op_assign_trait:ident, $op_assign:ident, $called_fn_name:ident) => {
+        impl $op_trait for DynamicFloat {
+            type Output = DynamicFloat;
+            fn $op(self, rhs: DynamicFloat) -> DynamicFloat {
+                self.$called_fn_name(&rhs, None)
+            }
+        }
+
+        impl $op_trait<&'_ DynamicFloat> for DynamicFloat {
+            type Output = DynamicFloat;
+            fn $op(self, rhs: &DynamicFloat) -> DynamicFloat {
+                self.$called_fn_name(rhs, None)
+            }
+        }
+        impl $op_trait<DynamicFloat> for &'_ DynamicFloat {
+            type Output = DynamicFloat;
+            fn $op(self, rhs: DynamicFloat) -> DynamicFloat {
+                self.$called_fn_name(&rhs, None)
+            }
+        }
+
+        impl<'a, 'b> $op_trait<&'a DynamicFloat> for &'b DynamicFloat {
+            type Output = DynamicFloat;
+            fn $op(self, rhs: &DynamicFloat) -> DynamicFloat {
+                self.$called_fn_name(rhs, None)
+            }
+        }
+
+        impl $op_assign_trait for DynamicFloat {
+            fn $op_assign(&mut self, rhs: DynamicFloat) {
+                *self = self.$called_fn_name(&rhs, None);
+            }
+        }
+
+        impl $op_assign_trait<&'_ DynamicFloat> for DynamicFloat {
+            fn $op_assign(&mut self, rhs: &DynamicFloat) {
+                *self = self.$called_fn_name(rhs, None);
+            }
+        }
+    };
+}
+
+impl_dynamic_float_binary_op_trait!(Add, add, AddAssign, add_assign, add_with_rounding_mode);
+impl_dynamic_float_binary_op_trait!(Sub, sub, SubAssign, sub_assign, sub_with_rounding_mode);
+impl_dynamic_float_binary_op_trait!(Mul, mul, MulAssign, mul_assign, mul_with_rounding_mode);
+impl_dynamic_float_binary_op_trait!(Div, div, DivAssign, div_assign, div_with_rounding_mode);
+
+impl DynamicFloat {
+    /// assign the least floating-point number that compares greater than
+    /// `self` to `self`, see `Float::next_up_assign`
+    pub fn next_up_assign(&mut self) {
+        self.value.next_up_assign(Some(&mut self.fp_state));
+        self.exact_value = None;
+    }
+    /// assign the greatest floating-point number that compares less than
+    /// `self` to `self`, see `Float::next_down_assign`
+    pub fn next_down_assign(&mut self) {
+        self.value.next_down_assign(Some(&mut self.fp_state));
+        self.exact_value = None;
+    }
+    /// assign `self * 2^scale` to `self`, see `Float::scale_b_assign`
+    pub fn scale_b_assign(&mut self, scale: BigInt, rounding_mode: Option<RoundingMode>) {
+        self.value
+            .scale_b_assign(scale, rounding_mode, Some(&mut self.fp_state));
+        self.exact_value = None;
+    }
+}
+
+impl Neg for &'_ DynamicFloat {
+    type Output = DynamicFloat;
+    fn neg(self) -> DynamicFloat {
+        -self.clone()
+    }
+}
+
+impl Neg for DynamicFloat {
+    type Output = DynamicFloat;
+    fn neg(mut self) -> DynamicFloat {
+        self.neg_assign();
+        self.exact_value = self.exact_value.map(|v| -v);
+        self
+    }
+}
+
+macro_rules! doctest {
+    ($x:expr) => {
+        #[doc = $x]
+        #[cfg(doctest)]
+        pub struct ReadmeDoctests;
+    };
+}
+
+doctest!(include_str!("../README.md"));
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::cognitive_complexity)]
+    use super::*;
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(
+            &format!("{:?}", F16::from_bits(0x0000)),
+            "Float { traits: F16Traits, bits: 0x0000, sign: Positive, \
+             exponent_field: 0x00, mantissa_field: 0x000, class: PositiveZero }",
+        );
+        assert_eq!(
+            &format!("{:?}", F16::from_bits(0x8000)),
+            "Float { traits: F16Traits, bits: 0x8000, sign: Negative, \
+             exponent_field: 0x00, mantissa_field: 0x000, class: NegativeZero }",
+        );
+        assert_eq!(
+            &format!("{:?}", F16::from_bits(0xFC00)),
+            "Float { traits: F16Traits, bits: 0xFC00, sign: Negative, \
+             exponent_field: 0x1F, mantissa_field: 0x000, class: NegativeInfinity }",
+        );
+        assert_eq!(
+            &format!("{:?}", F16::from_bits(0xFE00)),
+            "Float { traits: F16Traits, bits: 0xFE00, sign: Negative, \
+             exponent_field: 0x1F, mantissa_field: 0x200, class: QuietNaN }",
+        );
+        assert_eq!(
+            &format!("{:?}", F16::from_bits(0x0001)),
+            "Float { traits: F16Traits, bits: 0x0001, sign: Positive, \
+             exponent_field: 0x00, mantissa_field: 0x001, class: PositiveSubnormal }",
+        );
+        assert_eq!(
+            &format!("{:?}", F16::from_bits(0x3C00)),
+            "Float { traits: F16Traits, bits: 0x3C00, sign: Positive, \
+             exponent_field: 0x0F, mantissa_field: 0x000, class: PositiveNormal }",
+        );
+        assert_eq!(
+            &format!(
+                "{:?}",
+                F16WithPlatformProperties::from_bits_and_traits(
+                    0x1234,
+                    F16WithPlatformPropertiesTraits(PlatformProperties::RISC_V)
+                )
+            ),
+            "Float { traits: F16WithPlatformPropertiesTraits(PlatformProperties::RISC_V), \
+             bits: 0x1234, sign: Positive, exponent_field: 0x04, \
+             mantissa_field: 0x234, class: PositiveNormal }",
+        );
+        assert_eq!(
+            &format!(
+                "{:?}",
+                F16WithPlatformProperties::from_bits_and_traits(
+                    0x1234,
+                    F16WithPlatformPropertiesTraits(PlatformProperties::SPARC)
+                )
+            ),
+            "Float { traits: F16WithPlatformPropertiesTraits(PlatformProperties::SPARC), \
+             bits: 0x1234, sign: Positive, exponent_field: 0x04, \
+             mantissa_field: 0x234, class: PositiveNormal }",
+        );
+        assert_eq!(
+            &format!(
+                "{:?}",
+                F16WithPlatformProperties::from_bits_and_traits(
+                    0x1234,
+                    F16WithPlatformPropertiesTraits(PlatformProperties::X86_SSE)
+                )
+            ),
+            "Float { traits: F16WithPlatformPropertiesTraits(PlatformProperties::X86_SSE), \
+             bits: 0x1234, sign: Positive, exponent_field: 0x04, \
+             mantissa_field: 0x234, class: PositiveNormal }",
+        );
+        assert_eq!(
+            &format!(
+                "{:?}",
+                F16WithPlatformProperties::from_bits_and_traits(
+                    0x1234,
+                    F16WithPlatformPropertiesTraits(PlatformProperties::HPPA)
+                )
+            ),
+            "Float { traits: F16WithPlatformPropertiesTraits(PlatformProperties::HPPA), \
+             bits: 0x1234, sign: Positive, exponent_field: 0x04, \
+             mantissa_field: 0x234, class: PositiveNormal }",
+        );
+        assert_eq!(
+            &format!(
+                "{:?}",
+                F16WithPlatformProperties::from_bits_and_traits(
+                    0x1234,
+                    F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
+                )
+            ),
+            "Float { traits: F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY), \
+             bits: 0x1234, sign: Positive, exponent_field: 0x04, \
+             mantissa_field: 0x234, class: PositiveNormal }",
+        );
+        assert_eq!(
+            &format!(
+                "{:?}",
+                F16WithPlatformProperties::from_bits_and_traits(
+                    0x1234,
+                    F16WithPlatformPropertiesTraits(PlatformProperties {
+                        canonical_nan_sign: Sign::Negative,
+                        ..PlatformProperties::MIPS_LEGACY
+                    })
+                )
+            ),
+            "Float { traits: F16WithPlatformPropertiesTraits(PlatformProperties { \
+             canonical_nan_sign: Negative, canonical_nan_mantissa_msb: false, \
+             canonical_nan_mantissa_second_to_msb: true, \
+             canonical_nan_mantissa_rest: true, \
+             std_bin_ops_nan_propagation_mode: FirstSecondPreferringSNaN, \
+             fma_nan_propagation_mode: FirstSecondThirdPreferringSNaN, \
+             fma_inf_zero_qnan_result: CanonicalAndGenerateInvalid, \
+             round_to_integral_nan_propagation_mode: First, \
+             next_up_or_down_nan_propagation_mode: First, \
+             scale_b_nan_propagation_mode: First, \
+             sqrt_nan_propagation_mode: First, \
+             float_to_float_conversion_nan_propagation_mode: RetainMostSignificantBits, \
+             rsqrt_nan_propagation_mode: First, \
+             quiet_nan_format: MIPSLegacy }), \
+             bits: 0x1234, sign: Positive, exponent_field: 0x04, \
+             mantissa_field: 0x234, class: PositiveNormal }",
+        );
+    }
+
+    #[test]
+    fn test_class() {
+        use FloatClass::*;
+        assert_eq!(F16::from_bits(0x0000).class(), PositiveZero);
+        assert_eq!(F16::from_bits(0x0001).class(), PositiveSubnormal);
+        assert_eq!(F16::from_bits(0x03FF).class(), PositiveSubnormal);
+        assert_eq!(F16::from_bits(0x0400).class(), PositiveNormal);
+        assert_eq!(F16::from_bits(0x3C00).class(), PositiveNormal);
+        assert_eq!(F16::from_bits(0x7BFF).class(), PositiveNormal);
+        assert_eq!(F16::from_bits(0x7C00).class(), PositiveInfinity);
+        assert_eq!(F16::from_bits(0x7C01).class(), SignalingNaN);
+        assert_eq!(F16::from_bits(0x7DFF).class(), SignalingNaN);
+        assert_eq!(F16::from_bits(0x7E00).class(), QuietNaN);
+        assert_eq!(F16::from_bits(0x7FFF).class(), QuietNaN);
+        assert_eq!(F16::from_bits(0x8000).class(), NegativeZero);
+        assert_eq!(F16::from_bits(0x8001).class(), NegativeSubnormal);
+        assert_eq!(F16::from_bits(0x83FF).class(), NegativeSubnormal);
+        assert_eq!(F16::from_bits(0x8400).class(), NegativeNormal);
+        assert_eq!(F16::from_bits(0xBC00).class(), NegativeNormal);
+        assert_eq!(F16::from_bits(0xFBFF).class(), NegativeNormal);
+        assert_eq!(F16::from_bits(0xFC00).class(), NegativeInfinity);
+        assert_eq!(F16::from_bits(0xFC01).class(), SignalingNaN);
+        assert_eq!(F16::from_bits(0xFDFF).class(), SignalingNaN);
+        assert_eq!(F16::from_bits(0xFE00).class(), QuietNaN);
+        assert_eq!(F16::from_bits(0xFFFF).class(), QuietNaN);
+        assert_eq!(
+            Float::from_bits_and_traits(
+                0x7C01,
+                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
+            )
+            .class(),
+            QuietNaN
+        );
+        assert_eq!(
+            Float::from_bits_and_traits(
+                0x7DFF,
+                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
+            )
+            .class(),
+            QuietNaN
+        );
+        assert_eq!(
+            Float::from_bits_and_traits(
+                0x7E00,
+                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
+            )
+            .class(),
+            SignalingNaN
+        );
+        assert_eq!(
+            Float::from_bits_and_traits(
+                0x7FFF,
+                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
+            )
+            .class(),
+            SignalingNaN
+        );
+        assert_eq!(
+            Float::from_bits_and_traits(
+                0xFC01,
+                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
+            )
+            .class(),
+            QuietNaN
+        );
+        assert_eq!(
+            Float::from_bits_and_traits(
+                0xFDFF,
+                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
+            )
+            .class(),
+            QuietNaN
+        );
+        assert_eq!(
+            Float::from_bits_and_traits(
+                0xFE00,
+                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
+            )
+            .class(),
+            SignalingNaN
+        );
+        assert_eq!(
+            Float::from_bits_and_traits(
+                0xFFFF,
+                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
+            )
+            .class(),
+            SignalingNaN
+        );
+    }
+
+    #[test]
+    fn test_to_ratio() {
+        macro_rules! test_case {
+            ($value:expr, $expected_ratio:expr) => {
+                let value: F16 = $value;
+                let expected_ratio: Option<Ratio<i128>> = $expected_ratio;
+                println!("value: {:?}", value);
+                println!("expected_ratio: {:?}", expected_ratio);
+                let ratio = value.to_ratio();
+                println!("ratio: {:?}", ratio.as_ref().map(ToString::to_string));
+                let expected_ratio = expected_ratio.map(|v| {
+                    let (n, d) = v.into();
+                    Ratio::new(n.into(), d.into())
+                });
+                assert!(ratio == expected_ratio);
+            };
+        }
+
+        let r = |n, d| Some(Ratio::new(n, d));
+
+        test_case!(F16::from_bits(0x0000), r(0, 1));
+        test_case!(F16::from_bits(0x0001), r(1, 1 << 24));
+        test_case!(F16::from_bits(0x03FF), r(1023, 1 << 24));
+        test_case!(F16::from_bits(0x0400), r(1, 1 << 14));
+        test_case!(F16::from_bits(0x3C00), r(1, 1));
+        test_case!(F16::from_bits(0x7BFF), r(65504, 1));
+        test_case!(F16::from_bits(0x7C00), None);
+        test_case!(F16::from_bits(0x7C01), None);
+        test_case!(F16::from_bits(0x7DFF), None);
+        test_case!(F16::from_bits(0x7E00), None);
+        test_case!(F16::from_bits(0x7FFF), None);
+        test_case!(F16::from_bits(0x8000), r(0, 1));
+        test_case!(F16::from_bits(0x8001), r(-1, 1 << 24));
+        test_case!(F16::from_bits(0x83FF), r(-1023, 1 << 24));
+        test_case!(F16::from_bits(0x8400), r(-1, 1 << 14));
+        test_case!(F16::from_bits(0xBC00), r(-1, 1));
+        test_case!(F16::from_bits(0xFBFF), r(-65504, 1));
+        test_case!(F16::from_bits(0xFC00), None);
+        test_case!(F16::from_bits(0xFC01), None);
+        test_case!(F16::from_bits(0xFDFF), None);
+        test_case!(F16::from_bits(0xFE00), None);
+        test_case!(F16::from_bits(0xFFFF), None);
+    }
+
+    #[test]
+    fn test_log_b() {
+        macro_rules! test_case {
+            ($value:expr, $expected_result:expr) => {
+                let value: F16 = $value;
+                let expected_result: Option<i32> = $expected_result;
+                let expected_status_flags: StatusFlags = if expected_result.is_some() {
+                    StatusFlags::empty()
+                } else {
+                    StatusFlags::empty().signal_invalid_operation()
+                };
+                println!("value: {:?}", value);
+                println!("expected_result: {:?}", expected_result);
+                println!("expected_status_flags: {:?}", expected_status_flags);
+                let mut fp_state = FPState::default();
+                let result = value.log_b(Some(&mut fp_state));
+                println!("result: {:?}", result.as_ref().map(ToString::to_string));
+                let expected_result: Option<BigInt> = expected_result.map(Into::into);
+                println!("status_flags: {:?}", fp_state.status_flags);
+                assert!(result == expected_result);
+                assert!(fp_state.status_flags == expected_status_flags);
+            };
+        }
+
+        test_case!(F16::from_bits(0x0000), None);
+        test_case!(F16::from_bits(0x0001), Some(-24));
+        test_case!(F16::from_bits(0x0002), Some(-23));
+        test_case!(F16::from_bits(0x03FF), Some(-15));
+        test_case!(F16::from_bits(0x0400), Some(-14));
+        test_case!(F16::from_bits(0x3C00), Some(0));
+        test_case!(F16::from_bits(0x7BFF), Some(15));
+        test_case!(F16::from_bits(0x7C00), None);
+        test_case!(F16::from_bits(0x7C01), None);
+        test_case!(F16::from_bits(0x7DFF), None);
+        test_case!(F16::from_bits(0x7E00), None);
+        test_case!(F16::from_bits(0x7FFF), None);
+        test_case!(F16::from_bits(0x8000), None);
+        test_case!(F16::from_bits(0x8001), Some(-24));
+        test_case!(F16::from_bits(0x8002), Some(-23));
+        test_case!(F16::from_bits(0x83FF), Some(-15));
+        test_case!(F16::from_bits(0x8400), Some(-14));
+        test_case!(F16::from_bits(0xBC00), Some(0));
+        test_case!(F16::from_bits(0xFBFF), Some(15));
+        test_case!(F16::from_bits(0xFC00), None);
+        test_case!(F16::from_bits(0xFC01), None);
+        test_case!(F16::from_bits(0xFDFF), None);
+        test_case!(F16::from_bits(0xFE00), None);
+        test_case!(F16::from_bits(0xFFFF), None);
+    }
+
+    #[test]
+    fn test_bytes() {
+        let value = F32::from_bits(0x1234_5678);
+        assert_eq!(value.to_le_bytes(), [0x78, 0x56, 0x34, 0x12]);
+        assert_eq!(value.to_be_bytes(), [0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(
+            F32::from_le_bytes(&[0x78, 0x56, 0x34, 0x12]).map(Float::into_bits),
+            Some(0x1234_5678)
+        );
+        assert_eq!(
+            F32::from_be_bytes(&[0x12, 0x34, 0x56, 0x78]).map(Float::into_bits),
+            Some(0x1234_5678)
+        );
+        assert!(F32::from_le_bytes(&[0x78, 0x56, 0x34]).is_none());
+        assert!(F32::from_le_bytes(&[0x78, 0x56, 0x34, 0x12, 0x00]).is_none());
+        assert_eq!(F16::from_bits(0x0001).to_le_bytes(), [0x01, 0x00]);
+        assert_eq!(F16::positive_zero().to_be_bytes(), [0x00, 0x00]);
+        let properties = FloatProperties::new(5, 6);
+        let value = DynamicFloat::from_bits(0xFFFu32.into(), properties).unwrap();
+        assert_eq!(value.to_le_bytes(), [0xFF, 0x0F]);
+        assert_eq!(
+            DynamicFloat::from_le_bytes(&[0xFF, 0x0F], properties).map(DynamicFloat::into_bits),
+            Some(0xFFFu32.into())
+        );
+        assert!(DynamicFloat::from_le_bytes(&[0xFF, 0x1F], properties).is_none());
+        let value = F128::from_bits(1 << 127);
+        assert_eq!(
+            F128::from_le_bytes(&value.to_le_bytes()).map(Float::into_bits),
+            Some(1 << 127)
+        );
+    }
+
+    #[test]
+    fn test_pown() {
+        macro_rules! test_case {
+            ($value:expr, $exponent:expr, $expected_result:expr, $expected_status_flags:expr) => {
+                let value: F16 = $value;
+                let exponent: i64 = $exponent;
+                let expected_result: F16 = $expected_result;
+                let expected_status_flags: StatusFlags = $expected_status_flags;
+                println!("value: {:?}", value);
+                println!("exponent: {}", exponent);
+                let mut fp_state = FPState::default();
+                let result = value.pown(exponent, None, Some(&mut fp_state));
+                println!("result: {:?}", result);
+                println!("status_flags: {:?}", fp_state.status_flags);
+                assert_eq!(result.bits(), expected_result.bits());
+                assert_eq!(fp_state.status_flags, expected_status_flags);
+            };
+        }
+
+        let none = StatusFlags::empty();
+        test_case!(F16::from_bits(0x4000), 3, F16::from_bits(0x4800), none);
+        test_case!(F16::from_bits(0xC000), 3, F16::from_bits(0xC800), none);
+        test_case!(F16::from_bits(0xC000), 2, F16::from_bits(0x4400), none);
+        test_case!(F16::from_bits(0x4000), -2, F16::from_bits(0x3400), none);
+        test_case!(F16::quiet_nan(), 0, F16::from_bits(0x3C00), none);
+        test_case!(F16::positive_infinity(), 0, F16::from_bits(0x3C00), none);
+        test_case!(F16::quiet_nan(), 1, F16::quiet_nan(), none);
+        test_case!(
+            F16::signaling_nan(),
+            0,
+            F16::quiet_nan(),
+            none.signal_invalid_operation()
+        );
+        test_case!(
+            F16::negative_zero(),
+            -3,
+            F16::negative_infinity(),
+            none.signal_division_by_zero()
+        );
+        test_case!(
+            F16::negative_zero(),
+            -2,
+            F16::positive_infinity(),
+            none.signal_division_by_zero()
+        );
+        test_case!(F16::negative_zero(), 3, F16::negative_zero(), none);
+        test_case!(F16::negative_infinity(), -3, F16::negative_zero(), none);
+        test_case!(F16::negative_infinity(), 2, F16::positive_infinity(), none);
+        test_case!(
+            F16::from_bits(0x4000),
+            16,
+            F16::positive_infinity(),
+            none.signal_overflow_with_inexact()
+        );
+        test_case!(
+            F16::from_bits(0x4000),
+            i64::MAX,
+            F16::positive_infinity(),
+            none.signal_overflow_with_inexact()
+        );
+        test_case!(
+            F16::from_bits(0xC000),
+            i64::MIN,
+            F16::positive_zero(),
+            none.signal_underflow_with_inexact()
+        );
+        test_case!(F16::from_bits(0x3E00), 2, F16::from_bits(0x4080), none);
+        test_case!(
+            F16::from_bits(0x3C01),
+            3,
+            F16::from_bits(0x3C03),
+            none.signal_inexact()
+        );
+        // ±1 raised to any power is exact
+        test_case!(
+            F16::from_bits(0x3C00),
+            i64::MIN,
+            F16::from_bits(0x3C00),
+            none
+        );
+        test_case!(
+            F16::from_bits(0x3C00),
+            i64::MAX,
+            F16::from_bits(0x3C00),
+            none
+        );
+        test_case!(
+            F16::from_bits(0xBC00),
+            i64::MIN,
+            F16::from_bits(0x3C00),
+            none
+        );
+        test_case!(
+            F16::from_bits(0xBC00),
+            i64::MAX,
+            F16::from_bits(0xBC00),
+            none
+        );
+        test_case!(
+            F16::from_bits(0x4000),
+            i64::MIN,
+            F16::positive_zero(),
+            none.signal_underflow_with_inexact()
+        );
+        // values near one with huge exponents
+        test_case!(
+            F16::from_bits(0x3C01),
+            1 << 40,
+            F16::positive_infinity(),
+            none.signal_overflow_with_inexact()
+        );
+        test_case!(
+            F16::from_bits(0x3C01),
+            i64::MAX,
+            F16::positive_infinity(),
+            none.signal_overflow_with_inexact()
+        );
+        test_case!(
+            F16::from_bits(0x3C01),
+            i64::MIN,
+            F16::positive_zero(),
+            none.signal_underflow_with_inexact()
+        );
+        test_case!(
+            F16::from_bits(0xBBFF),
+            (1 << 40) + 1,
+            F16::negative_zero(),
+            none.signal_underflow_with_inexact()
+        );
+        // results that are too big to calculate exactly match rounding the
+        // exact result
+        for &bits in &[0x3C01u16, 0x3BFF, 0xBC03, 0x3E00] {
+            for &exponent in &[15i64, 16, 99, -99, 300, -301] {
+                for &rounding_mode in &[
+                    RoundingMode::TiesToEven,
+                    RoundingMode::TowardZero,
+                    RoundingMode::TowardPositive,
+                    RoundingMode::TowardNegative,
+                ] {
+                    let value = F16::from_bits(bits);
+                    let mut fp_state = FPState::default();
+                    let result = value.pown(exponent, Some(rounding_mode), Some(&mut fp_state));
+                    let (numer, denom): (BigInt, BigInt) = value.to_ratio().unwrap().into();
+                    let abs_exponent = exponent.unsigned_abs() as usize;
+                    let exact = Ratio::new(
+                        Pow::pow(&numer, abs_exponent),
+                        Pow::pow(&denom, abs_exponent),
+                    );
+                    let exact = if exponent < 0 { exact.recip() } else { exact };
+                    let mut expected_fp_state = FPState::default();
+                    let expected = F16::from_real_algebraic_number(
+                        &exact.into(),
+                        Some(rounding_mode),
+                        Some(&mut expected_fp_state),
+                    );
+                    assert_eq!(
+                        (result.bits(), fp_state.status_flags),
+                        (expected.bits(), expected_fp_state.status_flags),
+                        "{:#X} {} {:?}",
+                        bits,
+                        exponent,
+                        rounding_mode
+                    );
+                }
+            }
+        }
+        // `(1 + 2^-52)^(2^50)` is close to `e^(1/4)`
+        let result = F64::from_bits(0x3FF0_0000_0000_0001).pown(1 << 50, None, None);
+        let expected = 0.25f64.exp();
+        assert!((f64::from(result) - expected).abs() <= 2.0 * f64::EPSILON);
+    }
+
+    #[test]
+    #[cfg(not(feature = "rational-only"))]
+    fn test_sqrt() {
+        let sqrt_f16 = |bits: u16, rounding_mode: Option<RoundingMode>| {
+            let mut fp_state = FPState::default();
+            let result = F16::from_bits(bits).sqrt(rounding_mode, Some(&mut fp_state));
+            (*result.bits(), fp_state.status_flags)
+        };
+        let sqrt_f32 = |bits: u32, rounding_mode: Option<RoundingMode>| {
+            let mut fp_state = FPState::default();
+            let result = F32::from_bits(bits).sqrt(rounding_mode, Some(&mut fp_state));
+            (*result.bits(), fp_state.status_flags)
+        };
+        // perfect squares are exact
+        assert_eq!(sqrt_f16(0x4880, None), (0x4200, StatusFlags::empty()));
+        assert_eq!(
+            sqrt_f32(0x4110_0000, None),
+            (0x4040_0000, StatusFlags::empty())
+        );
+        assert_eq!(
+            sqrt_f32(0x4010_0000, None),
+            (0x3FC0_0000, StatusFlags::empty())
+        );
+        assert_eq!(
+            sqrt_f32(0x3F80_0000, None),
+            (0x3F80_0000, StatusFlags::empty())
+        );
+        let mut fp_state = FPState::default();
+        let result = F64::from_bits(0x4341_C379_37E0_8000).sqrt(None, Some(&mut fp_state));
+        // sqrt(10^16) = 10^8
+        assert_eq!(*result.bits(), 0x4197_D784_0000_0000);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        // sqrt(2) = 1.41421356...
+        assert_eq!(
+            sqrt_f16(0x4000, None),
+            (0x3DA8, StatusFlags::empty().signal_inexact())
+        );
+        assert_eq!(
+            sqrt_f16(0x4000, Some(RoundingMode::TowardPositive)),
+            (0x3DA9, StatusFlags::empty().signal_inexact())
+        );
+        assert_eq!(
+            sqrt_f32(0x4000_0000, None),
+            (0x3FB5_04F3, StatusFlags::empty().signal_inexact())
+        );
+        assert_eq!(
+            sqrt_f32(0x4000_0000, Some(RoundingMode::TowardPositive)),
+            (0x3FB5_04F4, StatusFlags::empty().signal_inexact())
+        );
+        // smallest subnormal: 2^-24 is a perfect square, 2^-149 isn't
+        assert_eq!(sqrt_f16(0x0001, None), (0x0C00, StatusFlags::empty()));
+        assert_eq!(
+            sqrt_f32(0x0000_0001, None),
+            (0x1A35_04F3, StatusFlags::empty().signal_inexact())
+        );
+        // special cases
+        assert_eq!(
+            sqrt_f32(0x8000_0000, None),
+            (0x8000_0000, StatusFlags::empty())
+        );
+        assert_eq!(
+            sqrt_f32(0x0000_0000, None),
+            (0x0000_0000, StatusFlags::empty())
+        );
+        assert_eq!(
+            sqrt_f32(0x7F80_0000, None),
+            (0x7F80_0000, StatusFlags::empty())
+        );
+        for &bits in &[0xBF80_0000, 0x8000_0001, 0xFF80_0000] {
+            let (result, status_flags) = sqrt_f32(bits, None);
+            assert!(F32::from_bits(result).is_nan());
+            assert_eq!(
+                status_flags,
+                StatusFlags::empty().signal_invalid_operation()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "rational-only"))]
+    fn test_cbrt() {
+        let mut fp_state = FPState::default();
+        let result = F16::from_bits(0x4800).cbrt(None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x4000);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        let result = F16::from_bits(0xC800).cbrt(None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0xC000);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        // cbrt(2) = 1.2599... which rounds to 0x3D0A
+        let result = F16::from_bits(0x4000).cbrt(None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x3D0A);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty().signal_inexact());
+        let mut fp_state = FPState::default();
+        let result = F16::negative_zero().cbrt(None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x8000);
+        let result = F16::negative_infinity().cbrt(None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0xFC00);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        let result = F16::signaling_nan().cbrt(None, Some(&mut fp_state));
+        assert!(result.is_quiet_nan());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "rational-only"))]
+    fn test_rsqrt() {
+        let rsqrt = |bits: u32, rounding_mode: Option<RoundingMode>| {
+            let mut fp_state = FPState::default();
+            let result = F32::from_bits(bits).rsqrt(rounding_mode, Some(&mut fp_state));
+            (*result.bits(), fp_state.status_flags)
+        };
+        // IEEE 754 requires rSqrt(±0) to be ±inf, signaling division by zero
+        assert_eq!(
+            rsqrt(0x0000_0000, None),
+            (0x7F80_0000, StatusFlags::empty().signal_division_by_zero())
+        );
+        assert_eq!(
+            rsqrt(0x8000_0000, None),
+            (0xFF80_0000, StatusFlags::empty().signal_division_by_zero())
+        );
+        assert_eq!(
+            rsqrt(0x7F80_0000, None),
+            (0x0000_0000, StatusFlags::empty())
+        );
+        let (result, status_flags) = rsqrt(0xFF80_0000, None);
+        assert!(F32::from_bits(result).is_nan());
+        assert_eq!(
+            status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        // -5
+        let (result, status_flags) = rsqrt(0xC0A0_0000, None);
+        assert!(F32::from_bits(result).is_nan());
+        assert_eq!(
+            status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        // 4 -> 0.5, exactly
+        assert_eq!(
+            rsqrt(0x4080_0000, None),
+            (0x3F00_0000, StatusFlags::empty())
+        );
+        // 2 -> 0.70710678..., between 0x3F3504F3 and 0x3F3504F4
+        assert_eq!(
+            rsqrt(0x4000_0000, None),
+            (0x3F35_04F3, StatusFlags::empty().signal_inexact())
+        );
+        assert_eq!(
+            rsqrt(0x4000_0000, Some(RoundingMode::TowardPositive)),
+            (0x3F35_04F4, StatusFlags::empty().signal_inexact())
+        );
+        let mut fp_state = FPState::default();
+        let result = F64::from_bits(0x4010_0000_0000_0000).rsqrt(None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x3FE0_0000_0000_0000);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+    }
+
+    #[test]
+    #[cfg(not(feature = "rational-only"))]
+    fn test_newton_steps() {
+        let two = F32::from_bits(0x4000_0000);
+        let one = F32::from_bits(0x3F80_0000);
+        let exact_sqrt = RealAlgebraicNumber::from(2).pow((1, 2));
+        let steps = two.sqrt_newton_steps(&one, 6);
+        assert_eq!(steps.len(), 6);
+        // 1.5, 17/12 rounded, then quadratic convergence
+        assert_eq!(*steps[0].0.bits(), 0x3FC0_0000);
+        assert_eq!(*steps[1].0.bits(), 0x3FB5_5555);
+        for (value, error) in &steps {
+            assert_eq!(
+                *error,
+                value.to_real_algebraic_number().unwrap() - &exact_sqrt
+            );
+        }
+        for window in steps.windows(2) {
+            assert!(window[1].1.abs() <= window[0].1.abs());
+        }
+        let sqrt = two.sqrt(None, None);
+        assert_eq!(steps[3].0.bits(), sqrt.bits());
+        assert_eq!(steps[5].0.bits(), sqrt.bits());
+
+        let steps = two.rsqrt_newton_steps(&F32::from_bits(0x3F00_0000), 5);
+        assert_eq!(steps.len(), 5);
+        // 0.5 * (3 - 2 * 0.25) / 2 = 0.625
+        assert_eq!(*steps[0].0.bits(), 0x3F20_0000);
+        let exact_rsqrt = exact_sqrt.recip();
+        for (value, error) in &steps {
+            assert_eq!(
+                *error,
+                value.to_real_algebraic_number().unwrap() - &exact_rsqrt
+            );
+        }
+        assert_eq!(steps[4].0.bits(), two.rsqrt(None, None).bits());
+        // an estimate that's too large makes the next iterate negative
+        assert!(two
+            .rsqrt_newton_steps(&F32::from_bits(0x4000_0000), 3)
+            .is_empty());
+
+        assert!(two.sqrt_newton_steps(&F32::positive_zero(), 3).is_empty());
+        assert!(F32::from_bits(0xC000_0000)
+            .sqrt_newton_steps(&one, 3)
+            .is_empty());
+        assert!(F32::quiet_nan().sqrt_newton_steps(&one, 3).is_empty());
+        assert!(two.sqrt_newton_steps(&one, 0).is_empty());
+    }
+
+    #[test]
+    #[cfg(not(feature = "rational-only"))]
+    fn test_hypot() {
+        let mut fp_state = FPState::default();
+        let result =
+            F16::from_bits(0xC200).hypot(&F16::from_bits(0x4400), None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x4500);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        let result = F16::negative_zero().hypot(&F16::negative_zero(), None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x0000);
+        let result = F16::negative_infinity().hypot(&F16::quiet_nan(), None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x7C00);
+        let result = F16::quiet_nan().hypot(&F16::negative_infinity(), None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x7C00);
+        let result = F16::quiet_nan().hypot(&F16::from_bits(0x3C00), None, Some(&mut fp_state));
+        assert!(result.is_quiet_nan());
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        let result =
+            F16::positive_infinity().hypot(&F16::signaling_nan(), None, Some(&mut fp_state));
+        assert!(result.is_quiet_nan());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+    }
+
+    #[test]
+    fn test_bit_eq() {
+        let positive_zero = F32::positive_zero();
+        let negative_zero = F32::negative_zero();
+        assert_eq!(
+            positive_zero.compare_quiet(&negative_zero, None),
+            Some(Ordering::Equal)
+        );
+        assert!(!positive_zero.bit_eq(&negative_zero));
+        assert!(positive_zero.bit_eq(&F32::positive_zero()));
+        let nan = F32::quiet_nan();
+        assert_eq!(nan.compare_quiet(&nan, None), None);
+        assert!(nan.bit_eq(&F32::quiet_nan()));
+        assert!(!nan.bit_eq(&F32::signaling_nan()));
+        let a = DynamicFloat::positive_zero(FloatProperties::STANDARD_16);
+        let b = DynamicFloat::positive_zero(FloatProperties::STANDARD_32);
+        assert!(!a.value.bit_eq(&b.value));
+    }
+
+    #[test]
+    fn test_no_inf_nan_saturation() {
+        let properties = FloatProperties::new(4, 3).with_has_inf_nan(false);
+        assert!(!properties.has_inf_nan());
+        assert!(!properties.is_standard());
+        let value = |bits: u32| Float::from_bits_and_traits(BigUint::from(bits), properties);
+        assert_eq!(value(0x78).class(), FloatClass::PositiveNormal);
+        assert_eq!(value(0xFF).class(), FloatClass::NegativeNormal);
+        assert_eq!(
+            value(0x7F).to_ratio(),
+            Some(Ratio::from_integer(480.into()))
+        );
+        assert_eq!(
+            *Float::signed_max_normal_with_traits(Sign::Positive, properties).bits(),
+            0x7Fu32.into()
+        );
+        for &rounding_mode in &[
+            RoundingMode::TiesToEven,
+            RoundingMode::TowardZero,
+            RoundingMode::TowardNegative,
+            RoundingMode::TowardPositive,
+            RoundingMode::TiesToAway,
+            RoundingMode::TiesToOdd,
+        ] {
+            let mut fp_state = FPState::default();
+            let result = value(0x77).mul(&value(0x77), Some(rounding_mode), Some(&mut fp_state));
+            assert_eq!(*result.bits(), 0x7Fu32.into());
+            assert_eq!(
+                fp_state.status_flags,
+                StatusFlags::empty().signal_overflow_with_inexact()
+            );
+            let mut fp_state = FPState::default();
+            let result = value(0x77).mul(&value(0xF7), Some(rounding_mode), Some(&mut fp_state));
+            assert_eq!(*result.bits(), 0xFFu32.into());
+            assert_eq!(
+                fp_state.status_flags,
+                StatusFlags::empty().signal_overflow_with_inexact()
+            );
+            let mut fp_state = FPState::default();
+            let result = value(0x7F).add(&value(0x7F), Some(rounding_mode), Some(&mut fp_state));
+            assert_eq!(*result.bits(), 0x7Fu32.into());
+            assert_eq!(
+                fp_state.status_flags,
+                StatusFlags::empty().signal_overflow_with_inexact()
+            );
+            let mut fp_state = FPState::default();
+            let result = value(0x7F).fused_mul_add(
+                &value(0x7F),
+                &value(0x00),
+                Some(rounding_mode),
+                Some(&mut fp_state),
+            );
+            assert_eq!(*result.bits(), 0x7Fu32.into());
+            assert_eq!(
+                fp_state.status_flags,
+                StatusFlags::empty().signal_overflow_with_inexact()
+            );
+        }
+        let mut fp_state = FPState::default();
+        let result =
+            F32::from_bits(0x447A0000) // 1000.0
+                .convert_to_float_with_traits(None, Some(&mut fp_state), properties);
+        assert_eq!(*result.bits(), 0x7Fu32.into());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_overflow_with_inexact()
+        );
+        let mut fp_state = FPState::default();
+        let result = F32::negative_infinity().convert_to_float_with_traits(
+            None,
+            Some(&mut fp_state),
+            properties,
+        );
+        assert_eq!(*result.bits(), 0xFFu32.into());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let mut fp_state = FPState::default();
+        let result =
+            F32::quiet_nan().convert_to_float_with_traits(None, Some(&mut fp_state), properties);
+        assert_eq!(*result.bits(), 0x00u32.into());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        // exactly infinite results of finite operands saturate too
+        let mut fp_state = FPState::default();
+        let result = value(0x38).div(&value(0x80), None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0xFFu32.into());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_division_by_zero()
+        );
+        let mut fp_state = FPState::default();
+        let result = value(0x00).pown(-1, None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x7Fu32.into());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_division_by_zero()
+        );
+        let result = value(0x7F).next_up(None);
+        assert_eq!(*result.bits(), 0x7Fu32.into());
+        let result = value(0xFF).next_down(None);
+        assert_eq!(*result.bits(), 0xFFu32.into());
+    }
+
+    #[test]
+    fn test_clamp_to_representable() {
+        let clamp = |bits: u16| {
+            let mut fp_state = FPState::default();
+            let (result, clamped) =
+                F16::from_bits(bits).clamp_to_representable(Some(&mut fp_state));
+            (*result.bits(), clamped, fp_state.status_flags)
+        };
+        let overflow = StatusFlags::empty().signal_overflow_with_inexact();
+        assert_eq!(clamp(0x7C00), (0x7BFF, true, overflow));
+        assert_eq!(clamp(0xFC00), (0xFBFF, true, overflow));
+        for &bits in &[
+            0x0000, 0x8000, 0x0001, 0x3C00, 0x7BFF, 0xFBFF, 0x7E00, 0x7D00,
+        ] {
+            assert_eq!(clamp(bits), (bits, false, StatusFlags::empty()));
+        }
+        // formats without infinities never need clamping
+        let properties = FloatProperties::arm_alt_half();
+        let max = Float::signed_max_normal_with_traits(Sign::Positive, properties);
+        let (result, clamped) = max.clamp_to_representable(None);
+        assert_eq!(result.bits(), max.bits());
+        assert!(!clamped);
+
+        let infinity = DynamicFloat::positive_infinity(FloatProperties::STANDARD_32);
+        let (result, clamped) = infinity.clamp_to_representable();
+        assert!(clamped);
+        assert_eq!(result.bits(), &BigUint::from(0x7F7F_FFFFu32));
+        assert_eq!(result.fp_state.status_flags, overflow);
+        let one = DynamicFloat::from_bits(0x3F80_0000u32.into(), FloatProperties::STANDARD_32)
+            .unwrap()
+            .with_exact_value_tracking();
+        let (result, clamped) = one.clamp_to_representable();
+        assert!(!clamped);
+        assert_eq!(result.bits(), one.bits());
+        assert_eq!(result.exact_value, one.exact_value);
+        assert_eq!(result.fp_state, one.fp_state);
+    }
+
+    #[test]
+    fn test_arm_alt_half() {
+        let properties = FloatProperties::arm_alt_half();
+        assert!(!properties.has_inf_nan());
+        assert!(!properties.is_standard());
+        assert_eq!(properties.width(), 16);
+        assert_eq!(
+            properties,
+            FloatProperties::STANDARD_16.with_has_inf_nan(false)
+        );
+        let value = |bits: u32| Float::from_bits_and_traits(BigUint::from(bits), properties);
+        // the all-ones exponent is normal, unlike `STANDARD_16`
+        assert_eq!(value(0x7C00).class(), FloatClass::PositiveNormal);
+        assert_eq!(value(0xFE00).class(), FloatClass::NegativeNormal);
+        assert_eq!(
+            value(0x7C00).to_ratio(),
+            Some(Ratio::from_integer(65536.into()))
+        );
+        assert_eq!(
+            value(0x7FFF).to_ratio(),
+            Some(Ratio::from_integer(131008.into()))
+        );
+        assert_eq!(F16::from_bits(0x7C00).class(), FloatClass::PositiveInfinity);
+        // values that overflow `STANDARD_16` are still finite
+        let mut fp_state = FPState::default();
+        let result = value(0x7BFF).add(&value(0x7BFF), None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x7FFFu32.into());
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        // overflow saturates
+        let mut fp_state = FPState::default();
+        let result = value(0x7FFF).add(&value(0x7FFF), None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x7FFFu32.into());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_overflow_with_inexact()
+        );
+        let mut fp_state = FPState::default();
+        let result = value(0x7FFF).mul(&value(0xC000), None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0xFFFFu32.into());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_overflow_with_inexact()
+        );
+    }
+
+    #[test]
+    fn test_rounding_mode_helpers() {
+        use RoundingMode::*;
+        for &(rounding_mode, on_tie, on_overflow, zero_sign) in &[
+            (
+                TiesToEven,
+                Some((false, true)),
+                (true, true),
+                Sign::Positive,
+            ),
+            (TowardZero, None, (false, false), Sign::Positive),
+            (TowardNegative, None, (false, true), Sign::Negative),
+            (TowardPositive, None, (true, false), Sign::Positive),
+            (TiesToAway, Some((true, true)), (true, true), Sign::Positive),
+            (TiesToOdd, Some((true, false)), (true, true), Sign::Positive),
+        ] {
+            assert_eq!(
+                rounding_mode
+                    .rounds_away_on_tie(false)
+                    .and_then(|even| Some((even, rounding_mode.rounds_away_on_tie(true)?))),
+                on_tie,
+                "{:?}",
+                rounding_mode
+            );
+            assert_eq!(
+                (
+                    rounding_mode.rounds_away_on_overflow(Sign::Positive),
+                    rounding_mode.rounds_away_on_overflow(Sign::Negative)
+                ),
+                on_overflow,
+                "{:?}",
+                rounding_mode
+            );
+            assert_eq!(
+                rounding_mode.exact_zero_sign(),
+                zero_sign,
+                "{:?}",
+                rounding_mode
+            );
+        }
+    }
+
+    #[test]
+    fn test_ties_to_odd() {
+        let to = Some(RoundingMode::TiesToOdd);
+        let te = Some(RoundingMode::TiesToEven);
+        let inexact = StatusFlags::empty().signal_inexact();
+        let check = |lhs: u16, rhs: u16, rounding_mode, expected: u16, flags| {
+            let mut fp_state = FPState::default();
+            let result =
+                F16::from_bits(lhs).add(&F16::from_bits(rhs), rounding_mode, Some(&mut fp_state));
+            assert_eq!(
+                (*result.bits(), fp_state.status_flags),
+                (expected, flags),
+                "{:#06X} + {:#06X} {:?}",
+                lhs,
+                rhs,
+                rounding_mode
+            );
+        };
+        // 1 + 2^-11 is a tie between 1 and the next larger value
+        check(0x3C00, 0x1000, to, 0x3C01, inexact);
+        check(0x3C00, 0x1000, te, 0x3C00, inexact);
+        check(0xBC00, 0x9000, to, 0xBC01, inexact);
+        // 1 + 3 * 2^-11 is a tie with an odd lower neighbor
+        check(0x3C01, 0x1000, to, 0x3C01, inexact);
+        check(0x3C01, 0x1000, te, 0x3C02, inexact);
+        // not a tie, so round to nearest
+        check(0x3C00, 0x0C00, to, 0x3C00, inexact);
+        check(0x3C00, 0x1200, to, 0x3C01, inexact);
+        // exact results are unchanged
+        check(0x3C00, 0x3C00, to, 0x4000, StatusFlags::empty());
+        // overflow rounds to infinity, like the other round-to-nearest modes
+        check(
+            0x7BFF,
+            0x7BFF,
+            to,
+            0x7C00,
+            StatusFlags::empty().signal_overflow_with_inexact(),
+        );
+        // exact zero results are positive
+        check(0x3C00, 0xBC00, to, 0x0000, StatusFlags::empty());
+        for &(bits, expected) in &[
+            (0x3800, 1),
+            (0x3E00, 1),
+            (0x4100, 3),
+            (0x4300, 3),
+            (0xB800, -1),
+            (0xBE00, -1),
+            (0x3A00, 1),
+            (0x3D00, 1),
+        ] {
+            let result = F16::from_bits(bits).round_to_integer(false, to, None);
+            assert_eq!(result, Some(BigInt::from(expected)), "{:#06X}", bits);
+        }
+    }
+
+    #[test]
+    fn test_exact_underflow() {
+        let min_subnormal = F16::signed_min_subnormal(Sign::Positive);
+        let mut fp_state = FPState::default();
+        assert_eq!(
+            fp_state.exception_handling_mode,
+            ExceptionHandlingMode::IgnoreExactUnderflow
+        );
+        let result = min_subnormal.add(&F16::positive_zero(), None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x0001);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        let result = F16::from_bits(0x0002).mul(&F16::from_bits(0x3800), None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x0001);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        let result = F16::from_bits(0x0003).mul(&F16::from_bits(0x3800), None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x0002);
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_underflow_with_inexact()
+        );
+        let mut fp_state = FPState {
+            exception_handling_mode: ExceptionHandlingMode::SignalExactUnderflow,
+            ..FPState::default()
+        };
+        let result = min_subnormal.add(&F16::positive_zero(), None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x0001);
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_underflow()
+        );
+    }
+
+    #[test]
+    fn test_tininess_detection() {
+        fn check(
+            value: &RealAlgebraicNumber,
+            tininess_detection_mode: TininessDetectionMode,
+            expected_bits: u16,
+            expected_status_flags: StatusFlags,
+        ) {
+            let mut fp_state = FPState {
+                tininess_detection_mode,
+                ..FPState::default()
+            };
+            let result = F16::from_real_algebraic_number(value, None, Some(&mut fp_state));
+            assert_eq!(
+                (*result.bits(), fp_state.status_flags),
+                (expected_bits, expected_status_flags),
+                "value = {:?}, tininess_detection_mode = {:?}",
+                value,
+                tininess_detection_mode
+            );
+        }
+        // 2^-14 * (1 - 2^-13) rounds up to the smallest normal even with unbounded exponent range
+        let value =
+            RealAlgebraicNumber::from(Ratio::new(BigInt::from((1 << 13) - 1), BigInt::one() << 27));
+        check(
+            &value,
+            TininessDetectionMode::BeforeRounding,
+            0x0400,
+            StatusFlags::empty().signal_underflow_with_inexact(),
+        );
+        check(
+            &value,
+            TininessDetectionMode::AfterRounding,
+            0x0400,
+            StatusFlags::empty().signal_inexact(),
+        );
+        // 2^-14 * (1 - 2^-11) only rounds up to the smallest normal because of the subnormal precision
+        let value =
+            RealAlgebraicNumber::from(Ratio::new(BigInt::from((1 << 11) - 1), BigInt::one() << 25));
+        check(
+            &value,
+            TininessDetectionMode::BeforeRounding,
+            0x0400,
+            StatusFlags::empty().signal_underflow_with_inexact(),
+        );
+        check(
+            &value,
+            TininessDetectionMode::AfterRounding,
+            0x0400,
+            StatusFlags::empty().signal_underflow_with_inexact(),
+        );
+        // the smallest normal itself is never tiny
+        let value = RealAlgebraicNumber::from(Ratio::new(BigInt::one(), BigInt::one() << 14));
+        check(
+            &value,
+            TininessDetectionMode::BeforeRounding,
+            0x0400,
+            StatusFlags::empty(),
+        );
+        check(
+            &value,
+            TininessDetectionMode::AfterRounding,
+            0x0400,
+            StatusFlags::empty(),
+        );
+        // the same boundary through an arithmetic operation
+        let mut fp_state = FPState {
+            tininess_detection_mode: TininessDetectionMode::AfterRounding,
+            ..FPState::default()
+        };
+        let result = F16::from_bits(0x0400).mul(&F16::from_bits(0x3BFF), None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x0400);
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_underflow_with_inexact()
+        );
+    }
+
+    /// compares results against the host's `f32`/`f64` arithmetic, which is
+    /// assumed to be IEEE 754 conformant with round to nearest, ties to even.
+    ///
+    /// On x86-64, the operations are run using inline assembly so the status
+    /// flags can be read from `MXCSR` and compared too, using x86's
+    /// tininess detection after rounding. Other hosts' status flags aren't
+    /// accessible from Rust, so only results are compared there, as is
+    /// `fused_mul_add` on x86-64 processors without FMA.
+    ///
+    /// NaN results are only checked for being NaN, since the NaN payload
+    /// propagation rules are platform-specific.
+    #[cfg(feature = "host-fuzz-test")]
+    #[test]
+    fn test_host_fuzz() {
+        use rand::{Rng, SeedableRng};
+        use rand_pcg::Pcg64Mcg;
+        const ITERATIONS: usize = 2000;
+        #[cfg(target_arch = "x86_64")]
+        let has_fma = is_x86_feature_detected!("fma");
+        /// runs `$instruction` with the `MXCSR` status flags cleared,
+        /// returning the result and the raised status flags.
+        #[cfg(target_arch = "x86_64")]
+        macro_rules! host_op {
+            ($instruction:expr, $a:expr $(, $b:ident = $b_value:expr)*) => {{
+                let mut result = $a;
+                let mut mxcsr = 0u32;
+                // Safety: only the `MXCSR` status flags and `mxcsr` are modified
+                unsafe {
+                    std::arch::asm!(
+                        "stmxcsr [{mxcsr}]",
+                        "and dword ptr [{mxcsr}], 0xFFFFFFC0",
+                        "ldmxcsr [{mxcsr}]",
+                        $instruction,
+                        "stmxcsr [{mxcsr}]",
+                        mxcsr = in(reg) &mut mxcsr,
+                        a = inout(xmm_reg) result,
+                        $($b = in(xmm_reg) $b_value,)*
+                        options(nostack),
+                    );
+                }
+                let mut status_flags = StatusFlags::empty();
+                if mxcsr & 0x01 != 0 {
+                    status_flags = status_flags.signal_invalid_operation();
+                }
+                // 0x02 is x86's denormal operand flag, which isn't an IEEE 754 flag
+                if mxcsr & 0x04 != 0 {
+                    status_flags = status_flags.signal_division_by_zero();
+                }
+                if mxcsr & 0x08 != 0 {
+                    status_flags = status_flags.signal_overflow();
+                }
+                if mxcsr & 0x10 != 0 {
+                    status_flags = status_flags.signal_underflow();
+                }
+                if mxcsr & 0x20 != 0 {
+                    status_flags = status_flags.signal_inexact();
+                }
+                (result, Some(status_flags))
+            }};
+        }
+        macro_rules! fuzz {
+            ($float:ident, $native:ident, $suffix:literal, $gen:expr) => {{
+                fn check(
+                    name: &str,
+                    inputs: &[$native],
+                    (host, host_status_flags): ($native, Option<StatusFlags>),
+                    op: impl FnOnce(&mut FPState) -> $float,
+                ) {
+                    let mut fp_state = FPState {
+                        exception_handling_mode: ExceptionHandlingMode::IgnoreExactUnderflow,
+                        tininess_detection_mode: TininessDetectionMode::AfterRounding,
+                        ..FPState::default()
+                    };
+                    let result = op(&mut fp_state);
+                    if host.is_nan() {
+                        assert!(
+                            result.is_nan(),
+                            "{}{:?}: host = {:?}, result = {:?}",
+                            name,
+                            inputs,
+                            host,
+                            result
+                        );
+                    } else {
+                        assert_eq!(
+                            host.to_bits(),
+                            *result.bits(),
+                            "{}{:?}: host = {:?}, result = {:?}",
+                            name,
+                            inputs,
+                            host,
+                            result
+                        );
+                    }
+                    if let Some(host_status_flags) = host_status_flags {
+                        assert_eq!(
+                            host_status_flags, fp_state.status_flags,
+                            "{}{:?}: host = {:?}, result = {:?}",
+                            name, inputs, host, result
+                        );
+                    }
+                }
+                let mut rng = Pcg64Mcg::seed_from_u64(0x5EED);
+                for _ in 0..ITERATIONS {
+                    let a = $native::from_bits($gen(&mut rng));
+                    let b = $native::from_bits($gen(&mut rng));
+                    let c = $native::from_bits($gen(&mut rng));
+                    let fa = $float::from_bits(a.to_bits());
+                    let fb = $float::from_bits(b.to_bits());
+                    let fc = $float::from_bits(c.to_bits());
+                    #[cfg(target_arch = "x86_64")]
+                    let (add, sub, mul, div, sqrt) = (
+                        host_op!(concat!("adds", $suffix, " {a}, {b}"), a, b = b),
+                        host_op!(concat!("subs", $suffix, " {a}, {b}"), a, b = b),
+                        host_op!(concat!("muls", $suffix, " {a}, {b}"), a, b = b),
+                        host_op!(concat!("divs", $suffix, " {a}, {b}"), a, b = b),
+                        host_op!(concat!("sqrts", $suffix, " {a}, {a}"), a),
+                    );
+                    #[cfg(target_arch = "x86_64")]
+                    let fused_mul_add = if has_fma {
+                        host_op!(
+                            concat!("vfmadd213s", $suffix, " {a}, {b}, {c}"),
+                            a,
+                            b = b,
+                            c = c
+                        )
+                    } else {
+                        (a.mul_add(b, c), None)
+                    };
+                    #[cfg(not(target_arch = "x86_64"))]
+                    let (add, sub, mul, div, sqrt, fused_mul_add) = (
+                        (a + b, None),
+                        (a - b, None),
+                        (a * b, None),
+                        (a / b, None),
+                        (a.sqrt(), None),
+                        (a.mul_add(b, c), None),
+                    );
+                    check("add", &[a, b], add, |fp_state| {
+                        fa.add(&fb, None, Some(fp_state))
+                    });
+                    check("sub", &[a, b], sub, |fp_state| {
+                        fa.sub(&fb, None, Some(fp_state))
+                    });
+                    check("mul", &[a, b], mul, |fp_state| {
+                        fa.mul(&fb, None, Some(fp_state))
+                    });
+                    check("div", &[a, b], div, |fp_state| {
+                        fa.div(&fb, None, Some(fp_state))
+                    });
+                    #[cfg(not(feature = "rational-only"))]
+                    check("sqrt", &[a], sqrt, |fp_state| fa.sqrt(None, Some(fp_state)));
+                    #[cfg(feature = "rational-only")]
+                    let _ = sqrt;
+                    check("fused_mul_add", &[a, b, c], fused_mul_add, |fp_state| {
+                        fa.fused_mul_add(&fb, &fc, None, Some(fp_state))
+                    });
+                }
+            }};
+        }
+        fuzz!(F32, f32, "s", |rng: &mut Pcg64Mcg| rng.gen::<u32>());
+        fuzz!(F64, f64, "d", |rng: &mut Pcg64Mcg| rng.gen::<u64>());
+    }
+
+    #[test]
+    fn test_default_nan() {
+        let default_nan = |platform_properties| {
+            *F32WithPlatformProperties::default_nan_with_traits(F32WithPlatformPropertiesTraits(
+                platform_properties,
+            ))
+            .bits()
+        };
+        assert_eq!(default_nan(PlatformProperties::RISC_V), 0x7FC0_0000);
+        assert_eq!(default_nan(PlatformProperties::X86_SSE), 0xFFC0_0000);
+        assert_eq!(default_nan(PlatformProperties::SPARC), 0x7FFF_FFFF);
+        assert_eq!(default_nan(PlatformProperties::HPPA), 0x7FA0_0000);
+        assert_eq!(default_nan(PlatformProperties::MIPS_LEGACY), 0x7FBF_FFFF);
+        let x86_sse_default_nan = F32WithPlatformProperties::default_nan_with_traits(
+            F32WithPlatformPropertiesTraits(PlatformProperties::X86_SSE),
+        );
+        assert_eq!(x86_sse_default_nan.sign(), Sign::Negative);
+        assert!(x86_sse_default_nan.is_quiet_nan());
+        let result = F32::positive_zero().div(&F32::positive_zero(), None, None);
+        assert!(result.bit_eq(&F32::default_nan()));
+    }
+
+    #[test]
+    fn test_canonical_nan_sign() {
+        let traits = F32WithPlatformPropertiesTraits(PlatformProperties::X86_SSE);
+        let quiet_nan = F32WithPlatformProperties::quiet_nan_with_traits(traits);
+        assert_eq!(*quiet_nan.bits(), 0xFFC0_0000);
+        let signaling_nan = F32WithPlatformProperties::signaling_nan_with_traits(traits);
+        assert_eq!(*signaling_nan.bits(), 0xFF80_0001);
+        let zero = F32WithPlatformProperties::positive_zero_with_traits(traits);
+        let mut fp_state = FPState::default();
+        let result = zero.div(&zero, None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0xFFC0_0000);
+        assert_eq!(result.sign(), Sign::Negative);
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        assert_eq!(*F32::quiet_nan().bits(), 0x7FC0_0000);
+        assert_eq!(*F32::signaling_nan().bits(), 0x7F80_0001);
+    }
+
+    #[test]
+    fn test_matches_platform_default_nan() {
+        let platforms: &[(PlatformProperties, u32)] = &[
+            (PlatformProperties::ARM, 0x7FC0_0000),
+            (PlatformProperties::RISC_V, 0x7FC0_0000),
+            (PlatformProperties::POWER, 0x7FC0_0000),
+            (PlatformProperties::MIPS_2008, 0x7FC0_0000),
+            (PlatformProperties::X86_SSE, 0xFFC0_0000),
+            (PlatformProperties::SPARC, 0x7FFF_FFFF),
+            (PlatformProperties::HPPA, 0x7FA0_0000),
+            (PlatformProperties::MIPS_LEGACY, 0x7FBF_FFFF),
+        ];
+        for &(platform_properties, default_nan_bits) in platforms {
+            let traits = F32WithPlatformPropertiesTraits(platform_properties);
+            let value = |bits| F32WithPlatformProperties::from_bits_and_traits(bits, traits);
+            let zero = value(0);
+            let infinity = value(0x7F80_0000);
+            let results = [
+                zero.div(&zero, None, None),
+                infinity.sub(&infinity, None, None),
+                infinity.mul(&zero, None, None),
+                #[cfg(not(feature = "rational-only"))]
+                value(0xBF80_0000).sqrt(None, None),
+            ];
+            for result in results.iter() {
+                assert_eq!(
+                    *result.bits(),
+                    default_nan_bits,
+                    "{:?}",
+                    platform_properties
+                );
+                assert!(
+                    result.matches_platform_default_nan(),
+                    "{:?}",
+                    platform_properties
+                );
+            }
+            assert!(value(default_nan_bits).matches_platform_default_nan());
+            // the sign and payload have to match too
+            assert!(!value(default_nan_bits ^ 0x8000_0000).matches_platform_default_nan());
+            assert!(!value(default_nan_bits ^ 1).matches_platform_default_nan());
+            assert!(!zero.matches_platform_default_nan());
+            assert!(!infinity.matches_platform_default_nan());
+        }
+        // a propagated NaN is quiet, but isn't the default NaN
+        let traits = F32WithPlatformPropertiesTraits(PlatformProperties::X86_SSE);
+        let propagated = F32WithPlatformProperties::from_bits_and_traits(0x7FC0_0001, traits).add(
+            &F32WithPlatformProperties::from_bits_and_traits(0x3F80_0000, traits),
+            None,
+            None,
+        );
+        assert!(propagated.is_quiet_nan());
+        assert!(!propagated.matches_platform_default_nan());
+        // formats without NaNs never match
+        let no_inf_nan = FloatProperties::STANDARD_16.with_has_inf_nan(false);
+        let zero = Float::positive_zero_with_traits(no_inf_nan);
+        let result = zero.div(&zero, None, None);
+        assert!(!result.matches_platform_default_nan());
+    }
+
+    #[test]
+    fn test_possible_flags() {
+        assert_eq!(
+            OpKind::Div.possible_flags(),
+            StatusFlags::empty()
+                .signal_division_by_zero()
+                .signal_invalid_operation()
+                .signal_overflow()
+                .signal_underflow()
+                .signal_inexact()
+        );
+        assert_eq!(
+            OpKind::Sqrt.possible_flags(),
+            StatusFlags::empty()
+                .signal_invalid_operation()
+                .signal_inexact()
+        );
+        assert_eq!(OpKind::SignOp.possible_flags(), StatusFlags::empty());
+        let values: Vec<F16> = [
+            0x0000, 0x8000, 0x0001, 0x83FF, 0x0400, 0x1000, 0x3C00, 0xBC00, 0x3E00, 0x4200, 0x7BFF,
+            0xFBFF, 0x7C00, 0xFC00, 0x7E00, 0x7D00,
+        ]
+        .iter()
+        .map(|&bits| F16::from_bits(bits))
+        .collect();
+        let check = |op: OpKind, f: &mut dyn FnMut(&mut FPState)| {
+            for &rounding_mode in &[RoundingMode::TiesToEven, RoundingMode::TowardZero] {
+                let mut fp_state = FPState {
+                    rounding_mode,
+                    exception_handling_mode: ExceptionHandlingMode::SignalExactUnderflow,
+                    ..FPState::default()
+                };
+                f(&mut fp_state);
+                assert_eq!(
+                    fp_state.status_flags.merge(op.possible_flags()),
+                    op.possible_flags(),
+                    "{:?} signaled {:?}",
+                    op,
+                    fp_state.status_flags
+                );
+            }
+        };
+        for a in &values {
+            check(OpKind::RoundToInteger, &mut |fp_state| {
+                a.round_to_integer(true, None, Some(fp_state));
+            });
+            check(OpKind::RoundToIntegral, &mut |fp_state| {
+                a.round_to_integral(true, None, Some(fp_state));
+            });
+            check(OpKind::NextUpOrDown, &mut |fp_state| {
+                a.next_up(Some(fp_state));
+            });
+            check(OpKind::LogB, &mut |fp_state| {
+                a.log_b(Some(fp_state));
+            });
+            check(OpKind::ScaleB, &mut |fp_state| {
+                a.scale_b(BigInt::from(-20), None, Some(fp_state));
+                a.scale_b(BigInt::from(20), None, Some(fp_state));
+            });
+            #[cfg(not(feature = "rational-only"))]
+            check(OpKind::Sqrt, &mut |fp_state| {
+                a.sqrt(None, Some(fp_state));
+            });
+            #[cfg(not(feature = "rational-only"))]
+            check(OpKind::RSqrt, &mut |fp_state| {
+                a.rsqrt(None, Some(fp_state));
+            });
+            check(OpKind::PowN, &mut |fp_state| {
+                a.pown(-3, None, Some(fp_state));
+                a.pown(5, None, Some(fp_state));
+            });
+            #[cfg(not(feature = "rational-only"))]
+            check(OpKind::Cbrt, &mut |fp_state| {
+                a.cbrt(None, Some(fp_state));
+            });
+            check(OpKind::ConvertFromFloat, &mut |fp_state| {
+                let _: F32 = a.convert_to_float(None, Some(fp_state));
+                let _: F16 = a
+                    .convert_to_float::<F32Traits>(None, None)
+                    .convert_to_float(None, Some(fp_state));
+            });
+            for b in &values {
+                check(OpKind::Add, &mut |fp_state| {
+                    a.add(b, None, Some(fp_state));
+                });
+                check(OpKind::Sub, &mut |fp_state| {
+                    a.sub(b, None, Some(fp_state));
+                });
+                check(OpKind::Mul, &mut |fp_state| {
+                    a.mul(b, None, Some(fp_state));
+                });
+                check(OpKind::Div, &mut |fp_state| {
+                    a.div(b, None, Some(fp_state));
+                });
+                check(OpKind::IEEE754Remainder, &mut |fp_state| {
+                    a.ieee754_remainder(b, None, Some(fp_state));
+                });
+                check(OpKind::FusedMulAdd, &mut |fp_state| {
+                    a.fused_mul_add(b, &values[4], None, Some(fp_state));
+                });
+                #[cfg(not(feature = "rational-only"))]
+                check(OpKind::Hypot, &mut |fp_state| {
+                    a.hypot(b, None, Some(fp_state));
+                });
+                check(OpKind::Compare, &mut |fp_state| {
+                    a.compare_signaling(b, Some(fp_state));
+                });
+            }
+        }
+        check(OpKind::ConvertFromInt, &mut |fp_state| {
+            F16::from_u32(u32::MAX, None, Some(fp_state));
+            F16::from_u32(2049, None, Some(fp_state));
+        });
+    }
+
+    #[test]
+    fn test_hex_string() {
+        let cases: &[(u32, &str)] = &[
+            (0x0000_0000, "0x0p+0"),
+            (0x8000_0000, "-0x0p+0"),
+            (0x3F80_0000, "0x1p+0"),
+            (0x4040_0000, "0x1.8p+1"),
+            (0xBE80_0001, "-0x1.000002p-2"),
+            (0x0000_0001, "0x0.000002p-126"),
+            (0x007F_FFFF, "0x0.fffffep-126"),
+            (0x7F7F_FFFF, "0x1.fffffep+127"),
+            (0x7F80_0000, "inf"),
+            (0xFF80_0000, "-inf"),
+            (0x7FC0_0000, "nan(0x400000)"),
+            (0xFF80_0001, "-snan(0x1)"),
+        ];
+        for &(bits, text) in cases {
+            let value = F32::from_bits(bits);
+            assert_eq!(value.to_hex_string(), text);
+            let parsed = F32::from_hex_str(text, None, None).unwrap();
+            assert_eq!(*parsed.bits(), bits, "{}", text);
+            value.debug_assert_hex_roundtrip();
+        }
+        assert_eq!(F16::from_bits(0x0001).to_hex_string(), "0x0.004p-14");
+        let parse = |text: &str, rounding_mode: Option<RoundingMode>| {
+            let mut fp_state = FPState::default();
+            F32::from_hex_str(text, rounding_mode, Some(&mut fp_state))
+                .map(|v| (*v.bits(), fp_state.status_flags))
+        };
+        assert_eq!(
+            parse("+0X1.8P1", None),
+            Some((0x4040_0000, StatusFlags::empty()))
+        );
+        assert_eq!(
+            parse("0x18", None),
+            Some((0x41C0_0000, StatusFlags::empty()))
+        );
+        assert_eq!(
+            parse("-Infinity", None),
+            Some((0xFF80_0000, StatusFlags::empty()))
+        );
+        assert_eq!(
+            parse("nan", None),
+            Some((0x7FC0_0000, StatusFlags::empty()))
+        );
+        assert_eq!(
+            parse("0x1.0000001p+0", None),
+            Some((0x3F80_0000, StatusFlags::empty().signal_inexact()))
+        );
+        assert_eq!(
+            parse("0x1.0000001p+0", Some(RoundingMode::TowardPositive)),
+            Some((0x3F80_0001, StatusFlags::empty().signal_inexact()))
+        );
+        assert_eq!(
+            parse("0x1p+99999999999", None),
+            Some((
+                0x7F80_0000,
+                StatusFlags::empty().signal_overflow_with_inexact()
+            ))
+        );
+        assert_eq!(
+            parse("-0x1p-99999999999", None),
+            Some((
+                0x8000_0000,
+                StatusFlags::empty().signal_underflow_with_inexact()
+            ))
+        );
+        assert_eq!(
+            parse("0x1p-99999999999", Some(RoundingMode::TowardPositive)),
+            Some((
+                0x0000_0001,
+                StatusFlags::empty().signal_underflow_with_inexact()
+            ))
+        );
+        for &text in &[
+            "",
+            "0x",
+            "0x.p1",
+            "1.5",
+            "0x1g",
+            "0x1p",
+            "nan(0x0)",
+            "nan(1)",
+            "snan(0x400000)",
+        ] {
+            assert_eq!(parse(text, None), None, "{:?}", text);
+        }
+        for bits in (0..=0xFFFFu16).step_by(7) {
+            F16::from_bits(bits).debug_assert_hex_roundtrip();
+        }
+    }
+
+    #[test]
+    fn test_from_str_radix() {
+        type Expected = Result<(u32, StatusFlags), ParseError>;
+        let cases: &[(&str, u32, Expected)] = &[
+            ("1.1", 2, Ok((0x3FC0_0000, StatusFlags::empty()))),
+            ("-0.11", 2, Ok((0xBF40_0000, StatusFlags::empty()))),
+            ("+1P-1", 2, Ok((0x3F00_0000, StatusFlags::empty()))),
+            ("1.8p1", 16, Ok((0x4040_0000, StatusFlags::empty()))),
+            ("A.8", 16, Ok((0x4128_0000, StatusFlags::empty()))),
+            ("z", 36, Ok((0x420C_0000, StatusFlags::empty()))),
+            ("Z.i", 36, Ok((0x420E_0000, StatusFlags::empty()))),
+            ("10", 10, Ok((0x4120_0000, StatusFlags::empty()))),
+            (
+                "0.1",
+                10,
+                Ok((0x3DCC_CCCD, StatusFlags::empty().signal_inexact())),
+            ),
+            (
+                "0.1",
+                3,
+                Ok((0x3EAA_AAAB, StatusFlags::empty().signal_inexact())),
+            ),
+            ("-0", 7, Ok((0x8000_0000, StatusFlags::empty()))),
+            (".1", 4, Ok((0x3E80_0000, StatusFlags::empty()))),
+            ("1.", 5, Ok((0x3F80_0000, StatusFlags::empty()))),
+            (
+                "1p1000000",
+                2,
+                Ok((
+                    0x7F80_0000,
+                    StatusFlags::empty().signal_overflow().signal_inexact(),
+                )),
+            ),
+            (
+                "-1p-1000000",
+                16,
+                Ok((
+                    0x8000_0000,
+                    StatusFlags::empty().signal_underflow().signal_inexact(),
+                )),
+            ),
+            ("inf", 10, Ok((0x7F80_0000, StatusFlags::empty()))),
+            ("-Infinity", 2, Ok((0xFF80_0000, StatusFlags::empty()))),
+            ("nan", 16, Ok((0x7FC0_0000, StatusFlags::empty()))),
+            // valid digits in base 36
+            ("nan", 36, Ok((0x46EB_DE00, StatusFlags::empty()))),
+            ("2", 2, Err(ParseError)),
+            ("g", 16, Err(ParseError)),
+            ("1p1", 10, Err(ParseError)),
+            ("1p", 2, Err(ParseError)),
+            ("", 10, Err(ParseError)),
+            (".", 10, Err(ParseError)),
+            ("-", 10, Err(ParseError)),
+            ("1.2.3", 10, Err(ParseError)),
+            ("1_0", 10, Err(ParseError)),
+            ("+-1", 10, Err(ParseError)),
+            ("infinite", 10, Err(ParseError)),
+        ];
+        for &(text, radix, expected) in cases {
+            let mut fp_state = FPState::default();
+            let result = F32::from_str_radix(text, radix, None, Some(&mut fp_state))
+                .map(|v| (*v.bits(), fp_state.status_flags));
+            assert_eq!(result, expected, "{:?} {}", text, radix);
+        }
+        // formatting and parsing back gives the same value
+        for &bits in &[0x3DCC_CCCDu32, 0x0000_0001, 0x7F7F_FFFF, 0xC049_0FDB] {
+            let value = F32::from_bits(bits);
+            for radix in 2..=36 {
+                let text = value.to_string_radix(radix, None);
+                let parsed = F32::from_str_radix(&text, radix, None, None).unwrap();
+                assert_eq!(*parsed.bits(), bits, "{:?} {}", text, radix);
+            }
+        }
+        let result =
+            DynamicFloat::from_str_radix("0.1", 3, None, None, FloatProperties::STANDARD_16)
+                .unwrap();
+        assert_eq!(*result.bits(), BigUint::from(0x3555u32));
+        assert!(result.fp_state.status_flags.inexact());
+    }
+
+    #[test]
+    fn test_to_string_radix() {
+        let cases: &[(u16, u32, Option<usize>, &str)] = &[
+            (0x0000, 10, None, "0"),
+            (0x8000, 2, Some(3), "-0"),
+            (0x3E00, 2, None, "1.1"),
+            (0x3E00, 16, None, "1.8"),
+            (0xBA00, 2, None, "-0.11"),
+            (0x3E00, 10, None, "1.5"),
+            (0x5060, 36, None, "z"),
+            (0x5060, 8, None, "43"),
+            (0x0001, 2, None, "0.000000000000000000000001"),
+            (0x0001, 10, None, "0.000000059604644775390625"),
+            (0x0001, 10, Some(3), "0.0000000596"),
+            (0x7BFF, 10, None, "65504"),
+            (0x7BFF, 10, Some(3), "65500"),
+            (0x7BFF, 10, Some(2), "66000"),
+            // ties to even
+            (0x4100, 10, Some(1), "2"),
+            (0x4300, 10, Some(1), "4"),
+            // 9.9609375, rounding carries into a new digit
+            (0x48FB, 10, Some(2), "10"),
+            // 0.5 doesn't terminate in base 3
+            (0x3800, 3, None, "0.11111111"),
+            (0x3800, 3, Some(3), "0.112"),
+            (0x7C00, 10, None, "inf"),
+            (0xFC00, 3, Some(1), "-inf"),
+            (0x7E00, 10, None, "nan(0x200)"),
+        ];
+        for &(bits, radix, max_digits, text) in cases {
+            let value = F16::from_bits(bits);
+            assert_eq!(
+                value.to_string_radix(radix, max_digits),
+                text,
+                "{:?} {} {:?}",
+                value,
+                radix,
+                max_digits
+            );
+        }
+        assert_eq!(
+            F32::from_bits(0x3DCC_CCCD).to_string_radix(10, None),
+            "0.100000001490116119384765625"
+        );
+        assert_eq!(
+            F32::from_bits(0x3DCC_CCCD).to_string_radix(10, Some(9)),
+            "0.100000001"
+        );
+        // enough digits to round-trip
+        assert_eq!(
+            F32::from_bits(0x3DCC_CCCD).to_string_radix(5, None),
+            F32::from_bits(0x3DCC_CCCD).to_string_radix(5, Some(12))
+        );
+    }
+
+    #[test]
+    fn test_x87_extended() {
+        let properties = FloatProperties::x87_extended();
+        assert_eq!(properties.width(), 80);
+        assert_eq!(properties.fraction_width(), 63);
+        assert!(!properties.has_implicit_leading_bit());
+        assert!(!properties.is_standard());
+        let cases: &[(u128, FloatClass)] = &[
+            (0x0000_0000_0000_0000_0000, FloatClass::PositiveZero),
+            (0x8000_0000_0000_0000_0000, FloatClass::NegativeZero),
+            (0x3FFF_8000_0000_0000_0000, FloatClass::PositiveNormal),
+            (0xBFFF_C000_0000_0000_0000, FloatClass::NegativeNormal),
+            (0x0000_0000_0000_0000_0001, FloatClass::PositiveSubnormal),
+            (0x0000_7FFF_FFFF_FFFF_FFFF, FloatClass::PositiveSubnormal),
+            (0x7FFE_FFFF_FFFF_FFFF_FFFF, FloatClass::PositiveNormal),
+            (0x7FFF_8000_0000_0000_0000, FloatClass::PositiveInfinity),
+            (0xFFFF_8000_0000_0000_0000, FloatClass::NegativeInfinity),
+            (0x7FFF_C000_0000_0000_0000, FloatClass::QuietNaN),
+            (0xFFFF_C000_0000_0000_0001, FloatClass::QuietNaN),
+            (0x7FFF_8000_0000_0000_0001, FloatClass::SignalingNaN),
+            (0x7FFF_A000_0000_0000_0000, FloatClass::SignalingNaN),
+            // pseudo-infinity
+            (0x7FFF_0000_0000_0000_0000, FloatClass::SignalingNaN),
+            (0xFFFF_0000_0000_0000_0000, FloatClass::SignalingNaN),
+            // pseudo-NaNs
+            (0x7FFF_4000_0000_0000_0000, FloatClass::SignalingNaN),
+            (0x7FFF_0000_0000_0000_0001, FloatClass::SignalingNaN),
+            // unnormals and pseudo-zeros
+            (0x3FFF_4000_0000_0000_0000, FloatClass::SignalingNaN),
+            (0x0001_0000_0000_0000_0001, FloatClass::SignalingNaN),
+            (0x3FFF_0000_0000_0000_0000, FloatClass::SignalingNaN),
+            (0xBFFF_0000_0000_0000_0000, FloatClass::SignalingNaN),
+            // pseudo-denormals
+            (0x0000_8000_0000_0000_0000, FloatClass::PositiveSubnormal),
+            (0x8000_FFFF_FFFF_FFFF_FFFF, FloatClass::NegativeSubnormal),
+        ];
+        for &(bits, class) in cases {
+            assert_eq!(F80::from_bits(bits).class(), class, "{:#X}", bits);
+        }
+        assert_eq!(*F80::positive_infinity().bits(), 0x7FFF_8000_0000_0000_0000);
+        assert_eq!(*F80::negative_infinity().bits(), 0xFFFF_8000_0000_0000_0000);
+        assert_eq!(*F80::quiet_nan().bits(), 0x7FFF_C000_0000_0000_0000);
+        assert_eq!(*F80::signaling_nan().bits(), 0x7FFF_8000_0000_0000_0001);
+        assert_eq!(*F80::default_nan().bits(), 0x7FFF_C000_0000_0000_0000);
+        let x86_default_nan = F80WithPlatformProperties::default_nan_with_traits(
+            F80WithPlatformPropertiesTraits(PlatformProperties::X86_SSE),
+        );
+        assert_eq!(*x86_default_nan.bits(), 0xFFFF_C000_0000_0000_0000);
+        assert_eq!(
+            *F80::signed_max_normal(Sign::Positive).bits(),
+            0x7FFE_FFFF_FFFF_FFFF_FFFF
+        );
+        assert_eq!(
+            *F80::from_bits(0x7FFF_8000_0000_0000_0001)
+                .to_quiet_nan()
+                .bits(),
+            0x7FFF_C000_0000_0000_0001
+        );
+        assert_eq!(
+            *F80::from_bits(0x3FFF_8000_0000_0000_0000)
+                .add(&F80::from_bits(0x3FFF_8000_0000_0000_0000), None, None)
+                .bits(),
+            0x4000_8000_0000_0000_0000
+        );
+        let one: F80 = F80::from_bits(0x3FFF_8000_0000_0000_0000);
+        assert_eq!(
+            *one.div(&F80::positive_zero(), None, None).bits(),
+            0x7FFF_8000_0000_0000_0000
+        );
+        let mut fp_state = FPState::default();
+        let result = one.add(
+            &F80::from_bits(0x7FFF_0000_0000_0000_0000),
+            None,
+            Some(&mut fp_state),
+        );
+        assert!(result.is_quiet_nan());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let result: F64 = F80::from_bits(0x7FFF_E000_0000_0000_0000).convert_to_float(None, None);
+        assert!(result.is_quiet_nan());
+        let result: F80 = F64::from_bits(0x7FF8_0000_0000_0001).convert_to_float(None, None);
+        assert!(result.is_quiet_nan());
+        let result: F80 = F64::from_bits(0x3FF0_0000_0000_0000).convert_to_float(None, None);
+        assert_eq!(*result.bits(), 0x3FFF_8000_0000_0000_0000);
+    }
+
+    #[test]
+    fn test_x87_legacy_encodings() {
+        let one = F80::from_bits(0x3FFF_8000_0000_0000_0000);
+        let min_normal = F80::from_bits(0x0001_8000_0000_0000_0000);
+        let pseudo_denormal = F80::from_bits(0x0000_8000_0000_0000_0000);
+        assert!(pseudo_denormal.is_pseudo_denormal());
+        assert!(!pseudo_denormal.is_unsupported_encoding());
+        assert!(!min_normal.is_pseudo_denormal());
+        assert!(!F80::from_bits(0x0000_4000_0000_0000_0000).is_pseudo_denormal());
+        assert!(!F32::from_bits(0x0000_0001).is_pseudo_denormal());
+        // pseudo-denormals have the same value as if the exponent field were one
+        assert_eq!(pseudo_denormal.to_ratio(), min_normal.to_ratio(),);
+        let mut fp_state = FPState::default();
+        let result = pseudo_denormal.mul(&one, None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), *min_normal.bits());
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        // rounding up the largest denormal gives the smallest normal,
+        // not a pseudo-denormal
+        let result = F80::from_bits(0x0000_7FFF_FFFF_FFFF_FFFF).add(
+            &F80::from_bits(0x0000_0000_0000_0000_0001),
+            None,
+            None,
+        );
+        assert_eq!(*result.bits(), *min_normal.bits());
+        let unsupported: &[u128] = &[
+            // pseudo-infinity
+            0x7FFF_0000_0000_0000_0000,
+            // pseudo-NaN
+            0x7FFF_4000_0000_0000_0000,
+            // unnormals
+            0x3FFF_4000_0000_0000_0000,
+            0x0001_0000_0000_0000_0001,
+            // pseudo-zero
+            0x3FFF_0000_0000_0000_0000,
+        ];
+        for &bits in unsupported {
+            let value = F80::from_bits(bits);
+            assert!(value.is_unsupported_encoding(), "{:#X}", bits);
+            assert!(value.is_signaling_nan(), "{:#X}", bits);
+            assert_eq!(value.to_ratio(), None, "{:#X}", bits);
+            assert_eq!(*value.to_quiet_nan().bits(), *F80::default_nan().bits());
+            let mut fp_state = FPState::default();
+            let result = one.add(&value, None, Some(&mut fp_state));
+            assert!(result.is_quiet_nan(), "{:#X}", bits);
+            assert!(!result.is_unsupported_encoding(), "{:#X}", bits);
+            assert_eq!(
+                fp_state.status_flags,
+                StatusFlags::empty().signal_invalid_operation(),
+                "{:#X}",
+                bits
+            );
+        }
+        for &bits in &[
+            0x0000_0000_0000_0000_0000u128,
+            0x0000_0000_0000_0000_0001,
+            0x3FFF_8000_0000_0000_0000,
+            0x7FFF_8000_0000_0000_0000,
+            0x7FFF_C000_0000_0000_0000,
+            0x7FFF_8000_0000_0000_0001,
+        ] {
+            assert!(
+                !F80::from_bits(bits).is_unsupported_encoding(),
+                "{:#X}",
+                bits
+            );
+        }
+        assert!(!F32::from_bits(0x3F80_0000).is_unsupported_encoding());
+        assert!(FloatProperties::x87_extended().unnormals_are_invalid());
+        assert!(!FloatProperties::STANDARD_64.unnormals_are_invalid());
+        // without `unnormals_are_invalid`, unnormals are classified by their value
+        let properties = FloatProperties::x87_extended().with_unnormals_are_invalid(false);
+        let cases: &[(u128, FloatClass, u128)] = &[
+            (
+                0x3FFF_4000_0000_0000_0000,
+                FloatClass::PositiveNormal,
+                0x3FFE_8000_0000_0000_0000,
+            ),
+            (
+                0x0002_0000_0000_0000_0001,
+                FloatClass::PositiveSubnormal,
+                0x0000_0000_0000_0000_0002,
+            ),
+            (
+                0x0001_0000_0000_0000_0001,
+                FloatClass::PositiveSubnormal,
+                0x0000_0000_0000_0000_0001,
+            ),
+            (
+                0x3FFF_0000_0000_0000_0000,
+                FloatClass::PositiveZero,
+                0x0000_0000_0000_0000_0000,
+            ),
+            (
+                0xBFFF_0000_0000_0000_0000,
+                FloatClass::NegativeZero,
+                0x8000_0000_0000_0000_0000,
+            ),
+        ];
+        for &(bits, class, canonical_bits) in cases {
+            let value = Float::from_bits_and_traits(BigUint::from(bits), properties);
+            let canonical = Float::from_bits_and_traits(BigUint::from(canonical_bits), properties);
+            assert_eq!(value.class(), class, "{:#X}", bits);
+            assert!(!value.is_unsupported_encoding(), "{:#X}", bits);
+            assert!(!value.is_canonical(), "{:#X}", bits);
+            assert_eq!(value.to_ratio(), canonical.to_ratio(), "{:#X}", bits);
+            let mut fp_state = FPState::default();
+            let result = value.canonicalize(Some(&mut fp_state));
+            assert_eq!(*result.bits(), *canonical.bits(), "{:#X}", bits);
+            assert_eq!(fp_state.status_flags, StatusFlags::empty(), "{:#X}", bits);
+        }
+        // pseudo-infinities and pseudo-NaNs are also classified by their
+        // value, like on the 8087 and 80287
+        let cases: &[(u128, FloatClass, u128)] = &[
+            (
+                0x7FFF_0000_0000_0000_0000,
+                FloatClass::PositiveInfinity,
+                0x7FFF_8000_0000_0000_0000,
+            ),
+            (
+                0xFFFF_0000_0000_0000_0000,
+                FloatClass::NegativeInfinity,
+                0xFFFF_8000_0000_0000_0000,
+            ),
+            (
+                0x7FFF_4000_0000_0000_0001,
+                FloatClass::QuietNaN,
+                0x7FFF_C000_0000_0000_0001,
+            ),
+        ];
+        for &(bits, class, canonical_bits) in cases {
+            let value = Float::from_bits_and_traits(BigUint::from(bits), properties);
+            assert_eq!(value.class(), class, "{:#X}", bits);
+            assert!(!value.is_unsupported_encoding(), "{:#X}", bits);
+            assert!(!value.is_canonical(), "{:#X}", bits);
+            let mut fp_state = FPState::default();
+            let result = value.canonicalize(Some(&mut fp_state));
+            assert_eq!(*result.bits(), BigUint::from(canonical_bits), "{:#X}", bits);
+            assert_eq!(fp_state.status_flags, StatusFlags::empty(), "{:#X}", bits);
+        }
+        let pseudo_snan =
+            Float::from_bits_and_traits(BigUint::from(0x7FFF_0000_0000_0000_0001u128), properties);
+        assert_eq!(pseudo_snan.class(), FloatClass::SignalingNaN);
+        assert!(!pseudo_snan.is_unsupported_encoding());
+        let mut fp_state = FPState::default();
+        let result = pseudo_snan.canonicalize(Some(&mut fp_state));
+        assert_eq!(
+            *result.bits(),
+            BigUint::from(0x7FFF_C000_0000_0000_0001u128)
+        );
+        assert!(fp_state.status_flags.invalid_operation());
+        let pseudo_infinity =
+            Float::from_bits_and_traits(BigUint::from(0x7FFF_0000_0000_0000_0000u128), properties);
+        let one =
+            Float::from_bits_and_traits(BigUint::from(0x3FFF_8000_0000_0000_0000u128), properties);
+        let sum = pseudo_infinity.add(&one, None, None);
+        assert!(sum.is_positive_infinity());
+    }
+
+    #[test]
+    fn test_is_canonical() {
+        let cases: &[(u128, bool)] = &[
+            (0x0000_0000_0000_0000_0000, true),
+            (0x8000_0000_0000_0000_0000, true),
+            (0x0000_0000_0000_0000_0001, true),
+            (0x3FFF_8000_0000_0000_0000, true),
+            (0x7FFF_8000_0000_0000_0000, true),
+            (0x7FFF_C000_0000_0000_0000, true),
+            (0x7FFF_8000_0000_0000_0001, true),
+            // pseudo-denormal
+            (0x0000_8000_0000_0000_0000, false),
+            // unnormal
+            (0x3FFF_4000_0000_0000_0000, false),
+            // pseudo-zero
+            (0x3FFF_0000_0000_0000_0000, false),
+            // pseudo-infinity
+            (0xFFFF_0000_0000_0000_0000, false),
+            // pseudo-NaN
+            (0x7FFF_4000_0000_0000_0000, false),
+        ];
+        for &(bits, expected) in cases {
+            assert_eq!(F80::from_bits(bits).is_canonical(), expected, "{:#X}", bits);
+        }
+        for bits in (0..=0xFFFFu16).step_by(0x7F) {
+            assert!(F16::from_bits(bits).is_canonical(), "{:#X}", bits);
+        }
+    }
+
+    #[test]
+    fn test_sum() {
+        let values = [
+            F16::from_bits(0x6BFF),
+            F16::from_bits(0x3C00),
+            F16::from_bits(0xEBFF),
+            F16::from_bits(0x1001),
+        ];
+        let rounding_modes = [
+            RoundingMode::TiesToEven,
+            RoundingMode::TowardZero,
+            RoundingMode::TowardNegative,
+            RoundingMode::TowardPositive,
+            RoundingMode::TiesToAway,
+            RoundingMode::TiesToOdd,
+        ];
+        for &rounding_mode in &rounding_modes {
+            let mut expected_fp_state = FPState::default();
+            let expected = F16::sum(&values, Some(rounding_mode), Some(&mut expected_fp_state));
+            let mut permutation = values;
+            // iterate through all permutations using Heap's algorithm
+            let mut counters = [0; 4];
+            let mut index = 0;
+            loop {
+                let mut fp_state = FPState::default();
+                let result = F16::sum(&permutation, Some(rounding_mode), Some(&mut fp_state));
+                assert!(result.bit_eq(&expected), "{:?}", permutation);
+                assert_eq!(fp_state, expected_fp_state, "{:?}", permutation);
+                while index < permutation.len() && counters[index] >= index {
+                    counters[index] = 0;
+                    index += 1;
+                }
+                if index >= permutation.len() {
+                    break;
+                }
+                if index % 2 == 0 {
+                    permutation.swap(0, index);
+                } else {
+                    permutation.swap(counters[index], index);
+                }
+                counters[index] += 1;
+                index = 0;
+            }
+        }
+        // adding one operation at a time depends on the order
+        let sequential = values[0]
+            .add(&values[2], None, None)
+            .add(&values[1], None, None)
+            .add(&values[3], None, None);
+        let reordered_sequential = values[0]
+            .add(&values[1], None, None)
+            .add(&values[2], None, None)
+            .add(&values[3], None, None);
+        assert!(!sequential.bit_eq(&reordered_sequential));
+        let mut fp_state = FPState::default();
+        let result = F16::sum(&values, None, Some(&mut fp_state));
+        assert!(result.bit_eq(&sequential));
+        assert_eq!(fp_state.status_flags, StatusFlags::empty().signal_inexact());
+        // special cases
+        assert_eq!(*F16::sum(&[], None, None).bits(), 0x0000);
+        let negative_zeros = [F16::negative_zero(), F16::negative_zero()];
+        assert_eq!(*F16::sum(&negative_zeros, None, None).bits(), 0x8000);
+        let cancelling = [F16::from_bits(0x3C00), F16::from_bits(0xBC00)];
+        assert_eq!(*F16::sum(&cancelling, None, None).bits(), 0x0000);
+        assert_eq!(
+            *F16::sum(&cancelling, Some(RoundingMode::TowardNegative), None).bits(),
+            0x8000
+        );
+        let infinities = [
+            F16::positive_infinity(),
+            F16::from_bits(0x3C00),
+            F16::positive_infinity(),
+        ];
+        assert_eq!(*F16::sum(&infinities, None, None).bits(), 0x7C00);
+        let mut fp_state = FPState::default();
+        let result = F16::sum(
+            &[F16::positive_infinity(), F16::negative_infinity()],
+            None,
+            Some(&mut fp_state),
+        );
+        assert!(result.bit_eq(&F16::default_nan()));
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let mut fp_state = FPState::default();
+        let result = F16::sum(
+            &[F16::signaling_nan(), F16::from_bits(0x7E01)],
+            None,
+            Some(&mut fp_state),
+        );
+        assert!(result.bit_eq(&F16::default_nan()));
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let dynamic_values: Vec<_> = values
+            .iter()
+            .map(|v| DynamicFloat::convert_from_float(v, None, None, FloatProperties::STANDARD_16))
+            .collect();
+        let result = DynamicFloat::sum(&dynamic_values, None, FloatProperties::STANDARD_16);
+        assert_eq!(*result.bits(), BigUint::from(*sequential.bits()));
+        assert!(result.fp_state.status_flags.inexact());
+    }
+
+    #[test]
+    fn test_mean() {
+        // summing first would overflow
+        let largest = [F16::from_bits(0x7BFF), F16::from_bits(0x7BFF)];
+        let naive =
+            largest[0]
+                .add(&largest[1], None, None)
+                .div(&F16::from_bits(0x4000), None, None);
+        assert!(naive.is_infinity());
+        let mut fp_state = FPState::default();
+        let result = F16::mean(&largest, None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x7BFF);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        // 4/3, rounded only once
+        let values = [
+            F16::from_bits(0x3C00),
+            F16::from_bits(0x3C00),
+            F16::from_bits(0x4000),
+        ];
+        let mut fp_state = FPState::default();
+        let result = F16::mean(&values, None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x3D55);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty().signal_inexact());
+        assert_eq!(
+            *F16::mean(&values, Some(RoundingMode::TowardPositive), None).bits(),
+            0x3D56
+        );
+        // special cases
+        let mut fp_state = FPState::default();
+        let result = F16::mean(&[], None, Some(&mut fp_state));
+        assert!(result.bit_eq(&F16::default_nan()));
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let mut fp_state = FPState::default();
+        let result = F16::mean(
+            &[F16::positive_infinity(), F16::negative_infinity()],
+            None,
+            Some(&mut fp_state),
+        );
+        assert!(result.bit_eq(&F16::default_nan()));
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let infinities = [F16::negative_infinity(), F16::from_bits(0x3C00)];
+        assert_eq!(*F16::mean(&infinities, None, None).bits(), 0xFC00);
+        let nans = [F16::from_bits(0x3C00), F16::quiet_nan()];
+        assert!(F16::mean(&nans, None, None).is_nan());
+        let negative_zeros = [F16::negative_zero(), F16::negative_zero()];
+        assert_eq!(*F16::mean(&negative_zeros, None, None).bits(), 0x8000);
+        let cancelling = [F16::from_bits(0x3C00), F16::from_bits(0xBC00)];
+        assert_eq!(*F16::mean(&cancelling, None, None).bits(), 0x0000);
+        assert_eq!(
+            *F16::mean(&cancelling, Some(RoundingMode::TowardNegative), None).bits(),
+            0x8000
+        );
+        let dynamic_values: Vec<_> = values
+            .iter()
+            .map(|v| DynamicFloat::convert_from_float(v, None, None, FloatProperties::STANDARD_16))
+            .collect();
+        let result = DynamicFloat::mean(&dynamic_values, None, FloatProperties::STANDARD_16);
+        assert_eq!(*result.bits(), BigUint::from(0x3D55u32));
+        assert!(result.fp_state.status_flags.inexact());
+        let result = DynamicFloat::mean(&[], None, FloatProperties::STANDARD_16);
+        assert!(result.is_nan());
+        assert!(result.fp_state.status_flags.invalid_operation());
+    }
+
+    #[test]
+    fn test_dot() {
+        let value = F16::from_bits;
+        // 65504 * 2 overflows, but the exact dot product doesn't
+        let lhs = [value(0x7BFF), value(0x7BFF), value(0x3C00)];
+        let rhs = [value(0x4000), value(0xC000), value(0x1000)];
+        let mut fp_state = FPState::default();
+        let naive = lhs[0].mul(&rhs[0], None, Some(&mut fp_state));
+        assert!(naive.is_infinity());
+        assert!(fp_state.status_flags.overflow());
+        let mut fp_state = FPState::default();
+        let result = F16::dot(&lhs, &rhs, None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x1000);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        // `1 * 1 + 2^-11 * 1` is rounded once
+        let lhs = [value(0x3C00), value(0x1000)];
+        let rhs = [value(0x3C00), value(0x3C00)];
+        let mut fp_state = FPState::default();
+        let result = F16::dot(&lhs, &rhs, None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x3C00);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty().signal_inexact());
+        // special cases
+        assert_eq!(*F16::dot(&[], &[], None, None).bits(), 0x0000);
+        let negative_zero_product = F16::dot(
+            &[F16::positive_zero(), F16::negative_zero()],
+            &[F16::negative_zero(), F16::positive_zero()],
+            None,
+            None,
+        );
+        assert_eq!(*negative_zero_product.bits(), 0x8000);
+        let mut fp_state = FPState::default();
+        let result = F16::dot(
+            &[F16::positive_infinity()],
+            &[F16::positive_zero()],
+            None,
+            Some(&mut fp_state),
+        );
+        assert!(result.bit_eq(&F16::default_nan()));
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let infinity = F16::dot(
+            &[F16::negative_infinity(), value(0x3C00)],
+            &[value(0xC000), value(0x3C00)],
+            None,
+            None,
+        );
+        assert_eq!(*infinity.bits(), 0x7C00);
+        let mut fp_state = FPState::default();
+        let result = F16::dot(
+            &[F16::positive_infinity(), F16::positive_infinity()],
+            &[value(0x3C00), value(0xBC00)],
+            None,
+            Some(&mut fp_state),
+        );
+        assert!(result.bit_eq(&F16::default_nan()));
+        assert!(fp_state.status_flags.invalid_operation());
+    }
+
+    #[test]
+    fn test_flags_match_across_orders() {
+        let value = F16::from_bits;
+        let values = |bits: &[u16]| -> Vec<F16> { bits.iter().copied().map(value).collect() };
+        assert!(F16::flags_match_across_orders(&[], None, None, None));
+        assert!(F16::flags_match_across_orders(
+            &values(&[0x3C00]),
+            None,
+            None,
+            None
+        ));
+        // adding one operation at a time, `1 + 2^-11` is rounded, but
+        // `2^-11 + 2^-11` isn't
+        let rounded = values(&[0x3C00, 0x1000, 0x1000]);
+        let mut left_fp_state = FPState::default();
+        rounded[0]
+            .add(&rounded[1], None, Some(&mut left_fp_state))
+            .add(&rounded[2], None, Some(&mut left_fp_state));
+        let mut right_fp_state = FPState::default();
+        rounded[0].add(
+            &rounded[1].add(&rounded[2], None, Some(&mut right_fp_state)),
+            None,
+            Some(&mut right_fp_state),
+        );
+        assert_ne!(left_fp_state.status_flags, right_fp_state.status_flags);
+        assert!(F16::flags_match_across_orders(&rounded, None, None, None));
+        // adding one operation at a time, the largest finite value added to
+        // itself overflows
+        let overflows = values(&[0x7BFF, 0x7BFF, 0xFBFF]);
+        assert!(F16::flags_match_across_orders(&overflows, None, None, None));
+        let fp_state = FPState {
+            rounding_mode: RoundingMode::TowardZero,
+            ..FPState::default()
+        };
+        assert!(F16::flags_match_across_orders(
+            &overflows,
+            None,
+            None,
+            Some(&fp_state)
+        ));
+        let weights = values(&[0x4000, 0x4000, 0x4000]);
+        assert!(F16::flags_match_across_orders(
+            &overflows,
+            Some(&weights),
+            None,
+            None
+        ));
+        let weights = values(&[0x3C00, 0x3555, 0x3555]);
+        assert!(F16::flags_match_across_orders(
+            &rounded,
+            Some(&weights),
+            Some(RoundingMode::TowardPositive),
+            None
+        ));
+    }
+
+    #[test]
+    fn test_is_exactly_representable_in() {
+        let f16 = F16Traits;
+        let f64 = F64Traits;
+        let cases: &[(u32, bool)] = &[
+            (0x3F80_0000, true),
+            (0xBF80_0000, true),
+            (0x8000_0000, true),
+            // 1/3, needs more precision
+            (0x3EAA_AAAB, false),
+            // 65504, largest F16
+            (0x477F_E000, true),
+            // 65536, overflows
+            (0x4780_0000, false),
+            // 2^-24, smallest F16 subnormal
+            (0x3380_0000, true),
+            // 2^-25, underflows
+            (0x3300_0000, false),
+            // 3 * 2^-24, subnormal with enough precision
+            (0x3440_0000, true),
+            (0x7F80_0000, true),
+            (0xFF80_0000, true),
+            (0x7FC0_0000, true),
+        ];
+        for &(bits, expected) in cases {
+            let value = F32::from_bits(bits);
+            assert_eq!(
+                value.is_exactly_representable_in(&f16),
+                expected,
+                "{:#X}",
+                bits
+            );
+            assert!(value.is_exactly_representable_in(&f64), "{:#X}", bits);
+        }
+        // the default platform's conversions always produce the default NaN
+        assert!(!F32::from_bits(0x7FE0_0000).is_exactly_representable_in(&f64));
+        assert!(!F32::from_bits(0xFFC0_0000).is_exactly_representable_in(&f64));
+        // converting signaling NaNs signals invalid operation
+        assert!(!F32::from_bits(0x7F80_0001).is_exactly_representable_in(&f16));
+        assert!(!F32::from_bits(0x7F80_0001).is_exactly_representable_in(&f64));
+        // NaN payloads are kept if they fit when conversions retain the most
+        // significant bits of the payload
+        let platform_properties = PlatformProperties::ARM;
+        let value = |bits| {
+            F32WithPlatformProperties::from_bits_and_traits(
+                bits,
+                F32WithPlatformPropertiesTraits(platform_properties),
+            )
+        };
+        let f16 = F16WithPlatformPropertiesTraits(platform_properties);
+        let f64 = F64WithPlatformPropertiesTraits(platform_properties);
+        assert!(value(0x7FE0_0000).is_exactly_representable_in(&f16));
+        assert!(!value(0x7FC0_0001).is_exactly_representable_in(&f16));
+        assert!(value(0x7FC0_0001).is_exactly_representable_in(&f64));
+        let no_inf_nan = FloatProperties::new(4, 3).with_has_inf_nan(false);
+        assert!(F32::from_bits(0x3F80_0000).is_exactly_representable_in(&no_inf_nan));
+        assert!(!F32::positive_infinity().is_exactly_representable_in(&no_inf_nan));
+        assert!(!F32::quiet_nan().is_exactly_representable_in(&no_inf_nan));
+        let unsigned = FloatProperties::new_with_extended_flags(
+            8,
+            23,
+            true,
+            false,
+            PlatformProperties::default(),
+        );
+        assert!(F32::from_bits(0x3F80_0000).is_exactly_representable_in(&unsigned));
+        assert!(!F32::from_bits(0xBF80_0000).is_exactly_representable_in(&unsigned));
+    }
+
+    #[test]
+    fn test_compare_with_bigint() {
+        let two_pow_53 = BigInt::one() << 53;
+        // 2^53 + 2, the next F64 after 2^53
+        let value = F64::from_bits(0x4340_0000_0000_0001);
+        assert_eq!(
+            value.compare_with_bigint(&two_pow_53, false, None),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            value.compare_with_bigint(&(&two_pow_53 + 1), false, None),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            value.compare_with_bigint(&(&two_pow_53 + 2), false, None),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            value.compare_with_bigint(&(&two_pow_53 + 3), false, None),
+            Some(Ordering::Less)
+        );
+        // converting 2^53 + 1 to F64 first would round it to 2^53
+        let two_pow_53_f64 = F64::from_bits(0x4340_0000_0000_0000);
+        let rounded: F64 = F64::from_bigint(&two_pow_53 + 1, None, None);
+        assert_eq!(
+            two_pow_53_f64.compare_quiet(&rounded, None),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            two_pow_53_f64.compare_with_bigint(&(&two_pow_53 + 1), false, None),
+            Some(Ordering::Less)
+        );
+        let half = F64::from_bits(0x3FE0_0000_0000_0000);
+        assert_eq!(
+            half.compare_with_bigint(&BigInt::zero(), false, None),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            half.neg().compare_with_bigint(&BigInt::zero(), false, None),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            F64::negative_zero().compare_with_bigint(&BigInt::zero(), false, None),
+            Some(Ordering::Equal)
+        );
+        let huge = BigInt::one() << 2000;
+        assert_eq!(
+            F64::positive_infinity().compare_with_bigint(&huge, false, None),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            F64::negative_infinity().compare_with_bigint(&-huge, false, None),
+            Some(Ordering::Less)
+        );
+        let mut fp_state = FPState::default();
+        assert_eq!(
+            F64::quiet_nan().compare_with_bigint(&BigInt::zero(), true, Some(&mut fp_state)),
+            None
+        );
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        assert_eq!(
+            F64::quiet_nan().compare_with_bigint(&BigInt::zero(), false, Some(&mut fp_state)),
+            None
+        );
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let mut fp_state = FPState::default();
+        assert_eq!(
+            F64::signaling_nan().compare_with_bigint(&BigInt::zero(), true, Some(&mut fp_state)),
+            None
+        );
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+    }
+
+    #[test]
+    fn test_compare_with_ratio() {
+        let third = Ratio::new(BigInt::one(), BigInt::from(3));
+        let mut fp_state = FPState::default();
+        let rounded = F16::from_real_algebraic_number(
+            &third.clone().into(),
+            Some(RoundingMode::TiesToEven),
+            Some(&mut fp_state),
+        );
+        assert!(fp_state.status_flags.inexact());
+        assert_eq!(
+            rounded.compare_with_ratio(&third, false, None),
+            Some(Ordering::Less)
+        );
+        let rounded_up = F16::from_real_algebraic_number(
+            &third.clone().into(),
+            Some(RoundingMode::TowardPositive),
+            None,
+        );
+        assert_eq!(
+            rounded_up.compare_with_ratio(&third, false, None),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            rounded_up.neg().compare_with_ratio(&-&third, false, None),
+            Some(Ordering::Less)
+        );
+        let three_quarters = Ratio::new(BigInt::from(3), BigInt::from(4));
+        assert_eq!(
+            F16::from_bits(0x3A00).compare_with_ratio(&three_quarters, false, None),
+            Some(Ordering::Equal)
+        );
+        let huge = Ratio::from(BigInt::one() << 100);
+        assert_eq!(
+            F16::positive_infinity().compare_with_ratio(&huge, false, None),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            F16::negative_infinity().compare_with_ratio(&huge, false, None),
+            Some(Ordering::Less)
+        );
+        let mut fp_state = FPState::default();
+        assert_eq!(
+            F16::signaling_nan().compare_with_ratio(&third, true, Some(&mut fp_state)),
+            None
+        );
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let value = DynamicFloat::from(rounded.convert_to_float_with_traits(
+            None,
+            None,
+            FloatProperties::STANDARD_16,
+        ));
+        assert_eq!(
+            value.compare_with_ratio(&third, false),
+            (Some(Ordering::Less), FPState::default())
+        );
+    }
+
+    #[test]
+    fn test_significand_bits() {
+        let cases: &[(u16, Option<usize>, Option<usize>)] = &[
+            (0x0000, Some(11), Some(0)),
+            (0x8000, Some(11), Some(0)),
+            // 1.0
+            (0x3C00, Some(10), Some(1)),
+            // 1.5
+            (0x3E00, Some(9), Some(2)),
+            // -3.0
+            (0xC200, Some(9), Some(2)),
+            // 1.0 + 2^-10
+            (0x3C01, Some(0), Some(11)),
+            // smallest subnormal
+            (0x0001, Some(0), Some(1)),
+            // largest subnormal
+            (0x03FF, Some(0), Some(10)),
+            (0x0200, Some(9), Some(1)),
+            (0x7BFF, Some(0), Some(11)),
+            (0x7C00, None, None),
+            (0xFE00, None, None),
+            (0x7C01, None, None),
+        ];
+        for &(bits, trailing_zeros, bit_length) in cases {
+            let value = F16::from_bits(bits);
+            assert_eq!(
+                value.mantissa_trailing_zeros(),
+                trailing_zeros,
+                "{:#X}",
+                bits
+            );
+            assert_eq!(value.significand_bit_length(), bit_length, "{:#X}", bits);
         }
+        let one = F80::from_bits(0x3FFF_8000_0000_0000_0000);
+        assert_eq!(one.mantissa_trailing_zeros(), Some(63));
+        assert_eq!(one.significand_bit_length(), Some(1));
+        // a value that needs more than F32's precision
+        let value = F64::from_bits(0x3FF0_0000_1000_0000);
+        assert_eq!(value.significand_bit_length(), Some(25));
+        assert!(!value.is_exactly_representable_in(&F32Traits));
+        let value = F64::from_bits(0x3FF0_0000_2000_0000);
+        assert_eq!(value.significand_bit_length(), Some(24));
+        assert!(value.is_exactly_representable_in(&F32Traits));
     }
-}
 
-impl<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits>> fmt::Debug for Float<FT> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let properties = self.properties();
-        let mut debug_struct = f.debug_struct("Float");
-        debug_struct.field("traits", &self.traits);
-        debug_struct.field(
-            "bits",
-            &format_args!(
-                "0x{value:0width$X}",
-                value = self.bits(),
-                width = (properties.width() + 3) / 4
+    #[test]
+    fn test_to_simplest_rational_within_ulp() {
+        let r = |n: i128, d: i128| Some(Ratio::new(BigInt::from(n), BigInt::from(d)));
+        let f16 = |bits| F16::from_bits(bits).to_simplest_rational_within_ulp();
+        assert_eq!(f16(0x2E66), r(1, 10));
+        assert_eq!(f16(0xAE66), r(-1, 10));
+        assert_eq!(f16(0x3555), r(1, 3));
+        assert_eq!(f16(0x3C00), r(1, 1));
+        assert_eq!(f16(0x0000), r(0, 1));
+        assert_eq!(f16(0x8000), r(0, 1));
+        // the interval is `(2^-25, 3 * 2^-25)`
+        assert_eq!(f16(0x0001), r(1, 11_184_811));
+        // the interval is `(65488, 65520)`, ties round to infinity
+        assert_eq!(f16(0x7BFF), r(65489, 1));
+        // the mantissa is even, so the interval is `[32760, 32784]`
+        assert_eq!(f16(0x7800), r(32760, 1));
+        assert_eq!(f16(0x7C00), None);
+        assert_eq!(f16(0x7E00), None);
+        assert_eq!(
+            F32::from_bits(0x3DCC_CCCD).to_simplest_rational_within_ulp(),
+            r(1, 10)
+        );
+        assert_eq!(
+            F64::from_bits(0x3FB9_9999_9999_999A).to_simplest_rational_within_ulp(),
+            r(1, 10)
+        );
+        // pi
+        assert_eq!(
+            F64::from_bits(0x4009_21FB_5444_2D18).to_simplest_rational_within_ulp(),
+            r(245_850_922, 78_256_779)
+        );
+    }
+
+    #[test]
+    fn test_to_double_double() {
+        let one = 0x3FFF_u128 << 112;
+        let cases: &[(u128, Option<(f64, f64)>)] = &[
+            (one, Some((1.0, 0.0))),
+            (one | 1 << 12, Some((1.0, 2.0f64.powi(-100)))),
+            // `1 + 2^-60 + 2^-112` needs exactly 53 bits after `hi`
+            (
+                one | 1 << 52 | 1,
+                Some((1.0, 2.0f64.powi(-60) + 2.0f64.powi(-112))),
             ),
+            // `hi` rounds up, so `lo` is negative
+            (
+                one | 1 << 59 | 1 << 12,
+                Some((1.0 + 2.0f64.powi(-52), 2.0f64.powi(-100) - 2.0f64.powi(-53))),
+            ),
+            // 1/3 needs 113 bits
+            (0x3FFD_5555_5555_5555_5555_5555_5555_5555, None),
+            (1 << 127, Some((-0.0, 0.0))),
+            (0x7FFF_u128 << 112, None),
+            (0x7FFE_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF, None),
+            // too small for `f64`
+            (1, None),
+        ];
+        for &(bits, expected) in cases {
+            let result = F128::from_bits(bits).to_double_double();
+            assert_eq!(
+                result.map(|(hi, lo)| (hi.to_bits(), lo.to_bits())),
+                expected.map(|(hi, lo)| (hi.to_bits(), lo.to_bits())),
+                "{:#X}",
+                bits
+            );
+        }
+        assert_eq!(F64::from(0.1f64).to_double_double(), Some((0.1f64, 0.0)));
+        assert_eq!(
+            F16::from_bits(0x3555).to_double_double(),
+            Some((1365.0 / 4096.0, 0.0))
         );
-        if properties.has_sign_bit() {
-            debug_struct.field("sign", &self.sign());
+    }
+
+    #[test]
+    fn test_to_ratio_parts() {
+        let cases: &[(u16, Sign, u32, u32, u32, i64)] = &[
+            (0x0000, Sign::Positive, 0, 1, 0, -24),
+            (0x8000, Sign::Negative, 0, 1, 0, -24),
+            (0x3C00, Sign::Positive, 1, 1, 0x400, -10),
+            (0xBE00, Sign::Negative, 3, 2, 0x600, -10),
+            (0x7BFF, Sign::Positive, 65504, 1, 0x7FF, 5),
+            (0x0001, Sign::Positive, 1, 1 << 24, 1, -24),
+            (0x0400, Sign::Positive, 1, 1 << 14, 0x400, -24),
+            (0x3555, Sign::Positive, 0x555, 1 << 12, 0x555, -12),
+        ];
+        for &(bits, sign, numerator, denominator, mantissa, exponent) in cases {
+            let value = F16::from_bits(bits);
+            assert_eq!(
+                value.to_ratio_parts(),
+                Some((sign, numerator.into(), denominator.into())),
+                "{:#X}",
+                bits
+            );
+            assert_eq!(
+                value.to_scientific_ratio(),
+                Some((sign, mantissa.into(), exponent)),
+                "{:#X}",
+                bits
+            );
+            let mut expected_ratio = Ratio::new(BigInt::from(numerator), BigInt::from(denominator));
+            if sign == Sign::Negative {
+                expected_ratio = -expected_ratio;
+            }
+            assert_eq!(value.to_ratio(), Some(expected_ratio), "{:#X}", bits);
         }
-        debug_struct.field(
-            "exponent_field",
-            &format_args!(
-                "0x{value:0width$X}",
-                value = self.exponent_field(),
-                width = (properties.exponent_width() + 3) / 4
-            ),
+        assert_eq!(F16::positive_infinity().to_ratio_parts(), None);
+        assert_eq!(F16::quiet_nan().to_scientific_ratio(), None);
+    }
+
+    #[test]
+    fn test_to_ulp_grid_integer() {
+        let cases: &[(u16, i64)] = &[
+            (0x0000, 0),
+            (0x8000, 0),
+            (0x0001, 1),
+            (0x8001, -1),
+            (0x03FF, 0x3FF),
+            (0x0400, 0x400),
+            (0x0401, 0x401),
+            (0x0800, 0x800),
+            (0x3C00, 1 << 24),
+            (0xBE00, -3 << 23),
+            (0x7BFF, 65504 << 24),
+        ];
+        for &(bits, expected) in cases {
+            assert_eq!(
+                F16::from_bits(bits).to_ulp_grid_integer(),
+                Some(expected.into()),
+                "{:#X}",
+                bits
+            );
+        }
+        assert_eq!(F16::positive_infinity().to_ulp_grid_integer(), None);
+        assert_eq!(F16::quiet_nan().to_ulp_grid_integer(), None);
+        // consecutive positive values are ordered and agree with `to_ratio`
+        let min_positive_subnormal = FloatProperties::STANDARD_16.min_positive_subnormal();
+        let mut previous = BigInt::zero();
+        for bits in 0x0001..0x7C00u16 {
+            let value = F16::from_bits(bits);
+            let grid_integer = value.to_ulp_grid_integer().unwrap();
+            assert!(grid_integer > previous, "{:#X}", bits);
+            assert_eq!(
+                Ratio::from_integer(grid_integer.clone()),
+                value.to_ratio().unwrap() / &min_positive_subnormal
+            );
+            previous = grid_integer;
+        }
+        // explicit leading bits, including a pseudo-denormal
+        let properties = FloatProperties::x87_extended();
+        for &bits in &[
+            0x0000_0000_0000_0000_0001u128,
+            0x0000_8000_0000_0000_0000,
+            0x0001_8000_0000_0000_0000,
+            0x3FFF_C000_0000_0000_0000,
+        ] {
+            let value = Float::from_bits_and_traits(BigUint::from(bits), properties);
+            assert_eq!(
+                Ratio::from_integer(value.to_ulp_grid_integer().unwrap()),
+                value.to_ratio().unwrap() / properties.min_positive_subnormal(),
+                "{:#X}",
+                bits
+            );
+        }
+    }
+
+    #[test]
+    fn test_abs_diff() {
+        let rounding_modes = [
+            RoundingMode::TiesToEven,
+            RoundingMode::TowardZero,
+            RoundingMode::TowardNegative,
+            RoundingMode::TowardPositive,
+            RoundingMode::TiesToAway,
+            RoundingMode::TiesToOdd,
+        ];
+        // 1 and 2^-12: the exact difference isn't representable
+        let one = F16::from_bits(0x3C00);
+        let small = F16::from_bits(0x0C00);
+        for &rounding_mode in &rounding_modes {
+            let expected = one.sub(&small, Some(rounding_mode), None);
+            let mut fp_state = FPState::default();
+            let result = small.abs_diff(&one, Some(rounding_mode), Some(&mut fp_state));
+            assert!(result.bit_eq(&expected), "{:?}", rounding_mode);
+            assert_eq!(fp_state.status_flags, StatusFlags::empty().signal_inexact());
+            assert!(one
+                .abs_diff(&small, Some(rounding_mode), None)
+                .bit_eq(&expected));
+            let result = one.abs_diff(&one, Some(rounding_mode), None);
+            assert_eq!(*result.bits(), 0x0000, "{:?}", rounding_mode);
+            let result =
+                F16::negative_zero().abs_diff(&F16::negative_zero(), Some(rounding_mode), None);
+            assert_eq!(*result.bits(), 0x0000, "{:?}", rounding_mode);
+        }
+        // rounding the negative difference toward negative would round away
+        // from zero
+        let naive = small
+            .sub(&one, Some(RoundingMode::TowardNegative), None)
+            .abs();
+        let result = small.abs_diff(&one, Some(RoundingMode::TowardNegative), None);
+        assert!(!result.bit_eq(&naive));
+        assert_eq!(
+            *F16::negative_infinity().abs_diff(&one, None, None).bits(),
+            0x7C00
         );
-        debug_struct.field(
-            "mantissa_field",
-            &format_args!(
-                "0x{value:0width$X}",
-                value = self.mantissa_field(),
-                width = (properties.mantissa_width() + 3) / 4
-            ),
+        let mut fp_state = FPState::default();
+        let result =
+            F16::positive_infinity().abs_diff(&F16::positive_infinity(), None, Some(&mut fp_state));
+        assert!(result.is_nan());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
         );
-        debug_struct.field("class", &self.class());
-        debug_struct.finish()
+        let nan = F16::from_bits(0x7E01);
+        assert_eq!(*one.abs_diff(&nan, None, None).bits(), 0x7E00);
+        let nan = F16WithPlatformProperties::from_bits_and_traits(
+            0x7E01,
+            F16WithPlatformPropertiesTraits(PlatformProperties::X86_SSE),
+        );
+        let one = F16WithPlatformProperties::from_bits_and_traits(
+            0xBC00,
+            F16WithPlatformPropertiesTraits(PlatformProperties::X86_SSE),
+        );
+        assert_eq!(*one.abs_diff(&nan, None, None).bits(), 0x7E01);
+        assert_eq!(*nan.abs_diff(&one, None, None).bits(), 0x7E01);
     }
-}
 
-/// standard 16-bit float
-pub type F16 = Float<F16Traits>;
-/// standard 32-bit float
-pub type F32 = Float<F32Traits>;
-/// standard 64-bit float
-pub type F64 = Float<F64Traits>;
-/// standard 128-bit float
-pub type F128 = Float<F128Traits>;
+    #[test]
+    fn test_minimum_maximum_clamp() {
+        let one = F16::from_bits(0x3C00);
+        let two = F16::from_bits(0x4000);
+        let negative_one = F16::from_bits(0xBC00);
+        let positive_zero = F16::positive_zero();
+        let negative_zero = F16::negative_zero();
+        let check = |a: &F16, b: &F16, expected_min: &F16, expected_max: &F16| {
+            for &(a, b) in &[(a, b), (b, a)] {
+                let mut fp_state = FPState::default();
+                assert!(a.minimum(b, Some(&mut fp_state)).bit_eq(expected_min));
+                assert!(a.maximum(b, Some(&mut fp_state)).bit_eq(expected_max));
+                assert_eq!(fp_state.status_flags, StatusFlags::empty());
+            }
+        };
+        check(&one, &two, &one, &two);
+        check(&negative_one, &one, &negative_one, &one);
+        check(
+            &negative_zero,
+            &positive_zero,
+            &negative_zero,
+            &positive_zero,
+        );
+        check(
+            &negative_zero,
+            &negative_zero,
+            &negative_zero,
+            &negative_zero,
+        );
+        check(
+            &F16::negative_infinity(),
+            &one,
+            &F16::negative_infinity(),
+            &one,
+        );
+        let mut fp_state = FPState::default();
+        assert!(one
+            .minimum(&F16::quiet_nan(), Some(&mut fp_state))
+            .is_quiet_nan());
+        assert!(F16::quiet_nan()
+            .maximum(&one, Some(&mut fp_state))
+            .is_quiet_nan());
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        assert!(one
+            .maximum(&F16::signaling_nan(), Some(&mut fp_state))
+            .is_quiet_nan());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
 
-/// standard 16-bit float
-pub type F16WithPlatformProperties = Float<F16WithPlatformPropertiesTraits>;
-/// standard 32-bit float
-pub type F32WithPlatformProperties = Float<F32WithPlatformPropertiesTraits>;
-/// standard 64-bit float
-pub type F64WithPlatformProperties = Float<F64WithPlatformPropertiesTraits>;
-/// standard 128-bit float
-pub type F128WithPlatformProperties = Float<F128WithPlatformPropertiesTraits>;
+        let cases: &[(u16, u16)] = &[
+            (0xC000, 0xBC00),
+            (0xBC00, 0xBC00),
+            (0x3800, 0x3800),
+            (0x4000, 0x3C00),
+            (0x7C00, 0x3C00),
+            (0xFC00, 0xBC00),
+            (0x8000, 0x8000),
+        ];
+        for &(bits, expected) in cases {
+            let mut fp_state = FPState::default();
+            let result = F16::from_bits(bits).clamp(&negative_one, &one, Some(&mut fp_state));
+            assert_eq!(*result.bits(), expected, "{:#X}", bits);
+            assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        }
+        // `-0.0` is less than `+0.0`
+        assert_eq!(
+            *negative_zero.clamp(&positive_zero, &one, None).bits(),
+            0x0000
+        );
+        assert_eq!(
+            *positive_zero
+                .clamp(&negative_one, &negative_zero, None)
+                .bits(),
+            0x8000
+        );
+        assert!(F16::quiet_nan().clamp(&negative_one, &one, None).is_nan());
+        assert!(one.clamp(&F16::quiet_nan(), &one, None).is_nan());
+        assert!(one.clamp(&negative_one, &F16::quiet_nan(), None).is_nan());
+    }
 
-/// `Float` with attached `FPState` and dynamically settable `FloatProperties`
-#[derive(Clone, Debug)]
-pub struct DynamicFloat {
-    /// floating-point state
-    pub fp_state: FPState,
-    /// floating-point value; also accessible through `*self`
-    pub value: Float<FloatProperties>,
-    _private: (),
-}
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "clamp called with lo > hi")]
+    fn test_clamp_lo_greater_than_hi() {
+        F16::from_bits(0x3C00).clamp(&F16::from_bits(0x4000), &F16::from_bits(0x3800), None);
+    }
 
-impl Deref for DynamicFloat {
-    type Target = Float<FloatProperties>;
-    /// returns `&self.value`
-    fn deref(&self) -> &Float<FloatProperties> {
-        &self.value
+    #[test]
+    fn test_lerp() {
+        let rounding_modes = [
+            RoundingMode::TiesToEven,
+            RoundingMode::TowardZero,
+            RoundingMode::TowardNegative,
+            RoundingMode::TowardPositive,
+            RoundingMode::TiesToAway,
+            RoundingMode::TiesToOdd,
+        ];
+        let endpoints: &[(u16, u16)] = &[
+            (0x3C00, 0x4000),
+            (0xC500, 0x5A3F),
+            (0x5A3F, 0xC500),
+            (0x0001, 0x03FF),
+            (0x8000, 0x8000),
+            (0x3555, 0x3555),
+        ];
+        let one = F16::from_bits(0x3C00);
+        for &rounding_mode in &rounding_modes {
+            for &(a, b) in endpoints {
+                let a = F16::from_bits(a);
+                let b = F16::from_bits(b);
+                let mut fp_state = FPState::default();
+                assert!(a
+                    .lerp(
+                        &b,
+                        &F16::positive_zero(),
+                        Some(rounding_mode),
+                        Some(&mut fp_state)
+                    )
+                    .bit_eq(&a));
+                assert!(a
+                    .lerp(&b, &one, Some(rounding_mode), Some(&mut fp_state))
+                    .bit_eq(&b));
+                assert_eq!(fp_state.status_flags, StatusFlags::empty());
+                let (lo, hi) = if a.compare_quiet(&b, None) == Some(Ordering::Greater) {
+                    (&b, &a)
+                } else {
+                    (&a, &b)
+                };
+                let mut previous = a;
+                // every `t` from 0 to 1 in steps of 2^-8
+                for t in 0..=0x100u16 {
+                    let t = F16::from_u16(t, None, None).scale_b(BigInt::from(-8), None, None);
+                    let result = a.lerp(&b, &t, Some(rounding_mode), None);
+                    assert_ne!(result.compare_quiet(lo, None), Some(Ordering::Less));
+                    assert_ne!(result.compare_quiet(hi, None), Some(Ordering::Greater));
+                    let expected = if a.compare_quiet(&b, None) == Some(Ordering::Greater) {
+                        Ordering::Greater
+                    } else {
+                        Ordering::Less
+                    };
+                    assert_ne!(
+                        previous.compare_quiet(&result, None),
+                        Some(expected.reverse())
+                    );
+                    previous = result;
+                }
+            }
+        }
+        // rounding only once is more accurate than computing the steps separately
+        let a = F16::from_bits(0x3C00);
+        let b = F16::from_bits(0x401A);
+        let t = F16::from_bits(0x3555);
+        let naive = a.add(&b.sub(&a, None, None).mul(&t, None, None), None, None);
+        let result = a.lerp(&b, &t, None, None);
+        assert_eq!(*result.bits(), 0x3D67);
+        assert!(!result.bit_eq(&naive));
+        // special values
+        let mut fp_state = FPState::default();
+        assert!(a
+            .lerp(&b, &F16::quiet_nan(), None, Some(&mut fp_state))
+            .is_quiet_nan());
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        assert_eq!(
+            *a.lerp(&b, &F16::positive_infinity(), None, Some(&mut fp_state))
+                .bits(),
+            0x7C00
+        );
+        assert_eq!(
+            *b.lerp(&a, &F16::positive_infinity(), None, Some(&mut fp_state))
+                .bits(),
+            0xFC00
+        );
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        assert!(a
+            .lerp(&a, &F16::positive_infinity(), None, Some(&mut fp_state))
+            .is_nan());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let mut fp_state = FPState::default();
+        let result =
+            F16::negative_infinity().lerp(&F16::positive_infinity(), &t, None, Some(&mut fp_state));
+        assert!(result.is_nan());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        let mut fp_state = FPState::default();
+        let max = F16::from_bits(0x7BFF);
+        let result = max
+            .neg()
+            .lerp(&max, &F16::from_bits(0x4000), None, Some(&mut fp_state));
+        assert!(result.is_positive_infinity());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_overflow_with_inexact()
+        );
     }
-}
 
-impl DerefMut for DynamicFloat {
-    /// returns `&mut self.value`
-    fn deref_mut(&mut self) -> &mut Float<FloatProperties> {
-        &mut self.value
+    #[test]
+    fn test_standard_widths() {
+        let widths: Vec<usize> = FloatProperties::standard_widths().take(7).collect();
+        assert_eq!(widths, [16, 32, 64, 128, 160, 192, 224]);
+        for width in FloatProperties::standard_widths().take(100) {
+            let properties = FloatProperties::standard(width).unwrap();
+            assert_eq!(properties.width(), width);
+            assert!(properties.is_standard());
+        }
+        for width in (0..2000).filter(|&width| FloatProperties::standard(width).is_some()) {
+            assert!(FloatProperties::standard_widths()
+                .take_while(|&v| v <= width)
+                .any(|v| v == width));
+        }
+        // (width, exponent_width, precision) from IEEE 754's table 3.5
+        let cases: &[(usize, usize, usize)] = &[
+            (16, 5, 11),
+            (32, 8, 24),
+            (64, 11, 53),
+            (128, 15, 113),
+            (160, 16, 144),
+            (256, 19, 237),
+            (512, 23, 489),
+            (1024, 27, 997),
+        ];
+        for &(width, exponent_width, precision) in cases {
+            let properties = FloatProperties::standard(width).unwrap();
+            assert_eq!(properties.exponent_width(), exponent_width, "{}", width);
+            assert_eq!(properties.fraction_width() + 1, precision, "{}", width);
+        }
     }
-}
 
-impl From<Float<FloatProperties>> for DynamicFloat {
-    fn from(value: Float<FloatProperties>) -> Self {
-        Self {
-            fp_state: FPState::default(),
-            value,
-            _private: (),
+    #[test]
+    fn test_standard_exponent_width_boundaries() {
+        // `exponent_width + 13` must be `round(4 * log2(width))`, which is `n`
+        // if and only if `2^(2 * n - 1) <= width^8 < 2^(2 * n + 1)`
+        let check = |width: usize| {
+            let properties = FloatProperties::standard(width).unwrap();
+            let n = properties.exponent_width() + 13;
+            let width_pow_8 = BigUint::from(width).pow(8u32);
+            assert!(BigUint::one() << (2 * n - 1) <= width_pow_8, "{}", width);
+            assert!(width_pow_8 < BigUint::one() << (2 * n + 1), "{}", width);
+        };
+        for log2_width in 8..=30 {
+            let width = 1usize << log2_width;
+            check(width - 32);
+            check(width);
+            check(width + 32);
+        }
+        // widths where `4 * log2(width)` is within `1e-7` of `n + 0.5`
+        let near_ties = [
+            146_365_472,
+            206_992_032,
+            292_730_944,
+            348_117_728,
+            413_984_064,
+            492_312_800,
+            585_461_888,
+            696_235_424,
+            696_235_456,
+            827_968_128,
+            984_625_568,
+            984_625_600,
+        ];
+        for &width in &near_ties {
+            check(width);
+            check(width - 32);
+            check(width + 32);
         }
     }
-}
 
-impl From<DynamicFloat> for Float<FloatProperties> {
-    fn from(value: DynamicFloat) -> Self {
-        value.value
+    #[test]
+    fn test_to_static_float() {
+        let value =
+            DynamicFloat::from_bits(0x3E00u32.into(), FloatProperties::STANDARD_16).unwrap();
+        assert_eq!(
+            value.to_static_float::<F16Traits>().map(|v| *v.bits()),
+            Some(0x3E00)
+        );
+        assert!(value.to_static_float::<F32Traits>().is_none());
+        let nan = DynamicFloat::from_bits(
+            0xFFF8_0000_0000_0001u64.into(),
+            FloatProperties::STANDARD_64,
+        )
+        .unwrap();
+        assert_eq!(
+            nan.to_static_float::<F64Traits>().map(|v| *v.bits()),
+            Some(0xFFF8_0000_0000_0001)
+        );
+        let value = DynamicFloat::convert_from_float(
+            &F128::from_bits(0x3FFF_8000 << 96),
+            None,
+            None,
+            FloatProperties::STANDARD_128,
+        );
+        assert_eq!(
+            value.to_static_float::<F128Traits>().map(|v| *v.bits()),
+            Some(0x3FFF_8000 << 96)
+        );
+        let value = DynamicFloat::new(FloatProperties::x87_extended());
+        assert!(value.to_static_float::<F80Traits>().is_some());
+        assert!(value.to_static_float::<F128Traits>().is_none());
+        // the platform properties must match too
+        let value = DynamicFloat::new(FloatProperties::standard_32_with_platform_properties(
+            PlatformProperties::X86_SSE,
+        ));
+        assert!(value.to_static_float::<F32Traits>().is_none());
     }
-}
 
-macro_rules! impl_dynamic_float_fn {
-    (
-        $(#[doc = $doc:literal])+
-        $fn_name:ident, $called_fn_name:ident,
-        (&self$(, $args:ident: $arg_types:ty)*)
-    ) => {
-        impl DynamicFloat {
-            $(#[doc = $doc])+
-            pub fn $fn_name(
-                &self,
-                $($args: $arg_types,)*
-            ) -> Self {
-                let mut fp_state = self.fp_state;
-                let value = self
-                    .value
-                    .$called_fn_name($($args,)* Some(&mut fp_state));
-                Self {
-                    fp_state,
-                    value,
-                    _private: (),
+    #[test]
+    fn test_native_conversions() {
+        for &value in &[0.0f32, -0.0, 1.5, -1e30, f32::INFINITY, f32::MIN_POSITIVE] {
+            assert_eq!(F32::from(value).into_bits(), value.to_bits());
+            assert_eq!(f32::from(F32::from(value)).to_bits(), value.to_bits());
+        }
+        for &value in &[0.0f64, -0.0, 1.5, -1e300, f64::NEG_INFINITY, 5e-324] {
+            assert_eq!(F64::from(value).into_bits(), value.to_bits());
+            assert_eq!(f64::from(F64::from(value)).to_bits(), value.to_bits());
+        }
+        // NaN payloads are preserved
+        let nan = f32::from_bits(0x7FA0_1234);
+        assert_eq!(F32::from(nan).into_bits(), 0x7FA0_1234);
+        assert_eq!(
+            f32::from(F32::from_bits(0xFFC0_0001)).to_bits(),
+            0xFFC0_0001
+        );
+        let nan = f64::from_bits(0x7FF0_0000_0000_0001);
+        assert_eq!(F64::from(nan).into_bits(), 0x7FF0_0000_0000_0001);
+
+        let convert = |value: f64| F32::try_from(F64::from(value)).map(f32::from);
+        assert_eq!(convert(1.5).map(f32::to_bits), Ok(1.5f32.to_bits()));
+        assert_eq!(
+            convert(0.1).map(f32::to_bits),
+            Ok((0.1f64 as f32).to_bits())
+        );
+        assert_eq!(
+            convert(f64::INFINITY).map(f32::to_bits),
+            Ok(f32::INFINITY.to_bits())
+        );
+        assert!(convert(f64::NAN).unwrap().is_nan());
+        assert!(convert(1e300).is_err());
+        assert!(convert(-1e300).is_err());
+        assert_eq!(convert(1e-300).map(f32::to_bits), Ok(0));
+    }
+
+    #[test]
+    fn test_const_exponent_values() {
+        const F32_BIAS: u64 = FloatProperties::STANDARD_32.exponent_bias_u64();
+        const F64_INF_NAN: u64 = FloatProperties::STANDARD_64.exponent_inf_nan_u64();
+        assert_eq!(F32_BIAS, 127);
+        assert_eq!(F64_INF_NAN, 0x7FF);
+        for &properties in &[
+            FloatProperties::STANDARD_16,
+            FloatProperties::STANDARD_32,
+            FloatProperties::STANDARD_64,
+            FloatProperties::STANDARD_128,
+            FloatProperties::x87_extended(),
+            FloatProperties::new(0, 4),
+            FloatProperties::new(64, 4),
+            FloatProperties::new(3, 4).with_has_inf_nan(false),
+        ] {
+            assert_eq!(
+                properties.exponent_bias_u64(),
+                properties.exponent_bias::<BigUint>().to_u64().unwrap(),
+                "{:?}",
+                properties
+            );
+            assert_eq!(
+                properties.exponent_inf_nan_u64(),
+                properties.exponent_inf_nan::<BigUint>().to_u64().unwrap(),
+                "{:?}",
+                properties
+            );
+            if properties.exponent_width() != 0 {
+                assert_eq!(
+                    properties.exponent_max_normal_u64(),
+                    properties
+                        .exponent_max_normal::<BigUint>()
+                        .to_u64()
+                        .unwrap(),
+                    "{:?}",
+                    properties
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_midpoint() {
+        let none = StatusFlags::empty();
+        let check = |a: F16, b: F16, rounding_mode, expected: F16, flags: StatusFlags| {
+            for &(a, b) in &[(a, b), (b, a)] {
+                let mut fp_state = FPState::default();
+                let result = a.midpoint(&b, Some(rounding_mode), Some(&mut fp_state));
+                assert_eq!(
+                    (*result.bits(), fp_state.status_flags),
+                    (*expected.bits(), flags),
+                    "{:?} {:?} {:?}",
+                    a,
+                    b,
+                    rounding_mode
+                );
+            }
+        };
+        let te = RoundingMode::TiesToEven;
+        check(
+            F16::from_bits(0x3C00),
+            F16::from_bits(0x4200),
+            te,
+            F16::from_bits(0x4000),
+            none,
+        );
+        // the naive expression overflows
+        check(
+            F16::from_bits(0x7BFF),
+            F16::from_bits(0x7BFE),
+            te,
+            F16::from_bits(0x7BFE),
+            none.signal_inexact(),
+        );
+        check(
+            F16::from_bits(0x7BFF),
+            F16::from_bits(0x7BFF),
+            te,
+            F16::from_bits(0x7BFF),
+            none,
+        );
+        // halving first rounds twice: `a / 2 + b / 2` rounds 2^-25 and
+        // 3 * 2^-25 up to 2^-24 and 2 * 2^-24, giving 3 * 2^-24, but the
+        // exact midpoint is 2 * 2^-24
+        let a = F16::from_bits(0x0001);
+        let b = F16::from_bits(0x0003);
+        let tp = RoundingMode::TowardPositive;
+        let half = F16::from_bits(0x3800);
+        let naive = a
+            .mul(&half, Some(tp), None)
+            .add(&b.mul(&half, Some(tp), None), Some(tp), None);
+        assert_eq!(*naive.bits(), 0x0003);
+        check(a, b, tp, F16::from_bits(0x0002), none);
+        // the smallest subnormal and zero
+        check(
+            F16::from_bits(0x0001),
+            F16::positive_zero(),
+            te,
+            F16::positive_zero(),
+            none.signal_underflow_with_inexact(),
+        );
+        check(
+            F16::from_bits(0x0001),
+            F16::from_bits(0x8001),
+            RoundingMode::TowardNegative,
+            F16::negative_zero(),
+            none,
+        );
+        check(
+            F16::from_bits(0x3C00),
+            F16::from_bits(0xBC00),
+            te,
+            F16::positive_zero(),
+            none,
+        );
+        for &rounding_mode in &[
+            RoundingMode::TiesToEven,
+            RoundingMode::TowardZero,
+            RoundingMode::TowardNegative,
+            RoundingMode::TowardPositive,
+            RoundingMode::TiesToAway,
+            RoundingMode::TiesToOdd,
+        ] {
+            for &zero in &[F16::positive_zero(), F16::negative_zero()] {
+                check(zero, zero, rounding_mode, zero, none);
+            }
+        }
+        check(
+            F16::positive_infinity(),
+            F16::from_bits(0x3C00),
+            te,
+            F16::positive_infinity(),
+            none,
+        );
+        check(
+            F16::positive_infinity(),
+            F16::negative_infinity(),
+            te,
+            F16::quiet_nan(),
+            none.signal_invalid_operation(),
+        );
+        check(
+            F16::signaling_nan(),
+            F16::positive_zero(),
+            te,
+            F16::quiet_nan(),
+            none.signal_invalid_operation(),
+        );
+    }
+
+    #[test]
+    fn test_unit_roundoff() {
+        let ratio = |denom_log2: usize| Ratio::new(BigInt::one(), BigInt::one() << denom_log2);
+        assert_eq!(FloatProperties::STANDARD_16.unit_roundoff(), ratio(11));
+        assert_eq!(
+            FloatProperties::STANDARD_32.unit_roundoff(),
+            Ratio::from_float(f32::EPSILON / 2.0).unwrap()
+        );
+        assert_eq!(
+            FloatProperties::STANDARD_64.unit_roundoff(),
+            Ratio::from_float(f64::EPSILON / 2.0).unwrap()
+        );
+        assert_eq!(FloatProperties::STANDARD_128.unit_roundoff(), ratio(113));
+        // the explicit leading bit isn't part of the fraction
+        assert_eq!(FloatProperties::x87_extended().unit_roundoff(), ratio(64));
+    }
+
+    #[test]
+    fn test_representable_range() {
+        let ratio = |numer: i64, denom_log2: usize| {
+            Ratio::new(BigInt::from(numer), BigInt::one() << denom_log2)
+        };
+        let properties = FloatProperties::STANDARD_16;
+        assert_eq!(properties.max_finite_value(), ratio(65504, 0));
+        assert_eq!(properties.min_positive_normal(), ratio(1, 14));
+        assert_eq!(properties.min_positive_subnormal(), ratio(1, 24));
+        let properties = FloatProperties::STANDARD_32;
+        assert_eq!(
+            properties.max_finite_value(),
+            Ratio::from_float(f32::MAX).unwrap()
+        );
+        assert_eq!(
+            properties.min_positive_normal(),
+            Ratio::from_float(f32::MIN_POSITIVE).unwrap()
+        );
+        assert_eq!(properties.min_positive_subnormal(), ratio(1, 149));
+        let properties = FloatProperties::STANDARD_64;
+        assert_eq!(
+            properties.max_finite_value(),
+            Ratio::from_float(f64::MAX).unwrap()
+        );
+        assert_eq!(
+            properties.min_positive_normal(),
+            Ratio::from_float(f64::MIN_POSITIVE).unwrap()
+        );
+        assert_eq!(properties.min_positive_subnormal(), ratio(1, 1074));
+        let properties = FloatProperties::x87_extended();
+        assert_eq!(properties.min_positive_normal(), ratio(1, 16382));
+        assert_eq!(properties.min_positive_subnormal(), ratio(1, 16445));
+        assert_eq!(
+            properties.max_finite_value(),
+            Ratio::from(((BigInt::one() << 64) - 1) << (16383usize - 63))
+        );
+        // without infinities and NaNs, the largest exponent is used for
+        // normal values
+        let properties = FloatProperties::new(5, 10).with_has_inf_nan(false);
+        assert_eq!(properties.max_finite_value(), ratio(131008, 0));
+    }
+
+    #[test]
+    fn test_exponent_ranges() {
+        let check = |properties: FloatProperties, normal: (i64, i64), all: (i64, i64)| {
+            assert_eq!(
+                properties.normal_exponent_range(),
+                normal,
+                "{:?}",
+                properties
+            );
+            assert_eq!(
+                properties.mathematical_exponent_range(),
+                all,
+                "{:?}",
+                properties
+            );
+        };
+        check(FloatProperties::STANDARD_16, (-14, 15), (-24, 15));
+        check(FloatProperties::STANDARD_32, (-126, 127), (-149, 127));
+        check(FloatProperties::STANDARD_64, (-1022, 1023), (-1074, 1023));
+        check(
+            FloatProperties::STANDARD_128,
+            (-16382, 16383),
+            (-16494, 16383),
+        );
+        check(
+            FloatProperties::x87_extended(),
+            (-16382, 16383),
+            (-16445, 16383),
+        );
+        // the largest exponent field is used for normal values
+        check(
+            FloatProperties::STANDARD_16.with_has_inf_nan(false),
+            (-14, 16),
+            (-24, 16),
+        );
+        // the exponents match the extreme values
+        for &properties in &[
+            FloatProperties::STANDARD_16,
+            FloatProperties::STANDARD_32,
+            FloatProperties::x87_extended(),
+        ] {
+            let (min, max) = properties.mathematical_exponent_range();
+            let power_of_two = |exponent: i64| {
+                if exponent < 0 {
+                    Ratio::new(BigInt::one(), BigInt::one() << (-exponent) as usize)
+                } else {
+                    Ratio::from_integer(BigInt::one() << exponent as usize)
                 }
-            }
+            };
+            assert_eq!(properties.min_positive_subnormal(), power_of_two(min));
+            assert!(properties.max_finite_value() >= power_of_two(max));
+            assert!(properties.max_finite_value() < power_of_two(max + 1));
+            let (min_normal, _) = properties.normal_exponent_range();
+            assert_eq!(properties.min_positive_normal(), power_of_two(min_normal));
         }
-    };
-    (
-        $(#[doc = $doc:literal])+
-        $fn_name:ident, $checked_fn_name:ident, $called_fn_name:ident,
-        (&self$(, $before_args:ident: $before_arg_types:ty)*),
-        ($($float_args:ident: &Self),*),
-        ($($after_args:ident: $after_arg_types:ty),*)
-    ) => {
-        impl DynamicFloat {
-            $(#[doc = $doc])+
-            pub fn $fn_name(
-                &self,
-                $($before_args: $before_args_type,)*
-                $($float_args: &Self,)*
-                $($after_args: $after_arg_types,)*
-            ) -> Self {
-                let mut fp_state = self.fp_state;
-                $(fp_state.merge_assign($float_args.fp_state);)*
-                let value = self
-                    .value
-                    .$called_fn_name($($before_args,)* $(&$float_args.value,)* $($after_args,)* Some(&mut fp_state));
-                Self {
-                    fp_state,
-                    value,
-                    _private: (),
-                }
-            }
-            $(#[doc = $doc])+
-            pub fn $checked_fn_name(
-                &self,
-                $($before_args: $before_args_type,)*
-                $($float_args: &Self,)*
-                $($after_args: $after_arg_types,)*
-            ) -> Result<Self, FPStateMergeFailed> {
-                let mut fp_state = self.fp_state;
-                $(fp_state.checked_merge_assign($float_args.fp_state)?;)*
-                let value = self
-                    .value
-                    .$called_fn_name($($before_args,)* $(&$float_args.value,)* $($after_args,)* Some(&mut fp_state));
-                Ok(Self {
-                    fp_state,
-                    value,
-                    _private: (),
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_dynamic_range_decades() {
+        let check_decades = |properties: FloatProperties, expected: f64| {
+            let decades = properties.dynamic_range_decades();
+            assert!(
+                (decades - expected).abs() < 1e-9,
+                "{:?}: {} != {}",
+                properties,
+                decades,
+                expected
+            );
+        };
+        check_decades(
+            FloatProperties::STANDARD_16,
+            (65504.0f64 * 16777216.0).log10(),
+        );
+        check_decades(
+            FloatProperties::STANDARD_32,
+            f64::from(f32::MAX).log10() + 149.0 * 2.0f64.log10(),
+        );
+        check_decades(
+            FloatProperties::STANDARD_64,
+            f64::MAX.log10() + 1074.0 * 2.0f64.log10(),
+        );
+        // `2^16384 * (1 - 2^-113)` and `2^-16494`
+        check_decades(
+            FloatProperties::STANDARD_128,
+            (16384.0 + 16494.0) * 2.0f64.log10(),
+        );
+    }
+
+    #[test]
+    fn test_can_represent_exact_product_and_sum() {
+        let f16 = FloatProperties::STANDARD_16;
+        let f32 = FloatProperties::STANDARD_32;
+        let f64 = FloatProperties::STANDARD_64;
+        let f128 = FloatProperties::STANDARD_128;
+        assert!(f64.can_represent_exact_product(f32, f32));
+        assert!(f32.can_represent_exact_product(f16, f16));
+        assert!(f128.can_represent_exact_product(f64, f64));
+        assert!(FloatProperties::x87_extended().can_represent_exact_product(f32, f32));
+        assert!(!f64.can_represent_exact_product(f64, f64));
+        assert!(!f32.can_represent_exact_product(f32, f16));
+        // `65504 + 2^-24` needs 40 bits
+        assert!(!f32.can_represent_exact_sum(f16, f16));
+        assert!(f64.can_represent_exact_sum(f16, f16));
+        assert!(!f64.can_represent_exact_sum(f32, f32));
+        assert!(!f64
+            .with_has_inf_nan(false)
+            .can_represent_exact_product(f16, f16));
+        let unsigned = FloatProperties::new_with_extended_flags(
+            11,
+            52,
+            true,
+            false,
+            PlatformProperties::default(),
+        );
+        assert!(!unsigned.can_represent_exact_sum(f16, f16));
+        // compare against checking every pair of values of tiny formats
+        let values = |properties: FloatProperties| -> Vec<Ratio<BigInt>> {
+            (0u32..1 << properties.width())
+                .filter_map(|bits| {
+                    Float::from_bits_and_traits(BigUint::from(bits), properties).to_ratio()
+                })
+                .collect()
+        };
+        let all_exact =
+            |wide: FloatProperties,
+             a: FloatProperties,
+             b: FloatProperties,
+             op: fn(&Ratio<BigInt>, &Ratio<BigInt>) -> Ratio<BigInt>| {
+                let b_values = values(b);
+                values(a).iter().all(|a| {
+                    b_values.iter().all(|b| {
+                        let mut fp_state = FPState::default();
+                        let result = Float::from_real_algebraic_number_with_traits(
+                            &op(a, b).into(),
+                            None,
+                            Some(&mut fp_state),
+                            wide,
+                        );
+                        result.to_ratio().as_ref() == Some(&op(a, b))
+                            && !fp_state.status_flags.overflow()
+                    })
                 })
+            };
+        let small = FloatProperties::new(3, 2);
+        let small_wide_mantissa = FloatProperties::new(3, 5);
+        for &wide in &[
+            FloatProperties::new(4, 4),
+            FloatProperties::new(4, 5),
+            FloatProperties::new(5, 4),
+            FloatProperties::new(5, 6),
+            FloatProperties::new(5, 9),
+        ] {
+            for &(a, b) in &[(small, small), (small, small_wide_mantissa)] {
+                assert_eq!(
+                    wide.can_represent_exact_product(a, b),
+                    all_exact(wide, a, b, |a, b| a * b),
+                    "{:?} * {:?} in {:?}",
+                    a,
+                    b,
+                    wide
+                );
+                assert_eq!(
+                    wide.can_represent_exact_sum(a, b),
+                    all_exact(wide, a, b, |a, b| a + b),
+                    "{:?} + {:?} in {:?}",
+                    a,
+                    b,
+                    wide
+                );
             }
         }
-    };
-}
+    }
 
-macro_rules! impl_dynamic_float_from_int_type {
-    ($from_int_with_traits:ident, $from_int:ident, $int:ident) => {
-        impl DynamicFloat {
-            /// convert from integer to floating-point.
-            /// `rounding_mode` only used for this conversion.
-            pub fn $from_int(
-                value: $int,
-                rounding_mode: Option<RoundingMode>,
-                fp_state: Option<FPState>,
-                properties: FloatProperties,
-            ) -> Self {
-                let mut fp_state = fp_state.unwrap_or_default();
-                let value = Float::$from_int_with_traits(
-                    value,
-                    rounding_mode,
-                    Some(&mut fp_state),
-                    properties,
+    #[test]
+    fn test_power_of_two() {
+        let none = StatusFlags::empty();
+        let check = |exponent: i64, rounding_mode, expected_bits: u16, flags: StatusFlags| {
+            let mut fp_state = FPState::default();
+            let result = F16::power_of_two(exponent, Some(rounding_mode), Some(&mut fp_state));
+            assert_eq!(
+                (*result.bits(), fp_state.status_flags),
+                (expected_bits, flags),
+                "{} {:?}",
+                exponent,
+                rounding_mode
+            );
+        };
+        let te = RoundingMode::TiesToEven;
+        check(0, te, 0x3C00, none);
+        check(-1, te, 0x3800, none);
+        check(15, te, 0x7800, none);
+        check(16, te, 0x7C00, none.signal_overflow_with_inexact());
+        check(
+            16,
+            RoundingMode::TowardZero,
+            0x7BFF,
+            none.signal_overflow_with_inexact(),
+        );
+        check(i64::MAX, te, 0x7C00, none.signal_overflow_with_inexact());
+        // the normal/subnormal boundary
+        check(-14, te, 0x0400, none);
+        check(-15, te, 0x0200, none);
+        check(-24, te, 0x0001, none);
+        check(-25, te, 0x0000, none.signal_underflow_with_inexact());
+        check(
+            -25,
+            RoundingMode::TiesToAway,
+            0x0001,
+            none.signal_underflow_with_inexact(),
+        );
+        check(-26, te, 0x0000, none.signal_underflow_with_inexact());
+        check(
+            i64::MIN,
+            RoundingMode::TowardPositive,
+            0x0001,
+            none.signal_underflow_with_inexact(),
+        );
+        let result = DynamicFloat::power_of_two(-149, None, None, FloatProperties::STANDARD_32);
+        assert_eq!(result.into_bits(), BigUint::one());
+    }
+
+    #[test]
+    fn test_non_computational_ops_dont_signal() {
+        fn check<FT: FloatTraits + Default>(value: Float<FT>) {
+            let negative = Float::<FT>::negative_zero();
+            let positive = Float::<FT>::positive_zero();
+            let signaling_nan = Float::<FT>::signaling_nan();
+            let bits = |v: &Float<FT>| -> BigInt { v.bits().clone().into() };
+            let sign_mask: BigInt = value.properties().sign_field_mask::<FT::Bits>().into();
+            let positive_bits = bits(&value) & !&sign_mask;
+            let negative_bits = &positive_bits | &sign_mask;
+            let results = [
+                (value.abs(), &positive_bits),
+                (value.abs().neg(), &negative_bits),
+                (value.neg().neg(), &bits(&value)),
+                (value.copy_sign(&negative), &negative_bits),
+                (value.copy_sign(&positive), &positive_bits),
+            ];
+            for (result, expected_bits) in results.iter() {
+                assert_eq!(bits(result), **expected_bits, "{:?}", value);
+                assert_eq!(
+                    result.class().is_signaling_nan(),
+                    value.class().is_signaling_nan()
                 );
-                Self {
-                    fp_state,
-                    value,
-                    _private: (),
-                }
             }
-        }
-    };
-}
+            // a signaling NaN sign source doesn't signal either
+            assert_eq!(bits(&positive.copy_sign(&signaling_nan)), bits(&positive));
+            assert_eq!(
+                bits(&negative.copy_sign(&signaling_nan.neg())),
+                bits(&negative)
+            );
 
-macro_rules! impl_dynamic_float_to_int_type {
-    ($name:ident, $int:ident) => {
-        impl DynamicFloat {
-            /// convert `self` to an integer, returning the result as a tuple of an integer or `None`, and `FPState`
-            pub fn $name(
-                &self,
-                exact: bool,
-                rounding_mode: Option<RoundingMode>,
-            ) -> (Option<$int>, FPState) {
-                let mut fp_state = self.fp_state;
-                let result = self.value.$name(exact, rounding_mode, Some(&mut fp_state));
-                (result, fp_state)
+            let properties = value.properties();
+            let value =
+                DynamicFloat::from_bits(bits(&value).to_biguint().unwrap(), properties).unwrap();
+            let results = [
+                value.abs(),
+                -&value,
+                value.copy_sign(&(-&value).value),
+                DynamicFloat::positive_zero(properties).copy_sign(&value.value),
+            ];
+            for result in results.iter() {
+                assert_eq!(result.fp_state, FPState::default(), "{:?}", value);
             }
         }
-    };
-}
+        check(F16::signaling_nan());
+        check(F16::from_bits(0xFC01));
+        check(F32::from_bits(0x7F80_1234));
+        check(F64::signaling_nan().neg());
+        check(F80::signaling_nan());
+        check(F80::from_bits(0x7FFF_0000_0000_0000_0001));
+        check(F16::quiet_nan());
+        check(F16::from_bits(0x3C00));
+    }
 
-impl DynamicFloat {
-    /// create from `properties`
-    pub fn new(properties: FloatProperties) -> Self {
-        Self {
-            fp_state: FPState::default(),
-            value: Float::from_bits_and_traits(BigUint::zero(), properties),
-            _private: (),
-        }
+    #[test]
+    fn test_quiet_bit() {
+        type MIPSF16 = Float<F16WithPlatformPropertiesTraits>;
+        let mips_traits = F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY);
+        let mips_quiet_nan = MIPSF16::quiet_nan_with_traits(mips_traits);
+        let mips_signaling_nan = MIPSF16::signaling_nan_with_traits(mips_traits);
+        assert_eq!(*mips_quiet_nan.bits(), 0x7DFF);
+        assert_eq!(*mips_signaling_nan.bits(), 0x7E00);
+        assert!(mips_quiet_nan.is_quiet_bit_set());
+        assert!(!mips_signaling_nan.is_quiet_bit_set());
+        assert!(F16::quiet_nan().is_quiet_bit_set());
+        assert!(!F16::signaling_nan().is_quiet_bit_set());
+        assert!(F80::quiet_nan().is_quiet_bit_set());
+        assert!(!F80::signaling_nan().is_quiet_bit_set());
+
+        let mut value = F16::from_bits(0x7C05);
+        value.set_quiet_bit(true);
+        assert_eq!(*value.bits(), 0x7E05);
+        assert_eq!(value.class(), FloatClass::QuietNaN);
+        value.set_quiet_bit(false);
+        assert_eq!(*value.bits(), 0x7C05);
+        assert_eq!(value.class(), FloatClass::SignalingNaN);
+
+        let mut value = MIPSF16::from_bits_and_traits(0xFC05, mips_traits);
+        assert_eq!(value.class(), FloatClass::QuietNaN);
+        value.set_quiet_bit(false);
+        assert_eq!(*value.bits(), 0xFE05);
+        assert_eq!(value.class(), FloatClass::SignalingNaN);
+        value.set_quiet_bit(true);
+        assert_eq!(*value.bits(), 0xFC05);
+        assert!(value.is_quiet_bit_set());
+
+        // x87 keeps the explicit integer bit
+        let mut value = F80::signaling_nan();
+        value.set_quiet_bit(true);
+        assert_eq!(*value.bits(), 0x7FFF_C000_0000_0000_0001);
+        assert_eq!(value.class(), FloatClass::QuietNaN);
     }
-    /// create from `bits` and `properties`
-    pub fn from_bits(bits: BigUint, properties: FloatProperties) -> Option<Self> {
-        if bits <= properties.overall_mask::<BigUint>() {
-            Some(Self {
-                fp_state: FPState::default(),
-                value: Float::from_bits_and_traits(bits, properties),
-                _private: (),
-            })
-        } else {
-            None
+
+    #[test]
+    fn test_canonicalize() {
+        let check = |value: F80, expected_bits: u128, invalid: bool| {
+            let mut fp_state = FPState::default();
+            let result = value.canonicalize(Some(&mut fp_state));
+            assert_eq!(
+                (*result.bits(), fp_state.status_flags),
+                (
+                    expected_bits,
+                    if invalid {
+                        StatusFlags::empty().signal_invalid_operation()
+                    } else {
+                        StatusFlags::empty()
+                    }
+                ),
+                "{:?}",
+                value
+            );
+            assert!(result.is_canonical());
+        };
+        // canonical values are unchanged
+        for &bits in &[
+            0x0000_0000_0000_0000_0000,
+            0x8000_0000_0000_0000_0001,
+            0x3FFF_8000_0000_0000_0000,
+            0xFFFF_8000_0000_0000_0000,
+            0x7FFF_C000_0000_0000_1234,
+        ] {
+            check(F80::from_bits(bits), bits, false);
         }
+        // pseudo-denormals become normal
+        let pseudo_denormal = F80::from_bits(0x8000_8000_0000_0000_0001);
+        check(pseudo_denormal, 0x8001_8000_0000_0000_0001, false);
+        assert_eq!(
+            pseudo_denormal.to_ratio(),
+            F80::from_bits(0x8001_8000_0000_0000_0001).to_ratio()
+        );
+        // signaling NaNs and unsupported encodings signal invalid
+        check(
+            F80::from_bits(0x7FFF_8000_0000_0000_1234),
+            0x7FFF_C000_0000_0000_1234,
+            true,
+        );
+        let default_nan = *F80::quiet_nan().bits();
+        // pseudo-NaN
+        check(
+            F80::from_bits(0x7FFF_4000_0000_0000_0000),
+            default_nan,
+            true,
+        );
+        // pseudo-infinity
+        check(
+            F80::from_bits(0xFFFF_0000_0000_0000_0000),
+            default_nan,
+            true,
+        );
+        // unnormal
+        check(
+            F80::from_bits(0x3FFF_4000_0000_0000_0000),
+            default_nan,
+            true,
+        );
+
+        let mut fp_state = FPState::default();
+        let result = F16::signaling_nan().canonicalize(Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x7E01);
+        assert!(fp_state.status_flags.invalid_operation());
+        let result = F16::from_bits(0x8001).canonicalize(None);
+        assert_eq!(*result.bits(), 0x8001);
     }
-    /// get the bits of `self`
-    pub fn into_bits(self) -> BigUint {
-        self.value.into_bits()
-    }
-    /// get the positive zero value
-    pub fn positive_zero(properties: FloatProperties) -> Self {
-        Float::positive_zero_with_traits(properties).into()
-    }
-    /// get the negative zero value
-    pub fn negative_zero(properties: FloatProperties) -> Self {
-        Float::negative_zero_with_traits(properties).into()
-    }
-    /// get the zero with sign `sign`
-    pub fn signed_zero(sign: Sign, properties: FloatProperties) -> Self {
-        Float::signed_zero_with_traits(sign, properties).into()
+
+    #[test]
+    fn test_save_restore_modes() {
+        let mut fp_state = FPState {
+            rounding_mode: RoundingMode::TowardZero,
+            status_flags: StatusFlags::empty().signal_inexact(),
+            tininess_detection_mode: TininessDetectionMode::BeforeRounding,
+            flush_to_zero_mode: FlushToZeroMode::Arithmetic,
+            ..FPState::default()
+        };
+        let modes = fp_state.save_modes();
+        assert_eq!(
+            modes,
+            FPModes {
+                rounding_mode: RoundingMode::TowardZero,
+                tininess_detection_mode: TininessDetectionMode::BeforeRounding,
+                flush_to_zero_mode: FlushToZeroMode::Arithmetic,
+                ..FPModes::default()
+            }
+        );
+        fp_state.restore_modes(FPModes {
+            exception_handling_mode: ExceptionHandlingMode::SignalExactUnderflow,
+            ..FPModes::default()
+        });
+        assert_eq!(
+            fp_state,
+            FPState {
+                status_flags: StatusFlags::empty().signal_inexact(),
+                exception_handling_mode: ExceptionHandlingMode::SignalExactUnderflow,
+                ..FPState::default()
+            }
+        );
+        fp_state.restore_modes(modes);
+        assert_eq!(fp_state.save_modes(), modes);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty().signal_inexact());
     }
-    /// get the positive infinity value
-    pub fn positive_infinity(properties: FloatProperties) -> Self {
-        Float::positive_infinity_with_traits(properties).into()
+
+    #[test]
+    fn test_tracing_fp_state() {
+        let mut records = Vec::new();
+        let mut tracer = TracingFPState::new(FPState::default(), |record: &OperationRecord| {
+            records.push(record.clone())
+        });
+        let one = F16::from_bits(0x3C00);
+        let three = F16::from_bits(0x4200);
+        let third = tracer.trace_div(&one, &three, None);
+        // inexact was already set, so it isn't new
+        tracer.trace_div(&one, &three, None);
+        let nan = F16::signaling_nan();
+        let ordering = tracer.trace_compare_quiet(&third, &nan);
+        assert_eq!(ordering, None);
+        // operations without a `trace_*` method
+        let rounded = tracer.trace(OpKind::RoundToIntegral, &[&three], |fp_state| {
+            three.round_to_integral(true, None, Some(fp_state))
+        });
+        assert_eq!(*rounded.bits(), 0x4200);
+        let fp_state = tracer.into_fp_state();
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty()
+                .signal_inexact()
+                .signal_invalid_operation()
+        );
+        let record = |op_kind, operands: &[u16], result: Option<u16>, new_flags| OperationRecord {
+            op_kind,
+            operands: operands.iter().map(|&v| BigUint::from(v)).collect(),
+            result: result.map(BigUint::from),
+            new_flags,
+        };
+        assert_eq!(
+            records,
+            [
+                record(
+                    OpKind::Div,
+                    &[0x3C00, 0x4200],
+                    Some(0x3555),
+                    StatusFlags::empty().signal_inexact()
+                ),
+                record(
+                    OpKind::Div,
+                    &[0x3C00, 0x4200],
+                    Some(0x3555),
+                    StatusFlags::empty()
+                ),
+                record(
+                    OpKind::Compare,
+                    &[0x3555, 0x7C01],
+                    None,
+                    StatusFlags::empty().signal_invalid_operation()
+                ),
+                record(
+                    OpKind::RoundToIntegral,
+                    &[0x4200],
+                    Some(0x4200),
+                    StatusFlags::empty()
+                ),
+            ]
+        );
+        let mut op_kinds = Vec::new();
+        let mut tracer = TracingFPState::new(FPState::default(), |record: &OperationRecord| {
+            op_kinds.push((record.op_kind, record.operands.len()))
+        });
+        tracer.trace_add(&one, &three, None);
+        tracer.trace_sub(&one, &three, None);
+        tracer.trace_mul(&one, &three, None);
+        tracer.trace_fused_mul_add(&one, &three, &one, None);
+        #[cfg(not(feature = "rational-only"))]
+        tracer.trace_sqrt(&three, None);
+        tracer.trace_compare_signaling(&one, &three);
+        tracer.into_fp_state();
+        let mut expected = vec![
+            (OpKind::Add, 2),
+            (OpKind::Sub, 2),
+            (OpKind::Mul, 2),
+            (OpKind::FusedMulAdd, 3),
+        ];
+        #[cfg(not(feature = "rational-only"))]
+        expected.push((OpKind::Sqrt, 1));
+        expected.push((OpKind::Compare, 2));
+        assert_eq!(op_kinds, expected);
     }
-    /// get the negative infinity value
-    pub fn negative_infinity(properties: FloatProperties) -> Self {
-        Float::negative_infinity_with_traits(properties).into()
+
+    #[test]
+    fn test_interchange_bits() {
+        for &bits in &[0x0000u16, 0x8000, 0x3C00, 0xBC01, 0x7C00, 0xFE00, 0x0001] {
+            let value = F16::from_bits(bits);
+            assert_eq!(value.to_interchange_bits(), BigUint::from(bits));
+            let parsed = F16::from_interchange_bits(&BigUint::from(bits)).unwrap();
+            assert_eq!(*parsed.bits(), bits);
+        }
+        assert!(F16::from_interchange_bits(&BigUint::from(0x1_0000u32)).is_none());
+        let value = F64::from_bits(0xC009_21FB_5444_2D18);
+        assert_eq!(
+            value.to_interchange_bits(),
+            BigUint::from(0xC009_21FB_5444_2D18u64)
+        );
+        // formats without a sign bit or an implicit leading bit
+        let properties = FloatProperties::new_with_extended_flags(
+            15,
+            64,
+            false,
+            true,
+            PlatformProperties::default(),
+        );
+        let bits = BigUint::from(0xC000u32) << 64 | BigUint::from(0xC000_0000_0000_0000u64);
+        let value = Float::from_interchange_bits_with_traits(&bits, properties).unwrap();
+        assert_eq!(value.sign(), Sign::Negative);
+        assert_eq!(value.exponent_field(), BigUint::from(0x4000u32));
+        assert_eq!(value.to_interchange_bits(), bits);
+        assert_eq!(*value.bits(), bits);
+        let properties = FloatProperties::new_with_extended_flags(
+            8,
+            8,
+            true,
+            false,
+            PlatformProperties::default(),
+        );
+        let value = Float::from_interchange_bits_with_traits(&BigUint::from(0xFFFFu32), properties)
+            .unwrap();
+        assert_eq!(value.sign(), Sign::Positive);
+        assert_eq!(value.to_interchange_bits(), BigUint::from(0xFFFFu32));
+        assert!(
+            Float::from_interchange_bits_with_traits(&BigUint::from(0x1_0000u32), properties)
+                .is_none()
+        );
+        let value = DynamicFloat::from_interchange_bits(
+            &BigUint::from(0x3C00u32),
+            FloatProperties::STANDARD_16,
+        )
+        .unwrap();
+        assert_eq!(*value.bits(), BigUint::from(0x3C00u32));
     }
-    /// get the infinity with sign `sign`
-    pub fn signed_infinity(sign: Sign, properties: FloatProperties) -> Self {
-        Float::signed_infinity_with_traits(sign, properties).into()
+
+    #[test]
+    fn test_nan_box() {
+        assert_eq!(F64::nan_box_payload_max(), 0x0007_FFFF_FFFF_FFFF);
+        for &payload in &[0, 1, 0x1234_5678_9ABC, 0x0007_FFFF_FFFF_FFFF] {
+            let boxed = F64::nan_box(payload).unwrap();
+            assert_eq!(boxed.class(), FloatClass::QuietNaN);
+            assert_eq!(*boxed.bits(), 0x7FF8_0000_0000_0000 | payload);
+            assert_eq!(boxed.nan_unbox(), Some(payload));
+            assert_eq!(boxed.neg().nan_unbox(), Some(payload));
+        }
+        // x86 propagates quiet NaN payloads through operations
+        type X86F64 = Float<F64WithPlatformPropertiesTraits>;
+        let traits = F64WithPlatformPropertiesTraits(PlatformProperties::X86_SSE);
+        let boxed = X86F64::nan_box_with_traits(0x1234, traits).unwrap();
+        let result = boxed.add(&X86F64::positive_zero_with_traits(traits), None, None);
+        assert_eq!(result.nan_unbox(), Some(0x1234));
+        assert!(F64::nan_box(0x0008_0000_0000_0000).is_none());
+        assert_eq!(F64::signaling_nan().nan_unbox(), None);
+        assert_eq!(F64::positive_infinity().nan_unbox(), None);
+        assert_eq!(F64::from_bits(0x3FF0_0000_0000_0000).nan_unbox(), None);
+        type MIPSF32 = Float<F32WithPlatformPropertiesTraits>;
+        let traits = F32WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY);
+        assert!(MIPSF32::nan_box_with_traits(0, traits).is_none());
+        let boxed = MIPSF32::nan_box_with_traits(0x12_3456, traits).unwrap();
+        assert_eq!(*boxed.bits(), 0x7F92_3456);
+        assert_eq!(boxed.class(), FloatClass::QuietNaN);
+        assert_eq!(boxed.nan_unbox(), Some(0x12_3456));
+        assert_eq!(MIPSF32::signaling_nan_with_traits(traits).nan_unbox(), None);
+        let properties = FloatProperties::new_with_extended_flags(
+            15,
+            64,
+            false,
+            true,
+            PlatformProperties::default(),
+        );
+        let boxed = Float::nan_box_with_traits(BigUint::from(5u32), properties).unwrap();
+        assert_eq!(boxed.class(), FloatClass::QuietNaN);
+        assert_eq!(boxed.nan_unbox(), Some(BigUint::from(5u32)));
+        assert!(Float::nan_box_with_traits(BigUint::from(1u64 << 62), properties).is_none());
     }
-    /// get the canonical quiet NaN, which is also just the canonical NaN
-    pub fn quiet_nan(properties: FloatProperties) -> Self {
-        Float::quiet_nan_with_traits(properties).into()
+
+    #[test]
+    fn test_signaling_nan_with_payload() {
+        let bits = |v: Option<F32>| v.map(|v| *v.bits());
+        assert_eq!(
+            bits(F32::signaling_nan_with_payload(Sign::Positive, 1)),
+            Some(0x7F80_0001)
+        );
+        assert_eq!(
+            bits(F32::signaling_nan_with_payload(Sign::Negative, 0x12345)),
+            Some(0xFF81_2345)
+        );
+        assert_eq!(
+            bits(F32::signaling_nan_with_payload(Sign::Positive, 0x3F_FFFF)),
+            Some(0x7FBF_FFFF)
+        );
+        // would be an infinity
+        assert_eq!(
+            bits(F32::signaling_nan_with_payload(Sign::Positive, 0)),
+            None
+        );
+        // would set the quiet bit
+        assert_eq!(
+            bits(F32::signaling_nan_with_payload(Sign::Positive, 0x40_0000)),
+            None
+        );
+        // the payload is kept when quieting
+        let value = F32::signaling_nan_with_payload(Sign::Negative, 0x12345).unwrap();
+        assert_eq!(*value.to_quiet_nan().bits(), 0xFFC1_2345);
+        let mut fp_state = FPState::default();
+        value.add(&F32::from_bits(0x3F80_0000), None, Some(&mut fp_state));
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+        // signaling NaNs have the quiet bit set in MIPS legacy
+        let properties =
+            FloatProperties::standard_32_with_platform_properties(PlatformProperties::MIPS_LEGACY);
+        let mips = |sign, payload: u32| {
+            Float::signaling_nan_with_payload_with_traits(sign, BigUint::from(payload), properties)
+                .map(Float::into_bits)
+        };
+        assert_eq!(
+            mips(Sign::Positive, 0),
+            Some(Float::signaling_nan_with_traits(properties).into_bits())
+        );
+        assert_eq!(mips(Sign::Positive, 0), Some(0x7FC0_0000u32.into()));
+        assert_eq!(mips(Sign::Negative, 5), Some(0xFFC0_0005u32.into()));
+        assert_eq!(mips(Sign::Positive, 0x40_0000), None);
+        // formats without NaNs or without a sign bit
+        let properties = FloatProperties::new(4, 3).with_has_inf_nan(false);
+        assert!(Float::signaling_nan_with_payload_with_traits(
+            Sign::Positive,
+            BigUint::one(),
+            properties
+        )
+        .is_none());
+        let properties = FloatProperties::new_with_extended_flags(
+            8,
+            23,
+            true,
+            false,
+            PlatformProperties::default(),
+        );
+        assert_eq!(
+            Float::signaling_nan_with_payload_with_traits(
+                Sign::Positive,
+                BigUint::one(),
+                properties
+            )
+            .map(Float::into_bits),
+            Some(0x7F80_0001u32.into())
+        );
+        assert!(Float::signaling_nan_with_payload_with_traits(
+            Sign::Negative,
+            BigUint::one(),
+            properties
+        )
+        .is_none());
+        // the explicit leading bit is set for x87
+        let value = Float::signaling_nan_with_payload_with_traits(
+            Sign::Positive,
+            BigUint::one(),
+            FloatProperties::x87_extended(),
+        )
+        .unwrap();
+        assert_eq!(
+            value.into_bits(),
+            BigUint::from(0x7FFF_8000_0000_0000_0001u128)
+        );
+        let value = DynamicFloat::signaling_nan_with_payload(
+            Sign::Negative,
+            0x100u32.into(),
+            FloatProperties::STANDARD_16,
+        )
+        .unwrap();
+        assert_eq!(value.bits(), &BigUint::from(0xFD00u32));
+        assert!(value.is_signaling_nan());
     }
-    /// get the canonical signaling NaN
-    pub fn signaling_nan(properties: FloatProperties) -> Self {
-        Float::signaling_nan_with_traits(properties).into()
+
+    #[test]
+    fn test_default_nan_mode() {
+        let arm = FloatProperties::standard_32_with_platform_properties(PlatformProperties::ARM);
+        let arm_dn = FloatProperties::standard_32_with_platform_properties(
+            PlatformProperties::ARM.with_default_nan_mode(),
+        );
+        assert_ne!(PlatformProperties::ARM, arm_dn.platform_properties());
+        let value =
+            |bits: u32, properties| Float::from_bits_and_traits(BigUint::from(bits), properties);
+        let add = |lhs: u32, rhs: u32, properties: FloatProperties| {
+            let mut fp_state = FPState::default();
+            let result =
+                value(lhs, properties).add(&value(rhs, properties), None, Some(&mut fp_state));
+            (result.into_bits().to_u32().unwrap(), fp_state.status_flags)
+        };
+        let none = StatusFlags::empty();
+        let invalid = StatusFlags::empty().signal_invalid_operation();
+        assert_eq!(add(0x7FC0_0001, 0x7FC0_0002, arm), (0x7FC0_0001, none));
+        assert_eq!(add(0x7FC0_0001, 0x7FC0_0002, arm_dn), (0x7FC0_0000, none));
+        assert_eq!(add(0x7FC0_0001, 0x7F80_0002, arm), (0x7FC0_0002, invalid));
+        assert_eq!(
+            add(0x7FC0_0001, 0x7F80_0002, arm_dn),
+            (0x7FC0_0000, invalid)
+        );
+        let mut fp_state = FPState::default();
+        let result = Float::<F64Traits>::convert_from_float(
+            &value(0xFFC0_0001, arm_dn),
+            None,
+            Some(&mut fp_state),
+        );
+        assert_eq!(*result.bits(), 0x7FF8_0000_0000_0000);
+        #[cfg(not(feature = "rational-only"))]
+        assert_eq!(
+            value(0xFFC0_0001, arm_dn).sqrt(None, None).into_bits(),
+            BigUint::from(0x7FC0_0000u32)
+        );
+        // `(inf * 0) + QNaN` still signals invalid operation
+        let power_dn = FloatProperties::standard_32_with_platform_properties(
+            PlatformProperties::POWER.with_default_nan_mode(),
+        );
+        let mut fp_state = FPState::default();
+        let result = value(0x7F80_0000, power_dn).fused_mul_add(
+            &value(0, power_dn),
+            &value(0x7FC0_0001, power_dn),
+            None,
+            Some(&mut fp_state),
+        );
+        assert_eq!(result.into_bits(), BigUint::from(0x7FC0_0000u32));
+        assert_eq!(fp_state.status_flags, invalid);
+        assert_eq!(
+            PlatformProperties::RISC_V.with_default_nan_mode(),
+            PlatformProperties::RISC_V
+        );
     }
-    /// convert `self` into a quiet NaN
-    pub fn into_quiet_nan(self) -> Self {
-        let Self {
-            fp_state,
-            value,
-            _private: _,
-        } = self;
-        Self {
-            fp_state,
-            value: value.into_quiet_nan(),
-            _private: (),
+
+    #[test]
+    fn test_flush_to_zero() {
+        // which operations flush their subnormal results in each mode:
+        //
+        // | operation                  | Disabled | Arithmetic | ArithmeticAndConversions |
+        // |----------------------------|----------|------------|--------------------------|
+        // | add, sub, mul, div, fma    | no       | yes        | yes                      |
+        // | scale_b                    | no       | yes        | yes                      |
+        // | convert_from_float         | no       | no         | yes                      |
+        // | from_real_algebraic_number | no       | no         | yes                      |
+        // | ieee754_remainder          | no       | no         | no                       |
+        // | sqrt of a subnormal        | no       | no         | no                       |
+        let modes = [
+            FlushToZeroMode::Disabled,
+            FlushToZeroMode::Arithmetic,
+            FlushToZeroMode::ArithmeticAndConversions,
+        ];
+        type Case = (&'static str, fn(&mut FPState) -> F16, u16, [bool; 3]);
+        let cases: &[Case] = &[
+            (
+                "add",
+                |fp_state| {
+                    F16::from_bits(0x0600).add(&F16::from_bits(0x8400), None, Some(fp_state))
+                },
+                0x0200,
+                [false, true, true],
+            ),
+            (
+                "add negative",
+                |fp_state| {
+                    F16::from_bits(0x8600).add(&F16::from_bits(0x0400), None, Some(fp_state))
+                },
+                0x8200,
+                [false, true, true],
+            ),
+            (
+                "sub",
+                |fp_state| {
+                    F16::from_bits(0x0600).sub(&F16::from_bits(0x0400), None, Some(fp_state))
+                },
+                0x0200,
+                [false, true, true],
+            ),
+            (
+                "mul",
+                |fp_state| {
+                    F16::from_bits(0x0400).mul(&F16::from_bits(0x3A00), None, Some(fp_state))
+                },
+                0x0300,
+                [false, true, true],
+            ),
+            (
+                "mul by power of two",
+                |fp_state| {
+                    F16::from_bits(0x0400).mul(&F16::from_bits(0x3800), None, Some(fp_state))
+                },
+                0x0200,
+                [false, true, true],
+            ),
+            (
+                "div",
+                |fp_state| {
+                    F16::from_bits(0x0600).div(&F16::from_bits(0x4200), None, Some(fp_state))
+                },
+                0x0200,
+                [false, true, true],
+            ),
+            (
+                "fused_mul_add",
+                |fp_state| {
+                    F16::from_bits(0x0400).fused_mul_add(
+                        &F16::from_bits(0x3A00),
+                        &F16::from_bits(0x8100),
+                        None,
+                        Some(fp_state),
+                    )
+                },
+                0x0200,
+                [false, true, true],
+            ),
+            (
+                "scale_b",
+                |fp_state| F16::from_bits(0x0400).scale_b(BigInt::from(-1), None, Some(fp_state)),
+                0x0200,
+                [false, true, true],
+            ),
+            (
+                "convert_from_float",
+                |fp_state| {
+                    F16::convert_from_float(&F32::from_bits(0x3800_0000), None, Some(fp_state))
+                },
+                0x0200,
+                [false, false, true],
+            ),
+            (
+                "from_real_algebraic_number",
+                |fp_state| {
+                    F16::from_real_algebraic_number(
+                        &RealAlgebraicNumber::from(Ratio::new(BigInt::one(), BigInt::one() << 15)),
+                        None,
+                        Some(fp_state),
+                    )
+                },
+                0x0200,
+                [false, false, true],
+            ),
+            (
+                "ieee754_remainder",
+                |fp_state| {
+                    F16::from_bits(0x0500).ieee754_remainder(
+                        &F16::from_bits(0x0400),
+                        None,
+                        Some(fp_state),
+                    )
+                },
+                0x0100,
+                [false, false, false],
+            ),
+            #[cfg(not(feature = "rational-only"))]
+            (
+                "sqrt",
+                |fp_state| F16::from_bits(0x0100).sqrt(None, Some(fp_state)),
+                0x1C00,
+                [false, false, false],
+            ),
+        ];
+        for &(name, op, unflushed, flushed) in cases {
+            for (&mode, &flushed) in modes.iter().zip(&flushed) {
+                let mut fp_state = FPState {
+                    flush_to_zero_mode: mode,
+                    ..FPState::default()
+                };
+                let result = op(&mut fp_state);
+                let (expected_bits, expected_flags) = if flushed {
+                    (
+                        unflushed & 0x8000,
+                        StatusFlags::empty().signal_underflow_with_inexact(),
+                    )
+                } else {
+                    (unflushed, StatusFlags::empty())
+                };
+                assert_eq!(
+                    (*result.bits(), fp_state.status_flags),
+                    (expected_bits, expected_flags),
+                    "op = {}, mode = {:?}",
+                    name,
+                    mode
+                );
+            }
         }
+        assert!(FlushToZeroMode::Arithmetic.flushes(OpKind::Hypot));
+        assert!(FlushToZeroMode::Arithmetic.flushes(OpKind::PowN));
+        assert!(!FlushToZeroMode::Arithmetic.flushes(OpKind::ConvertFromFloat));
+        assert!(!FlushToZeroMode::ArithmeticAndConversions.flushes(OpKind::RoundToIntegral));
+        assert!(!FlushToZeroMode::Disabled.flushes(OpKind::Add));
     }
-    /// convert `self` into a quiet NaN
-    pub fn to_quiet_nan(&self) -> Self {
-        let Self {
-            fp_state,
-            ref value,
-            _private: _,
-        } = *self;
-        Self {
-            fp_state,
-            value: value.to_quiet_nan(),
-            _private: (),
+
+    #[test]
+    fn test_arithmetic_status_flags() {
+        type Op = fn(&F16, &F16, Option<RoundingMode>, Option<&mut FPState>) -> F16;
+        let add: Op = F16::add;
+        let sub: Op = F16::sub;
+        let mul: Op = F16::mul;
+        let div: Op = F16::div;
+        let nan = *F16::default_nan().bits();
+        let none = StatusFlags::empty();
+        let inexact = StatusFlags::empty().signal_inexact();
+        let invalid = StatusFlags::empty().signal_invalid_operation();
+        let division_by_zero = StatusFlags::empty().signal_division_by_zero();
+        let overflow = StatusFlags::empty().signal_overflow().signal_inexact();
+        let underflow = StatusFlags::empty().signal_underflow().signal_inexact();
+        use RoundingMode::*;
+        #[rustfmt::skip]
+        let cases: &[(&str, Op, u16, u16, RoundingMode, u16, StatusFlags)] = &[
+            // exact results
+            ("add", add, 0x3C00, 0x4000, TiesToEven, 0x4200, none),
+            ("sub", sub, 0x3C00, 0x4000, TiesToEven, 0xBC00, none),
+            ("mul", mul, 0x3E00, 0x3E00, TiesToEven, 0x4080, none),
+            ("div", div, 0x4200, 0x4000, TiesToEven, 0x3E00, none),
+            ("sub", sub, 0x4200, 0x4200, TiesToEven, 0x0000, none),
+            ("sub", sub, 0x4200, 0x4200, TowardNegative, 0x8000, none),
+            ("add", add, 0x8000, 0x8000, TiesToEven, 0x8000, none),
+            ("mul", mul, 0x8000, 0x3C00, TiesToEven, 0x8000, none),
+            ("add", add, 0x7C00, 0x3C00, TiesToEven, 0x7C00, none),
+            ("mul", mul, 0xFC00, 0xBC00, TiesToEven, 0x7C00, none),
+            ("div", div, 0x3C00, 0x7C00, TiesToEven, 0x0000, none),
+            // inexact results
+            ("div", div, 0x3C00, 0x4200, TiesToEven, 0x3555, inexact),
+            ("div", div, 0x3C00, 0x4200, TowardPositive, 0x3556, inexact),
+            ("add", add, 0x3C00, 0x1000, TiesToEven, 0x3C00, inexact),
+            ("add", add, 0x3C00, 0x1000, TiesToAway, 0x3C01, inexact),
+            ("add", add, 0x3C00, 0x1000, TiesToOdd, 0x3C01, inexact),
+            ("sub", sub, 0x3C00, 0x0C00, TowardZero, 0x3BFF, inexact),
+            // invalid operations
+            ("add", add, 0x7C00, 0xFC00, TiesToEven, nan, invalid),
+            ("sub", sub, 0x7C00, 0x7C00, TiesToEven, nan, invalid),
+            ("mul", mul, 0x7C00, 0x0000, TiesToEven, nan, invalid),
+            ("mul", mul, 0x8000, 0xFC00, TiesToEven, nan, invalid),
+            ("div", div, 0x0000, 0x8000, TiesToEven, nan, invalid),
+            ("div", div, 0x7C00, 0xFC00, TiesToEven, nan, invalid),
+            ("add", add, 0x7C01, 0x3C00, TiesToEven, nan, invalid),
+            // division by zero
+            ("div", div, 0x3C00, 0x0000, TiesToEven, 0x7C00, division_by_zero),
+            ("div", div, 0xBC00, 0x0000, TiesToEven, 0xFC00, division_by_zero),
+            ("div", div, 0x3C00, 0x8000, TiesToEven, 0xFC00, division_by_zero),
+            ("div", div, 0x0001, 0x8000, TiesToEven, 0xFC00, division_by_zero),
+            // overflow
+            ("mul", mul, 0x7BFF, 0x4000, TiesToEven, 0x7C00, overflow),
+            ("mul", mul, 0x7BFF, 0x4000, TowardZero, 0x7BFF, overflow),
+            ("mul", mul, 0xFBFF, 0x4000, TowardPositive, 0xFBFF, overflow),
+            ("add", add, 0x7BFF, 0x7BFF, TiesToEven, 0x7C00, overflow),
+            ("sub", sub, 0xFBFF, 0x7BFF, TiesToEven, 0xFC00, overflow),
+            ("div", div, 0x7BFF, 0x3800, TowardNegative, 0x7BFF, overflow),
+            // gradual underflow
+            ("mul", mul, 0x0400, 0x3800, TiesToEven, 0x0200, none),
+            ("div", div, 0x0003, 0x4000, TiesToEven, 0x0002, underflow),
+            ("div", div, 0x0401, 0x4000, TiesToEven, 0x0200, underflow),
+            ("div", div, 0x0401, 0x4000, TowardPositive, 0x0201, underflow),
+            ("mul", mul, 0x0001, 0x3800, TiesToEven, 0x0000, underflow),
+            ("mul", mul, 0x0001, 0x3800, TowardPositive, 0x0001, underflow),
+            ("mul", mul, 0x8001, 0x3800, TiesToEven, 0x8000, underflow),
+            ("sub", sub, 0x0400, 0x0001, TiesToEven, 0x03FF, none),
+        ];
+        for &(name, op, lhs, rhs, rounding_mode, expected_bits, expected_status_flags) in cases {
+            let mut fp_state = FPState::default();
+            let result = op(
+                &F16::from_bits(lhs),
+                &F16::from_bits(rhs),
+                Some(rounding_mode),
+                Some(&mut fp_state),
+            );
+            assert_eq!(
+                (*result.bits(), fp_state.status_flags),
+                (expected_bits, expected_status_flags),
+                "{}(0x{:04X}, 0x{:04X}, {:?})",
+                name,
+                lhs,
+                rhs,
+                rounding_mode
+            );
         }
     }
-    /// get the largest finite value with sign `sign`
-    pub fn signed_max_normal(sign: Sign, properties: FloatProperties) -> Self {
-        Float::signed_max_normal_with_traits(sign, properties).into()
+
+    #[test]
+    fn test_exact_arithmetic() {
+        let one = F16::from_bits(0x3C00);
+        let two = F16::from_bits(0x4000);
+        let three = F16::from_bits(0x4200);
+        let bits = |v: Option<F16>| v.map(|v| *v.bits());
+        assert_eq!(bits(one.add_exact(&two, None)), Some(0x4200));
+        assert_eq!(bits(one.sub_exact(&two, None)), Some(0xBC00));
+        assert_eq!(bits(one.mul_exact(&two, None)), Some(0x4000));
+        assert_eq!(bits(one.div_exact(&two, None)), Some(0x3800));
+        // 1 + 2^-11 is halfway between two values
+        assert_eq!(bits(one.add_exact(&F16::from_bits(0x1000), None)), None);
+        assert_eq!(bits(one.div_exact(&three, None)), None);
+        assert_eq!(bits(three.mul_exact(&three, None)), Some(0x4880));
+        // overflow
+        assert_eq!(bits(F16::from_bits(0x7BFF).mul_exact(&two, None)), None);
+        assert_eq!(
+            bits(F16::from_bits(0x7BFF).add_exact(&F16::from_bits(0x7BFF), None)),
+            None
+        );
+        // exact subnormal results don't underflow
+        assert_eq!(
+            bits(F16::from_bits(0x0400).div_exact(&two, None)),
+            Some(0x0200)
+        );
+        assert_eq!(bits(F16::from_bits(0x0001).div_exact(&two, None)), None);
+        assert_eq!(
+            bits(one.sub_exact(&one, Some(RoundingMode::TowardNegative))),
+            Some(0x8000)
+        );
+        assert_eq!(
+            bits(one.div_exact(&F16::positive_zero(), None)),
+            Some(0x7C00)
+        );
+        assert!(F16::positive_infinity()
+            .sub_exact(&F16::positive_infinity(), None)
+            .unwrap()
+            .is_nan());
     }
-    /// get the subnormal value closest to zero with sign `sign`
-    pub fn signed_min_subnormal(sign: Sign, properties: FloatProperties) -> Self {
-        Float::signed_min_subnormal_with_traits(sign, properties).into()
+
+    #[test]
+    fn test_mul_exact_to() {
+        // F32 * F32 always fits in F64, including the extremes
+        let mut values = vec![
+            0x0000_0000u32,
+            0x8000_0000,
+            0x0000_0001,
+            0x8000_0001,
+            0x007F_FFFF,
+            0x0080_0000,
+            0x3F80_0000,
+            0x3F80_0001,
+            0x3FFF_FFFF,
+            0x7F7F_FFFF,
+            0xFF7F_FFFF,
+        ];
+        let mut state = 0x1234_5678u32;
+        for _ in 0..16 {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            if F32::from_bits(state).is_finite() {
+                values.push(state);
+            }
+        }
+        for &lhs in &values {
+            for &rhs in &values {
+                let lhs = F32::from_bits(lhs);
+                let rhs = F32::from_bits(rhs);
+                let product = lhs
+                    .mul_exact_to(&rhs, F64Traits)
+                    .unwrap_or_else(|| panic!("{:?} * {:?} isn't exact", lhs, rhs));
+                let mut fp_state = FPState::default();
+                let expected = F64::convert_from_float(&lhs, None, None).mul(
+                    &F64::convert_from_float(&rhs, None, None),
+                    None,
+                    Some(&mut fp_state),
+                );
+                assert_eq!(product.bits(), expected.bits(), "{:?} * {:?}", lhs, rhs);
+                assert_eq!(fp_state.status_flags, StatusFlags::empty());
+            }
+        }
+        let bits = |v: Option<F64>| v.map(|v| *v.bits());
+        // (1 + 2^-52)^2 needs 105 bits
+        let value = F64::from_bits(0x3FF0_0000_0000_0001);
+        assert_eq!(bits(value.mul_exact_to(&value, F64Traits)), None);
+        // overflows and underflows in the wide format
+        let max = F64::from_bits(0x7FEF_FFFF_FFFF_FFFF);
+        assert_eq!(bits(max.mul_exact_to(&max, F64Traits)), None);
+        let tiny = F64::from_bits(0x0000_0000_0000_0001);
+        assert_eq!(bits(tiny.mul_exact_to(&tiny, F64Traits)), None);
+        assert_eq!(
+            bits(F32::from_bits(0x8000_0000).mul_exact_to(&F32::from_bits(0x3F80_0000), F64Traits)),
+            Some(0x8000_0000_0000_0000)
+        );
+        assert_eq!(
+            bits(F32::positive_infinity().mul_exact_to(&F32::from_bits(0xBF80_0000), F64Traits)),
+            Some(0xFFF0_0000_0000_0000)
+        );
+        assert!(F32::positive_infinity()
+            .mul_exact_to(&F32::positive_zero(), F64Traits)
+            .unwrap()
+            .is_nan());
+        let no_inf_nan = FloatProperties::STANDARD_64.with_has_inf_nan(false);
+        assert!(F32::quiet_nan()
+            .mul_exact_to(&F32::positive_zero(), no_inf_nan)
+            .is_none());
     }
-    /// round from a `RealAlgebraicNumber` into a floating-point value.
-    /// `rounding_mode` only used for this conversion
-    pub fn from_real_algebraic_number(
-        value: &RealAlgebraicNumber,
-        rounding_mode: Option<RoundingMode>,
-        fp_state: Option<FPState>,
-        properties: FloatProperties,
-    ) -> Self {
-        let mut fp_state = fp_state.unwrap_or_default();
-        let value = Float::from_real_algebraic_number_with_traits(
-            value,
-            rounding_mode,
-            Some(&mut fp_state),
-            properties,
+
+    #[test]
+    fn test_apply() {
+        let one = F16::from_bits(0x3C00);
+        let two = F16::from_bits(0x4000);
+        let three = F16::from_bits(0x4200);
+        let apply = |value: &F16, op: FloatOp, operands: &[&F16]| {
+            let mut fp_state = FPState::default();
+            let result = value.apply(op, operands, None, Some(&mut fp_state));
+            (*result.bits(), fp_state.status_flags)
+        };
+        let empty = StatusFlags::empty();
+        assert_eq!(apply(&one, FloatOp::Add, &[&two]), (0x4200, empty));
+        assert_eq!(apply(&one, FloatOp::Sub, &[&two]), (0xBC00, empty));
+        assert_eq!(apply(&two, FloatOp::Mul, &[&three]), (0x4600, empty));
+        assert_eq!(
+            apply(&one, FloatOp::Div, &[&three]),
+            (0x3555, empty.signal_inexact())
         );
-        Self {
+        assert_eq!(
+            apply(&three, FloatOp::IEEE754Remainder, &[&two]),
+            (0xBC00, empty)
+        );
+        assert_eq!(
+            apply(&two, FloatOp::FusedMulAdd, &[&three, &one]),
+            (0x4700, empty)
+        );
+        // 2.5
+        let two_and_a_half = F16::from_bits(0x4100);
+        assert_eq!(
+            apply(
+                &two_and_a_half,
+                FloatOp::RoundToIntegral(RoundingMode::TiesToAway),
+                &[]
+            ),
+            (0x4200, empty)
+        );
+        assert_eq!(
+            apply(&two_and_a_half, FloatOp::RoundToIntegralExact, &[]),
+            (0x4000, empty.signal_inexact())
+        );
+        assert_eq!(apply(&one, FloatOp::NextUp, &[]), (0x3C01, empty));
+        assert_eq!(apply(&one, FloatOp::NextDown, &[]), (0x3BFF, empty));
+        assert_eq!(apply(&three, FloatOp::ScaleB(-1), &[]), (0x3E00, empty));
+        assert_eq!(apply(&three, FloatOp::PowN(2), &[]), (0x4880, empty));
+        #[cfg(not(feature = "rational-only"))]
+        {
+            let four = F16::from_bits(0x4400);
+            assert_eq!(apply(&four, FloatOp::Sqrt, &[]), (0x4000, empty));
+            assert_eq!(apply(&four, FloatOp::RSqrt, &[]), (0x3800, empty));
+            assert_eq!(
+                apply(&F16::from_bits(0x4800), FloatOp::Cbrt, &[]),
+                (0x4000, empty)
+            );
+            assert_eq!(apply(&three, FloatOp::Hypot, &[&four]), (0x4500, empty));
+        }
+        assert_eq!(apply(&one, FloatOp::Neg, &[]), (0xBC00, empty));
+        assert_eq!(apply(&one.neg(), FloatOp::Abs, &[]), (0x3C00, empty));
+        assert_eq!(
+            apply(&one, FloatOp::CopySign, &[&two.neg()]),
+            (0xBC00, empty)
+        );
+        // the rounding mode passed to `apply` is used
+        assert_eq!(
+            *one.apply(
+                FloatOp::Div,
+                &[&three],
+                Some(RoundingMode::TowardPositive),
+                None
+            )
+            .bits(),
+            0x3556
+        );
+        assert_eq!(FloatOp::FusedMulAdd.operand_count(), 3);
+        assert_eq!(FloatOp::NextDown.kind(), OpKind::NextUpOrDown);
+        assert_eq!(FloatOp::CopySign.kind(), OpKind::SignOp);
+        let one = DynamicFloat::from_bits(0x3C00u32.into(), FloatProperties::STANDARD_16).unwrap();
+        let three =
+            DynamicFloat::from_bits(0x4200u32.into(), FloatProperties::STANDARD_16).unwrap();
+        let result = one.apply(FloatOp::Div, &[&three], None);
+        assert_eq!(result.bits(), &BigUint::from(0x3555u32));
+        assert_eq!(result.fp_state.status_flags, empty.signal_inexact());
+    }
+
+    #[test]
+    fn test_dynamic_float_unary_fp_state() {
+        // the rounding mode and the already-set flags come from `self`
+        let fp_state = FPState {
+            rounding_mode: RoundingMode::TowardPositive,
+            status_flags: StatusFlags::empty().signal_division_by_zero(),
+            ..FPState::default()
+        };
+        let value = |bits: u32| DynamicFloat {
             fp_state,
-            value,
-            _private: (),
+            ..DynamicFloat::from_bits(bits.into(), FloatProperties::STANDARD_16).unwrap()
+        };
+        let check = |result: DynamicFloat, bits: u32, flags: StatusFlags| {
+            assert_eq!(result.bits(), &BigUint::from(bits));
+            assert_eq!(result.fp_state.rounding_mode, RoundingMode::TowardPositive);
+            assert_eq!(
+                result.fp_state.status_flags,
+                flags.merge(fp_state.status_flags)
+            );
+        };
+        let inexact = StatusFlags::empty().signal_inexact();
+        let three = value(0x4200);
+        #[cfg(not(feature = "rational-only"))]
+        {
+            check(three.sqrt(None), 0x3EEE, inexact);
+            check(three.rsqrt(None), 0x389F, inexact);
+            check(three.cbrt(None), 0x3DC5, inexact);
         }
+        check(three.next_up(), 0x4201, StatusFlags::empty());
+        check(
+            three.scale_b((-30).into(), None),
+            0x0001,
+            StatusFlags::empty().signal_underflow_with_inexact(),
+        );
+        let two_and_a_half = value(0x4100);
+        check(
+            two_and_a_half.round_to_integral(true, None),
+            0x4200,
+            inexact,
+        );
+        check(
+            two_and_a_half.round_to_integral(false, Some(RoundingMode::TowardZero)),
+            0x4000,
+            StatusFlags::empty(),
+        );
+        // non-floating-point results are returned with the updated `FPState`
+        let (result, result_fp_state) = two_and_a_half.round_to_integer(true, None);
+        assert_eq!(result, Some(3.into()));
+        assert_eq!(
+            result_fp_state.status_flags,
+            inexact.merge(fp_state.status_flags)
+        );
+        let (result, result_fp_state) = three.log_b();
+        assert_eq!(result, Some(1.into()));
+        assert_eq!(result_fp_state, fp_state);
     }
-}
-impl_dynamic_float_fn!(
-    /// add two `DynamicFloat` values, returning the result
-    add_with_rounding_mode,
-    checked_add_with_rounding_mode,
-    add,
-    (&self),
-    (rhs: &Self),
-    (rounding_mode: Option<RoundingMode>)
-);
-impl_dynamic_float_fn!(
-    /// subtract two `DynamicFloat` values, returning the result
-    sub_with_rounding_mode,
-    checked_sub_with_rounding_mode,
-    sub,
-    (&self),
-    (rhs: &Self),
-    (rounding_mode: Option<RoundingMode>)
-);
-impl_dynamic_float_fn!(
-    /// multiply two `DynamicFloat` values, returning the result
-    mul_with_rounding_mode,
-    checked_mul_with_rounding_mode,
-    mul,
-    (&self),
-    (rhs: &Self),
-    (rounding_mode: Option<RoundingMode>)
-);
-impl_dynamic_float_fn!(
-    /// divide two `DynamicFloat` values, returning the result
-    div_with_rounding_mode,
-    checked_div_with_rounding_mode,
-    div,
-    (&self),
-    (rhs: &Self),
-    (rounding_mode: Option<RoundingMode>)
-);
-impl_dynamic_float_fn!(
-    /// calculate the IEEE 754 remainder of two `DynamicFloat` values, returning the result
-    ieee754_remainder,
-    checked_ieee754_remainder,
-    ieee754_remainder,
-    (&self),
-    (rhs: &Self),
-    (rounding_mode: Option<RoundingMode>)
-);
-impl_dynamic_float_fn!(
-    /// calculate the result of `(self * factor) + term` rounding only once, returning the result
-    fused_mul_add,
-    checked_fused_mul_add,
-    fused_mul_add,
-    (&self),
-    (factor: &Self, term: &Self),
-    (rounding_mode: Option<RoundingMode>)
-);
 
-impl DynamicFloat {
-    /// round `self` to an integer, returning the result as a tuple of an integer or `None`, and `FPState`
-    pub fn round_to_integer(
-        &self,
-        exact: bool,
-        rounding_mode: Option<RoundingMode>,
-    ) -> (Option<BigInt>, FPState) {
-        let mut fp_state = self.fp_state;
-        let value = self
-            .value
-            .round_to_integer(exact, rounding_mode, Some(&mut fp_state));
-        (value, fp_state)
+    #[test]
+    fn test_dynamic_float_to_int_type() {
+        let f32_value =
+            |bits: u32| DynamicFloat::from_bits(bits.into(), FloatProperties::STANDARD_32).unwrap();
+        let invalid = StatusFlags::empty().signal_invalid_operation();
+        // 2^31 is out of range for `i32`, even though the `BigInt` result isn't
+        let two_to_31 = f32_value(0x4F00_0000);
+        let (result, fp_state) = two_to_31.to_i32(false, None);
+        assert_eq!(result, None);
+        assert_eq!(fp_state.status_flags, invalid);
+        let (result, fp_state) = two_to_31.to_u32(false, None);
+        assert_eq!(result, Some(1 << 31));
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        let (result, fp_state) = two_to_31.to_bigint(false, None);
+        assert_eq!(result, Some(BigInt::one() << 31));
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        assert_eq!(two_to_31.to_i64(false, None).0, Some(1 << 31));
+        assert_eq!(two_to_31.to_i16(false, None).0, None);
+        // the largest `f32` below 2^31
+        assert_eq!(
+            f32_value(0x4EFF_FFFF).to_i32(false, None).0,
+            Some(0x7FFF_FF80)
+        );
+        let minus_two_to_31 = f32_value(0xCF00_0000);
+        assert_eq!(minus_two_to_31.to_i32(false, None).0, Some(i32::MIN));
+        let (result, fp_state) = minus_two_to_31.to_u32(false, None);
+        assert_eq!(result, None);
+        assert_eq!(fp_state.status_flags, invalid);
+        // the range check is done after rounding; the flags from `self` are kept
+        let mut value = DynamicFloat::from_bits(
+            (f64::from(i32::MAX) + 0.5).to_bits().into(),
+            FloatProperties::STANDARD_64,
+        )
+        .unwrap();
+        value.fp_state.status_flags = StatusFlags::empty().signal_division_by_zero();
+        let (result, fp_state) = value.to_i32(true, Some(RoundingMode::TowardZero));
+        assert_eq!(result, Some(i32::MAX));
+        assert_eq!(
+            fp_state.status_flags,
+            value.fp_state.status_flags.signal_inexact()
+        );
+        let (result, fp_state) = value.to_i32(true, Some(RoundingMode::TiesToEven));
+        assert_eq!(result, None);
+        assert_eq!(
+            fp_state.status_flags,
+            value.fp_state.status_flags.merge(invalid)
+        );
+        let (result, fp_state) = f32_value(0x7FC0_0000).to_u8(false, None);
+        assert_eq!(result, None);
+        assert_eq!(fp_state.status_flags, invalid);
     }
-}
 
-impl_dynamic_float_fn!(
-    /// round `self` to an integer, returning the result as a `DynamicFloat`
-    round_to_integral,
-    round_to_integral,
-    (&self, exact: bool, rounding_mode: Option<RoundingMode>)
-);
-impl_dynamic_float_fn!(
-    /// compute the result of `next_up` or `next_down`
-    next_up_or_down,
-    next_up_or_down,
-    (&self, up_or_down: UpOrDown)
-);
-impl_dynamic_float_fn!(
-    /// compute the least floating-point number that compares greater than `self`
-    next_up,
-    next_up,
-    (&self)
-);
-impl_dynamic_float_fn!(
-    /// compute the greatest floating-point number that compares less than `self`
-    next_down,
-    next_down,
-    (&self)
-);
+    #[test]
+    fn test_unary_assign() {
+        type Op = fn(&F16, &mut FPState) -> F16;
+        type AssignOp = fn(&mut F16, &mut FPState);
+        let ops: &[(Op, AssignOp)] = &[
+            (|v, s| v.next_up(Some(s)), |v, s| v.next_up_assign(Some(s))),
+            (
+                |v, s| v.next_down(Some(s)),
+                |v, s| v.next_down_assign(Some(s)),
+            ),
+            (
+                |v, s| v.scale_b((-20).into(), None, Some(s)),
+                |v, s| v.scale_b_assign((-20).into(), None, Some(s)),
+            ),
+            (
+                |v, s| v.scale_b(3.into(), None, Some(s)),
+                |v, s| v.scale_b_assign(3.into(), None, Some(s)),
+            ),
+            (
+                |v, s| v.scale_b((-3).into(), Some(RoundingMode::TowardPositive), Some(s)),
+                |v, s| v.scale_b_assign((-3).into(), Some(RoundingMode::TowardPositive), Some(s)),
+            ),
+        ];
+        for (index, &(op, assign_op)) in ops.iter().enumerate() {
+            // `scale_b` is slow when it rounds, so skip most values
+            let step = if index < 2 { 1 } else { 61 };
+            for bits in (0..=0xFFFFu16).step_by(step) {
+                let value = F16::from_bits(bits);
+                let mut expected_fp_state = FPState::default();
+                let expected = op(&value, &mut expected_fp_state);
+                let mut fp_state = FPState::default();
+                let mut result = value;
+                assign_op(&mut result, &mut fp_state);
+                assert_eq!(result.bits(), expected.bits(), "{:#06X}", bits);
+                assert_eq!(fp_state, expected_fp_state, "{:#06X}", bits);
+            }
+        }
+
+        let value = DynamicFloat::from_bits(0x4100u32.into(), FloatProperties::STANDARD_16)
+            .unwrap()
+            .with_exact_value_tracking();
+        let mut result = value.clone();
+        result.next_up_assign();
+        assert_eq!(result.bits(), value.next_up().bits());
+        assert_eq!(result.exact_value, None);
+        result.next_down_assign();
+        assert_eq!(result.bits(), value.bits());
+        result.scale_b_assign(2.into(), None);
+        assert_eq!(result.bits(), &BigUint::from(0x4900u32));
+        result.scale_b_assign((-30).into(), Some(RoundingMode::TowardZero));
+        let expected = value
+            .scale_b(2.into(), None)
+            .scale_b((-30).into(), Some(RoundingMode::TowardZero));
+        assert_eq!(result.bits(), expected.bits());
+        assert_eq!(result.fp_state, expected.fp_state);
+        assert!(result.fp_state.status_flags.underflow());
+    }
 
-impl DynamicFloat {
-    /// get the floor of the log base 2 of the absolute value of `self`
-    pub fn log_b(&self) -> (Option<BigInt>, FPState) {
-        let mut fp_state = self.fp_state;
-        let value = self.value.log_b(Some(&mut fp_state));
-        (value, fp_state)
+    #[test]
+    #[should_panic(expected = "wrong number of operands")]
+    fn test_apply_wrong_operand_count() {
+        let one = F16::from_bits(0x3C00);
+        one.apply(FloatOp::Add, &[], None, None);
     }
-}
 
-impl_dynamic_float_fn!(
-    /// get `self * 2^scale`
-    scale_b,
-    scale_b,
-    (&self, scale: BigInt, rounding_mode: Option<RoundingMode>)
-);
-impl_dynamic_float_fn!(
-    /// get the square-root of `self`
-    sqrt,
-    sqrt,
-    (&self, rounding_mode: Option<RoundingMode>)
-);
+    #[test]
+    fn test_try_arithmetic() {
+        let f16_one =
+            Float::from_bits_and_traits(BigUint::from(0x3C00u32), FloatProperties::STANDARD_16);
+        let f16_two =
+            Float::from_bits_and_traits(BigUint::from(0x4000u32), FloatProperties::STANDARD_16);
+        let f32_one = Float::from_bits_and_traits(
+            BigUint::from(0x3F80_0000u32),
+            FloatProperties::STANDARD_32,
+        );
+        let bits = |v: Result<Float<FloatProperties>, _>| v.map(Float::into_bits);
+        assert_eq!(
+            bits(f16_one.try_add(&f16_two, None, None)),
+            Ok(BigUint::from(0x4200u32))
+        );
+        assert_eq!(
+            bits(f16_one.try_sub(&f16_two, None, None)),
+            Ok(BigUint::from(0xBC00u32))
+        );
+        assert_eq!(
+            bits(f16_one.try_mul(&f16_two, None, None)),
+            Ok(BigUint::from(0x4000u32))
+        );
+        assert_eq!(
+            bits(f16_one.try_div(&f16_two, None, None)),
+            Ok(BigUint::from(0x3800u32))
+        );
+        let mut fp_state = FPState::default();
+        assert_eq!(
+            bits(f16_one.try_add(&f32_one, None, Some(&mut fp_state))),
+            Err(FloatPropertiesIncompatible)
+        );
+        assert_eq!(
+            bits(f32_one.try_sub(&f16_one, None, Some(&mut fp_state))),
+            Err(FloatPropertiesIncompatible)
+        );
+        assert_eq!(
+            bits(f16_two.try_mul(&f32_one, None, Some(&mut fp_state))),
+            Err(FloatPropertiesIncompatible)
+        );
+        assert_eq!(
+            bits(f32_one.try_div(&f16_two, None, Some(&mut fp_state))),
+            Err(FloatPropertiesIncompatible)
+        );
+        assert_eq!(fp_state, FPState::default());
+    }
 
-impl DynamicFloat {
-    /// convert `src` to the floating-point format specified by `properties`.
-    pub fn convert_from_dynamic_float(
-        src: &Self,
-        rounding_mode: Option<RoundingMode>,
-        properties: FloatProperties,
-    ) -> Self {
-        let mut fp_state = src.fp_state;
-        let value = Float::convert_from_float_with_traits(
-            &src.value,
-            rounding_mode,
+    #[test]
+    fn test_fused_mul_add_mixed() {
+        // 0x3C01 is 1 + 2^-10, squaring gives 1 + 2^-9 + 2^-20 which is exact in F32
+        let a = F16::from_bits(0x3C01);
+        let term = F16::from_bits(0xBC00); // -1
+        let mut fp_state = FPState::default();
+        let result: F32 = a.fused_mul_add_mixed(
+            &a,
+            &term,
+            F32Traits,
+            Some(RoundingMode::TiesToEven),
             Some(&mut fp_state),
-            properties,
         );
-        Self {
-            fp_state,
-            value,
-            _private: (),
+        assert_eq!(result.bits(), &0x3B00_1000);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+
+        // result is rounded once into F32
+        let third = F16::from_bits(0x3555); // approximately 1/3
+        let result: F32 = third.fused_mul_add_mixed(
+            &F16::from_bits(0x4200), // 3
+            &term,
+            F32Traits,
+            Some(RoundingMode::TiesToEven),
+            Some(&mut fp_state),
+        );
+        assert_eq!(result.bits(), &0xB980_0000); // -2^-12
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+
+        // same formats match fused_mul_add
+        let x = F16::from_bits(0x4500);
+        let y = F16::from_bits(0x3E66);
+        let z = F16::from_bits(0xC0CD);
+        let mut fp_state1 = FPState::default();
+        let mut fp_state2 = FPState::default();
+        let expected = x.fused_mul_add(&y, &z, None, Some(&mut fp_state1));
+        let result: F16 = x.fused_mul_add_mixed(&y, &z, F16Traits, None, Some(&mut fp_state2));
+        assert_eq!(result.bits(), expected.bits());
+        assert_eq!(fp_state1.status_flags, fp_state2.status_flags);
+
+        // infinity times zero is invalid
+        let mut fp_state = FPState::default();
+        let result: F32 = F16::positive_infinity().fused_mul_add_mixed(
+            &F16::positive_zero(),
+            &F32::from_bits(0x3F80_0000),
+            F32Traits,
+            None,
+            Some(&mut fp_state),
+        );
+        assert_eq!(result.bits(), F32::quiet_nan().bits());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+
+        // signaling NaN signals invalid and the quieted NaN is converted
+        let mut fp_state = FPState::default();
+        let result: F32 = F16::from_bits(0x3C00).fused_mul_add_mixed(
+            &F16::signaling_nan(),
+            &F16::from_bits(0x3C00),
+            F32Traits,
+            None,
+            Some(&mut fp_state),
+        );
+        assert!(result.is_nan());
+        assert!(!result.is_signaling_nan());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
+
+        // exact zero sum uses the rounding mode's zero sign
+        let result: F32 = F16::from_bits(0x3C00).fused_mul_add_mixed(
+            &F16::from_bits(0x3C00),
+            &F16::from_bits(0xBC00),
+            F32Traits,
+            Some(RoundingMode::TowardNegative),
+            None,
+        );
+        assert_eq!(result.bits(), &0x8000_0000);
+    }
+
+    #[test]
+    fn test_next_integer_up_or_down() {
+        fn case(bits: u16, expected_up: u16, expected_down: u16) {
+            let value = F16::from_bits(bits);
+            let mut fp_state = FPState::default();
+            assert_eq!(
+                *value.next_integer_up(Some(&mut fp_state)).bits(),
+                expected_up,
+                "next_integer_up({:#06X})",
+                bits
+            );
+            assert_eq!(
+                *value.next_integer_down(Some(&mut fp_state)).bits(),
+                expected_down,
+                "next_integer_down({:#06X})",
+                bits
+            );
+            assert_eq!(fp_state.status_flags, StatusFlags::empty());
         }
+        case(0x0000, 0x3C00, 0xBC00); // 0 -> 1, -1
+        case(0x8000, 0x3C00, 0xBC00); // -0 -> 1, -1
+        case(0x3C00, 0x4000, 0x0000); // 1 -> 2, +0
+        case(0xBC00, 0x8000, 0xC000); // -1 -> -0, -2
+        case(0x3800, 0x3C00, 0x0000); // 0.5 -> 1, +0
+        case(0xB800, 0x8000, 0xBC00); // -0.5 -> -0, -1
+        case(0x0001, 0x3C00, 0x0000); // min subnormal -> 1, +0
+        case(0x4100, 0x4200, 0x4000); // 2.5 -> 3, 2
+        case(0x6800, 0x6801, 0x67FF); // 2048: spacing is 2 above and 1 below
+        case(0x6801, 0x6802, 0x6800); // 2050 -> 2052, 2048
+        case(0x7800, 0x7801, 0x77FF); // 32768: spacing is more than 1
+        case(0x7BFF, 0x7C00, 0x7BFE); // max normal -> infinity
+        case(0xFBFF, 0xFBFE, 0xFC00); // -max normal -> -infinity
+        case(0x7C00, 0x7C00, 0x7BFF); // infinity
+        case(0xFC00, 0xFBFF, 0xFC00); // -infinity
+        case(0x7E00, 0x7E00, 0x7E00); // NaN
+        let mut fp_state = FPState::default();
+        assert!(F16::signaling_nan()
+            .next_integer_up(Some(&mut fp_state))
+            .is_quiet_nan());
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_invalid_operation()
+        );
     }
-    /// convert `src` to the floating-point format specified by `properties`.
-    pub fn convert_from_float<SrcFT: FloatTraits>(
-        src: &Float<SrcFT>,
-        rounding_mode: Option<RoundingMode>,
-        fp_state: Option<FPState>,
-        properties: FloatProperties,
-    ) -> Self {
-        let mut fp_state = fp_state.unwrap_or_default();
-        let value = Float::convert_from_float_with_traits(
-            src,
-            rounding_mode,
-            Some(&mut fp_state),
+
+    #[test]
+    fn test_decompose_compose_fields() {
+        for &bits in &[0x0000u16, 0x8000, 0x3C00, 0xC3FF, 0x03FF, 0x7C00, 0xFE01] {
+            let value = F16::from_bits(bits);
+            let (sign, exponent_field, mantissa_field) = value.decompose_fields();
+            assert_eq!(sign, value.sign());
+            assert_eq!(exponent_field, value.exponent_field());
+            assert_eq!(mantissa_field, value.mantissa_field());
+            let composed = F16::compose_fields(sign, exponent_field, mantissa_field);
+            assert_eq!(*composed.bits(), bits);
+        }
+        let value = F80::compose_fields(Sign::Negative, 0x3FFF, 0x8000_0000_0000_0000);
+        assert_eq!(*value.bits(), 0xBFFF_8000_0000_0000_0000);
+        let properties = FloatProperties::new_with_extended_flags(
+            5,
+            10,
+            true,
+            false,
+            PlatformProperties::default(),
+        );
+        let value = Float::<FloatProperties>::compose_fields_with_traits(
+            Sign::Positive,
+            BigUint::from(0x0Fu8),
+            BigUint::from(0x200u16),
             properties,
         );
-        Self {
-            fp_state,
-            value,
-            _private: (),
-        }
+        assert_eq!(value.bits(), &BigUint::from(0x3E00u16));
     }
-    /// convert `self` to the floating-point format specified by `properties`.
-    pub fn convert_to_dynamic_float(
-        &self,
-        rounding_mode: Option<RoundingMode>,
-        properties: FloatProperties,
-    ) -> Self {
-        Self::convert_from_dynamic_float(self, rounding_mode, properties)
+
+    #[test]
+    #[should_panic(expected = "exponent out of range")]
+    fn test_compose_fields_exponent_out_of_range() {
+        F16::compose_fields(Sign::Positive, 0x20, 0);
     }
-    /// compute the absolute value of `self`
-    pub fn abs(&self) -> Self {
-        let mut retval = self.clone();
-        retval.abs_assign();
-        retval
+
+    #[test]
+    #[should_panic(expected = "mantissa out of range")]
+    fn test_compose_fields_mantissa_out_of_range() {
+        F16::compose_fields(Sign::Positive, 0, 0x400);
     }
-    /// construct a `DynamicFloat` from `self` but with the sign of `sign_src`
-    pub fn copy_sign<FT2: FloatTraits>(&self, sign_src: &Float<FT2>) -> Self {
-        let mut retval = self.clone();
-        retval.set_sign(sign_src.sign());
-        retval
+
+    #[test]
+    fn test_reinterpret_bits_as() {
+        #[derive(Copy, Clone, PartialEq, Debug)]
+        struct TF32Traits;
+        impl FloatTraits for TF32Traits {
+            type Bits = u32;
+            fn properties(&self) -> FloatProperties {
+                FloatProperties::new(8, 10)
+            }
+        }
+        // a register holding the TF32 value 1.0 in its low bits
+        let register = F32::from_bits(0x0001_FC00);
+        assert!(register.is_subnormal());
+        let value = register.reinterpret_bits_as(TF32Traits).unwrap();
+        assert_eq!(*value.bits(), 0x0001_FC00);
+        assert_eq!(
+            value.to_real_algebraic_number(),
+            Some(RealAlgebraicNumber::from(BigInt::one()))
+        );
+        assert!(F32::from_bits(0x3F80_0000)
+            .reinterpret_bits_as(TF32Traits)
+            .is_none());
+        let value = F32::from_bits(0x3F80_0000)
+            .reinterpret_bits_as(F32WithPlatformPropertiesTraits(PlatformProperties::RISC_V))
+            .unwrap();
+        assert_eq!(*value.bits(), 0x3F80_0000);
+        let value =
+            Float::from_bits_and_traits(BigUint::from(0x3_FE00u32), FloatProperties::STANDARD_32)
+                .reinterpret_bits_as(FloatProperties::new(8, 10))
+                .unwrap();
+        assert!(value.is_nan());
+        assert!(Float::from_bits_and_traits(
+            BigUint::from(0x8_0000u32),
+            FloatProperties::STANDARD_32
+        )
+        .reinterpret_bits_as(FloatProperties::new(8, 10))
+        .is_none());
     }
-    /// compare two `DynamicFloat` values
-    pub fn compare(&self, rhs: &Self, quiet: bool) -> (Option<Ordering>, FPState) {
-        let mut fp_state = self.fp_state;
-        fp_state.merge_assign(rhs.fp_state);
-        let result = self.value.compare(&rhs.value, quiet, Some(&mut fp_state));
-        (result, fp_state)
+
+    #[test]
+    fn test_would_signal_as_operand() {
+        let one = F80::from_bits(0x3FFF_8000_0000_0000_0000);
+        for &(bits, expected) in &[
+            (0x3FFF_8000_0000_0000_0000u128, false), // 1.0
+            (0x7FFF_8000_0000_0000_0000, false),     // infinity
+            (0x7FFF_C000_0000_0000_0000, false),     // quiet NaN
+            (0x7FFF_A000_0000_0000_0000, true),      // signaling NaN
+            (0x7FFF_4000_0000_0000_0000, true),      // pseudo-NaN
+            (0x3FFF_4000_0000_0000_0000, true),      // unnormal
+        ] {
+            let value = F80::from_bits(bits);
+            assert_eq!(value.would_signal_as_operand(), expected, "{:?}", value);
+            assert_eq!(value.class().would_signal_as_operand(), expected);
+            let ops: &[fn(&F80, &F80, &mut FPState)] = &[
+                |a, b, fp_state| {
+                    let _ = a.add(b, None, Some(fp_state));
+                },
+                |a, b, fp_state| {
+                    let _ = b.sub(a, None, Some(fp_state));
+                },
+                |a, b, fp_state| {
+                    let _ = a.mul(b, None, Some(fp_state));
+                },
+                |a, b, fp_state| {
+                    let _ = b.div(a, None, Some(fp_state));
+                },
+                |a, b, fp_state| {
+                    let _ = a.fused_mul_add(b, b, None, Some(fp_state));
+                },
+                |a, _, fp_state| {
+                    let _ = a.round_to_integral(false, None, Some(fp_state));
+                },
+                |a, _, fp_state| {
+                    let _ = a.canonicalize(Some(fp_state));
+                },
+                |a, b, fp_state| {
+                    let _ = a.minimum(b, Some(fp_state));
+                },
+                |a, b, fp_state| {
+                    let _ = a.compare_quiet(b, Some(fp_state));
+                },
+            ];
+            for op in ops {
+                let mut fp_state = FPState::default();
+                op(&value, &one, &mut fp_state);
+                assert_eq!(
+                    fp_state.status_flags.invalid_operation(),
+                    expected,
+                    "{:?}",
+                    value
+                );
+            }
+        }
     }
-    /// compare two `DynamicFloat` values
-    pub fn checked_compare(
-        &self,
-        rhs: &Self,
-        quiet: bool,
-    ) -> Result<(Option<Ordering>, FPState), FPStateMergeFailed> {
-        let mut fp_state = self.fp_state;
-        fp_state.checked_merge_assign(rhs.fp_state)?;
-        let result = self.value.compare(&rhs.value, quiet, Some(&mut fp_state));
-        Ok((result, fp_state))
+
+    #[test]
+    fn test_round_to_precision() {
+        fn case(
+            bits: u32,
+            rounding_mode: RoundingMode,
+            expected_bits: u32,
+            expected_flags: StatusFlags,
+        ) {
+            let mut fp_state = FPState::default();
+            let result = F32::from_bits(bits).round_to_precision(
+                10,
+                Some(rounding_mode),
+                Some(&mut fp_state),
+            );
+            assert_eq!(
+                *result.bits(),
+                expected_bits,
+                "{:#010X} {:?}",
+                bits,
+                rounding_mode
+            );
+            assert_eq!(
+                fp_state.status_flags, expected_flags,
+                "{:#010X} {:?}",
+                bits, rounding_mode
+            );
+        }
+        let empty = StatusFlags::empty();
+        let inexact = StatusFlags::empty().signal_inexact();
+        // exactly representable with 10 significand bits
+        case(0x3F80_0000, RoundingMode::TiesToEven, 0x3F80_0000, empty);
+        // 1 + 2^-9
+        case(0x3F80_4000, RoundingMode::TiesToEven, 0x3F80_4000, empty);
+        // -123
+        case(0xC2F6_0000, RoundingMode::TiesToEven, 0xC2F6_0000, empty);
+        // 1 + 2^-10 is a tie
+        case(0x3F80_2000, RoundingMode::TiesToEven, 0x3F80_0000, inexact);
+        case(0x3F80_2000, RoundingMode::TiesToAway, 0x3F80_4000, inexact);
+        case(
+            0x3F80_2000,
+            RoundingMode::TowardPositive,
+            0x3F80_4000,
+            inexact,
+        );
+        case(0x3F80_2000, RoundingMode::TowardZero, 0x3F80_0000, inexact);
+        // 1 + 3 * 2^-10 is a tie that rounds up to even
+        case(0x3F80_6000, RoundingMode::TiesToEven, 0x3F80_8000, inexact);
+        case(
+            0xBF80_0001,
+            RoundingMode::TowardNegative,
+            0xBF80_4000,
+            inexact,
+        );
+        case(
+            0xBF80_0001,
+            RoundingMode::TowardPositive,
+            0xBF80_0000,
+            inexact,
+        );
+        // rounding up can carry into the exponent
+        case(0x3FFF_FFFF, RoundingMode::TiesToEven, 0x4000_0000, inexact);
+        // and overflow
+        case(
+            0x7F7F_FFFF,
+            RoundingMode::TiesToEven,
+            0x7F80_0000,
+            StatusFlags::empty().signal_overflow_with_inexact(),
+        );
+        case(0x7F7F_FFFF, RoundingMode::TowardZero, 0x7F7F_C000, inexact);
+        // subnormals use the same exponent range, with coarser spacing
+        case(0x0040_0000, RoundingMode::TiesToEven, 0x0040_0000, empty);
+        case(
+            0x0000_0003,
+            RoundingMode::TiesToEven,
+            0x0000_0000,
+            StatusFlags::empty().signal_underflow_with_inexact(),
+        );
+        case(
+            0x0000_0003,
+            RoundingMode::TowardPositive,
+            0x0000_4000,
+            StatusFlags::empty().signal_underflow_with_inexact(),
+        );
+        // special values
+        case(0x7F80_0000, RoundingMode::TiesToEven, 0x7F80_0000, empty);
+        case(0x8000_0000, RoundingMode::TiesToEven, 0x8000_0000, empty);
+        case(0x7FC0_0001, RoundingMode::TiesToEven, 0x7FC0_0001, empty);
+        case(
+            0x7F80_0001,
+            RoundingMode::TiesToEven,
+            0x7FC0_0001,
+            StatusFlags::empty().signal_invalid_operation(),
+        );
+        // the full precision is a no-op
+        let value = F32::from_bits(0x3F80_0001);
+        let mut fp_state = FPState::default();
+        assert_eq!(
+            value
+                .round_to_precision(24, None, Some(&mut fp_state))
+                .bits(),
+            value.bits()
+        );
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        // a single significand bit rounds to powers of two
+        let result = F32::from_bits(0x4040_0000).round_to_precision(1, None, None); // 3
+        assert_eq!(*result.bits(), 0x4080_0000);
+        let result = F80::from_bits(0x4000_E000_0000_0000_0000).round_to_precision(2, None, None); // 3.5
+        assert_eq!(*result.bits(), 0x4001_8000_0000_0000_0000);
     }
-    /// compare two `DynamicFloat` values
-    pub fn compare_quiet(&self, rhs: &Self) -> (Option<Ordering>, FPState) {
-        let mut fp_state = self.fp_state;
-        fp_state.merge_assign(rhs.fp_state);
-        let result = self.value.compare_quiet(&rhs.value, Some(&mut fp_state));
-        (result, fp_state)
+
+    #[test]
+    fn test_round_to_format_precision() {
+        // emulate the x87 storing a result to a `float` variable while
+        // keeping it in an 80-bit register
+        let f80 = |exponent: u128, mantissa: u128| F80::from_bits(exponent << 64 | mantissa);
+        let one = 1 << 63;
+        let none = StatusFlags::empty();
+        let inexact = StatusFlags::empty().signal_inexact();
+        let overflow = StatusFlags::empty().signal_overflow_with_inexact();
+        let underflow = StatusFlags::empty().signal_underflow_with_inexact();
+        let cases = [
+            (
+                f80(0x3FFF, one),
+                RoundingMode::TiesToEven,
+                f80(0x3FFF, one),
+                none,
+            ),
+            // 1 + 2^-30
+            (
+                f80(0x3FFF, one | 1 << 33),
+                RoundingMode::TiesToEven,
+                f80(0x3FFF, one),
+                inexact,
+            ),
+            (
+                f80(0x3FFF, one | 1 << 33),
+                RoundingMode::TowardPositive,
+                f80(0x3FFF, one | 1 << 40),
+                inexact,
+            ),
+            // 1 + 2^-23 + 2^-24 is a tie
+            (
+                f80(0x3FFF, one | 1 << 40 | 1 << 39),
+                RoundingMode::TiesToEven,
+                f80(0x3FFF, one | 1 << 41),
+                inexact,
+            ),
+            // 2^200 is out of `F32`'s range
+            (
+                f80(0x3FFF + 200, one),
+                RoundingMode::TiesToEven,
+                F80::positive_infinity(),
+                overflow,
+            ),
+            (
+                f80(0x3FFF + 200, one),
+                RoundingMode::TowardZero,
+                f80(0x3FFF + 127, 0xFFFF_FF00_0000_0000),
+                overflow,
+            ),
+            // 2^-140 * (1 + 2^-20) rounds to a multiple of 2^-149
+            (
+                f80(0x3FFF - 140, one | 1 << 43),
+                RoundingMode::TiesToEven,
+                f80(0x3FFF - 140, one),
+                underflow,
+            ),
+            (
+                F80::negative_zero(),
+                RoundingMode::TiesToEven,
+                F80::negative_zero(),
+                none,
+            ),
+            (
+                F80::negative_infinity(),
+                RoundingMode::TiesToEven,
+                F80::negative_infinity(),
+                none,
+            ),
+            (
+                F80::signaling_nan(),
+                RoundingMode::TiesToEven,
+                F80::signaling_nan().to_quiet_nan(),
+                StatusFlags::empty().signal_invalid_operation(),
+            ),
+        ];
+        for (value, rounding_mode, expected, expected_flags) in cases.iter() {
+            let mut fp_state = FPState::default();
+            let result = value.round_to_format_precision(
+                &F32Traits,
+                Some(*rounding_mode),
+                Some(&mut fp_state),
+            );
+            assert_eq!(
+                (result.bits(), fp_state.status_flags),
+                (expected.bits(), *expected_flags),
+                "{:?} {:?}",
+                value,
+                rounding_mode
+            );
+            if value.is_finite() {
+                // same as converting to `F32` and back
+                let narrow = F32::convert_from_float(value, Some(*rounding_mode), None);
+                let converted = F80::convert_from_float(&narrow, None, None);
+                assert_eq!(result.bits(), converted.bits(), "{:?}", value);
+            }
+        }
+        // the x87's precision control keeps the exponent range
+        let huge = f80(0x3FFF + 200, one);
+        assert_eq!(huge.round_to_precision(24, None, None).bits(), huge.bits());
+        let value = f80(0x3FFF, one | 1 << 33);
+        assert_eq!(
+            value.round_to_precision(24, None, None).bits(),
+            value
+                .round_to_format_precision(&F32Traits, None, None)
+                .bits()
+        );
     }
-    /// compare two `DynamicFloat` values
-    pub fn checked_compare_quiet(
-        &self,
-        rhs: &Self,
-    ) -> Result<(Option<Ordering>, FPState), FPStateMergeFailed> {
-        let mut fp_state = self.fp_state;
-        fp_state.checked_merge_assign(rhs.fp_state)?;
-        let result = self.value.compare_quiet(&rhs.value, Some(&mut fp_state));
-        Ok((result, fp_state))
+
+    #[test]
+    fn test_precision_control() {
+        let x87_add = |lhs: f64, rhs: f64, precision_control: Option<usize>| {
+            let mut fp_state = FPState {
+                precision_control,
+                ..FPState::default()
+            };
+            let lhs = F80::convert_from_float(&F64::from(lhs), None, None);
+            let rhs = F80::convert_from_float(&F64::from(rhs), None, None);
+            let sum = lhs.add(&rhs, None, Some(&mut fp_state));
+            (
+                f64::from(F64::convert_from_float(&sum, None, None)),
+                fp_state.status_flags,
+            )
+        };
+        // `1 + 2^-53 + 2^-105` is just over halfway between `1` and `1 + 2^-52`
+        let lhs = 1.0;
+        let rhs = 2.0f64.powi(-53) + 2.0f64.powi(-105);
+        let inexact = StatusFlags::empty().signal_inexact();
+        assert_eq!(
+            f64::from(F64::from(lhs).add(&F64::from(rhs), None, None)),
+            1.0 + 2.0f64.powi(-52)
+        );
+        // the classic x87 double rounding: rounding to 64 bits gives
+        // `1 + 2^-53`, which is a tie when rounding to `f64`
+        assert_eq!(x87_add(lhs, rhs, None), (1.0, inexact));
+        assert_eq!(x87_add(lhs, rhs, Some(64)), (1.0, inexact));
+        // with PC=double the sum is only rounded once
+        assert_eq!(
+            x87_add(lhs, rhs, Some(53)),
+            (1.0 + 2.0f64.powi(-52), inexact)
+        );
+        assert_eq!(x87_add(lhs, rhs, Some(24)), (1.0, inexact));
+        // the fast path for aligned operands is also rounded
+        let mut fp_state = FPState {
+            precision_control: Some(24),
+            ..FPState::default()
+        };
+        let sum = F80::convert_from_float(&F64::from(1.0), None, None).add(
+            &F80::convert_from_float(&F64::from(1.0 + 2.0f64.powi(-30)), None, None),
+            None,
+            Some(&mut fp_state),
+        );
+        assert_eq!(*sum.bits(), 0x4000_8000_0000_0000_0000);
+        assert_eq!(fp_state.status_flags, inexact);
+        // the exponent range isn't reduced, unlike `f64`
+        let mut fp_state = FPState {
+            precision_control: Some(53),
+            ..FPState::default()
+        };
+        let tiny = F80::convert_from_float(&F64::from_bits(1), None, None);
+        let product = tiny.mul(
+            &F80::convert_from_float(&F64::from(0.5), None, None),
+            None,
+            Some(&mut fp_state),
+        );
+        assert_eq!(product.log_b(None), Some(BigInt::from(-1075)));
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        // precision control that's not less than the format's precision has
+        // no effect
+        let mut fp_state = FPState {
+            precision_control: Some(53),
+            ..FPState::default()
+        };
+        let third = F32::from_bits(0x3F80_0000).div(
+            &F32::from_bits(0x4040_0000),
+            None,
+            Some(&mut fp_state),
+        );
+        assert_eq!(*third.bits(), 0x3EAA_AAAB);
+        // `Some(0)` is the same as `None`
+        assert_eq!(x87_add(lhs, rhs, Some(0)), x87_add(lhs, rhs, None));
+        // conversions aren't affected
+        let mut fp_state = FPState {
+            precision_control: Some(53),
+            ..FPState::default()
+        };
+        let converted = F80::from_u64(u64::MAX, None, Some(&mut fp_state));
+        assert_eq!(converted.to_u64(true, None, None), Some(u64::MAX));
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        let parsed = F80::from_str_radix("18446744073709551615", 10, None, Some(&mut fp_state));
+        assert_eq!(parsed.unwrap().bits(), converted.bits());
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        // but `fused_mul_add` and `sqrt` are
+        let f80 = |value: f64| F80::convert_from_float(&F64::from(value), None, None);
+        let f64_result =
+            F64::from(3.0).fused_mul_add(&F64::from(1.0 / 3.0), &F64::from(0.5), None, None);
+        let result = f80(3.0).fused_mul_add(&f80(1.0 / 3.0), &f80(0.5), None, Some(&mut fp_state));
+        assert_eq!(
+            F64::convert_from_float(&result, None, None).bits(),
+            f64_result.bits()
+        );
+        assert!(result.is_exactly_representable_in(&F64Traits));
+        #[cfg(not(feature = "rational-only"))]
+        {
+            let result = f80(2.0).sqrt(None, Some(&mut fp_state));
+            assert!(result.is_exactly_representable_in(&F64Traits));
+            assert_eq!(
+                F64::convert_from_float(&result, None, None).bits(),
+                F64::from(2.0).sqrt(None, None).bits()
+            );
+        }
     }
-    /// compare two `DynamicFloat` values
-    pub fn compare_signaling(&self, rhs: &Self) -> (Option<Ordering>, FPState) {
-        let mut fp_state = self.fp_state;
-        fp_state.merge_assign(rhs.fp_state);
-        let result = self
-            .value
-            .compare_signaling(&rhs.value, Some(&mut fp_state));
-        (result, fp_state)
+
+    #[test]
+    fn test_fp_state_merge() {
+        let all_flags = FPState::with_all_flags_set();
+        assert_eq!(all_flags.status_flags, StatusFlags::all());
+        assert_eq!(all_flags.save_modes(), FPModes::default());
+        let inexact = FPState {
+            status_flags: StatusFlags::empty().signal_inexact(),
+            ..FPState::default()
+        };
+        let overflow = FPState {
+            status_flags: StatusFlags::empty().signal_overflow(),
+            ..FPState::default()
+        };
+        // same modes with different flags combine the flags
+        assert_eq!(
+            inexact.checked_merge(overflow).map(|v| v.status_flags),
+            Ok(StatusFlags::empty().signal_inexact().signal_overflow())
+        );
+        assert_eq!(inexact.merge(all_flags), all_flags);
+        let mut merged = FPState::default();
+        merged.merge_assign(inexact);
+        merged.merge_assign(overflow);
+        assert_eq!(merged, inexact.merge(overflow));
+        // different modes are an error
+        let toward_zero = FPState {
+            rounding_mode: RoundingMode::TowardZero,
+            ..inexact
+        };
+        assert_eq!(toward_zero.checked_merge(overflow), Err(FPStateMergeFailed));
+        let mut value = toward_zero;
+        assert_eq!(
+            value.checked_merge_assign(overflow),
+            Err(FPStateMergeFailed)
+        );
+        assert_eq!(value, toward_zero, "unchanged on error");
+        let before_rounding = FPState {
+            tininess_detection_mode: TininessDetectionMode::BeforeRounding,
+            ..FPState::default()
+        };
+        assert_eq!(
+            before_rounding.checked_merge(FPState::default()),
+            Err(FPStateMergeFailed)
+        );
+        let signal_exact_underflow = FPState {
+            exception_handling_mode: ExceptionHandlingMode::SignalExactUnderflow,
+            ..FPState::default()
+        };
+        assert_eq!(
+            signal_exact_underflow.checked_merge(FPState::default()),
+            Err(FPStateMergeFailed)
+        );
     }
-    /// compare two `DynamicFloat` values
-    pub fn checked_compare_signaling(
-        &self,
-        rhs: &Self,
-    ) -> Result<(Option<Ordering>, FPState), FPStateMergeFailed> {
-        let mut fp_state = self.fp_state;
-        fp_state.checked_merge_assign(rhs.fp_state)?;
-        let result = self
-            .value
-            .compare_signaling(&rhs.value, Some(&mut fp_state));
-        Ok((result, fp_state))
+
+    #[test]
+    #[should_panic]
+    fn test_fp_state_merge_panics() {
+        let toward_zero = FPState {
+            rounding_mode: RoundingMode::TowardZero,
+            ..FPState::default()
+        };
+        toward_zero.merge(FPState::default());
     }
-}
 
-impl_dynamic_float_from_int_type!(from_bigint_with_traits, from_bigint, BigInt);
-impl_dynamic_float_from_int_type!(from_biguint_with_traits, from_biguint, BigUint);
-impl_dynamic_float_from_int_type!(from_u8_with_traits, from_u8, u8);
-impl_dynamic_float_from_int_type!(from_u16_with_traits, from_u16, u16);
-impl_dynamic_float_from_int_type!(from_u32_with_traits, from_u32, u32);
-impl_dynamic_float_from_int_type!(from_u64_with_traits, from_u64, u64);
-impl_dynamic_float_from_int_type!(from_u128_with_traits, from_u128, u128);
-impl_dynamic_float_from_int_type!(from_usize_with_traits, from_usize, usize);
-impl_dynamic_float_from_int_type!(from_i8_with_traits, from_i8, i8);
-impl_dynamic_float_from_int_type!(from_i16_with_traits, from_i16, i16);
-impl_dynamic_float_from_int_type!(from_i32_with_traits, from_i32, i32);
-impl_dynamic_float_from_int_type!(from_i64_with_traits, from_i64, i64);
-impl_dynamic_float_from_int_type!(from_i128_with_traits, from_i128, i128);
-impl_dynamic_float_from_int_type!(from_isize_with_traits, from_isize, isize);
-impl_dynamic_float_to_int_type!(to_bigint, BigInt);
-impl_dynamic_float_to_int_type!(to_biguint, BigUint);
-impl_dynamic_float_to_int_type!(to_u8, u8);
-impl_dynamic_float_to_int_type!(to_u16, u16);
-impl_dynamic_float_to_int_type!(to_u32, u32);
-impl_dynamic_float_to_int_type!(to_u64, u64);
-impl_dynamic_float_to_int_type!(to_u128, u128);
-impl_dynamic_float_to_int_type!(to_usize, usize);
-impl_dynamic_float_to_int_type!(to_i8, i8);
-impl_dynamic_float_to_int_type!(to_i16, i16);
-impl_dynamic_float_to_int_type!(to_i32, i32);
-impl_dynamic_float_to_int_type!(to_i64, i64);
-impl_dynamic_float_to_int_type!(to_i128, i128);
-impl_dynamic_float_to_int_type!(to_isize, isize);
-impl_dynamic_float_fn!(
-    /// compute reciprocal square-root (`1.0 / sqrt(self)`)
-    rsqrt,
-    rsqrt,
-    (&self, rounding_mode: Option<RoundingMode>)
-);
+    #[test]
+    fn test_from_decimal_parts() {
+        let three = BigUint::from(3u32);
+        let mut fp_state = FPState::default();
+        let result = F16::from_decimal_parts(Sign::Positive, &three, -1, None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x34CD);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty().signal_inexact());
+        let result = F16::from_decimal_parts(
+            Sign::Positive,
+            &three,
+            -1,
+            Some(RoundingMode::TowardZero),
+            None,
+        );
+        assert_eq!(*result.bits(), 0x34CC);
+        let result = F32::from_decimal_parts(Sign::Negative, &three, -1, None, None);
+        assert_eq!(*result.bits(), 0xBE99_999A);
+        let result = F32::from_decimal_parts(
+            Sign::Negative,
+            &three,
+            -1,
+            Some(RoundingMode::TowardZero),
+            None,
+        );
+        assert_eq!(*result.bits(), 0xBE99_9999);
+        let mut fp_state = FPState::default();
+        let result = F32::from_decimal_parts(
+            Sign::Positive,
+            &BigUint::from(125u32),
+            -3,
+            None,
+            Some(&mut fp_state),
+        );
+        assert_eq!(*result.bits(), 0x3E00_0000);
+        assert_eq!(fp_state.status_flags, StatusFlags::empty());
+        let zero = BigUint::zero();
+        assert_eq!(
+            *F32::from_decimal_parts(Sign::Negative, &zero, 1000, None, None).bits(),
+            0x8000_0000
+        );
+        // huge exponents are clamped
+        let mut fp_state = FPState::default();
+        let result =
+            F32::from_decimal_parts(Sign::Positive, &three, i64::MAX, None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x7F80_0000);
+        assert!(fp_state.status_flags.overflow());
+        let mut fp_state = FPState::default();
+        let result = F32::from_decimal_parts(
+            Sign::Negative,
+            &three,
+            i64::MIN,
+            Some(RoundingMode::TowardNegative),
+            Some(&mut fp_state),
+        );
+        assert_eq!(*result.bits(), 0x8000_0001);
+        assert!(fp_state.status_flags.underflow());
+        // same as parsing the decimal string
+        for &(digits, exponent) in &[
+            (1u64, -45i64),
+            (7, -46),
+            (340_282_356_779_733_661, 20),
+            (123_456_789, -7),
+        ] {
+            let from_parts = F32::from_decimal_parts(
+                Sign::Positive,
+                &BigUint::from(digits),
+                exponent,
+                None,
+                None,
+            );
+            let text = format!("{}e{}", digits, exponent);
+            let parsed = F32::from_rational_str(&text, None, None).unwrap();
+            assert_eq!(*from_parts.bits(), *parsed.bits(), "{}", text);
+        }
+        let result = DynamicFloat::from_decimal_parts(
+            Sign::Positive,
+            &three,
+            -1,
+            None,
+            None,
+            FloatProperties::STANDARD_16,
+        );
+        assert_eq!(*result.bits(), BigUint::from(0x34CDu32));
+        assert!(result.fp_state.status_flags.inexact());
+    }
 
-macro_rules! impl_dynamic_float_binary_op_trait {
-    ($op_trait:ident, $op:ident, $op_assign_trait:ident, $op_assign:ident, $called_fn_name:ident) => {
-        impl $op_trait for DynamicFloat {
-            type Output = DynamicFloat;
-            fn $op(self, rhs: DynamicFloat) -> DynamicFloat {
-                self.$called_fn_name(&rhs, None)
-            }
+    #[test]
+    fn test_from_rational_str() {
+        fn case(
+            text: &str,
+            rounding_mode: RoundingMode,
+            expected: Result<(u32, StatusFlags), ParseError>,
+        ) {
+            let mut fp_state = FPState::default();
+            let result = F32::from_rational_str(text, Some(rounding_mode), Some(&mut fp_state))
+                .map(|v| (*v.bits(), fp_state.status_flags));
+            assert_eq!(result, expected, "{:?} {:?}", text, rounding_mode);
+        }
+        let empty = StatusFlags::empty();
+        let inexact = StatusFlags::empty().signal_inexact();
+        case("1/3", RoundingMode::TiesToEven, Ok((0x3EAA_AAAB, inexact)));
+        case("1/3", RoundingMode::TowardZero, Ok((0x3EAA_AAAA, inexact)));
+        case("-1/3", RoundingMode::TiesToEven, Ok((0xBEAA_AAAB, inexact)));
+        case("+2/4", RoundingMode::TiesToEven, Ok((0x3F00_0000, empty)));
+        case("-0/7", RoundingMode::TiesToEven, Ok((0x8000_0000, empty)));
+        case("12", RoundingMode::TiesToEven, Ok((0x4140_0000, empty)));
+        case("0.1", RoundingMode::TiesToEven, Ok((0x3DCC_CCCD, inexact)));
+        case(".5", RoundingMode::TiesToEven, Ok((0x3F00_0000, empty)));
+        case("5.", RoundingMode::TiesToEven, Ok((0x40A0_0000, empty)));
+        case(
+            "1.5e-1",
+            RoundingMode::TiesToEven,
+            Ok((0x3E19_999A, inexact)),
+        );
+        case("25E+1", RoundingMode::TiesToEven, Ok((0x437A_0000, empty)));
+        case("-0.0", RoundingMode::TiesToEven, Ok((0x8000_0000, empty)));
+        // 2^24 + 1 is a tie
+        case(
+            "16777217",
+            RoundingMode::TiesToEven,
+            Ok((0x4B80_0000, inexact)),
+        );
+        case(
+            "16777217/1",
+            RoundingMode::TiesToAway,
+            Ok((0x4B80_0001, inexact)),
+        );
+        // huge exponents are clamped without changing the result
+        case(
+            "1e999999999999999999999",
+            RoundingMode::TiesToEven,
+            Ok((
+                0x7F80_0000,
+                StatusFlags::empty().signal_overflow_with_inexact(),
+            )),
+        );
+        case(
+            "1e999999999999999999999",
+            RoundingMode::TowardZero,
+            Ok((
+                0x7F7F_FFFF,
+                StatusFlags::empty().signal_overflow_with_inexact(),
+            )),
+        );
+        case(
+            "1e-999999999999999999999",
+            RoundingMode::TowardPositive,
+            Ok((
+                0x0000_0001,
+                StatusFlags::empty().signal_underflow_with_inexact(),
+            )),
+        );
+        case(
+            "-99999e-999999999999999999999",
+            RoundingMode::TiesToEven,
+            Ok((
+                0x8000_0000,
+                StatusFlags::empty().signal_underflow_with_inexact(),
+            )),
+        );
+        case(
+            "0e999999999999999999999",
+            RoundingMode::TiesToEven,
+            Ok((0, empty)),
+        );
+        for &text in &[
+            "", "-", "1/0", "1/", "/2", "1/-2", "1/2/3", "1.5/2", ".", "1e", "1e+", "e5", "1 ",
+            "0x10", "inf", "nan", "--1", "1.2.3",
+        ] {
+            case(text, RoundingMode::TiesToEven, Err(ParseError));
         }
+        let value =
+            DynamicFloat::from_rational_str("1/3", None, None, FloatProperties::STANDARD_16)
+                .unwrap();
+        assert_eq!(value.bits(), &BigUint::from(0x3555u16));
+        assert_eq!(value.fp_state.status_flags, inexact);
+    }
 
-        impl $op_trait<&'_ DynamicFloat> for DynamicFloat {
-            type Output = DynamicFloat;
-            fn $op(self, rhs: &DynamicFloat) -> DynamicFloat {
-                self.$called_fn_name(rhs, None)
-            }
+    #[test]
+    fn test_count_representable_in_range() {
+        let count = |lo: u16, hi: u16| {
+            F16::from_bits(lo)
+                .count_representable_in_range(&F16::from_bits(hi))
+                .map(|count| count.to_i64().expect("count fits in i64"))
+        };
+        assert_eq!(count(0x3C00, 0x3C00), Some(1));
+        assert_eq!(count(0x3C00, 0x4000), Some(0x401));
+        assert_eq!(count(0x0000, 0x0001), Some(2));
+        // -0.0 and +0.0 are both counted
+        assert_eq!(count(0x8000, 0x0000), Some(2));
+        assert_eq!(count(0x8001, 0x0001), Some(4));
+        assert_eq!(count(0xBC00, 0x3C00), Some(2 * 0x3C01));
+        assert_eq!(count(0xBC00, 0xBC00), Some(1));
+        assert_eq!(count(0xC000, 0xBC00), Some(0x401));
+        // every non-NaN value, including both infinities
+        assert_eq!(count(0xFC00, 0x7C00), Some(2 * 0x7C01));
+        // reversed ranges are empty
+        assert_eq!(count(0x4000, 0x3C00), Some(0));
+        assert_eq!(count(0x0000, 0x8000), Some(0));
+        assert_eq!(count(0x3C00, 0xBC00), Some(0));
+        assert_eq!(count(0x7E00, 0x3C00), None);
+        assert_eq!(count(0x3C00, 0x7C01), None);
+        assert_eq!(count(0xFE00, 0x7E00), None);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn test_sample_uniform_representable() {
+        use rand::SeedableRng;
+        use rand_pcg::Pcg64Mcg;
+        let mut rng = Pcg64Mcg::seed_from_u64(0x5EED);
+        let sample = |lo: u16, hi: u16, rng: &mut Pcg64Mcg| {
+            *F16::from_bits(lo)
+                .sample_uniform_representable(&F16::from_bits(hi), rng)
+                .bits()
+        };
+        assert_eq!(sample(0x3C00, 0x3C00, &mut rng), 0x3C00);
+        assert_eq!(sample(0x8000, 0x8000, &mut rng), 0x8000);
+        // subnormals on both sides of zero, including both zeros
+        let expected = [0x8002, 0x8001, 0x8000, 0x0000, 0x0001, 0x0002];
+        let mut hits = [0; 6];
+        for _ in 0..600 {
+            let bits = sample(0x8002, 0x0002, &mut rng);
+            let index = expected
+                .iter()
+                .position(|&v| v == bits)
+                .unwrap_or_else(|| panic!("out of range: {:#06X}", bits));
+            hits[index] += 1;
         }
-        impl $op_trait<DynamicFloat> for &'_ DynamicFloat {
-            type Output = DynamicFloat;
-            fn $op(self, rhs: DynamicFloat) -> DynamicFloat {
-                self.$called_fn_name(&rhs, None)
-            }
+        assert!(hits.iter().all(|&hits| hits > 50), "{:?}", hits);
+        // the whole format, only NaNs are excluded
+        for _ in 0..1000 {
+            let value = F16::from_bits(sample(0xFC00, 0x7C00, &mut rng));
+            assert!(!value.is_nan(), "{:?}", value);
+        }
+        for _ in 0..1000 {
+            let bits = sample(0xC000, 0xBC00, &mut rng);
+            assert!((0xBC00..=0xC000).contains(&bits), "{:#06X}", bits);
         }
+    }
 
-        impl<'a, 'b> $op_trait<&'a DynamicFloat> for &'b DynamicFloat {
-            type Output = DynamicFloat;
-            fn $op(self, rhs: &DynamicFloat) -> DynamicFloat {
-                self.$called_fn_name(rhs, None)
+    #[test]
+    fn test_add_or_sub_aligned() {
+        use rand::{Rng, SeedableRng};
+        use rand_pcg::Pcg64Mcg;
+        fn check<Bits: FloatBitsType, FT: FloatTraits<Bits = Bits> + Default>(
+            rng: &mut Pcg64Mcg,
+            iterations: usize,
+        ) where
+            Float<FT>: fmt::Debug,
+        {
+            let rounding_modes = [
+                RoundingMode::TiesToEven,
+                RoundingMode::TowardZero,
+                RoundingMode::TowardNegative,
+                RoundingMode::TowardPositive,
+                RoundingMode::TiesToAway,
+                RoundingMode::TiesToOdd,
+            ];
+            let properties = FT::default().properties();
+            let random_bits = |rng: &mut Pcg64Mcg, max: Bits| {
+                let bits = (u128::from(rng.gen::<u64>()) << 64) | u128::from(rng.gen::<u64>());
+                Bits::from_u128(bits & max.to_u128().unwrap()).unwrap()
+            };
+            let exponent_max: u64 = properties.exponent_max_normal::<Bits>().to_u64().unwrap();
+            let exponent_bias: u64 = properties.exponent_bias::<Bits>().to_u64().unwrap();
+            let fraction_width = properties.fraction_width() as u64;
+            // the general path is very slow for huge and tiny values, so stay
+            // near 1 for the wider formats
+            let exponent_range = (
+                exponent_bias.saturating_sub(300),
+                (exponent_bias + 300).min(exponent_max),
+            );
+            for _ in 0..iterations {
+                let exponent = rng.gen_range(exponent_range.0, exponent_range.1 + 1);
+                let mut lhs = Float::<FT>::positive_zero();
+                lhs.set_sign(if rng.gen() {
+                    Sign::Positive
+                } else {
+                    Sign::Negative
+                });
+                lhs.set_exponent_field(Bits::from_u64(exponent).unwrap());
+                lhs.set_mantissa_field(random_bits(rng, properties.mantissa_field_max()));
+                let mut rhs = lhs.clone();
+                let rhs_exponent = if rng.gen() {
+                    exponent.saturating_sub(rng.gen_range(0, fraction_width + 2))
+                } else {
+                    (exponent + rng.gen_range(0, fraction_width + 2)).min(exponent_range.1)
+                };
+                rhs.set_exponent_field(Bits::from_u64(rhs_exponent).unwrap());
+                if rng.gen() {
+                    // only change the low bits, so the sum cancels
+                    let low_bits_max =
+                        (Bits::one() << (properties.fraction_width() / 2)) - Bits::one();
+                    let mantissa = rhs.mantissa_field() ^ random_bits(rng, low_bits_max);
+                    rhs.set_mantissa_field(mantissa);
+                } else {
+                    rhs.set_mantissa_field(random_bits(rng, properties.mantissa_field_max()));
+                }
+                if rng.gen() {
+                    rhs.toggle_sign();
+                }
+                if !lhs.is_finite() || !rhs.is_finite() {
+                    // unnormals with an explicit leading bit
+                    continue;
+                }
+                let is_sub = rng.gen();
+                let rounding_mode = rounding_modes[rng.gen_range(0, rounding_modes.len())];
+                let mut fp_state = FPState::default();
+                let result = if is_sub {
+                    lhs.sub(&rhs, Some(rounding_mode), Some(&mut fp_state))
+                } else {
+                    lhs.add(&rhs, Some(rounding_mode), Some(&mut fp_state))
+                };
+                let lhs_value = lhs.to_ratio().unwrap();
+                let rhs_value = rhs.to_ratio().unwrap();
+                let exact = if is_sub {
+                    lhs_value - rhs_value
+                } else {
+                    lhs_value + rhs_value
+                };
+                let mut expected_fp_state = FPState::default();
+                let expected = if exact.is_zero() && !(lhs.is_zero() && rhs.is_zero()) {
+                    Float::signed_zero(rounding_mode.exact_zero_sign())
+                } else if exact.is_zero() {
+                    // both zeros, handled before the fast path
+                    continue;
+                } else {
+                    Float::from_real_algebraic_number(
+                        &exact.into(),
+                        Some(rounding_mode),
+                        Some(&mut expected_fp_state),
+                    )
+                };
+                assert!(
+                    result.bits() == expected.bits() && fp_state == expected_fp_state,
+                    "{:?} {} {:?} {:?}: {:?} {:?} != {:?} {:?}",
+                    lhs,
+                    if is_sub { "-" } else { "+" },
+                    rhs,
+                    rounding_mode,
+                    result,
+                    fp_state,
+                    expected,
+                    expected_fp_state
+                );
             }
         }
-
-        impl $op_assign_trait for DynamicFloat {
-            fn $op_assign(&mut self, rhs: DynamicFloat) {
-                *self = self.$called_fn_name(&rhs, None);
+        let mut rng = Pcg64Mcg::seed_from_u64(0x5EED);
+        check::<_, F16Traits>(&mut rng, 1000);
+        check::<_, F32Traits>(&mut rng, 200);
+        check::<_, F64Traits>(&mut rng, 20);
+        check::<_, F80Traits>(&mut rng, 20);
+        // inexact sums with a carry out are rounded by the fast path, check
+        // ties, values above and below ties, and carries out of the rounding
+        let rounding_modes = [
+            RoundingMode::TiesToEven,
+            RoundingMode::TowardZero,
+            RoundingMode::TowardNegative,
+            RoundingMode::TowardPositive,
+            RoundingMode::TiesToAway,
+            RoundingMode::TiesToOdd,
+        ];
+        let lhs_values = [0x3C00, 0x3C01, 0x3FFF, 0x3E00, 0xBC01, 0xBFFF];
+        let rhs_values = [
+            0x3C01, 0x3C02, 0x3BFF, 0x3801, 0x37FF, 0x3403, 0xBC03, 0xBBFF, 0xB7FF, 0x3FFF,
+        ];
+        for &rounding_mode in &rounding_modes {
+            for &lhs in &lhs_values {
+                for &rhs in &rhs_values {
+                    let lhs = F16::from_bits(lhs);
+                    let rhs = F16::from_bits(rhs);
+                    let exact = lhs.to_ratio().unwrap() + rhs.to_ratio().unwrap();
+                    if exact.is_zero() {
+                        continue;
+                    }
+                    let mut expected_fp_state = FPState::default();
+                    let expected = F16::from_real_algebraic_number(
+                        &exact.into(),
+                        Some(rounding_mode),
+                        Some(&mut expected_fp_state),
+                    );
+                    let mut fp_state = FPState::default();
+                    let result = lhs
+                        .add_or_sub_aligned(&rhs, rounding_mode, &mut fp_state, false)
+                        .unwrap_or_else(|| {
+                            panic!("{:?} + {:?} not handled by the fast path", lhs, rhs)
+                        });
+                    assert!(
+                        result.bits() == expected.bits() && fp_state == expected_fp_state,
+                        "{:?} + {:?} {:?}: {:?} {:?} != {:?} {:?}",
+                        lhs,
+                        rhs,
+                        rounding_mode,
+                        result,
+                        fp_state,
+                        expected,
+                        expected_fp_state
+                    );
+                }
             }
         }
+        // overflow is left to the general path
+        let largest = F16::from_bits(0x7BFF);
+        let mut fp_state = FPState::default();
+        assert!(largest
+            .add_or_sub_aligned(&largest, RoundingMode::TowardZero, &mut fp_state, false)
+            .is_none());
+        assert_eq!(fp_state, FPState::default());
+    }
 
-        impl $op_assign_trait<&'_ DynamicFloat> for DynamicFloat {
-            fn $op_assign(&mut self, rhs: &DynamicFloat) {
-                *self = self.$called_fn_name(rhs, None);
+    #[test]
+    fn test_mul_and_div_by_power_of_two() {
+        let rounding_modes = [
+            RoundingMode::TiesToEven,
+            RoundingMode::TowardZero,
+            RoundingMode::TowardNegative,
+            RoundingMode::TowardPositive,
+            RoundingMode::TiesToAway,
+            RoundingMode::TiesToOdd,
+        ];
+        // every third power of two from the smallest subnormal to 2^15, so
+        // results overflow, underflow, and round in the subnormal range
+        let powers_of_two: Vec<_> = (-24..=15)
+            .step_by(3)
+            .flat_map(|exponent| {
+                let power_of_two = F16::from_bits(0x3C00).scale_b(exponent.into(), None, None);
+                vec![power_of_two, power_of_two.neg()]
+            })
+            .collect();
+        for power_of_two in &powers_of_two {
+            assert!(power_of_two.abs().is_power_of_two());
+        }
+        // every F16 value, each with a different power of two and rounding
+        // mode, so the algebraic path stays fast enough
+        for bits in 0..=0xFFFFu16 {
+            let value = F16::from_bits(bits);
+            if !value.is_finite() || value.is_zero() {
+                continue;
             }
+            let value_value = value.to_ratio().unwrap();
+            let index = usize::from(bits);
+            let power_of_two = powers_of_two[index % powers_of_two.len()];
+            let power_of_two_value = power_of_two.to_ratio().unwrap();
+            let rounding_mode =
+                rounding_modes[(index / powers_of_two.len()) % rounding_modes.len()];
+            let check = |name: &str, result: F16, fp_state: FPState, exact| {
+                let mut expected_fp_state = FPState::default();
+                let expected = F16::from_real_algebraic_number(
+                    &RealAlgebraicNumber::from(exact),
+                    Some(rounding_mode),
+                    Some(&mut expected_fp_state),
+                );
+                assert_eq!(
+                    (*result.bits(), fp_state),
+                    (*expected.bits(), expected_fp_state),
+                    "{}({:?}, {:?}) {:?}",
+                    name,
+                    value,
+                    power_of_two,
+                    rounding_mode
+                );
+            };
+            let product = &value_value * &power_of_two_value;
+            let mut fp_state = FPState::default();
+            let result = value.mul(&power_of_two, Some(rounding_mode), Some(&mut fp_state));
+            check("mul", result, fp_state, product);
+            let mut commuted_fp_state = FPState::default();
+            let commuted =
+                power_of_two.mul(&value, Some(rounding_mode), Some(&mut commuted_fp_state));
+            assert_eq!(
+                (*commuted.bits(), commuted_fp_state),
+                (*result.bits(), fp_state),
+                "mul({:?}, {:?}) {:?}",
+                power_of_two,
+                value,
+                rounding_mode
+            );
+            let quotient = &value_value / &power_of_two_value;
+            let mut fp_state = FPState::default();
+            let result = value.div(&power_of_two, Some(rounding_mode), Some(&mut fp_state));
+            check("div", result, fp_state, quotient);
         }
-    };
-}
+        // the significand is still rounded when precision control is enabled
+        let value = F80::from_bits(0x3FFF_FFFF_FFFF_FFFF_FFFF);
+        let two = F80::from_bits(0x4000_8000_0000_0000_0000);
+        let mut fp_state = FPState {
+            precision_control: Some(53),
+            ..FPState::default()
+        };
+        let result = value.mul(&two, None, Some(&mut fp_state));
+        assert_eq!(*result.bits(), 0x4001_8000_0000_0000_0000);
+        assert!(fp_state.status_flags.inexact());
+        assert_eq!(
+            *value.mul(&two, None, None).bits(),
+            0x4000_FFFF_FFFF_FFFF_FFFF
+        );
+    }
 
-impl_dynamic_float_binary_op_trait!(Add, add, AddAssign, add_assign, add_with_rounding_mode);
-impl_dynamic_float_binary_op_trait!(Sub, sub, SubAssign, sub_assign, sub_with_rounding_mode);
-impl_dynamic_float_binary_op_trait!(Mul, mul, MulAssign, mul_assign, mul_with_rounding_mode);
-impl_dynamic_float_binary_op_trait!(Div, div, DivAssign, div_assign, div_with_rounding_mode);
+    #[test]
+    fn test_is_power_of_two() {
+        for &(bits, expected) in &[
+            (0x3F80_0000u32, true),
+            (0x4000_0000, true),
+            (0x0080_0000, true),
+            (0x7F00_0000, true),
+            (0x0000_0001, true),
+            (0x0040_0000, true),
+            (0x0000_0100, true),
+            (0x3FC0_0000, false),
+            (0x3F80_0001, false),
+            (0x007F_FFFF, false),
+            (0x0000_0003, false),
+            (0xBF80_0000, false),
+            (0x8000_0001, false),
+            (0x0000_0000, false),
+            (0x8000_0000, false),
+            (0x7F80_0000, false),
+            (0x7FC0_0000, false),
+            (0x7F80_0001, false),
+        ] {
+            let value = F32::from_bits(bits);
+            assert_eq!(value.is_power_of_two(), expected, "{:?}", value);
+            let ratio = value.to_ratio();
+            // in lowest terms, a power of two has a power of two numerator
+            // and denominator, one of which is one
+            let via_ratio = ratio.is_some_and(|ratio| {
+                let product = ratio.numer() * ratio.denom();
+                ratio.is_positive() && (&product & (&product - BigInt::one())).is_zero()
+            });
+            assert_eq!(via_ratio, expected, "{:?}", value);
+        }
+        // explicit leading bit
+        assert!(F80::from_bits(0x3FFF_8000_0000_0000_0000).is_power_of_two());
+        assert!(!F80::from_bits(0x3FFF_C000_0000_0000_0000).is_power_of_two());
+        // pseudo-denormal with only the explicit leading bit set
+        assert!(F80::from_bits(0x0000_8000_0000_0000_0000).is_power_of_two());
+        assert!(F80::from_bits(0x0000_0000_0000_0000_0001).is_power_of_two());
+        // unnormal
+        assert!(!F80::from_bits(0x3FFF_0000_0000_0000_0000).is_power_of_two());
+    }
 
-impl Neg for &'_ DynamicFloat {
-    type Output = DynamicFloat;
-    fn neg(self) -> DynamicFloat {
-        let mut retval = self.clone();
-        retval.neg_assign();
-        retval
+    #[test]
+    fn test_fp_state_invariants_guard() {
+        let mut fp_state = FPState {
+            status_flags: StatusFlags::empty().signal_inexact(),
+            ..FPState::default()
+        };
+        {
+            let mut guard = FPStateInvariantsGuard::new(&mut fp_state);
+            guard.status_flags = guard.status_flags.signal_overflow();
+        }
+        assert_eq!(
+            fp_state.status_flags,
+            StatusFlags::empty().signal_overflow_with_inexact()
+        );
     }
-}
 
-impl Neg for DynamicFloat {
-    type Output = DynamicFloat;
-    fn neg(mut self) -> DynamicFloat {
-        self.neg_assign();
-        self
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "operation cleared status flags")]
+    fn test_fp_state_invariants_guard_cleared_flags() {
+        let mut fp_state = FPState {
+            status_flags: StatusFlags::empty().signal_inexact(),
+            ..FPState::default()
+        };
+        let mut guard = FPStateInvariantsGuard::new(&mut fp_state);
+        guard.status_flags = StatusFlags::empty();
     }
-}
 
-#[cfg(test)]
-mod tests {
-    #![allow(clippy::cognitive_complexity)]
-    use super::*;
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "operation changed the floating-point modes")]
+    fn test_fp_state_invariants_guard_changed_modes() {
+        let mut fp_state = FPState::default();
+        let mut guard = FPStateInvariantsGuard::new(&mut fp_state);
+        guard.rounding_mode = RoundingMode::TowardZero;
+    }
 
     #[test]
-    fn test_debug() {
-        assert_eq!(
-            &format!("{:?}", F16::from_bits(0x0000)),
-            "Float { traits: F16Traits, bits: 0x0000, sign: Positive, \
-             exponent_field: 0x00, mantissa_field: 0x000, class: PositiveZero }",
-        );
-        assert_eq!(
-            &format!("{:?}", F16::from_bits(0x8000)),
-            "Float { traits: F16Traits, bits: 0x8000, sign: Negative, \
-             exponent_field: 0x00, mantissa_field: 0x000, class: NegativeZero }",
-        );
-        assert_eq!(
-            &format!("{:?}", F16::from_bits(0xFC00)),
-            "Float { traits: F16Traits, bits: 0xFC00, sign: Negative, \
-             exponent_field: 0x1F, mantissa_field: 0x000, class: NegativeInfinity }",
-        );
-        assert_eq!(
-            &format!("{:?}", F16::from_bits(0xFE00)),
-            "Float { traits: F16Traits, bits: 0xFE00, sign: Negative, \
-             exponent_field: 0x1F, mantissa_field: 0x200, class: QuietNaN }",
+    fn test_round_neighbors() {
+        fn case(value: Ratio<BigInt>, expected: (u32, u32, Ordering)) {
+            let (down, up, ordering) = F32::round_neighbors(&value.clone().into());
+            assert_eq!((*down.bits(), *up.bits(), ordering), expected, "{}", value);
+        }
+        let ratio = |numer: i32, denom: i32| Ratio::new(BigInt::from(numer), BigInt::from(denom));
+        let power_of_two = |exponent: i32| {
+            if exponent < 0 {
+                Ratio::new(BigInt::one(), BigInt::one() << -exponent as usize)
+            } else {
+                Ratio::from(BigInt::one() << exponent as usize)
+            }
+        };
+        case(ratio(1, 3), (0x3EAA_AAAA, 0x3EAA_AAAB, Ordering::Greater));
+        case(ratio(-1, 3), (0xBEAA_AAAB, 0xBEAA_AAAA, Ordering::Less));
+        case(ratio(1, 1), (0x3F80_0000, 0x3F80_0000, Ordering::Equal));
+        case(ratio(0, 1), (0x0000_0000, 0x0000_0000, Ordering::Equal));
+        case(
+            ratio(1, 1) + power_of_two(-24),
+            (0x3F80_0000, 0x3F80_0001, Ordering::Equal),
         );
-        assert_eq!(
-            &format!("{:?}", F16::from_bits(0x0001)),
-            "Float { traits: F16Traits, bits: 0x0001, sign: Positive, \
-             exponent_field: 0x00, mantissa_field: 0x001, class: PositiveSubnormal }",
+        case(
+            ratio(1, 1) + power_of_two(-25),
+            (0x3F80_0000, 0x3F80_0001, Ordering::Less),
         );
-        assert_eq!(
-            &format!("{:?}", F16::from_bits(0x3C00)),
-            "Float { traits: F16Traits, bits: 0x3C00, sign: Positive, \
-             exponent_field: 0x0F, mantissa_field: 0x000, class: PositiveNormal }",
+        case(
+            power_of_two(-200),
+            (0x0000_0000, 0x0000_0001, Ordering::Less),
         );
-        assert_eq!(
-            &format!(
-                "{:?}",
-                F16WithPlatformProperties::from_bits_and_traits(
-                    0x1234,
-                    F16WithPlatformPropertiesTraits(PlatformProperties::RISC_V)
-                )
-            ),
-            "Float { traits: F16WithPlatformPropertiesTraits(PlatformProperties::RISC_V), \
-             bits: 0x1234, sign: Positive, exponent_field: 0x04, \
-             mantissa_field: 0x234, class: PositiveNormal }",
+        case(
+            -power_of_two(-200),
+            (0x8000_0001, 0x8000_0000, Ordering::Greater),
         );
-        assert_eq!(
-            &format!(
-                "{:?}",
-                F16WithPlatformProperties::from_bits_and_traits(
-                    0x1234,
-                    F16WithPlatformPropertiesTraits(PlatformProperties::SPARC)
-                )
-            ),
-            "Float { traits: F16WithPlatformPropertiesTraits(PlatformProperties::SPARC), \
-             bits: 0x1234, sign: Positive, exponent_field: 0x04, \
-             mantissa_field: 0x234, class: PositiveNormal }",
+        // between the largest finite value and the overflow threshold
+        case(
+            power_of_two(128) - power_of_two(103),
+            (0x7F7F_FFFF, 0x7F80_0000, Ordering::Equal),
         );
-        assert_eq!(
-            &format!(
-                "{:?}",
-                F16WithPlatformProperties::from_bits_and_traits(
-                    0x1234,
-                    F16WithPlatformPropertiesTraits(PlatformProperties::X86_SSE)
-                )
-            ),
-            "Float { traits: F16WithPlatformPropertiesTraits(PlatformProperties::X86_SSE), \
-             bits: 0x1234, sign: Positive, exponent_field: 0x04, \
-             mantissa_field: 0x234, class: PositiveNormal }",
+        case(
+            power_of_two(128),
+            (0x7F7F_FFFF, 0x7F80_0000, Ordering::Greater),
         );
-        assert_eq!(
-            &format!(
-                "{:?}",
-                F16WithPlatformProperties::from_bits_and_traits(
-                    0x1234,
-                    F16WithPlatformPropertiesTraits(PlatformProperties::HPPA)
-                )
-            ),
-            "Float { traits: F16WithPlatformPropertiesTraits(PlatformProperties::HPPA), \
-             bits: 0x1234, sign: Positive, exponent_field: 0x04, \
-             mantissa_field: 0x234, class: PositiveNormal }",
+        case(
+            -power_of_two(200),
+            (0xFF80_0000, 0xFF7F_FFFF, Ordering::Less),
         );
-        assert_eq!(
-            &format!(
-                "{:?}",
-                F16WithPlatformProperties::from_bits_and_traits(
-                    0x1234,
-                    F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
-                )
-            ),
-            "Float { traits: F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY), \
-             bits: 0x1234, sign: Positive, exponent_field: 0x04, \
-             mantissa_field: 0x234, class: PositiveNormal }",
+    }
+
+    #[test]
+    fn test_is_rounding_mode_sensitive() {
+        fn case(value: Ratio<BigInt>, expected: bool) {
+            let value: RealAlgebraicNumber = value.into();
+            assert_eq!(F16::is_rounding_mode_sensitive(&value), expected);
+            let results: Vec<u16> = [
+                RoundingMode::TiesToEven,
+                RoundingMode::TowardZero,
+                RoundingMode::TowardNegative,
+                RoundingMode::TowardPositive,
+                RoundingMode::TiesToAway,
+                RoundingMode::TiesToOdd,
+            ]
+            .iter()
+            .map(|&rounding_mode| {
+                *F16::from_real_algebraic_number(&value, Some(rounding_mode), None).bits()
+            })
+            .collect();
+            let differs = results.iter().any(|bits| *bits != results[0]);
+            assert_eq!(differs, expected);
+        }
+        let ratio = |numer: i64, denom: i64| Ratio::new(BigInt::from(numer), BigInt::from(denom));
+        case(ratio(0, 1), false);
+        case(ratio(1, 1), false);
+        case(ratio(-3, 2), false);
+        case(ratio(65504, 1), false);
+        // smallest subnormal
+        case(ratio(1, 1 << 24), false);
+        case(ratio(1, 3), true);
+        case(ratio(-1, 3), true);
+        // halfway between 1 and the next value
+        case(ratio(2049, 2048), true);
+        case(ratio(1, 1 << 26), true);
+        case(ratio(-1, 1 << 26), true);
+        // rounds to either the largest finite value or infinity
+        case(ratio(65505, 1), true);
+        case(ratio(1 << 20, 1), true);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn test_from_real_algebraic_number_stochastic() {
+        /// returns the given values from `next_u64`, in order
+        struct SequenceRng(Vec<u64>);
+        impl rand_core::RngCore for SequenceRng {
+            fn next_u32(&mut self) -> u32 {
+                self.next_u64() as u32
+            }
+            fn next_u64(&mut self) -> u64 {
+                assert!(!self.0.is_empty(), "ran out of random numbers");
+                self.0.remove(0)
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                rand_core::impls::fill_bytes_via_next(self, dest)
+            }
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+        fn case(value: Ratio<BigInt>, random: &[u64], expected: (u32, StatusFlags)) {
+            let mut rng = SequenceRng(random.into());
+            let mut fp_state = FPState::default();
+            let result = F32::from_real_algebraic_number_stochastic(
+                &value.clone().into(),
+                &mut rng,
+                Some(&mut fp_state),
+            );
+            assert_eq!(
+                (*result.bits(), fp_state.status_flags),
+                expected,
+                "{} {:?}",
+                value,
+                random
+            );
+            assert!(rng.0.is_empty(), "{} {:?}", value, random);
+        }
+        let ratio = |numer: i32, denom: i32| Ratio::new(BigInt::from(numer), BigInt::from(denom));
+        let inexact = StatusFlags::empty().signal_inexact();
+        // exact values don't use the random number generator
+        case(ratio(1, 1), &[], (0x3F80_0000, StatusFlags::empty()));
+        // a quarter of the way from 1 to the next larger value:
+        // rounds up iff the random number is less than 2^62
+        let quarter = ratio(1, 1) + Ratio::new(BigInt::one(), BigInt::one() << 25);
+        case(quarter.clone(), &[0], (0x3F80_0001, inexact));
+        case(quarter.clone(), &[(1 << 62) - 1], (0x3F80_0001, inexact));
+        case(quarter.clone(), &[1 << 62], (0x3F80_0000, inexact));
+        case(quarter.clone(), &[u64::MAX], (0x3F80_0000, inexact));
+        case(-quarter, &[0], (0xBF80_0000, inexact));
+        // a third of the way from 1/2 to the next larger value needs more than
+        // 64 random bits when the first ones match
+        let third = ratio(1, 2) + Ratio::new(BigInt::one(), BigInt::from(3) << 24);
+        case(
+            third.clone(),
+            &[0x5555_5555_5555_5554],
+            (0x3F00_0001, inexact),
         );
-        assert_eq!(
-            &format!(
-                "{:?}",
-                F16WithPlatformProperties::from_bits_and_traits(
-                    0x1234,
-                    F16WithPlatformPropertiesTraits(PlatformProperties {
-                        canonical_nan_sign: Sign::Negative,
-                        ..PlatformProperties::MIPS_LEGACY
-                    })
-                )
+        case(
+            third.clone(),
+            &[0x5555_5555_5555_5556],
+            (0x3F00_0000, inexact),
+        );
+        case(
+            third.clone(),
+            &[0x5555_5555_5555_5555, 0x5555_5555_5555_5554],
+            (0x3F00_0001, inexact),
+        );
+        case(
+            third,
+            &[0x5555_5555_5555_5555, 0x5555_5555_5555_5556],
+            (0x3F00_0000, inexact),
+        );
+        // halfway between the largest finite value and the overflow threshold.
+        // overflow is only signaled when rounding up to infinity
+        let big = Ratio::from((BigInt::one() << 128) - (BigInt::one() << 103));
+        case(
+            big.clone(),
+            &[0],
+            (
+                0x7F80_0000,
+                StatusFlags::empty().signal_overflow_with_inexact(),
             ),
-            "Float { traits: F16WithPlatformPropertiesTraits(PlatformProperties { \
-             canonical_nan_sign: Negative, canonical_nan_mantissa_msb: false, \
-             canonical_nan_mantissa_second_to_msb: true, \
-             canonical_nan_mantissa_rest: true, \
-             std_bin_ops_nan_propagation_mode: FirstSecondPreferringSNaN, \
-             fma_nan_propagation_mode: FirstSecondThirdPreferringSNaN, \
-             fma_inf_zero_qnan_result: CanonicalAndGenerateInvalid, \
-             round_to_integral_nan_propagation_mode: First, \
-             next_up_or_down_nan_propagation_mode: First, \
-             scale_b_nan_propagation_mode: First, \
-             sqrt_nan_propagation_mode: First, \
-             float_to_float_conversion_nan_propagation_mode: RetainMostSignificantBits, \
-             rsqrt_nan_propagation_mode: First, \
-             quiet_nan_format: MIPSLegacy }), \
-             bits: 0x1234, sign: Positive, exponent_field: 0x04, \
-             mantissa_field: 0x234, class: PositiveNormal }",
         );
+        case(big, &[u64::MAX], (0x7F7F_FFFF, inexact));
     }
 
     #[test]
-    fn test_class() {
-        use FloatClass::*;
-        assert_eq!(F16::from_bits(0x0000).class(), PositiveZero);
-        assert_eq!(F16::from_bits(0x0001).class(), PositiveSubnormal);
-        assert_eq!(F16::from_bits(0x03FF).class(), PositiveSubnormal);
-        assert_eq!(F16::from_bits(0x0400).class(), PositiveNormal);
-        assert_eq!(F16::from_bits(0x3C00).class(), PositiveNormal);
-        assert_eq!(F16::from_bits(0x7BFF).class(), PositiveNormal);
-        assert_eq!(F16::from_bits(0x7C00).class(), PositiveInfinity);
-        assert_eq!(F16::from_bits(0x7C01).class(), SignalingNaN);
-        assert_eq!(F16::from_bits(0x7DFF).class(), SignalingNaN);
-        assert_eq!(F16::from_bits(0x7E00).class(), QuietNaN);
-        assert_eq!(F16::from_bits(0x7FFF).class(), QuietNaN);
-        assert_eq!(F16::from_bits(0x8000).class(), NegativeZero);
-        assert_eq!(F16::from_bits(0x8001).class(), NegativeSubnormal);
-        assert_eq!(F16::from_bits(0x83FF).class(), NegativeSubnormal);
-        assert_eq!(F16::from_bits(0x8400).class(), NegativeNormal);
-        assert_eq!(F16::from_bits(0xBC00).class(), NegativeNormal);
-        assert_eq!(F16::from_bits(0xFBFF).class(), NegativeNormal);
-        assert_eq!(F16::from_bits(0xFC00).class(), NegativeInfinity);
-        assert_eq!(F16::from_bits(0xFC01).class(), SignalingNaN);
-        assert_eq!(F16::from_bits(0xFDFF).class(), SignalingNaN);
-        assert_eq!(F16::from_bits(0xFE00).class(), QuietNaN);
-        assert_eq!(F16::from_bits(0xFFFF).class(), QuietNaN);
+    #[cfg(not(feature = "rational-only"))]
+    fn test_exact_value_tracking() {
+        let properties = FloatProperties::STANDARD_16;
+        let one = DynamicFloat::from_u8(1, None, None, properties).with_exact_value_tracking();
+        let two = DynamicFloat::from_u8(2, None, None, properties).with_exact_value_tracking();
+        let three = DynamicFloat::from_u8(3, None, None, properties).with_exact_value_tracking();
+        assert_eq!(one.exact_value, Some(RealAlgebraicNumber::one()));
+        assert_eq!(DynamicFloat::positive_zero(properties).exact_value, None);
+        let third = &one / &three;
         assert_eq!(
-            Float::from_bits_and_traits(
-                0x7C01,
-                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
-            )
-            .class(),
-            QuietNaN
+            third.exact_value,
+            Some(RealAlgebraicNumber::from(Ratio::new(1, 3)))
         );
+        assert_ne!(third.to_real_algebraic_number(), third.exact_value);
+        assert!(third.fp_state.status_flags.inexact());
         assert_eq!(
-            Float::from_bits_and_traits(
-                0x7DFF,
-                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
-            )
-            .class(),
-            QuietNaN
+            (&third * &three).exact_value,
+            Some(RealAlgebraicNumber::one())
         );
+        let sqrt_2 = two.sqrt(None);
         assert_eq!(
-            Float::from_bits_and_traits(
-                0x7E00,
-                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
-            )
-            .class(),
-            SignalingNaN
+            sqrt_2.exact_value,
+            Some(RealAlgebraicNumber::from(2).pow((1, 2)))
         );
+        let product = sqrt_2.mul_with_rounding_mode(&sqrt_2, None);
+        assert_eq!(product.exact_value, Some(RealAlgebraicNumber::from(2)));
+        let fma = third.fused_mul_add(&three, &-&one, None);
+        assert_eq!(fma.exact_value, Some(RealAlgebraicNumber::zero()));
         assert_eq!(
-            Float::from_bits_and_traits(
-                0x7FFF,
-                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
-            )
-            .class(),
-            SignalingNaN
+            (-&third).abs().exact_value,
+            Some(RealAlgebraicNumber::from(Ratio::new(1, 3)))
         );
         assert_eq!(
-            Float::from_bits_and_traits(
-                0xFC01,
-                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
-            )
-            .class(),
-            QuietNaN
+            third.copy_sign(&F16::negative_zero()).exact_value,
+            Some(RealAlgebraicNumber::from(Ratio::new(-1, 3)))
         );
+        let converted = third.convert_to_dynamic_float(None, FloatProperties::STANDARD_32);
+        assert_eq!(converted.exact_value, third.exact_value);
+        // operations that don't track, or whose exact result isn't real
         assert_eq!(
-            Float::from_bits_and_traits(
-                0xFDFF,
-                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
-            )
-            .class(),
-            QuietNaN
+            (&one / &DynamicFloat::positive_zero(properties)).exact_value,
+            None
         );
         assert_eq!(
-            Float::from_bits_and_traits(
-                0xFE00,
-                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
-            )
-            .class(),
-            SignalingNaN
+            (&one / &(&one - &one).with_exact_value_tracking()).exact_value,
+            None
         );
+        assert_eq!((-&one).sqrt(None).exact_value, None);
+        assert_eq!(third.round_to_integral(false, None).exact_value, None);
         assert_eq!(
-            Float::from_bits_and_traits(
-                0xFFFF,
-                F16WithPlatformPropertiesTraits(PlatformProperties::MIPS_LEGACY)
-            )
-            .class(),
-            SignalingNaN
+            (&one + &DynamicFloat::from(one.value.clone())).exact_value,
+            None
         );
     }
 
-    #[test]
-    fn test_to_ratio() {
-        macro_rules! test_case {
-            ($value:expr, $expected_ratio:expr) => {
-                let value: F16 = $value;
-                let expected_ratio: Option<Ratio<i128>> = $expected_ratio;
-                println!("value: {:?}", value);
-                println!("expected_ratio: {:?}", expected_ratio);
-                let ratio = value.to_ratio();
-                println!("ratio: {:?}", ratio.as_ref().map(ToString::to_string));
-                let expected_ratio = expected_ratio.map(|v| {
-                    let (n, d) = v.into();
-                    Ratio::new(n.into(), d.into())
-                });
-                assert!(ratio == expected_ratio);
-            };
-        }
-
-        let r = |n, d| Some(Ratio::new(n, d));
-
-        test_case!(F16::from_bits(0x0000), r(0, 1));
-        test_case!(F16::from_bits(0x0001), r(1, 1 << 24));
-        test_case!(F16::from_bits(0x03FF), r(1023, 1 << 24));
-        test_case!(F16::from_bits(0x0400), r(1, 1 << 14));
-        test_case!(F16::from_bits(0x3C00), r(1, 1));
-        test_case!(F16::from_bits(0x7BFF), r(65504, 1));
-        test_case!(F16::from_bits(0x7C00), None);
-        test_case!(F16::from_bits(0x7C01), None);
-        test_case!(F16::from_bits(0x7DFF), None);
-        test_case!(F16::from_bits(0x7E00), None);
-        test_case!(F16::from_bits(0x7FFF), None);
-        test_case!(F16::from_bits(0x8000), r(0, 1));
-        test_case!(F16::from_bits(0x8001), r(-1, 1 << 24));
-        test_case!(F16::from_bits(0x83FF), r(-1023, 1 << 24));
-        test_case!(F16::from_bits(0x8400), r(-1, 1 << 14));
-        test_case!(F16::from_bits(0xBC00), r(-1, 1));
-        test_case!(F16::from_bits(0xFBFF), r(-65504, 1));
-        test_case!(F16::from_bits(0xFC00), None);
-        test_case!(F16::from_bits(0xFC01), None);
-        test_case!(F16::from_bits(0xFDFF), None);
-        test_case!(F16::from_bits(0xFE00), None);
-        test_case!(F16::from_bits(0xFFFF), None);
-    }
-
-    #[test]
-    fn test_log_b() {
-        macro_rules! test_case {
-            ($value:expr, $expected_result:expr) => {
-                let value: F16 = $value;
-                let expected_result: Option<i32> = $expected_result;
-                let expected_status_flags: StatusFlags = if expected_result.is_some() {
-                    StatusFlags::empty()
-                } else {
-                    StatusFlags::empty().signal_invalid_operation()
-                };
-                println!("value: {:?}", value);
-                println!("expected_result: {:?}", expected_result);
-                println!("expected_status_flags: {:?}", expected_status_flags);
-                let mut fp_state = FPState::default();
-                let result = value.log_b(Some(&mut fp_state));
-                println!("result: {:?}", result.as_ref().map(ToString::to_string));
-                let expected_result: Option<BigInt> = expected_result.map(Into::into);
-                println!("status_flags: {:?}", fp_state.status_flags);
-                assert!(result == expected_result);
-                assert!(fp_state.status_flags == expected_status_flags);
-            };
-        }
-
-        test_case!(F16::from_bits(0x0000), None);
-        test_case!(F16::from_bits(0x0001), Some(-24));
-        test_case!(F16::from_bits(0x0002), Some(-23));
-        test_case!(F16::from_bits(0x03FF), Some(-15));
-        test_case!(F16::from_bits(0x0400), Some(-14));
-        test_case!(F16::from_bits(0x3C00), Some(0));
-        test_case!(F16::from_bits(0x7BFF), Some(15));
-        test_case!(F16::from_bits(0x7C00), None);
-        test_case!(F16::from_bits(0x7C01), None);
-        test_case!(F16::from_bits(0x7DFF), None);
-        test_case!(F16::from_bits(0x7E00), None);
-        test_case!(F16::from_bits(0x7FFF), None);
-        test_case!(F16::from_bits(0x8000), None);
-        test_case!(F16::from_bits(0x8001), Some(-24));
-        test_case!(F16::from_bits(0x8002), Some(-23));
-        test_case!(F16::from_bits(0x83FF), Some(-15));
-        test_case!(F16::from_bits(0x8400), Some(-14));
-        test_case!(F16::from_bits(0xBC00), Some(0));
-        test_case!(F16::from_bits(0xFBFF), Some(15));
-        test_case!(F16::from_bits(0xFC00), None);
-        test_case!(F16::from_bits(0xFC01), None);
-        test_case!(F16::from_bits(0xFDFF), None);
-        test_case!(F16::from_bits(0xFE00), None);
-        test_case!(F16::from_bits(0xFFFF), None);
-    }
-
     // FIXME: add more tests
 }
-
-macro_rules! doctest {
-    ($x:expr) => {
-        #[doc = $x]
-        extern {}
-    };
-}
-
-doctest!(include_str!("../README.md"));
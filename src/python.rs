@@ -4,26 +4,38 @@
 
 use crate::{
     python_macros::PythonEnum, BinaryNaNPropagationMode, DynamicFloat, ExceptionHandlingMode,
-    FMAInfZeroQNaNResult, FPState, FloatClass, FloatProperties,
-    FloatToFloatConversionNaNPropagationMode, PlatformProperties, QuietNaNFormat, RoundingMode,
-    Sign, StatusFlags, TernaryNaNPropagationMode, TininessDetectionMode, UnaryNaNPropagationMode,
-    UpOrDown,
+    FMAInfZeroQNaNResult, FPModes, FPState, Float, FloatClass, FloatProperties,
+    FloatToFloatConversionNaNPropagationMode, FlushToZeroMode, OpKind, PlatformProperties,
+    QuietNaNFormat, RoundingMode, Sign, StatusFlags, TernaryNaNPropagationMode,
+    TininessDetectionMode, UnaryNaNPropagationMode, UpOrDown,
 };
 use num_bigint::{BigInt, BigUint};
 use pyo3::{
     basic::CompareOp,
     exceptions::{TypeError, ValueError},
     prelude::*,
-    types::PyAny,
-    PyNativeType, PyNumberProtocol, PyObjectProtocol,
+    types::{PyAny, PyBytes, PyType},
+    wrap_pyfunction, PyContextProtocol, PyNativeType, PyNumberProtocol, PyObjectProtocol,
 };
 use std::{
     borrow::Cow,
+    cell::Cell,
+    cmp::Ordering,
     fmt::{self, Write as _},
     ops::Deref,
     sync::Arc,
 };
 
+thread_local! {
+    static DEFAULT_ROUNDING_MODE: Cell<Option<RoundingMode>> = Cell::new(None);
+}
+
+/// get the rounding mode to use when `rounding_mode` is `None`,
+/// taking the innermost active `rounding_mode` context manager into account.
+fn resolve_rounding_mode(rounding_mode: Option<RoundingMode>) -> Option<RoundingMode> {
+    rounding_mode.or_else(|| DEFAULT_ROUNDING_MODE.with(Cell::get))
+}
+
 pub(crate) trait ToPythonRepr {
     fn to_python_repr(&self) -> Cow<str>;
 }
@@ -94,6 +106,8 @@ pub(crate) fn simple_soft_float(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<PyStatusFlags>()?;
     m.add_class::<PyFloatProperties>()?;
     m.add_class::<PyFPState>()?;
+    m.add_class::<PyFPModes>()?;
+    m.add_class::<PyRoundingModeContext>()?;
     BinaryNaNPropagationMode::add_to_module(py, m)?;
     FloatToFloatConversionNaNPropagationMode::add_to_module(py, m)?;
     FMAInfZeroQNaNResult::add_to_module(py, m)?;
@@ -107,9 +121,166 @@ pub(crate) fn simple_soft_float(py: Python, m: &PyModule) -> PyResult<()> {
     PyPlatformProperties::add_to_module(py, m)?;
     ExceptionHandlingMode::add_to_module(py, m)?;
     TininessDetectionMode::add_to_module(py, m)?;
+    FlushToZeroMode::add_to_module(py, m)?;
+    OpKind::add_to_module(py, m)?;
+    m.add_wrapped(wrap_pyfunction!(convert_array))?;
+    m.add_wrapped(wrap_pyfunction!(sqrt))?;
+    m.add_wrapped(wrap_pyfunction!(cbrt))?;
+    m.add_wrapped(wrap_pyfunction!(hypot))?;
+    m.add_wrapped(wrap_pyfunction!(mean))?;
+    m.add_wrapped(wrap_pyfunction!(possible_flags))?;
     Ok(())
 }
 
+/// context manager that sets the default rounding mode for the current thread.
+///
+/// Inside the `with` block, operations called with `rounding_mode=None` use
+/// `rounding_mode` instead of the rounding mode from their `FPState`.
+/// `with` blocks can be nested, the innermost one takes precedence.
+#[pyclass(name = rounding_mode, module = "simple_soft_float")]
+#[text_signature = "(rounding_mode)"]
+struct PyRoundingModeContext {
+    rounding_mode: RoundingMode,
+    saved_rounding_modes: Vec<Option<RoundingMode>>,
+}
+
+#[pymethods]
+impl PyRoundingModeContext {
+    #[new]
+    fn new(rounding_mode: RoundingMode) -> Self {
+        PyRoundingModeContext {
+            rounding_mode,
+            saved_rounding_modes: Vec::new(),
+        }
+    }
+    /// the rounding mode set by this context manager
+    #[getter]
+    fn rounding_mode(&self) -> RoundingMode {
+        self.rounding_mode
+    }
+}
+
+#[pyproto]
+impl<'p> PyContextProtocol<'p> for PyRoundingModeContext {
+    fn __enter__(&mut self) -> PyResult<RoundingMode> {
+        let saved_rounding_mode =
+            DEFAULT_ROUNDING_MODE.with(|default| default.replace(Some(self.rounding_mode)));
+        self.saved_rounding_modes.push(saved_rounding_mode);
+        Ok(self.rounding_mode)
+    }
+    fn __exit__(
+        &mut self,
+        _ty: Option<&'p PyType>,
+        _value: Option<&'p PyAny>,
+        _traceback: Option<&'p PyAny>,
+    ) -> PyResult<bool> {
+        let saved_rounding_mode = self
+            .saved_rounding_modes
+            .pop()
+            .ok_or_else(|| PyErr::new::<ValueError, _>("__exit__ called without __enter__"))?;
+        DEFAULT_ROUNDING_MODE.with(|default| default.set(saved_rounding_mode));
+        Ok(false)
+    }
+}
+
+/// get the square-root of `value`
+#[pyfunction]
+#[text_signature = "(value, rounding_mode=None)"]
+#[args(rounding_mode = "None")]
+fn sqrt(value: &PyDynamicFloat, rounding_mode: Option<RoundingMode>) -> DynamicFloat {
+    value.value.sqrt(resolve_rounding_mode(rounding_mode))
+}
+
+/// get the cube-root of `value`
+#[pyfunction]
+#[text_signature = "(value, rounding_mode=None)"]
+#[args(rounding_mode = "None")]
+fn cbrt(value: &PyDynamicFloat, rounding_mode: Option<RoundingMode>) -> DynamicFloat {
+    value.value.cbrt(resolve_rounding_mode(rounding_mode))
+}
+
+/// compute `sqrt(x * x + y * y)` rounding only once, returning the result
+#[pyfunction]
+#[text_signature = "(x, y, rounding_mode=None)"]
+#[args(rounding_mode = "None")]
+fn hypot(
+    x: &PyDynamicFloat,
+    y: &PyDynamicFloat,
+    rounding_mode: Option<RoundingMode>,
+) -> PyResult<DynamicFloat> {
+    x.hypot(y.clone(), rounding_mode)
+}
+
+/// compute the arithmetic mean of the `DynamicFloat` values in the iterable
+/// `values`, rounding only once. `properties` is the format of the values and
+/// of the result.
+///
+/// the mean of no values is NaN and signals invalid operation.
+#[pyfunction]
+#[text_signature = "(values, properties, rounding_mode=None)"]
+#[args(rounding_mode = "None")]
+fn mean(
+    values: &PyAny,
+    properties: FloatProperties,
+    rounding_mode: Option<RoundingMode>,
+) -> PyResult<DynamicFloat> {
+    let mut dynamic_values = Vec::new();
+    for value in values.iter()? {
+        let value: &PyDynamicFloat = value?.extract()?;
+        properties.check_compatibility(value.value.properties())?;
+        dynamic_values.push(value.value.clone());
+    }
+    Ok(DynamicFloat::checked_mean(
+        &dynamic_values,
+        resolve_rounding_mode(rounding_mode),
+        properties,
+    )?)
+}
+
+/// get the union of all the `StatusFlags` that the operation `op` can signal,
+/// for any inputs, `FPState`, and `PlatformProperties`.
+#[pyfunction]
+#[text_signature = "(op)"]
+fn possible_flags(op: OpKind) -> StatusFlags {
+    op.possible_flags()
+}
+
+/// convert each bit pattern in the iterable `bits_array` from the
+/// floating-point format specified by `src_properties` to the format specified
+/// by `dest_properties`. `rounding_mode` is optional.
+///
+/// returns `(list of int, FPState)` where the status flags of all the
+/// conversions are accumulated into the returned `FPState`. This takes O(n)
+/// time and avoids creating a `DynamicFloat` object per element.
+#[pyfunction]
+#[text_signature = "(bits_array, src_properties, dest_properties, rounding_mode=None)"]
+#[args(rounding_mode = "None")]
+fn convert_array(
+    bits_array: &PyAny,
+    src_properties: FloatProperties,
+    dest_properties: FloatProperties,
+    rounding_mode: Option<RoundingMode>,
+) -> PyResult<(Vec<BigUint>, FPState)> {
+    let src_mask = src_properties.overall_mask::<BigUint>();
+    let mut fp_state = FPState::default();
+    let mut retval = Vec::new();
+    for bits in bits_array.iter()? {
+        let bits: BigUint = bits?.extract()?;
+        if bits > src_mask {
+            return Err(PyErr::new::<ValueError, _>("bits out of range"));
+        }
+        let src = Float::from_bits_and_traits(bits, src_properties);
+        let dest = Float::convert_from_float_with_traits(
+            &src,
+            resolve_rounding_mode(rounding_mode),
+            Some(&mut fp_state),
+            dest_properties,
+        );
+        retval.push(dest.into_bits());
+    }
+    Ok((retval, fp_state))
+}
+
 /// The dynamic state of a floating-point implementation
 #[pyclass(name = FPState, module = "simple_soft_float")]
 #[text_signature = "(\
@@ -118,7 +289,9 @@ pub(crate) fn simple_soft_float(py: Python, m: &PyModule) -> PyResult<()> {
                     rounding_mode=None, \
                     status_flags=None, \
                     exception_handling_mode=None, \
-                    tininess_detection_mode=None)"]
+                    tininess_detection_mode=None, \
+                    flush_to_zero_mode=None, \
+                    precision_control=None)"]
 struct PyFPState {
     value: FPState,
 }
@@ -145,7 +318,9 @@ impl PyFPState {
         rounding_mode = "None",
         status_flags = "None",
         exception_handling_mode = "None",
-        tininess_detection_mode = "None"
+        tininess_detection_mode = "None",
+        flush_to_zero_mode = "None",
+        precision_control = "None"
     )]
     fn new(
         value: Option<FPState>,
@@ -153,6 +328,8 @@ impl PyFPState {
         status_flags: Option<StatusFlags>,
         exception_handling_mode: Option<ExceptionHandlingMode>,
         tininess_detection_mode: Option<TininessDetectionMode>,
+        flush_to_zero_mode: Option<FlushToZeroMode>,
+        precision_control: Option<usize>,
     ) -> PyFPState {
         let mut value = value.unwrap_or_default();
         value.rounding_mode = rounding_mode.unwrap_or(value.rounding_mode);
@@ -161,6 +338,8 @@ impl PyFPState {
             exception_handling_mode.unwrap_or(value.exception_handling_mode);
         value.tininess_detection_mode =
             tininess_detection_mode.unwrap_or(value.tininess_detection_mode);
+        value.flush_to_zero_mode = flush_to_zero_mode.unwrap_or(value.flush_to_zero_mode);
+        value.precision_control = precision_control.or(value.precision_control);
         PyFPState { value }
     }
     /// the dynamic rounding mode -- used whenever the rounding mode is not explicitly overridden
@@ -183,11 +362,40 @@ impl PyFPState {
     fn tininess_detection_mode(&self) -> TininessDetectionMode {
         self.value.tininess_detection_mode
     }
+    /// the flush-to-zero mode
+    #[getter]
+    fn flush_to_zero_mode(&self) -> FlushToZeroMode {
+        self.value.flush_to_zero_mode
+    }
+    /// the precision control -- if not `None` or `0`, the results of
+    /// `add`, `sub`, `mul`, `div`, `sqrt`, and `fused_mul_add` are rounded to
+    /// this many significand bits while keeping the format's exponent range
+    #[getter]
+    fn precision_control(&self) -> Option<usize> {
+        self.value.precision_control
+    }
     /// combine two `FPState` values into one, returning the result
+    ///
+    /// the status flags are combined with bitwise or. raises `TypeError` if
+    /// any of the other fields differ.
     #[text_signature = "(self, other)"]
     fn merge(&self, other: FPState) -> PyResult<FPState> {
         Ok(self.value.checked_merge(other)?)
     }
+    /// get the modes (everything except for the status flags), like IEEE
+    /// 754's `saveModes`
+    #[text_signature = "(self)"]
+    fn save_modes(&self) -> FPModes {
+        self.value.save_modes()
+    }
+    /// return a copy of `self` with the modes (everything except for the
+    /// status flags) set from `modes`, like IEEE 754's `restoreModes`
+    #[text_signature = "(self, modes)"]
+    fn restore_modes(&self, modes: FPModes) -> FPState {
+        let mut retval = self.value;
+        retval.restore_modes(modes);
+        retval
+    }
 }
 
 #[pyproto]
@@ -200,6 +408,8 @@ impl PyObjectProtocol for PyFPState {
             status_flags,
             exception_handling_mode,
             tininess_detection_mode,
+            flush_to_zero_mode,
+            precision_control,
             _non_exhaustive: _,
         } = self.value;
         write!(retval, "rounding_mode={}, ", rounding_mode.to_python_repr()).unwrap();
@@ -212,10 +422,22 @@ impl PyObjectProtocol for PyFPState {
         .unwrap();
         write!(
             retval,
-            "tininess_detection_mode={}",
+            "tininess_detection_mode={}, ",
             tininess_detection_mode.to_python_repr()
         )
         .unwrap();
+        write!(
+            retval,
+            "flush_to_zero_mode={}, ",
+            flush_to_zero_mode.to_python_repr()
+        )
+        .unwrap();
+        match precision_control {
+            Some(precision_control) => {
+                write!(retval, "precision_control={}", precision_control).unwrap()
+            }
+            None => write!(retval, "precision_control=None").unwrap(),
+        }
         write!(retval, ")").unwrap();
         Ok(retval)
     }
@@ -231,6 +453,146 @@ impl PyObjectProtocol for PyFPState {
     }
 }
 
+/// The dynamic modes of a floating-point implementation: everything in
+/// `FPState` except for the status flags.
+#[pyclass(name = FPModes, module = "simple_soft_float")]
+#[text_signature = "(\
+                    value=None, \
+                    *, \
+                    rounding_mode=None, \
+                    exception_handling_mode=None, \
+                    tininess_detection_mode=None, \
+                    flush_to_zero_mode=None, \
+                    precision_control=None)"]
+struct PyFPModes {
+    value: FPModes,
+}
+
+impl<'source> FromPyObject<'source> for FPModes {
+    fn extract(object: &'source PyAny) -> PyResult<FPModes> {
+        let value: PyRef<PyFPModes> = object.extract()?;
+        Ok(value.value)
+    }
+}
+
+impl IntoPy<PyObject> for FPModes {
+    fn into_py(self, py: Python) -> PyObject {
+        PyFPModes { value: self }.into_py(py)
+    }
+}
+
+#[pymethods]
+impl PyFPModes {
+    #[new]
+    #[args(
+        value = "None",
+        "*",
+        rounding_mode = "None",
+        exception_handling_mode = "None",
+        tininess_detection_mode = "None",
+        flush_to_zero_mode = "None",
+        precision_control = "None"
+    )]
+    fn new(
+        value: Option<FPModes>,
+        rounding_mode: Option<RoundingMode>,
+        exception_handling_mode: Option<ExceptionHandlingMode>,
+        tininess_detection_mode: Option<TininessDetectionMode>,
+        flush_to_zero_mode: Option<FlushToZeroMode>,
+        precision_control: Option<usize>,
+    ) -> PyFPModes {
+        let mut value = value.unwrap_or_default();
+        value.rounding_mode = rounding_mode.unwrap_or(value.rounding_mode);
+        value.exception_handling_mode =
+            exception_handling_mode.unwrap_or(value.exception_handling_mode);
+        value.tininess_detection_mode =
+            tininess_detection_mode.unwrap_or(value.tininess_detection_mode);
+        value.flush_to_zero_mode = flush_to_zero_mode.unwrap_or(value.flush_to_zero_mode);
+        value.precision_control = precision_control.or(value.precision_control);
+        PyFPModes { value }
+    }
+    /// the dynamic rounding mode -- used whenever the rounding mode is not explicitly overridden
+    #[getter]
+    fn rounding_mode(&self) -> RoundingMode {
+        self.value.rounding_mode
+    }
+    /// the exception handling mode
+    #[getter]
+    fn exception_handling_mode(&self) -> ExceptionHandlingMode {
+        self.value.exception_handling_mode
+    }
+    /// the tininess detection mode
+    #[getter]
+    fn tininess_detection_mode(&self) -> TininessDetectionMode {
+        self.value.tininess_detection_mode
+    }
+    /// the flush-to-zero mode
+    #[getter]
+    fn flush_to_zero_mode(&self) -> FlushToZeroMode {
+        self.value.flush_to_zero_mode
+    }
+    /// the precision control -- if not `None` or `0`, the results of
+    /// `add`, `sub`, `mul`, `div`, `sqrt`, and `fused_mul_add` are rounded to
+    /// this many significand bits while keeping the format's exponent range
+    #[getter]
+    fn precision_control(&self) -> Option<usize> {
+        self.value.precision_control
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for PyFPModes {
+    fn __repr__(&self) -> PyResult<String> {
+        let mut retval = String::new();
+        write!(retval, "FPModes(").unwrap();
+        let FPModes {
+            rounding_mode,
+            exception_handling_mode,
+            tininess_detection_mode,
+            flush_to_zero_mode,
+            precision_control,
+            _non_exhaustive: _,
+        } = self.value;
+        write!(retval, "rounding_mode={}, ", rounding_mode.to_python_repr()).unwrap();
+        write!(
+            retval,
+            "exception_handling_mode={}, ",
+            exception_handling_mode.to_python_repr()
+        )
+        .unwrap();
+        write!(
+            retval,
+            "tininess_detection_mode={}, ",
+            tininess_detection_mode.to_python_repr()
+        )
+        .unwrap();
+        write!(
+            retval,
+            "flush_to_zero_mode={}, ",
+            flush_to_zero_mode.to_python_repr()
+        )
+        .unwrap();
+        match precision_control {
+            Some(precision_control) => {
+                write!(retval, "precision_control={}", precision_control).unwrap()
+            }
+            None => write!(retval, "precision_control=None").unwrap(),
+        }
+        write!(retval, ")").unwrap();
+        Ok(retval)
+    }
+    fn __richcmp__(&self, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        if let Ok(rhs) = FPModes::extract(other) {
+            match op {
+                CompareOp::Eq => return Ok((self.value == rhs).into_py(other.py())),
+                CompareOp::Ne => return Ok((self.value != rhs).into_py(other.py())),
+                CompareOp::Ge | CompareOp::Gt | CompareOp::Le | CompareOp::Lt => {}
+            };
+        }
+        Ok(other.py().NotImplemented())
+    }
+}
+
 /// IEEE 754 floating-point value with attached `FPState`
 #[pyclass(name = DynamicFloat, module = "simple_soft_float")]
 #[derive(Clone)]
@@ -302,6 +664,86 @@ impl PyDynamicFloat {
     fn bits(&self) -> BigUint {
         self.value.bits().clone()
     }
+    /// returns `True` if `self` and `other` have identical bits and `FloatProperties`.
+    ///
+    /// unlike `==`, this treats NaNs with the same payload as equal and
+    /// distinguishes `-0.0` from `+0.0`. `fp_state` is not compared.
+    #[text_signature = "($self, other)"]
+    fn bit_equal(&self, other: &PyDynamicFloat) -> bool {
+        self.value.value.bit_eq(&other.value.value)
+    }
+    /// get the mathematical value of `self` as `(sign, numerator, denominator)`
+    /// with the fraction in lowest terms. returns `None` for NaN and infinity.
+    #[text_signature = "($self)"]
+    fn to_ratio_parts(&self) -> Option<(Sign, BigUint, BigUint)> {
+        self.value.to_ratio_parts()
+    }
+    /// get the mathematical value of `self` as `(sign, mantissa, exponent)`
+    /// where the value is `sign * mantissa * 2 ** exponent`.
+    /// returns `None` for NaN and infinity.
+    #[text_signature = "($self)"]
+    fn to_scientific_ratio(&self) -> Option<(Sign, BigUint, i64)> {
+        self.value.to_scientific_ratio()
+    }
+    /// get the value of `self` as an integer multiple of the format's
+    /// smallest positive subnormal value. returns `None` for NaN and
+    /// infinity.
+    #[text_signature = "($self)"]
+    fn to_ulp_grid_integer(&self) -> Option<BigInt> {
+        self.value.to_ulp_grid_integer()
+    }
+    /// get the value of `self` as `(hi, lo)`, the unevaluated sum of two
+    /// `float`s (a double-double). returns `None` for NaN and infinity and
+    /// when two `float`s aren't enough to represent `self` exactly.
+    #[text_signature = "($self)"]
+    fn to_double_double(&self) -> Option<(f64, f64)> {
+        self.value.to_double_double()
+    }
+    /// get the underlying bits as a `bytes` object.
+    /// `byteorder` is either `"little"` or `"big"`.
+    #[text_signature = "($self, byteorder='little')"]
+    #[args(byteorder = "\"little\"")]
+    fn to_bytes(&self, py: Python, byteorder: &str) -> PyResult<PyObject> {
+        let bytes = match byteorder {
+            "little" => self.value.to_le_bytes(),
+            "big" => self.value.to_be_bytes(),
+            _ => {
+                return Err(PyErr::new::<ValueError, _>(
+                    "byteorder must be either 'little' or 'big'",
+                ))
+            }
+        };
+        Ok(PyBytes::new(py, &bytes).into())
+    }
+    /// create from a `bytes` object and `properties`.
+    /// `byteorder` is either `"little"` or `"big"`.
+    #[text_signature = "(data, properties, byteorder='little')"]
+    #[staticmethod]
+    #[args(data, properties, byteorder = "\"little\"")]
+    fn from_bytes(
+        data: &[u8],
+        properties: FloatProperties,
+        byteorder: &str,
+    ) -> PyResult<DynamicFloat> {
+        let byte_count = properties.width().div_ceil(8);
+        if data.len() != byte_count {
+            return Err(PyErr::new::<ValueError, _>(format!(
+                "wrong number of bytes: expected {}, got {}",
+                byte_count,
+                data.len()
+            )));
+        }
+        let value = match byteorder {
+            "little" => DynamicFloat::from_le_bytes(data, properties),
+            "big" => DynamicFloat::from_be_bytes(data, properties),
+            _ => {
+                return Err(PyErr::new::<ValueError, _>(
+                    "byteorder must be either 'little' or 'big'",
+                ))
+            }
+        };
+        value.ok_or_else(|| PyErr::new::<ValueError, _>("bits out of range"))
+    }
     /// floating-point state
     #[getter]
     fn fp_state(&self) -> FPState {
@@ -391,6 +833,52 @@ impl PyDynamicFloat {
     fn is_signaling_nan(&self) -> bool {
         self.value.is_signaling_nan()
     }
+    /// `true` if `self` is exactly the default NaN generated by invalid
+    /// operations, including its sign and payload
+    #[getter]
+    fn matches_platform_default_nan(&self) -> bool {
+        self.value.matches_platform_default_nan()
+    }
+    /// `true` if `self` is in its canonical encoding: always `true` for
+    /// formats with an implicit leading bit, otherwise the explicit leading
+    /// bit must be set exactly when the exponent field is non-zero.
+    #[getter]
+    fn is_canonical(&self) -> bool {
+        self.value.is_canonical()
+    }
+    /// `true` if the fraction's MSB is in the state that makes a NaN quiet,
+    /// which is set for `QuietNaNFormat.Standard` and clear for
+    /// `QuietNaNFormat.MIPSLegacy`. doesn't check if `self` is a NaN.
+    #[getter]
+    fn is_quiet_bit_set(&self) -> bool {
+        self.value.is_quiet_bit_set()
+    }
+    /// the number of trailing zero bits in the significand of `self`,
+    /// including the implicit leading bit (if any). `None` for NaN and infinity.
+    #[getter]
+    fn mantissa_trailing_zeros(&self) -> Option<usize> {
+        self.value.mantissa_trailing_zeros()
+    }
+    /// the number of significant bits `self` uses, from the most significant
+    /// one bit through the least significant one bit of the significand.
+    /// `None` for NaN and infinity.
+    #[getter]
+    fn significand_bit_length(&self) -> Option<usize> {
+        self.value.significand_bit_length()
+    }
+    /// `true` if `self` is a pseudo-denormal: the exponent field is zero but
+    /// the explicit leading bit is set.
+    #[getter]
+    fn is_pseudo_denormal(&self) -> bool {
+        self.value.is_pseudo_denormal()
+    }
+    /// `true` if `self` is a pseudo-infinity, pseudo-NaN, or unnormal: the
+    /// exponent field is non-zero but the explicit leading bit is clear.
+    /// these are classified as `SignalingNaN`.
+    #[getter]
+    fn is_unsupported_encoding(&self) -> bool {
+        self.value.is_unsupported_encoding()
+    }
     /// `true` if `self` is infinity
     #[getter]
     fn is_infinity(&self) -> bool {
@@ -468,12 +956,35 @@ impl PyDynamicFloat {
     fn quiet_nan(properties: FloatProperties) -> DynamicFloat {
         DynamicFloat::quiet_nan(properties)
     }
+    /// get the default NaN: the NaN generated by invalid operations and by
+    /// NaN propagation modes that produce the canonical NaN.
+    #[text_signature = "(properties)"]
+    #[staticmethod]
+    fn default_nan(properties: FloatProperties) -> DynamicFloat {
+        DynamicFloat::default_nan(properties)
+    }
     /// get the canonical signaling NaN
     #[text_signature = "(properties)"]
     #[staticmethod]
     fn signaling_nan(properties: FloatProperties) -> DynamicFloat {
         DynamicFloat::signaling_nan(properties)
     }
+    /// construct a signaling NaN with a specific sign and payload, returns
+    /// `None` if that isn't possible
+    #[text_signature = "(sign, payload, properties)"]
+    #[staticmethod]
+    fn signaling_nan_with_payload(
+        sign: Sign,
+        payload: BigUint,
+        properties: FloatProperties,
+    ) -> Option<DynamicFloat> {
+        DynamicFloat::signaling_nan_with_payload(sign, payload, properties)
+    }
+    /// get the canonical encoding of `self`, like IEEE 754's `canonicalize`
+    #[text_signature = "($self)"]
+    fn canonicalize(&self) -> DynamicFloat {
+        self.value.canonicalize()
+    }
     /// convert `self` into a quiet NaN
     #[text_signature = "($self)"]
     fn to_quiet_nan(&self) -> DynamicFloat {
@@ -508,7 +1019,87 @@ impl PyDynamicFloat {
             .check_compatibility(rhs.properties())?;
         Ok(self
             .value
-            .checked_add_with_rounding_mode(&rhs, rounding_mode)?)
+            .checked_add_with_rounding_mode(&rhs, resolve_rounding_mode(rounding_mode))?)
+    }
+    /// compute `|self - rhs|`, rounding only once
+    #[text_signature = "($self, rhs, rounding_mode=None)"]
+    #[args(rounding_mode = "None")]
+    fn abs_diff(
+        &self,
+        rhs: PyDynamicFloat,
+        rounding_mode: Option<RoundingMode>,
+    ) -> PyResult<DynamicFloat> {
+        self.value
+            .properties()
+            .check_compatibility(rhs.properties())?;
+        Ok(self
+            .value
+            .checked_abs_diff(&rhs, resolve_rounding_mode(rounding_mode))?)
+    }
+    /// compute `(self + rhs) / 2`, rounding only once
+    #[text_signature = "($self, rhs, rounding_mode=None)"]
+    #[args(rounding_mode = "None")]
+    fn midpoint(
+        &self,
+        rhs: PyDynamicFloat,
+        rounding_mode: Option<RoundingMode>,
+    ) -> PyResult<DynamicFloat> {
+        self.value
+            .properties()
+            .check_compatibility(rhs.properties())?;
+        Ok(self
+            .value
+            .checked_midpoint(&rhs, resolve_rounding_mode(rounding_mode))?)
+    }
+    /// linearly interpolate between `self` and `other`, computing
+    /// `self + (other - self) * t` exactly and rounding only once
+    #[text_signature = "($self, other, t, rounding_mode=None)"]
+    #[args(rounding_mode = "None")]
+    fn lerp(
+        &self,
+        other: PyDynamicFloat,
+        t: PyDynamicFloat,
+        rounding_mode: Option<RoundingMode>,
+    ) -> PyResult<DynamicFloat> {
+        self.value
+            .properties()
+            .check_compatibility(other.properties())?;
+        self.value
+            .properties()
+            .check_compatibility(t.properties())?;
+        Ok(self
+            .value
+            .checked_lerp(&other, &t, resolve_rounding_mode(rounding_mode))?)
+    }
+    /// compute the minimum of `self` and `rhs`, like IEEE 754's `minimum`.
+    /// NaNs propagate, and `-0.0` is less than `+0.0`.
+    #[text_signature = "($self, rhs)"]
+    fn minimum(&self, rhs: PyDynamicFloat) -> PyResult<DynamicFloat> {
+        self.value
+            .properties()
+            .check_compatibility(rhs.properties())?;
+        Ok(self.value.checked_minimum(&rhs)?)
+    }
+    /// compute the maximum of `self` and `rhs`, like IEEE 754's `maximum`.
+    /// NaNs propagate, and `-0.0` is less than `+0.0`.
+    #[text_signature = "($self, rhs)"]
+    fn maximum(&self, rhs: PyDynamicFloat) -> PyResult<DynamicFloat> {
+        self.value
+            .properties()
+            .check_compatibility(rhs.properties())?;
+        Ok(self.value.checked_maximum(&rhs)?)
+    }
+    /// clamp `self` to the range `[lo, hi]`, computed as
+    /// `lo.maximum(hi.minimum(self))`.
+    #[text_signature = "($self, lo, hi)"]
+    fn clamp(&self, lo: PyDynamicFloat, hi: PyDynamicFloat) -> PyResult<DynamicFloat> {
+        self.value
+            .properties()
+            .check_compatibility(lo.properties())?;
+        self.value
+            .properties()
+            .check_compatibility(hi.properties())?;
+        Ok(self.value.checked_clamp(&lo, &hi)?)
     }
     /// subtract floating-point numbers
     #[text_signature = "($self, rhs, rounding_mode=None)"]
@@ -523,7 +1114,7 @@ impl PyDynamicFloat {
             .check_compatibility(rhs.properties())?;
         Ok(self
             .value
-            .checked_sub_with_rounding_mode(&rhs, rounding_mode)?)
+            .checked_sub_with_rounding_mode(&rhs, resolve_rounding_mode(rounding_mode))?)
     }
     /// multiply floating-point numbers
     #[text_signature = "($self, rhs, rounding_mode=None)"]
@@ -538,7 +1129,7 @@ impl PyDynamicFloat {
             .check_compatibility(rhs.properties())?;
         Ok(self
             .value
-            .checked_mul_with_rounding_mode(&rhs, rounding_mode)?)
+            .checked_mul_with_rounding_mode(&rhs, resolve_rounding_mode(rounding_mode))?)
     }
     /// divide floating-point numbers
     #[text_signature = "($self, rhs, rounding_mode=None)"]
@@ -553,7 +1144,7 @@ impl PyDynamicFloat {
             .check_compatibility(rhs.properties())?;
         Ok(self
             .value
-            .checked_div_with_rounding_mode(&rhs, rounding_mode)?)
+            .checked_div_with_rounding_mode(&rhs, resolve_rounding_mode(rounding_mode))?)
     }
     /// compute the IEEE 754 remainder of two floating-point numbers
     #[text_signature = "($self, rhs, rounding_mode=None)"]
@@ -566,7 +1157,9 @@ impl PyDynamicFloat {
         self.value
             .properties()
             .check_compatibility(rhs.properties())?;
-        Ok(self.value.checked_ieee754_remainder(&rhs, rounding_mode)?)
+        Ok(self
+            .value
+            .checked_ieee754_remainder(&rhs, resolve_rounding_mode(rounding_mode))?)
     }
     /// calculate the result of `(self * factor) + term` rounding only once, returning the result
     #[text_signature = "($self, factor, term, rounding_mode=None)"]
@@ -583,9 +1176,11 @@ impl PyDynamicFloat {
         self.value
             .properties()
             .check_compatibility(term.properties())?;
-        Ok(self
-            .value
-            .checked_fused_mul_add(&factor, &term, rounding_mode)?)
+        Ok(self.value.checked_fused_mul_add(
+            &factor,
+            &term,
+            resolve_rounding_mode(rounding_mode),
+        )?)
     }
     /// round `self` to an integer, returning the result as an integer or `None`
     #[text_signature = "($self, *, exact = False, rounding_mode=None)"]
@@ -595,13 +1190,45 @@ impl PyDynamicFloat {
         exact: bool,
         rounding_mode: Option<RoundingMode>,
     ) -> (Option<BigInt>, FPState) {
-        self.value.round_to_integer(exact, rounding_mode)
+        self.value
+            .round_to_integer(exact, resolve_rounding_mode(rounding_mode))
     }
     /// round `self` to an integer, returning the result as a `DynamicFloat`
     #[text_signature = "($self, *, exact = False, rounding_mode=None)"]
     #[args("*", exact = "false", rounding_mode = "None")]
     fn round_to_integral(&self, exact: bool, rounding_mode: Option<RoundingMode>) -> DynamicFloat {
-        self.value.round_to_integral(exact, rounding_mode)
+        self.value
+            .round_to_integral(exact, resolve_rounding_mode(rounding_mode))
+    }
+    /// round `self` as if its significand had only `significand_bits` bits,
+    /// keeping the same format
+    #[text_signature = "($self, significand_bits, rounding_mode=None)"]
+    #[args(rounding_mode = "None")]
+    fn round_to_precision(
+        &self,
+        significand_bits: usize,
+        rounding_mode: Option<RoundingMode>,
+    ) -> PyResult<DynamicFloat> {
+        if significand_bits == 0 {
+            return Err(PyErr::new::<ValueError, _>(
+                "significand_bits must be non-zero",
+            ));
+        }
+        Ok(self
+            .value
+            .round_to_precision(significand_bits, resolve_rounding_mode(rounding_mode)))
+    }
+    /// round `self` to the precision and exponent range of the format
+    /// specified by `other`, keeping the same format
+    #[text_signature = "($self, other, rounding_mode=None)"]
+    #[args(rounding_mode = "None")]
+    fn round_to_format_precision(
+        &self,
+        other: FloatProperties,
+        rounding_mode: Option<RoundingMode>,
+    ) -> DynamicFloat {
+        self.value
+            .round_to_format_precision(&other, resolve_rounding_mode(rounding_mode))
     }
     /// compute the result of `next_up` or `next_down`
     #[text_signature = "($self, up_or_down)"]
@@ -618,22 +1245,44 @@ impl PyDynamicFloat {
     fn next_down(&self) -> DynamicFloat {
         self.value.next_down()
     }
+    /// compute the result of `next_integer_up` or `next_integer_down`
+    #[text_signature = "($self, up_or_down)"]
+    fn next_integer_up_or_down(&self, up_or_down: UpOrDown) -> DynamicFloat {
+        self.value.next_integer_up_or_down(up_or_down)
+    }
+    /// compute the least integer-valued floating-point number that compares greater than `self`
+    #[text_signature = "($self)"]
+    fn next_integer_up(&self) -> DynamicFloat {
+        self.value.next_integer_up()
+    }
+    /// compute the greatest integer-valued floating-point number that compares less than `self`
+    #[text_signature = "($self)"]
+    fn next_integer_down(&self) -> DynamicFloat {
+        self.value.next_integer_down()
+    }
     /// get the floor of the log base 2 of the absolute value of `self`
     #[text_signature = "($self)"]
     fn log_b(&self) -> (Option<BigInt>, FPState) {
         self.value.log_b()
     }
+    /// saturate infinities to the largest finite value with the same sign,
+    /// returning a tuple of the result and whether `self` was clamped
+    #[text_signature = "($self)"]
+    fn clamp_to_representable(&self) -> (DynamicFloat, bool) {
+        self.value.clamp_to_representable()
+    }
     /// get `self * 2**scale` where `scale` is an integer
     #[text_signature = "($self, scale, rounding_mode=None)"]
     #[args(rounding_mode = "None")]
     fn scale_b(&self, scale: BigInt, rounding_mode: Option<RoundingMode>) -> DynamicFloat {
-        self.value.scale_b(scale, rounding_mode)
+        self.value
+            .scale_b(scale, resolve_rounding_mode(rounding_mode))
     }
     /// get the square-root of `self`
     #[text_signature = "($self, rounding_mode=None)"]
     #[args(rounding_mode = "None")]
     fn sqrt(&self, rounding_mode: Option<RoundingMode>) -> DynamicFloat {
-        self.value.sqrt(rounding_mode)
+        self.value.sqrt(resolve_rounding_mode(rounding_mode))
     }
     /// convert `self` to the floating-point format specified by `properties`. `rounding_mode` is optional.
     #[text_signature = "($self, rounding_mode, properties)"]
@@ -643,7 +1292,15 @@ impl PyDynamicFloat {
         properties: FloatProperties,
     ) -> DynamicFloat {
         self.value
-            .convert_to_dynamic_float(rounding_mode, properties)
+            .convert_to_dynamic_float(resolve_rounding_mode(rounding_mode), properties)
+    }
+    /// `True` if converting `self` to the floating-point format specified by
+    /// `properties` is lossless: it signals none of `INEXACT`, `OVERFLOW`,
+    /// `UNDERFLOW`, or `INVALID_OPERATION`. infinities and NaNs must also
+    /// convert back to the same bits.
+    #[text_signature = "($self, properties)"]
+    fn is_exactly_representable_in(&self, properties: FloatProperties) -> bool {
+        self.value.is_exactly_representable_in(&properties)
     }
     /// compute the absolute value of `self`
     #[text_signature = "($self)"]
@@ -669,6 +1326,13 @@ impl PyDynamicFloat {
         let (ordering, fp_state) = self.value.checked_compare(&rhs.value, quiet)?;
         Ok((ordering.map(|ordering| ordering as i32), fp_state))
     }
+    /// compare `self` numerically with the exact integer `rhs`, without
+    /// rounding `rhs`. `quiet` is a `bool`. returns `(int or None, FPState)`
+    #[text_signature = "($self, rhs, quiet)"]
+    fn compare_with_int(&self, rhs: BigInt, quiet: bool) -> (Option<i32>, FPState) {
+        let (ordering, fp_state) = self.value.compare_with_bigint(&rhs, quiet);
+        (ordering.map(|ordering| ordering as i32), fp_state)
+    }
     /// compare two `DynamicFloat` values. returns `(int or None, FPState)`
     #[text_signature = "($self, rhs)"]
     fn compare_quiet(&self, rhs: &PyDynamicFloat) -> PyResult<(Option<i32>, FPState)> {
@@ -690,23 +1354,92 @@ impl PyDynamicFloat {
         rounding_mode: Option<RoundingMode>,
         fp_state: Option<FPState>,
     ) -> DynamicFloat {
-        DynamicFloat::from_bigint(value, rounding_mode, fp_state, properties)
+        DynamicFloat::from_bigint(
+            value,
+            resolve_rounding_mode(rounding_mode),
+            fp_state,
+            properties,
+        )
     }
     /// convert `self` to an integer, returning the result as a tuple of an integer or `None`, and `FPState`
-    #[text_signature = "($self, exact, rounding_mode=None)"]
-    #[args(exact = "false", rounding_mode = "None")]
+    ///
+    /// if `width` is not `None`, the result must fit in a `width`-bit
+    /// integer (signed if `signed` is `True`), otherwise the result is `None`
+    /// and invalid operation is signaled. `width` must be one of
+    /// 8, 16, 32, 64, or 128.
+    #[text_signature = "($self, exact, rounding_mode=None, width=None, signed=True)"]
+    #[args(
+        exact = "false",
+        rounding_mode = "None",
+        width = "None",
+        signed = "true"
+    )]
     fn to_int(
         &self,
         exact: bool,
         rounding_mode: Option<RoundingMode>,
-    ) -> (Option<BigInt>, FPState) {
-        self.value.to_bigint(exact, rounding_mode)
+        width: Option<u32>,
+        signed: bool,
+    ) -> PyResult<(Option<BigInt>, FPState)> {
+        let rounding_mode = resolve_rounding_mode(rounding_mode);
+        macro_rules! to_int {
+            ($name:ident) => {{
+                let (value, fp_state) = self.value.$name(exact, rounding_mode);
+                (value.map(Into::into), fp_state)
+            }};
+        }
+        Ok(match (width, signed) {
+            (None, _) => self.value.to_bigint(exact, rounding_mode),
+            (Some(8), true) => to_int!(to_i8),
+            (Some(16), true) => to_int!(to_i16),
+            (Some(32), true) => to_int!(to_i32),
+            (Some(64), true) => to_int!(to_i64),
+            (Some(128), true) => to_int!(to_i128),
+            (Some(8), false) => to_int!(to_u8),
+            (Some(16), false) => to_int!(to_u16),
+            (Some(32), false) => to_int!(to_u32),
+            (Some(64), false) => to_int!(to_u64),
+            (Some(128), false) => to_int!(to_u128),
+            (Some(_), _) => {
+                return Err(PyErr::new::<ValueError, _>(
+                    "width must be one of 8, 16, 32, 64, or 128",
+                ))
+            }
+        })
     }
     /// compute reciprocal square-root (`1.0 / sqrt(self)`)
     #[text_signature = "($self, rounding_mode=None)"]
     #[args(rounding_mode = "None")]
     fn rsqrt(&self, rounding_mode: Option<RoundingMode>) -> DynamicFloat {
-        self.value.rsqrt(rounding_mode)
+        self.value.rsqrt(resolve_rounding_mode(rounding_mode))
+    }
+    /// compute `self` raised to the integer power `exponent`
+    #[text_signature = "($self, exponent, rounding_mode=None)"]
+    #[args(rounding_mode = "None")]
+    fn pown(&self, exponent: i64, rounding_mode: Option<RoundingMode>) -> DynamicFloat {
+        self.value
+            .pown(exponent, resolve_rounding_mode(rounding_mode))
+    }
+    /// get the cube-root of `self`
+    #[text_signature = "($self, rounding_mode=None)"]
+    #[args(rounding_mode = "None")]
+    fn cbrt(&self, rounding_mode: Option<RoundingMode>) -> DynamicFloat {
+        self.value.cbrt(resolve_rounding_mode(rounding_mode))
+    }
+    /// compute `sqrt(self * self + rhs * rhs)` rounding only once, returning the result
+    #[text_signature = "($self, rhs, rounding_mode=None)"]
+    #[args(rounding_mode = "None")]
+    fn hypot(
+        &self,
+        rhs: PyDynamicFloat,
+        rounding_mode: Option<RoundingMode>,
+    ) -> PyResult<DynamicFloat> {
+        self.value
+            .properties()
+            .check_compatibility(rhs.properties())?;
+        Ok(self
+            .value
+            .checked_hypot(&rhs, resolve_rounding_mode(rounding_mode))?)
     }
 }
 
@@ -724,6 +1457,14 @@ impl PyNumberProtocol for PyDynamicFloat {
     fn __truediv__(lhs: PyDynamicFloat, rhs: PyDynamicFloat) -> PyResult<DynamicFloat> {
         lhs.div(rhs, None)
     }
+    fn __pow__(lhs: PyDynamicFloat, rhs: i64, modulo: Option<PyObject>) -> PyResult<DynamicFloat> {
+        if modulo.is_some() {
+            return Err(PyErr::new::<TypeError, _>(
+                "pow() 3rd argument not supported for DynamicFloat",
+            ));
+        }
+        Ok(lhs.pown(rhs, None))
+    }
     fn __abs__(&self) -> PyResult<DynamicFloat> {
         Ok(self.abs())
     }
@@ -803,6 +1544,12 @@ macro_rules! impl_platform_properties_new {
             fn quiet_nan_format(&self) -> QuietNaNFormat {
                 self.value.quiet_nan_format()
             }
+            /// construct a `PlatformProperties` from `self` in "default NaN"
+            /// mode, where every NaN result is the canonical NaN
+            #[text_signature = "($self)"]
+            fn with_default_nan_mode(&self) -> PlatformProperties {
+                self.value.with_default_nan_mode()
+            }
         }
 
         impl PlatformProperties {
@@ -847,9 +1594,9 @@ impl_platform_properties_new!(
     pub round_to_integral_nan_propagation_mode: UnaryNaNPropagationMode,
     /// NaN payload propagation mode for `next_up_or_down`, `next_up`, and `next_down`
     pub next_up_or_down_nan_propagation_mode: UnaryNaNPropagationMode,
-    /// NaN payload propagation mode for `scale_b`
+    /// NaN payload propagation mode for `scale_b` and `pown`
     pub scale_b_nan_propagation_mode: UnaryNaNPropagationMode,
-    /// NaN payload propagation mode for `sqrt`
+    /// NaN payload propagation mode for `sqrt` and `cbrt`
     pub sqrt_nan_propagation_mode: UnaryNaNPropagationMode,
     /// NaN payload propagation mode for float-to-float conversions
     pub float_to_float_conversion_nan_propagation_mode: FloatToFloatConversionNaNPropagationMode,
@@ -876,7 +1623,7 @@ impl PyObjectProtocol for PyPlatformProperties {
 
 /// properties of a particular floating-point format
 #[pyclass(name = FloatProperties, module = "simple_soft_float")]
-#[text_signature = "(exponent_width, mantissa_width, has_implicit_leading_bit, has_sign_bit, platform_properties)"]
+#[text_signature = "(exponent_width, mantissa_width, has_implicit_leading_bit, has_sign_bit, platform_properties, *, has_inf_nan=True, unnormals_are_invalid=False)"]
 #[derive(Copy, Clone)]
 struct PyFloatProperties {
     value: FloatProperties,
@@ -898,12 +1645,24 @@ impl IntoPy<PyObject> for FloatProperties {
 #[pymethods]
 impl PyFloatProperties {
     #[new]
+    #[args(
+        exponent_width,
+        mantissa_width,
+        has_implicit_leading_bit,
+        has_sign_bit,
+        platform_properties,
+        "*",
+        has_inf_nan = "true",
+        unnormals_are_invalid = "false"
+    )]
     fn __new__(
         exponent_width: usize,
         mantissa_width: usize,
         has_implicit_leading_bit: bool,
         has_sign_bit: bool,
         platform_properties: PlatformProperties,
+        has_inf_nan: bool,
+        unnormals_are_invalid: bool,
     ) -> Self {
         PyFloatProperties {
             value: FloatProperties::new_with_extended_flags(
@@ -912,7 +1671,9 @@ impl PyFloatProperties {
                 has_implicit_leading_bit,
                 has_sign_bit,
                 platform_properties,
-            ),
+            )
+            .with_has_inf_nan(has_inf_nan)
+            .with_unnormals_are_invalid(unnormals_are_invalid),
         }
     }
     /// construct `FloatProperties` for standard `width`-bit binary interchange format, if it exists
@@ -929,6 +1690,26 @@ impl PyFloatProperties {
         )
         .ok_or_else(|| PyErr::new::<ValueError, _>("not a valid standard float width"))
     }
+    /// `FloatProperties` for the x87 80-bit extended precision format,
+    /// which has an explicit integer bit instead of an implicit leading bit
+    #[text_signature = "(*, platform_properties=None)"]
+    #[staticmethod]
+    #[args("*", platform_properties = "None")]
+    fn x87_extended(platform_properties: Option<PlatformProperties>) -> FloatProperties {
+        FloatProperties::x87_extended_with_platform_properties(
+            platform_properties.unwrap_or_default(),
+        )
+    }
+    /// `FloatProperties` for ARM's alternative half-precision format, which
+    /// is like `standard(16)` but without infinities and NaNs
+    #[text_signature = "(*, platform_properties=None)"]
+    #[staticmethod]
+    #[args("*", platform_properties = "None")]
+    fn arm_alt_half(platform_properties: Option<PlatformProperties>) -> FloatProperties {
+        FloatProperties::arm_alt_half_with_platform_properties(
+            platform_properties.unwrap_or_default(),
+        )
+    }
     /// check if `self` is a standard binary interchange format.
     #[getter]
     fn is_standard(&self) -> bool {
@@ -954,6 +1735,24 @@ impl PyFloatProperties {
     fn has_sign_bit(&self) -> bool {
         self.value.has_sign_bit()
     }
+    /// if the floating-point format has infinities and NaNs.
+    ///
+    /// formats without infinities and NaNs use the all-ones exponent field
+    /// for normal numbers, and saturate to the largest finite value on overflow.
+    #[getter]
+    fn has_inf_nan(&self) -> bool {
+        self.value.has_inf_nan()
+    }
+    /// if unnormals (including pseudo-zeros) are invalid operands in the
+    /// floating-point format, so they are classified as signaling NaNs.
+    /// otherwise they are classified by their value.
+    ///
+    /// only formats without an implicit leading bit have unnormals. this is
+    /// `True` for `x87_extended`, matching the 80387 and later.
+    #[getter]
+    fn unnormals_are_invalid(&self) -> bool {
+        self.value.unnormals_are_invalid()
+    }
     /// get the `PlatformProperties`
     #[getter]
     fn platform_properties(&self) -> PlatformProperties {
@@ -1044,6 +1843,23 @@ impl PyFloatProperties {
     fn mantissa_field_msb_mask(&self) -> BigUint {
         self.value.mantissa_field_msb_mask()
     }
+    /// get the amount by which the floating-point bits should be shifted right
+    /// in order to extract the MSB of the fraction (the part of the mantissa
+    /// field after the radix point). The fraction's MSB selects between quiet
+    /// and signaling NaNs.
+    ///
+    /// this is the same as `mantissa_field_msb_shift` for formats with an implicit leading bit.
+    #[getter]
+    fn fraction_msb_shift(&self) -> usize {
+        self.value.fraction_msb_shift()
+    }
+    /// get the bitwise mask for the fraction's MSB (before shifting to extract).
+    ///
+    /// this is the same as `mantissa_field_msb_mask` for formats with an implicit leading bit.
+    #[getter]
+    fn fraction_msb_mask(&self) -> BigUint {
+        self.value.fraction_msb_mask()
+    }
     /// get the amount by which the exponent field is offset from the
     /// mathematical exponent for normal floating-point numbers.
     ///
@@ -1082,11 +1898,50 @@ impl PyFloatProperties {
     fn exponent_max_normal(&self) -> BigUint {
         self.value.exponent_max_normal()
     }
+    /// get the minimum and maximum mathematical exponents of normal
+    /// floating-point numbers as a `(min, max)` tuple.
+    #[getter]
+    fn normal_exponent_range(&self) -> (i64, i64) {
+        self.value.normal_exponent_range()
+    }
+    /// get the minimum and maximum mathematical exponents of all finite
+    /// non-zero values, including subnormals, as a `(min, max)` tuple.
+    #[getter]
+    fn mathematical_exponent_range(&self) -> (i64, i64) {
+        self.value.mathematical_exponent_range()
+    }
     /// get the mask for the whole floating-point format
     #[getter]
     fn overall_mask(&self) -> BigUint {
         self.value.overall_mask()
     }
+    /// get the unit roundoff, `2^-(fraction_width + 1)`, as a
+    /// `(numerator, denominator)` tuple: the bound on the relative error of
+    /// rounding to nearest (ignoring underflow and overflow).
+    #[getter]
+    fn unit_roundoff(&self) -> (BigInt, BigInt) {
+        let value = self.value.unit_roundoff();
+        (value.numer().clone(), value.denom().clone())
+    }
+    /// get the approximate number of decimal orders of magnitude between
+    /// the smallest positive subnormal and the largest finite value, which is
+    /// `log10(max_finite_value / min_positive_subnormal)`.
+    #[getter]
+    fn dynamic_range_decades(&self) -> f64 {
+        self.value.dynamic_range_decades()
+    }
+    /// check if this format can exactly represent the product of every pair
+    /// of values, the first in format `a` and the second in format `b`.
+    #[text_signature = "(self, a, b)"]
+    fn can_represent_exact_product(&self, a: FloatProperties, b: FloatProperties) -> bool {
+        self.value.can_represent_exact_product(a, b)
+    }
+    /// check if this format can exactly represent the sum of every pair of
+    /// values, the first in format `a` and the second in format `b`.
+    #[text_signature = "(self, a, b)"]
+    fn can_represent_exact_sum(&self, a: FloatProperties, b: FloatProperties) -> bool {
+        self.value.can_represent_exact_sum(a, b)
+    }
 }
 
 #[pyproto]
@@ -1135,4 +1990,24 @@ impl PyObjectProtocol for PyDynamicFloat {
     fn __repr__(&self) -> PyResult<String> {
         Ok(format!("<{:?}>", self.value))
     }
+    /// numeric comparison: NaNs compare unequal to everything, including themselves,
+    /// and `-0.0 == +0.0`. Use `bit_equal` to compare the bits instead.
+    fn __richcmp__(&self, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        if let Ok(rhs) = <PyRef<Self>>::extract(other) {
+            self.value
+                .properties()
+                .check_compatibility(rhs.value.properties())?;
+            let ordering = self.value.value.compare_quiet(&rhs.value.value, None);
+            let result = match op {
+                CompareOp::Eq => ordering == Some(Ordering::Equal),
+                CompareOp::Ne => ordering != Some(Ordering::Equal),
+                CompareOp::Lt => ordering == Some(Ordering::Less),
+                CompareOp::Le => ordering.map_or(false, |v| v != Ordering::Greater),
+                CompareOp::Gt => ordering == Some(Ordering::Greater),
+                CompareOp::Ge => ordering.map_or(false, |v| v != Ordering::Less),
+            };
+            return Ok(result.into_py(other.py()));
+        }
+        Ok(other.py().NotImplemented())
+    }
 }
@@ -4,8 +4,10 @@ use super::*;
 use std::any::Any;
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[allow(clippy::upper_case_acronyms)]
 enum TestCasePlatform {
     RISCV,
+    #[allow(dead_code)]
     POWER,
 }
 
@@ -37,7 +39,7 @@ macro_rules! impl_test_case_argument_for_int {
             fn parse_into(&mut self, text: &str) -> Result<(), String> {
                 let mut bytes = text.bytes();
                 let mut peek = bytes.next();
-                let sign = if $t::min_value() != 0 && peek == Some(b'-') {
+                let sign = if $t::MIN != 0 && peek == Some(b'-') {
                     peek = bytes.next();
                     Sign::Negative
                 } else {
@@ -95,10 +97,7 @@ macro_rules! impl_test_case_argument_for_int {
             #[allow(unused_comparisons)]
             fn debug(&self) -> String {
                 if *self < 0 {
-                    format!(
-                        "-{:#X}",
-                        ($unsigned_t::max_value() - *self as $unsigned_t) + 1
-                    )
+                    format!("-{:#X}", ($unsigned_t::MAX - *self as $unsigned_t) + 1)
                 } else {
                     format!("{:#X}", self)
                 }
@@ -218,6 +217,124 @@ impl TestCaseArgument for F32 {
     }
 }
 
+/// the `PlatformProperties` that can be named in `DynamicFloat` test cases
+const DYNAMIC_FLOAT_PLATFORMS: &[(&str, PlatformProperties)] = &[
+    ("ARM", PlatformProperties::ARM),
+    ("RISC_V", PlatformProperties::RISC_V),
+    ("POWER", PlatformProperties::POWER),
+    ("MIPS_2008", PlatformProperties::MIPS_2008),
+    ("X86_SSE", PlatformProperties::X86_SSE),
+    ("SPARC", PlatformProperties::SPARC),
+    ("HPPA", PlatformProperties::HPPA),
+    ("MIPS_LEGACY", PlatformProperties::MIPS_LEGACY),
+];
+
+/// `DynamicFloat`s are written as `<format>[/<platform>]:<bits>`, like
+/// `F32:0x3F800000` or `E4M3/X86_SSE:0x38`, where `<format>` is either
+/// `F<width>` for the standard format with that width, or
+/// `E<exponent_width>M<mantissa_width>` for a format with those field
+/// widths, an implicit leading bit, a sign bit, and infinities and NaNs.
+/// `<platform>` is the name of one of the `PlatformProperties` constants,
+/// and defaults to `PlatformProperties::default()`.
+impl TestCaseArgument for DynamicFloat {
+    fn parse_into(&mut self, text: &str) -> Result<(), String> {
+        let (format, bits_text) = text
+            .split_once(':')
+            .ok_or("DynamicFloat must be written as <format>[/<platform>]:<bits>")?;
+        let (format, platform_properties) = match format.split_once('/') {
+            Some((format, platform)) => {
+                let platform_properties = DYNAMIC_FLOAT_PLATFORMS
+                    .iter()
+                    .find(|&&(name, _)| name == platform)
+                    .map(|&(_, platform_properties)| platform_properties)
+                    .ok_or("invalid platform name")?;
+                (format, platform_properties)
+            }
+            None => (format, PlatformProperties::default()),
+        };
+        let properties = if let Some(width) = format.strip_prefix('F') {
+            width
+                .parse()
+                .ok()
+                .and_then(|width| {
+                    FloatProperties::standard_with_platform_properties(width, platform_properties)
+                })
+                .ok_or("invalid standard format name")?
+        } else {
+            let (exponent_width, mantissa_width) = format
+                .strip_prefix('E')
+                .and_then(|widths| widths.split_once('M'))
+                .and_then(|(exponent_width, mantissa_width)| {
+                    Some((exponent_width.parse().ok()?, mantissa_width.parse().ok()?))
+                })
+                .ok_or(
+                    "format must be written as F<width> or E<exponent_width>M<mantissa_width>",
+                )?;
+            if exponent_width < 2 || mantissa_width < 1 {
+                return Err("format's exponent or mantissa width is too small".into());
+            }
+            FloatProperties::new_with_platform_properties(
+                exponent_width,
+                mantissa_width,
+                platform_properties,
+            )
+        };
+        let mut bits = 0u128;
+        bits.parse_into(bits_text)?;
+        *self = DynamicFloat::from_bits(bits.into(), properties).ok_or("bits out of range")?;
+        Ok(())
+    }
+    fn same(&self, other: &dyn TestCaseArgument, _platform: TestCasePlatform) -> bool {
+        test_case_argument_same(self, other, |a, b| {
+            a.properties() == b.properties() && a.bits() == b.bits()
+        })
+    }
+    fn debug(&self) -> String {
+        let properties = self.properties();
+        let platform_properties = properties.platform_properties();
+        let format = if properties.is_standard() {
+            format!("F{}", properties.width())
+        } else if properties
+            == FloatProperties::new_with_platform_properties(
+                properties.exponent_width(),
+                properties.mantissa_width(),
+                platform_properties,
+            )
+        {
+            format!(
+                "E{}M{}",
+                properties.exponent_width(),
+                properties.mantissa_width()
+            )
+        } else {
+            format!("{:?}", properties)
+        };
+        let platform = if platform_properties == PlatformProperties::default() {
+            String::new()
+        } else if let Some(&(name, _)) = DYNAMIC_FLOAT_PLATFORMS
+            .iter()
+            .find(|&&(_, v)| v == platform_properties)
+        {
+            format!("/{}", name)
+        } else {
+            format!("/{:?}", platform_properties)
+        };
+        format!(
+            "{}{}:{:#0width$X}",
+            format,
+            platform,
+            self.bits(),
+            width = properties.width().div_ceil(4) + 2
+        )
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn make_assignment_target() -> Self {
+        DynamicFloat::new(FloatProperties::STANDARD_32)
+    }
+}
+
 macro_rules! impl_test_case_argument_for_enum {
     (enum $type:ident { $first_name:ident, $($name:ident,)* }) => {
         impl TestCaseArgument for $type {
@@ -252,6 +369,14 @@ impl_test_case_argument_for_enum! {
         TowardNegative,
         TowardPositive,
         TiesToAway,
+        TiesToOdd,
+    }
+}
+
+impl_test_case_argument_for_enum! {
+    enum Sign {
+        Positive,
+        Negative,
     }
 }
 
@@ -510,7 +635,7 @@ macro_rules! test_case {
                         $($output: (<$output_type>::make_assignment_target(), <$output_type>::make_assignment_target()),)+
                     }
                 }
-                fn io(&mut self) -> TestCaseIO {
+                fn io(&mut self) -> TestCaseIO<'_> {
                     let inputs = vec![
                         $(TestCaseInput {
                             name: stringify!($input),
@@ -691,6 +816,23 @@ test_case! {
     }
 }
 
+test_case! {
+    #[test_case_file_name = "dynamic_add.txt"]
+    #[platform = RISCV]
+    fn test_dynamic_add(lhs: DynamicFloat,
+                rhs: DynamicFloat,
+                rounding_mode: RoundingMode,
+                #[output] result: DynamicFloat,
+                #[output] sign: Sign,
+                #[output] status_flags: StatusFlags,
+    ) {
+        let result_value = lhs.add_with_rounding_mode(&rhs, Some(rounding_mode));
+        *sign = result_value.sign();
+        *status_flags = result_value.fp_state.status_flags;
+        *result = result_value;
+    }
+}
+
 fn mul_add_test_case(
     value1: F16,
     value2: F16,
@@ -909,6 +1051,7 @@ test_case! {
     }
 }
 
+#[cfg(not(feature = "rational-only"))]
 test_case! {
     #[test_case_file_name = "sqrt.txt"]
     #[platform = RISCV]
@@ -1057,6 +1200,7 @@ int_to_float_test_case!(test_u32_to_f32, "u32_to_f32.txt", u32, F32, from_u32);
 int_to_float_test_case!(test_i64_to_f32, "i64_to_f32.txt", i64, F32, from_i64);
 int_to_float_test_case!(test_u64_to_f32, "u64_to_f32.txt", u64, F32, from_u64);
 
+#[cfg(not(feature = "rational-only"))]
 test_case! {
     #[test_case_file_name = "rsqrt.txt"]
     #[platform = RISCV]
@@ -1077,3 +1221,147 @@ test_case! {
         *status_flags = fp_state.status_flags;
     }
 }
+
+/// run the vectors in a [Berkeley TestFloat](http://www.jhauser.us/arithmetic/TestFloat.html)
+/// format file, like the ones written by `testfloat_gen`: each line has the
+/// operands, the expected result, and the expected exception flags, all in
+/// hex without a `0x` prefix.
+///
+/// TestFloat takes the rounding and tininess detection modes on the command
+/// line rather than storing them in the file, so they're arguments here. like
+/// TestFloat's default, all NaN results are considered equal. lines starting
+/// with `#` are comments.
+fn execute_testfloat_vectors<FT: FloatTraits + Default>(
+    vectors: &str,
+    file_name: &str,
+    rounding_mode: RoundingMode,
+    tininess_detection_mode: TininessDetectionMode,
+    op: impl Fn(&[Float<FT>], &mut FPState) -> Float<FT>,
+) {
+    fn parse_hex<T: FloatBitsType>(text: &str, location: FileLocation) -> T {
+        BigInt::parse_bytes(text.as_bytes(), 16)
+            .and_then(|value| T::from_bigint(&value))
+            .unwrap_or_else(|| panic!("{}: invalid hex value: {:?}", location, text))
+    }
+    for (i, line) in vectors.lines().enumerate() {
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+        let location = FileLocation {
+            file_name,
+            line: i + 1,
+        };
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 3 {
+            panic!("{}: too few fields", location);
+        }
+        let (operands, expected) = fields.split_at(fields.len() - 2);
+        let operands: Vec<Float<FT>> = operands
+            .iter()
+            .map(|text| Float::from_bits(parse_hex(text, location)))
+            .collect();
+        let expected_result = Float::<FT>::from_bits(parse_hex(expected[0], location));
+        let flags: u8 = parse_hex(expected[1], location);
+        let mut expected_status_flags = StatusFlags::empty();
+        if flags & 0x01 != 0 {
+            expected_status_flags = expected_status_flags.signal_inexact();
+        }
+        if flags & 0x02 != 0 {
+            expected_status_flags = expected_status_flags.signal_underflow();
+        }
+        if flags & 0x04 != 0 {
+            expected_status_flags = expected_status_flags.signal_overflow();
+        }
+        if flags & 0x08 != 0 {
+            expected_status_flags = expected_status_flags.signal_division_by_zero();
+        }
+        if flags & 0x10 != 0 {
+            expected_status_flags = expected_status_flags.signal_invalid_operation();
+        }
+        if flags & !0x1F != 0 {
+            panic!("{}: invalid flags: {:#X}", location, flags);
+        }
+        let mut fp_state = FPState {
+            rounding_mode,
+            exception_handling_mode: ExceptionHandlingMode::IgnoreExactUnderflow,
+            tininess_detection_mode,
+            ..FPState::default()
+        };
+        let result = op(&operands, &mut fp_state);
+        let same_result = if expected_result.is_nan() {
+            result.is_nan()
+        } else {
+            result.bits() == expected_result.bits()
+        };
+        if !same_result || fp_state.status_flags != expected_status_flags {
+            panic!(
+                "{}: test case failed: operands = {:?}\n\
+                 expected result = {:?}, status_flags = {:?}\n\
+                 got result = {:?}, status_flags = {:?}",
+                location,
+                operands,
+                expected_result,
+                expected_status_flags,
+                result,
+                fp_state.status_flags,
+            );
+        }
+    }
+}
+
+macro_rules! testfloat_test_case {
+    ($test_name:ident, $file_name:literal, $traits:ident, $op:ident, $rounding_mode:ident, $tininess_detection_mode:ident) => {
+        #[test]
+        fn $test_name() {
+            let file_path = concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/test_data/testfloat/",
+                $file_name
+            );
+            execute_testfloat_vectors::<$traits>(
+                include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/test_data/testfloat/",
+                    $file_name
+                )),
+                file_path,
+                RoundingMode::$rounding_mode,
+                TininessDetectionMode::$tininess_detection_mode,
+                |operands, fp_state| operands[0].$op(&operands[1], None, Some(fp_state)),
+            );
+        }
+    };
+}
+
+testfloat_test_case!(
+    test_testfloat_f16_add,
+    "f16_add_rne.txt",
+    F16Traits,
+    add,
+    TiesToEven,
+    AfterRounding
+);
+testfloat_test_case!(
+    test_testfloat_f16_mul,
+    "f16_mul_rne.txt",
+    F16Traits,
+    mul,
+    TiesToEven,
+    AfterRounding
+);
+testfloat_test_case!(
+    test_testfloat_f32_add,
+    "f32_add_rne.txt",
+    F32Traits,
+    add,
+    TiesToEven,
+    AfterRounding
+);
+testfloat_test_case!(
+    test_testfloat_f32_mul,
+    "f32_mul_rne.txt",
+    F32Traits,
+    mul,
+    TiesToEven,
+    AfterRounding
+);
@@ -97,7 +97,8 @@ class TestRoundingMode(unittest.TestCase):
                           cls.TowardZero,
                           cls.TowardNegative,
                           cls.TowardPositive,
-                          cls.TiesToAway})
+                          cls.TiesToAway,
+                          cls.TiesToOdd})
 
 
 class TestSign(unittest.TestCase):
@@ -141,6 +142,17 @@ class TestTininessDetectionMode(unittest.TestCase):
                           cls.AfterRounding})
 
 
+class TestFlushToZeroMode(unittest.TestCase):
+    maxDiff = None
+
+    def test_enumerants(self):
+        cls = ssf.FlushToZeroMode
+        self.assertEqual(set(cls),
+                         {cls.Disabled,
+                          cls.Arithmetic,
+                          cls.ArithmeticAndConversions})
+
+
 class TestUnaryNaNPropagationMode(unittest.TestCase):
     maxDiff = None
 
@@ -161,6 +173,19 @@ class TestUpOrDown(unittest.TestCase):
                           cls.Down})
 
 
+class TestOpKind(unittest.TestCase):
+    maxDiff = None
+
+    def test_possible_flags(self):
+        self.assertEqual(ssf.possible_flags(ssf.OpKind.Sqrt),
+                         ssf.StatusFlags().signal_invalid_operation()
+                         .signal_inexact())
+        self.assertEqual(ssf.possible_flags(ssf.OpKind.SignOp),
+                         ssf.StatusFlags())
+        self.assertEqual(ssf.possible_flags(ssf.OpKind.Div).bits,
+                         ssf.StatusFlags.all().bits)
+
+
 class TestFPState(unittest.TestCase):
     maxDiff = None
 
@@ -173,22 +198,78 @@ class TestFPState(unittest.TestCase):
         exception_handling_mode = ssf.ExceptionHandlingMode \
             .IgnoreExactUnderflow
         tininess_detection_mode = ssf.TininessDetectionMode.AfterRounding
+        flush_to_zero_mode = ssf.FlushToZeroMode.Arithmetic
         obj = ssf.FPState(rounding_mode=rounding_mode,
                           status_flags=status_flags,
                           exception_handling_mode=exception_handling_mode,
-                          tininess_detection_mode=tininess_detection_mode)
+                          tininess_detection_mode=tininess_detection_mode,
+                          flush_to_zero_mode=flush_to_zero_mode,
+                          precision_control=53)
         obj = obj.merge(obj)
         self.assertEqual(obj.rounding_mode, rounding_mode)
         self.assertEqual(obj.status_flags, status_flags)
         self.assertEqual(obj.exception_handling_mode, exception_handling_mode)
         self.assertEqual(obj.tininess_detection_mode, tininess_detection_mode)
+        self.assertEqual(obj.flush_to_zero_mode, flush_to_zero_mode)
+        self.assertEqual(obj.precision_control, 53)
         self.assertEqual(
             repr(obj),
             "PlatformProperties(rounding_mode=RoundingMode.TiesToEven, "
             + "status_flags=StatusFlags(), "
             + "exception_handling_mode="
             + "ExceptionHandlingMode.IgnoreExactUnderflow, "
-            + "tininess_detection_mode=TininessDetectionMode.AfterRounding)")
+            + "tininess_detection_mode=TininessDetectionMode.AfterRounding, "
+            + "flush_to_zero_mode=FlushToZeroMode.Arithmetic, "
+            + "precision_control=53)")
+
+    def test_merge(self):
+        obj = ssf.FPState(status_flags=ssf.StatusFlags().set_inexact())
+        obj2 = ssf.FPState(status_flags=ssf.StatusFlags().set_overflow())
+        self.assertEqual(obj.merge(obj2).status_flags,
+                         ssf.StatusFlags().set_inexact().set_overflow())
+        obj3 = ssf.FPState(rounding_mode=ssf.RoundingMode.TowardZero)
+        with self.assertRaises(TypeError):
+            obj.merge(obj3)
+
+    def test_save_restore_modes(self):
+        obj = ssf.FPState(rounding_mode=ssf.RoundingMode.TowardZero,
+                          status_flags=ssf.StatusFlags().set_inexact())
+        modes = obj.save_modes()
+        self.assertEqual(modes,
+                         ssf.FPModes(rounding_mode=ssf.RoundingMode.TowardZero))
+        obj2 = obj.restore_modes(ssf.FPModes())
+        self.assertEqual(obj2.rounding_mode, ssf.RoundingMode.TiesToEven)
+        self.assertEqual(obj2.status_flags, ssf.StatusFlags().set_inexact())
+        self.assertEqual(obj2.restore_modes(modes), obj)
+
+
+class TestFPModes(unittest.TestCase):
+    maxDiff = None
+
+    def test_signatures(self):
+        check_signatures(self, ssf.FPModes)
+
+    def test_smoke_test(self):
+        rounding_mode = ssf.RoundingMode.TowardPositive
+        exception_handling_mode = ssf.ExceptionHandlingMode \
+            .SignalExactUnderflow
+        tininess_detection_mode = ssf.TininessDetectionMode.BeforeRounding
+        obj = ssf.FPModes(rounding_mode=rounding_mode,
+                          exception_handling_mode=exception_handling_mode,
+                          tininess_detection_mode=tininess_detection_mode)
+        self.assertEqual(obj.rounding_mode, rounding_mode)
+        self.assertEqual(obj.exception_handling_mode, exception_handling_mode)
+        self.assertEqual(obj.tininess_detection_mode, tininess_detection_mode)
+        self.assertEqual(obj.flush_to_zero_mode, ssf.FlushToZeroMode.Disabled)
+        self.assertIsNone(obj.precision_control)
+        self.assertEqual(
+            repr(obj),
+            "FPModes(rounding_mode=RoundingMode.TowardPositive, "
+            + "exception_handling_mode="
+            + "ExceptionHandlingMode.SignalExactUnderflow, "
+            + "tininess_detection_mode=TininessDetectionMode.BeforeRounding, "
+            + "flush_to_zero_mode=FlushToZeroMode.Disabled, "
+            + "precision_control=None)")
 
 
 class TestFloatProperties(unittest.TestCase):
@@ -204,12 +285,22 @@ class TestFloatProperties(unittest.TestCase):
             has_implicit_leading_bit=True,
             has_sign_bit=True,
             platform_properties=ssf.PlatformProperties_RISC_V)
+        obj = ssf.FloatProperties(
+            exponent_width=4,
+            mantissa_width=3,
+            has_implicit_leading_bit=True,
+            has_sign_bit=True,
+            platform_properties=ssf.PlatformProperties_RISC_V,
+            has_inf_nan=False)
+        self.assertEqual(obj.has_inf_nan, False)
+        self.assertEqual(obj.is_standard, False)
         obj = ssf.FloatProperties.standard(32)
         self.assertEqual(obj.is_standard, True)
         self.assertEqual(obj.exponent_width, 8)
         self.assertEqual(obj.mantissa_width, 23)
         self.assertEqual(obj.has_implicit_leading_bit, True)
         self.assertEqual(obj.has_sign_bit, True)
+        self.assertEqual(obj.has_inf_nan, True)
         self.assertEqual(obj.platform_properties,
                          ssf.PlatformProperties_RISC_V)
         self.assertEqual(obj.quiet_nan_format, ssf.QuietNaNFormat.Standard)
@@ -225,17 +316,97 @@ class TestFloatProperties(unittest.TestCase):
         self.assertEqual(obj.mantissa_field_normal_min, 0x00000000)
         self.assertEqual(obj.mantissa_field_msb_shift, 22)
         self.assertEqual(obj.mantissa_field_msb_mask, 0x00400000)
+        self.assertEqual(obj.fraction_msb_shift, 22)
+        self.assertEqual(obj.fraction_msb_mask, 0x00400000)
         self.assertEqual(obj.exponent_bias, 0x7F)
         self.assertEqual(obj.exponent_inf_nan, 0xFF)
         self.assertEqual(obj.exponent_zero_subnormal, 0)
         self.assertEqual(obj.exponent_min_normal, 1)
         self.assertEqual(obj.exponent_max_normal, 0xFE)
+        self.assertEqual(obj.normal_exponent_range, (-126, 127))
+        self.assertEqual(obj.mathematical_exponent_range, (-149, 127))
         self.assertEqual(obj.overall_mask, 0xFFFFFFFF)
+        self.assertAlmostEqual(obj.dynamic_range_decades, 83.38, places=2)
+        self.assertEqual(obj.unit_roundoff, (1, 2 ** 24))
+        f16 = ssf.FloatProperties.standard(16)
+        self.assertTrue(obj.can_represent_exact_product(f16, f16))
+        self.assertFalse(obj.can_represent_exact_sum(f16, f16))
         self.assertEqual(repr(obj),
                          "FloatProperties.standard(32, "
                          + "PlatformProperties_RISC_V)")
 
 
+    def test_arm_alt_half(self):
+        obj = ssf.FloatProperties.arm_alt_half()
+        self.assertEqual(obj.width, 16)
+        self.assertEqual(obj.has_inf_nan, False)
+        self.assertEqual(obj.is_standard, False)
+        cls = ssf.DynamicFloat
+        obj2 = cls(bits=0x3C00, properties=obj).div(cls.positive_zero(obj))
+        self.assertEqual(obj2.bits, 0x7FFF)
+        self.assertEqual(obj2.fp_state.status_flags,
+                         ssf.StatusFlags().set_division_by_zero())
+        self.assertEqual(cls(bits=0x7C00, properties=obj).float_class,
+                         ssf.FloatClass.PositiveNormal)
+
+
+    def test_x87_extended(self):
+        obj = ssf.FloatProperties.x87_extended()
+        self.assertEqual(obj.width, 80)
+        self.assertEqual(obj.has_implicit_leading_bit, False)
+        self.assertEqual(obj.fraction_width, 63)
+        self.assertEqual(obj.mantissa_field_msb_shift, 63)
+        self.assertEqual(obj.fraction_msb_shift, 62)
+        cls = ssf.DynamicFloat
+        self.assertEqual(cls.positive_infinity(obj).bits,
+                         0x7FFF_8000_0000_0000_0000)
+        self.assertEqual(cls.quiet_nan(obj).bits, 0x7FFF_C000_0000_0000_0000)
+        pseudo_infinity = cls(bits=0x7FFF_0000_0000_0000_0000, properties=obj)
+        self.assertEqual(pseudo_infinity.float_class,
+                         ssf.FloatClass.SignalingNaN)
+        self.assertTrue(pseudo_infinity.is_unsupported_encoding)
+        unnormal = cls(bits=0x3FFF_4000_0000_0000_0000, properties=obj)
+        self.assertEqual(unnormal.float_class, ssf.FloatClass.SignalingNaN)
+        self.assertTrue(unnormal.is_unsupported_encoding)
+        pseudo_denormal = cls(bits=0x0000_8000_0000_0000_0000, properties=obj)
+        self.assertEqual(pseudo_denormal.float_class,
+                         ssf.FloatClass.PositiveSubnormal)
+        self.assertTrue(pseudo_denormal.is_pseudo_denormal)
+        self.assertFalse(pseudo_denormal.is_unsupported_encoding)
+        self.assertFalse(pseudo_denormal.is_canonical)
+        self.assertFalse(unnormal.is_canonical)
+        self.assertFalse(pseudo_infinity.is_canonical)
+        self.assertTrue(cls.quiet_nan(obj).is_canonical)
+        obj2 = pseudo_denormal.canonicalize()
+        self.assertEqual(obj2.bits, 0x0001_8000_0000_0000_0000)
+        self.assertEqual(obj2.fp_state.status_flags, ssf.StatusFlags())
+        obj2 = unnormal.canonicalize()
+        self.assertEqual(obj2.bits, cls.quiet_nan(obj).bits)
+        self.assertEqual(obj2.fp_state.status_flags,
+                         ssf.StatusFlags().set_invalid_operation())
+        self.assertTrue(cls(bits=0x3FFF_8000_0000_0000_0000,
+                            properties=obj).is_canonical)
+        self.assertTrue(obj.unnormals_are_invalid)
+        obj = ssf.FloatProperties(
+            exponent_width=15,
+            mantissa_width=64,
+            has_implicit_leading_bit=False,
+            has_sign_bit=True,
+            platform_properties=ssf.PlatformProperties_RISC_V)
+        self.assertFalse(obj.unnormals_are_invalid)
+        unnormal = cls(bits=0x3FFF_4000_0000_0000_0000, properties=obj)
+        self.assertEqual(unnormal.float_class, ssf.FloatClass.PositiveNormal)
+        self.assertFalse(unnormal.is_unsupported_encoding)
+        self.assertEqual(unnormal.canonicalize().bits,
+                         0x3FFE_8000_0000_0000_0000)
+        pseudo_infinity = cls(bits=0x7FFF_0000_0000_0000_0000, properties=obj)
+        self.assertEqual(pseudo_infinity.float_class,
+                         ssf.FloatClass.PositiveInfinity)
+        self.assertFalse(pseudo_infinity.is_unsupported_encoding)
+        self.assertEqual(pseudo_infinity.canonicalize().bits,
+                         0x7FFF_8000_0000_0000_0000)
+
+
 class TestPlatformProperties(unittest.TestCase):
     maxDiff = None
 
@@ -341,6 +512,17 @@ class TestPlatformProperties(unittest.TestCase):
             + "rsqrt_nan_propagation_mode="
             + "UnaryNaNPropagationMode.AlwaysCanonical)")
 
+    def test_with_default_nan_mode(self):
+        obj = ssf.PlatformProperties_POWER.with_default_nan_mode()
+        self.assertEqual(obj.std_bin_ops_nan_propagation_mode,
+                         ssf.BinaryNaNPropagationMode.AlwaysCanonical)
+        self.assertEqual(obj.sqrt_nan_propagation_mode,
+                         ssf.UnaryNaNPropagationMode.AlwaysCanonical)
+        self.assertEqual(obj.fma_inf_zero_qnan_result,
+                         ssf.FMAInfZeroQNaNResult.CanonicalAndGenerateInvalid)
+        self.assertEqual(ssf.PlatformProperties_RISC_V.with_default_nan_mode(),
+                         ssf.PlatformProperties_RISC_V)
+
 
 class TestStatusFlags(unittest.TestCase):
     maxDiff = None
@@ -406,6 +588,23 @@ class TestDynamicFloat(unittest.TestCase):
         self.assertEqual(obj.fp_state,
                          ssf.FPState(status_flags=ssf.StatusFlags().set_inexact()))
 
+    def test_bytes(self):
+        cls = ssf.DynamicFloat
+        obj = cls(properties=self.properties, bits=0x12345678)
+        self.assertEqual(obj.to_bytes(), b"\x78\x56\x34\x12")
+        self.assertEqual(obj.to_bytes("big"), b"\x12\x34\x56\x78")
+        obj = cls.from_bytes(b"\x78\x56\x34\x12", self.properties)
+        self.assertEqual(obj.bits, 0x12345678)
+        self.assertEqual(obj.properties, self.properties)
+        obj = cls.from_bytes(b"\x12\x34\x56\x78", self.properties, "big")
+        self.assertEqual(obj.bits, 0x12345678)
+        with self.assertRaises(ValueError):
+            cls.from_bytes(b"\x00\x00\x00", self.properties)
+        with self.assertRaises(ValueError):
+            cls.from_bytes(b"\x00\x00\x00\x00\x00", self.properties)
+        with self.assertRaises(ValueError):
+            obj.to_bytes("middle")
+
     def test_constants(self):
         cls = ssf.DynamicFloat
         obj = cls.positive_zero(self.properties)
@@ -426,10 +625,24 @@ class TestDynamicFloat(unittest.TestCase):
         self.assertEqual(obj.bits, 0xFF800000)
         obj = cls.quiet_nan(self.properties)
         self.assertEqual(obj.bits, 0x7FC00000)
+        obj = cls.default_nan(self.properties)
+        self.assertEqual(obj.bits, 0x7FC00000)
+        obj = cls.default_nan(ssf.FloatProperties.standard(
+            32, platform_properties=ssf.PlatformProperties_X86_SSE))
+        self.assertEqual(obj.bits, 0xFFC00000)
+        self.assertTrue(obj.matches_platform_default_nan)
+        obj = cls(bits=0x7FC00000, properties=ssf.FloatProperties.standard(
+            32, platform_properties=ssf.PlatformProperties_X86_SSE))
+        self.assertFalse(obj.matches_platform_default_nan)
         obj = cls.signaling_nan(self.properties)
         self.assertEqual(obj.bits, 0x7F800001)
         obj = obj.to_quiet_nan()
         self.assertEqual(obj.bits & 0x7FC00000, 0x7FC00000)
+        obj = cls.signaling_nan_with_payload(ssf.Sign.Negative, 0x12345,
+                                             self.properties)
+        self.assertEqual(obj.bits, 0xFF812345)
+        self.assertIsNone(cls.signaling_nan_with_payload(ssf.Sign.Positive, 0,
+                                                         self.properties))
         obj = cls.signed_max_normal(ssf.Sign.Positive, self.properties)
         self.assertEqual(obj.bits, 0x7F7FFFFF)
         obj = cls.signed_max_normal(ssf.Sign.Negative, self.properties)
@@ -460,6 +673,60 @@ class TestDynamicFloat(unittest.TestCase):
         self.handle_binary_op("sub", operator.sub,
                               0x00000000, ssf.StatusFlags())
 
+    def test_abs_diff(self):
+        self.handle_binary_op("abs_diff", None,
+                              0x00000000, ssf.StatusFlags())
+        cls = ssf.DynamicFloat
+        one = cls(bits=0x3F800000, properties=self.properties)
+        two = cls(bits=0x40000000, properties=self.properties)
+        self.assertEqual(one.abs_diff(two).bits, 0x3F800000)
+        self.assertEqual(two.abs_diff(one).bits, 0x3F800000)
+
+    def test_is_quiet_bit_set(self):
+        cls = ssf.DynamicFloat
+        self.assertTrue(cls.quiet_nan(self.properties).is_quiet_bit_set)
+        self.assertFalse(cls.signaling_nan(self.properties).is_quiet_bit_set)
+        mips = ssf.FloatProperties.standard(
+            32, platform_properties=ssf.PlatformProperties_MIPS_LEGACY)
+        self.assertTrue(cls.quiet_nan(mips).is_quiet_bit_set)
+        self.assertFalse(cls(bits=0x7FC00000, properties=mips).is_quiet_bit_set)
+
+    def test_midpoint(self):
+        self.handle_binary_op("midpoint", None,
+                              0x00000000, ssf.StatusFlags())
+        cls = ssf.DynamicFloat
+        one = cls(bits=0x3F800000, properties=self.properties)
+        three = cls(bits=0x40400000, properties=self.properties)
+        self.assertEqual(one.midpoint(three).bits, 0x40000000)
+        max_normal = cls(bits=0x7F7FFFFF, properties=self.properties)
+        obj = max_normal.midpoint(max_normal)
+        self.assertEqual(obj.bits, 0x7F7FFFFF)
+        self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
+
+    def test_lerp(self):
+        cls = ssf.DynamicFloat
+        one = cls(bits=0x3F800000, properties=self.properties)
+        three = cls(bits=0x40400000, properties=self.properties)
+        half = cls(bits=0x3F000000, properties=self.properties)
+        obj = one.lerp(three, half)
+        self.assertEqual(obj.bits, 0x40000000)
+        self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
+
+    def test_minimum_maximum_clamp(self):
+        cls = ssf.DynamicFloat
+        one = cls(bits=0x3F800000, properties=self.properties)
+        two = cls(bits=0x40000000, properties=self.properties)
+        positive_zero = cls.positive_zero(self.properties)
+        negative_zero = cls.negative_zero(self.properties)
+        self.assertEqual(one.minimum(two).bits, 0x3F800000)
+        self.assertEqual(one.maximum(two).bits, 0x40000000)
+        self.assertEqual(positive_zero.minimum(negative_zero).bits,
+                         0x80000000)
+        self.assertTrue(one.maximum(cls.quiet_nan(self.properties)).is_nan)
+        self.assertEqual(two.clamp(positive_zero, one).bits, 0x3F800000)
+        self.assertEqual(negative_zero.clamp(positive_zero, one).bits,
+                         0x00000000)
+
     def test_mul(self):
         self.handle_binary_op("mul", operator.mul,
                               0x00000000, ssf.StatusFlags())
@@ -488,6 +755,31 @@ class TestDynamicFloat(unittest.TestCase):
         self.assertEqual(obj[0], 0)
         self.assertEqual(obj[1], ssf.FPState())
 
+    def test_round_to_precision(self):
+        cls = ssf.DynamicFloat
+        arg = cls(properties=self.properties, bits=0x3F802000)
+        obj = arg.round_to_precision(10)
+        self.assertEqual(obj.bits, 0x3F800000)
+        self.assertEqual(obj.fp_state.status_flags,
+                         ssf.StatusFlags().set_inexact())
+        obj = arg.round_to_precision(
+            10, rounding_mode=ssf.RoundingMode.TowardPositive)
+        self.assertEqual(obj.bits, 0x3F804000)
+        with self.assertRaises(ValueError):
+            arg.round_to_precision(0)
+
+    def test_round_to_format_precision(self):
+        cls = ssf.DynamicFloat
+        arg = cls(properties=self.properties, bits=0x3F802000)
+        obj = arg.round_to_format_precision(ssf.FloatProperties.standard(16))
+        self.assertEqual(obj.bits, 0x3F800000)
+        self.assertEqual(obj.properties, self.properties)
+        self.assertEqual(obj.fp_state.status_flags,
+                         ssf.StatusFlags().set_inexact())
+        arg = cls(properties=self.properties, bits=0x47800000)
+        obj = arg.round_to_format_precision(ssf.FloatProperties.standard(16))
+        self.assertEqual(obj.bits, 0x7F800000)
+
     def test_round_to_integral(self):
         cls = ssf.DynamicFloat
         rounding_mode = ssf.RoundingMode.TiesToEven
@@ -512,6 +804,22 @@ class TestDynamicFloat(unittest.TestCase):
         self.assertEqual(obj.bits, 0x80000001)
         self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
 
+    def test_next_integer_up_or_down(self):
+        cls = ssf.DynamicFloat
+        arg = cls.positive_zero(self.properties)
+        obj = arg.next_integer_up_or_down(ssf.UpOrDown.Up)
+        self.assertEqual(obj.bits, 0x3F800000)
+        self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
+        obj = arg.next_integer_up_or_down(ssf.UpOrDown.Down)
+        self.assertEqual(obj.bits, 0xBF800000)
+        self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
+        obj = arg.next_integer_up()
+        self.assertEqual(obj.bits, 0x3F800000)
+        self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
+        obj = arg.next_integer_down()
+        self.assertEqual(obj.bits, 0xBF800000)
+        self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
+
     def test_log_b(self):
         cls = ssf.DynamicFloat
         arg = cls.positive_zero(self.properties)
@@ -545,6 +853,18 @@ class TestDynamicFloat(unittest.TestCase):
         self.assertEqual(obj.bits, 0x00000000)
         self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
 
+    def test_is_exactly_representable_in(self):
+        cls = ssf.DynamicFloat
+        f16 = ssf.FloatProperties.standard(16)
+        self.assertTrue(cls(bits=0x3F800000, properties=self.properties)
+                        .is_exactly_representable_in(f16))
+        self.assertFalse(cls(bits=0x3EAAAAAB, properties=self.properties)
+                         .is_exactly_representable_in(f16))
+        self.assertFalse(cls(bits=0x47800000, properties=self.properties)
+                         .is_exactly_representable_in(f16))
+        self.assertTrue(cls.positive_infinity(self.properties)
+                        .is_exactly_representable_in(f16))
+
     def test_abs(self):
         cls = ssf.DynamicFloat
         arg = cls.positive_zero(self.properties)
@@ -572,6 +892,63 @@ class TestDynamicFloat(unittest.TestCase):
         self.assertEqual(obj.bits, 0x00000000)
         self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
 
+    def test_to_ratio_parts(self):
+        cls = ssf.DynamicFloat
+        value = cls(bits=0xBFC00000, properties=self.properties)
+        self.assertEqual(value.to_ratio_parts(), (ssf.Sign.Negative, 3, 2))
+        self.assertEqual(value.to_scientific_ratio(),
+                         (ssf.Sign.Negative, 0xC00000, -23))
+        self.assertEqual(value.to_double_double(), (-1.5, 0.0))
+        self.assertEqual(value.to_ulp_grid_integer(), -3 * 2 ** 148)
+        value = cls.positive_infinity(self.properties)
+        self.assertIsNone(value.to_ratio_parts())
+        self.assertIsNone(value.to_scientific_ratio())
+        self.assertIsNone(value.to_double_double())
+        self.assertIsNone(value.to_ulp_grid_integer())
+
+    def test_clamp_to_representable(self):
+        cls = ssf.DynamicFloat
+        value = cls.negative_infinity(self.properties)
+        obj, clamped = value.clamp_to_representable()
+        self.assertTrue(clamped)
+        self.assertEqual(obj.bits, 0xFF7FFFFF)
+        self.assertEqual(obj.fp_state.status_flags,
+                         ssf.StatusFlags().set_overflow().set_inexact())
+        value = cls(bits=0x3F800000, properties=self.properties)
+        obj, clamped = value.clamp_to_representable()
+        self.assertFalse(clamped)
+        self.assertEqual(obj.bits, 0x3F800000)
+        self.assertEqual(obj.fp_state, ssf.FPState())
+
+    def test_significand_bits(self):
+        cls = ssf.DynamicFloat
+        value = cls(bits=0x3FC00000, properties=self.properties)
+        self.assertEqual(value.mantissa_trailing_zeros, 22)
+        self.assertEqual(value.significand_bit_length, 2)
+        value = cls.positive_zero(self.properties)
+        self.assertEqual(value.mantissa_trailing_zeros, 24)
+        self.assertEqual(value.significand_bit_length, 0)
+        value = cls.quiet_nan(self.properties)
+        self.assertIsNone(value.mantissa_trailing_zeros)
+        self.assertIsNone(value.significand_bit_length)
+
+    def test_compare_with_int(self):
+        cls = ssf.DynamicFloat
+        # 2 ** 24 + 2
+        value = cls(bits=0x4B800001, properties=self.properties)
+        self.assertEqual(value.compare_with_int(2 ** 24 + 1, False),
+                         (1, ssf.FPState()))
+        self.assertEqual(value.compare_with_int(2 ** 24 + 2, False),
+                         (0, ssf.FPState()))
+        self.assertEqual(value.compare_with_int(2 ** 24 + 3, False),
+                         (-1, ssf.FPState()))
+        nan = cls.quiet_nan(self.properties)
+        self.assertEqual(nan.compare_with_int(0, True), (None, ssf.FPState()))
+        self.assertEqual(
+            nan.compare_with_int(0, False),
+            (None,
+             ssf.FPState(status_flags=ssf.StatusFlags().set_invalid_operation())))
+
     def test_compare(self):
         cls = ssf.DynamicFloat
         zero = cls.positive_zero(self.properties)
@@ -598,6 +975,38 @@ class TestDynamicFloat(unittest.TestCase):
         self.assertEqual(obj[0], 0)
         self.assertEqual(obj[1], ssf.FPState())
 
+    def test_richcmp(self):
+        cls = ssf.DynamicFloat
+        zero = cls.positive_zero(self.properties)
+        neg_zero = cls.negative_zero(self.properties)
+        one = cls.from_int(1, self.properties)
+        nan = cls.quiet_nan(self.properties)
+        self.assertTrue(zero == neg_zero)
+        self.assertFalse(zero != neg_zero)
+        self.assertTrue(zero < one)
+        self.assertTrue(zero <= one)
+        self.assertTrue(one > zero)
+        self.assertTrue(one >= one)
+        self.assertFalse(nan == nan)
+        self.assertTrue(nan != nan)
+        self.assertFalse(nan < one)
+        self.assertFalse(nan >= one)
+        other_properties = ssf.FloatProperties.standard(16)
+        with self.assertRaises(TypeError):
+            zero == cls.positive_zero(other_properties)
+
+    def test_bit_equal(self):
+        cls = ssf.DynamicFloat
+        zero = cls.positive_zero(self.properties)
+        neg_zero = cls.negative_zero(self.properties)
+        nan = cls.quiet_nan(self.properties)
+        self.assertFalse(zero.bit_equal(neg_zero))
+        self.assertTrue(zero.bit_equal(zero))
+        self.assertTrue(nan.bit_equal(cls.quiet_nan(self.properties)))
+        self.assertFalse(nan.bit_equal(nan.neg()))
+        other_properties = ssf.FloatProperties.standard(16)
+        self.assertFalse(zero.bit_equal(cls.positive_zero(other_properties)))
+
     def test_from_int(self):
         cls = ssf.DynamicFloat
         obj = cls.from_int(0, self.properties)
@@ -616,6 +1025,19 @@ class TestDynamicFloat(unittest.TestCase):
         obj = arg.to_int(exact=False, rounding_mode=rounding_mode)
         self.assertEqual(obj[0], 0)
         self.assertEqual(obj[1], ssf.FPState())
+        # 2^31 fits in an unsigned 32-bit integer but not a signed one
+        arg = cls(bits=0x4F000000, properties=self.properties)
+        obj = arg.to_int(width=32)
+        self.assertIsNone(obj[0])
+        self.assertEqual(obj[1].status_flags,
+                         ssf.StatusFlags().set_invalid_operation())
+        obj = arg.to_int(width=32, signed=False)
+        self.assertEqual(obj[0], 2 ** 31)
+        self.assertEqual(obj[1], ssf.FPState())
+        obj = arg.to_int()
+        self.assertEqual(obj[0], 2 ** 31)
+        with self.assertRaises(ValueError):
+            arg.to_int(width=31)
 
     def test_rsqrt(self):
         cls = ssf.DynamicFloat
@@ -626,6 +1048,70 @@ class TestDynamicFloat(unittest.TestCase):
         self.assertEqual(obj.fp_state.status_flags,
                          ssf.StatusFlags().set_division_by_zero())
 
+    def test_pown(self):
+        cls = ssf.DynamicFloat
+        arg = cls(properties=self.properties, bits=0x40000000)
+        obj = arg.pown(3)
+        self.assertEqual(obj.bits, 0x41000000)
+        self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
+        obj = arg ** -1
+        self.assertEqual(obj.bits, 0x3F000000)
+        self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
+        obj = cls.positive_zero(self.properties) ** -1
+        self.assertEqual(obj.bits, 0x7F800000)
+        self.assertEqual(obj.fp_state.status_flags,
+                         ssf.StatusFlags().set_division_by_zero())
+
+    def test_cbrt(self):
+        cls = ssf.DynamicFloat
+        rounding_mode = ssf.RoundingMode.TiesToEven
+        arg = cls(properties=self.properties, bits=0x41000000)
+        obj = arg.cbrt(rounding_mode)
+        self.assertEqual(obj.bits, 0x40000000)
+        self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
+        obj = ssf.cbrt(arg, rounding_mode=rounding_mode)
+        self.assertEqual(obj.bits, 0x40000000)
+        self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
+
+    def test_hypot(self):
+        cls = ssf.DynamicFloat
+        rounding_mode = ssf.RoundingMode.TiesToEven
+        x = cls(properties=self.properties, bits=0x40400000)
+        y = cls(properties=self.properties, bits=0x40800000)
+        obj = x.hypot(y, rounding_mode)
+        self.assertEqual(obj.bits, 0x40A00000)
+        self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
+        obj = ssf.hypot(x, y)
+        self.assertEqual(obj.bits, 0x40A00000)
+        self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
+
+    def test_mean(self):
+        cls = ssf.DynamicFloat
+        values = [cls(properties=self.properties, bits=bits)
+                  for bits in (0x3F800000, 0x40000000, 0x40400000, 0x3F800000)]
+        obj = ssf.mean(values, self.properties)
+        self.assertEqual(obj.bits, 0x3FE00000)
+        self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
+        values = [cls(properties=self.properties, bits=bits)
+                  for bits in (0x3F800000, 0x3F800000, 0x40000000)]
+        obj = ssf.mean(values, self.properties, ssf.RoundingMode.TowardZero)
+        self.assertEqual(obj.bits, 0x3FAAAAAA)
+        self.assertEqual(obj.fp_state.status_flags,
+                         ssf.StatusFlags().set_inexact())
+        obj = ssf.mean([], self.properties)
+        self.assertTrue(obj.is_nan)
+        self.assertEqual(obj.fp_state.status_flags,
+                         ssf.StatusFlags().set_invalid_operation())
+
+    def test_module_sqrt(self):
+        cls = ssf.DynamicFloat
+        arg = cls(properties=self.properties, bits=0x40800000)
+        obj = ssf.sqrt(arg)
+        self.assertEqual(obj.bits, 0x40000000)
+        self.assertEqual(obj.fp_state.status_flags, ssf.StatusFlags())
+        obj = ssf.sqrt(arg, ssf.RoundingMode.TiesToEven)
+        self.assertEqual(obj.bits, 0x40000000)
+
     def test_attributes(self):
         cls = ssf.DynamicFloat
         obj = cls.positive_zero(self.properties)
@@ -656,5 +1142,65 @@ class TestDynamicFloat(unittest.TestCase):
         self.assertEqual(obj.is_subnormal_or_zero, True)
 
 
+class TestConvertArray(unittest.TestCase):
+    maxDiff = None
+
+    def test_signature(self):
+        self.assertIsNotNone(inspect.signature(ssf.convert_array))
+
+    def test_convert_array(self):
+        src_properties = ssf.FloatProperties.standard(32)
+        dest_properties = ssf.FloatProperties.standard(16)
+        bits, fp_state = ssf.convert_array([0x00000000, 0x3F800000, 0x7F800000],
+                                           src_properties,
+                                           dest_properties)
+        self.assertEqual(bits, [0x0000, 0x3C00, 0x7C00])
+        self.assertEqual(fp_state, ssf.FPState())
+        bits, fp_state = ssf.convert_array(iter([0x3F800001, 0x7F7FFFFF]),
+                                           src_properties,
+                                           dest_properties,
+                                           ssf.RoundingMode.TowardZero)
+        self.assertEqual(bits, [0x3C00, 0x7BFF])
+        self.assertEqual(fp_state.status_flags,
+                         ssf.StatusFlags().set_inexact().set_overflow())
+        with self.assertRaises(ValueError):
+            ssf.convert_array([1 << 32], src_properties, dest_properties)
+
+
+class TestRoundingModeContext(unittest.TestCase):
+    maxDiff = None
+    properties = ssf.FloatProperties.standard(32)
+
+    def test_signatures(self):
+        check_signatures(self, ssf.rounding_mode)
+
+    def test_rounding_mode(self):
+        cls = ssf.DynamicFloat
+        one = cls.from_int(1, self.properties)
+        three = cls.from_int(3, self.properties)
+        self.assertEqual((one / three).bits, 0x3EAAAAAB)
+        with ssf.rounding_mode(ssf.RoundingMode.TowardZero) as rounding_mode:
+            self.assertEqual(rounding_mode, ssf.RoundingMode.TowardZero)
+            self.assertEqual((one / three).bits, 0x3EAAAAAA)
+            self.assertEqual(one.div(three).bits, 0x3EAAAAAA)
+            self.assertEqual(
+                one.div(three, ssf.RoundingMode.TowardPositive).bits,
+                0x3EAAAAAB)
+            with ssf.rounding_mode(ssf.RoundingMode.TowardPositive):
+                self.assertEqual((one / three).bits, 0x3EAAAAAB)
+                self.assertEqual((-one / three).bits, 0xBEAAAAAA)
+            self.assertEqual((one / three).bits, 0x3EAAAAAA)
+        self.assertEqual((one / three).bits, 0x3EAAAAAB)
+
+    def test_exception_restores_rounding_mode(self):
+        cls = ssf.DynamicFloat
+        one = cls.from_int(1, self.properties)
+        three = cls.from_int(3, self.properties)
+        with self.assertRaises(ZeroDivisionError):
+            with ssf.rounding_mode(ssf.RoundingMode.TowardZero):
+                raise ZeroDivisionError()
+        self.assertEqual((one / three).bits, 0x3EAAAAAB)
+
+
 if __name__ == '__main__':
     unittest.main()